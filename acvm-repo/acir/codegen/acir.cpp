@@ -243,6 +243,39 @@ namespace Program {
         static HeapVector bincodeDeserialize(std::vector<uint8_t>);
     };
 
+    struct ValueOrArray {
+
+        struct MemoryAddress {
+            Program::MemoryAddress value;
+
+            friend bool operator==(const MemoryAddress&, const MemoryAddress&);
+            std::vector<uint8_t> bincodeSerialize() const;
+            static MemoryAddress bincodeDeserialize(std::vector<uint8_t>);
+        };
+
+        struct HeapArray {
+            Program::HeapArray value;
+
+            friend bool operator==(const HeapArray&, const HeapArray&);
+            std::vector<uint8_t> bincodeSerialize() const;
+            static HeapArray bincodeDeserialize(std::vector<uint8_t>);
+        };
+
+        struct HeapVector {
+            Program::HeapVector value;
+
+            friend bool operator==(const HeapVector&, const HeapVector&);
+            std::vector<uint8_t> bincodeSerialize() const;
+            static HeapVector bincodeDeserialize(std::vector<uint8_t>);
+        };
+
+        std::variant<MemoryAddress, HeapArray, HeapVector> value;
+
+        friend bool operator==(const ValueOrArray&, const ValueOrArray&);
+        std::vector<uint8_t> bincodeSerialize() const;
+        static ValueOrArray bincodeDeserialize(std::vector<uint8_t>);
+    };
+
     struct BlackBoxOp {
 
         struct AES128Encrypt {
@@ -462,7 +495,17 @@ namespace Program {
             static ToRadix bincodeDeserialize(std::vector<uint8_t>);
         };
 
-        std::variant<AES128Encrypt, Blake2s, Blake3, Keccak256, Keccakf1600, EcdsaSecp256k1, EcdsaSecp256r1, SchnorrVerify, PedersenCommitment, PedersenHash, MultiScalarMul, EmbeddedCurveAdd, BigIntAdd, BigIntSub, BigIntMul, BigIntDiv, BigIntFromLeBytes, BigIntToLeBytes, Poseidon2Permutation, Sha256Compression, ToRadix> value;
+        struct UserDefined {
+            std::string name;
+            std::vector<Program::ValueOrArray> inputs;
+            std::vector<Program::ValueOrArray> outputs;
+
+            friend bool operator==(const UserDefined&, const UserDefined&);
+            std::vector<uint8_t> bincodeSerialize() const;
+            static UserDefined bincodeDeserialize(std::vector<uint8_t>);
+        };
+
+        std::variant<AES128Encrypt, Blake2s, Blake3, Keccak256, Keccakf1600, EcdsaSecp256k1, EcdsaSecp256r1, SchnorrVerify, PedersenCommitment, PedersenHash, MultiScalarMul, EmbeddedCurveAdd, BigIntAdd, BigIntSub, BigIntMul, BigIntDiv, BigIntFromLeBytes, BigIntToLeBytes, Poseidon2Permutation, Sha256Compression, ToRadix, UserDefined> value;
 
         friend bool operator==(const BlackBoxOp&, const BlackBoxOp&);
         std::vector<uint8_t> bincodeSerialize() const;
@@ -505,39 +548,6 @@ namespace Program {
         static HeapValueType bincodeDeserialize(std::vector<uint8_t>);
     };
 
-    struct ValueOrArray {
-
-        struct MemoryAddress {
-            Program::MemoryAddress value;
-
-            friend bool operator==(const MemoryAddress&, const MemoryAddress&);
-            std::vector<uint8_t> bincodeSerialize() const;
-            static MemoryAddress bincodeDeserialize(std::vector<uint8_t>);
-        };
-
-        struct HeapArray {
-            Program::HeapArray value;
-
-            friend bool operator==(const HeapArray&, const HeapArray&);
-            std::vector<uint8_t> bincodeSerialize() const;
-            static HeapArray bincodeDeserialize(std::vector<uint8_t>);
-        };
-
-        struct HeapVector {
-            Program::HeapVector value;
-
-            friend bool operator==(const HeapVector&, const HeapVector&);
-            std::vector<uint8_t> bincodeSerialize() const;
-            static HeapVector bincodeDeserialize(std::vector<uint8_t>);
-        };
-
-        std::variant<MemoryAddress, HeapArray, HeapVector> value;
-
-        friend bool operator==(const ValueOrArray&, const ValueOrArray&);
-        std::vector<uint8_t> bincodeSerialize() const;
-        static ValueOrArray bincodeDeserialize(std::vector<uint8_t>);
-    };
-
     struct BrilligOpcode {
 
         struct BinaryFieldOp {
@@ -1022,7 +1032,17 @@ namespace Program {
             static Sha256Compression bincodeDeserialize(std::vector<uint8_t>);
         };
 
-        std::variant<AES128Encrypt, AND, XOR, RANGE, Blake2s, Blake3, SchnorrVerify, PedersenCommitment, PedersenHash, EcdsaSecp256k1, EcdsaSecp256r1, MultiScalarMul, EmbeddedCurveAdd, Keccak256, Keccakf1600, RecursiveAggregation, BigIntAdd, BigIntSub, BigIntMul, BigIntDiv, BigIntFromLeBytes, BigIntToLeBytes, Poseidon2Permutation, Sha256Compression> value;
+        struct UserDefined {
+            std::string name;
+            std::vector<Program::FunctionInput> inputs;
+            std::vector<Program::Witness> outputs;
+
+            friend bool operator==(const UserDefined&, const UserDefined&);
+            std::vector<uint8_t> bincodeSerialize() const;
+            static UserDefined bincodeDeserialize(std::vector<uint8_t>);
+        };
+
+        std::variant<AES128Encrypt, AND, XOR, RANGE, Blake2s, Blake3, SchnorrVerify, PedersenCommitment, PedersenHash, EcdsaSecp256k1, EcdsaSecp256r1, MultiScalarMul, EmbeddedCurveAdd, Keccak256, Keccakf1600, RecursiveAggregation, BigIntAdd, BigIntSub, BigIntMul, BigIntDiv, BigIntFromLeBytes, BigIntToLeBytes, Poseidon2Permutation, Sha256Compression, UserDefined> value;
 
         friend bool operator==(const BlackBoxFuncCall&, const BlackBoxFuncCall&);
         std::vector<uint8_t> bincodeSerialize() const;
@@ -3504,6 +3524,50 @@ Program::BlackBoxFuncCall::Sha256Compression serde::Deserializable<Program::Blac
     return obj;
 }
 
+namespace Program {
+
+    inline bool operator==(const BlackBoxFuncCall::UserDefined &lhs, const BlackBoxFuncCall::UserDefined &rhs) {
+        if (!(lhs.name == rhs.name)) { return false; }
+        if (!(lhs.inputs == rhs.inputs)) { return false; }
+        if (!(lhs.outputs == rhs.outputs)) { return false; }
+        return true;
+    }
+
+    inline std::vector<uint8_t> BlackBoxFuncCall::UserDefined::bincodeSerialize() const {
+        auto serializer = serde::BincodeSerializer();
+        serde::Serializable<BlackBoxFuncCall::UserDefined>::serialize(*this, serializer);
+        return std::move(serializer).bytes();
+    }
+
+    inline BlackBoxFuncCall::UserDefined BlackBoxFuncCall::UserDefined::bincodeDeserialize(std::vector<uint8_t> input) {
+        auto deserializer = serde::BincodeDeserializer(input);
+        auto value = serde::Deserializable<BlackBoxFuncCall::UserDefined>::deserialize(deserializer);
+        if (deserializer.get_buffer_offset() < input.size()) {
+            throw serde::deserialization_error("Some input bytes were not read");
+        }
+        return value;
+    }
+
+} // end of namespace Program
+
+template <>
+template <typename Serializer>
+void serde::Serializable<Program::BlackBoxFuncCall::UserDefined>::serialize(const Program::BlackBoxFuncCall::UserDefined &obj, Serializer &serializer) {
+    serde::Serializable<decltype(obj.name)>::serialize(obj.name, serializer);
+    serde::Serializable<decltype(obj.inputs)>::serialize(obj.inputs, serializer);
+    serde::Serializable<decltype(obj.outputs)>::serialize(obj.outputs, serializer);
+}
+
+template <>
+template <typename Deserializer>
+Program::BlackBoxFuncCall::UserDefined serde::Deserializable<Program::BlackBoxFuncCall::UserDefined>::deserialize(Deserializer &deserializer) {
+    Program::BlackBoxFuncCall::UserDefined obj;
+    obj.name = serde::Deserializable<decltype(obj.name)>::deserialize(deserializer);
+    obj.inputs = serde::Deserializable<decltype(obj.inputs)>::deserialize(deserializer);
+    obj.outputs = serde::Deserializable<decltype(obj.outputs)>::deserialize(deserializer);
+    return obj;
+}
+
 namespace Program {
 
     inline bool operator==(const BlackBoxOp &lhs, const BlackBoxOp &rhs) {
@@ -4491,6 +4555,50 @@ Program::BlackBoxOp::ToRadix serde::Deserializable<Program::BlackBoxOp::ToRadix>
     return obj;
 }
 
+namespace Program {
+
+    inline bool operator==(const BlackBoxOp::UserDefined &lhs, const BlackBoxOp::UserDefined &rhs) {
+        if (!(lhs.name == rhs.name)) { return false; }
+        if (!(lhs.inputs == rhs.inputs)) { return false; }
+        if (!(lhs.outputs == rhs.outputs)) { return false; }
+        return true;
+    }
+
+    inline std::vector<uint8_t> BlackBoxOp::UserDefined::bincodeSerialize() const {
+        auto serializer = serde::BincodeSerializer();
+        serde::Serializable<BlackBoxOp::UserDefined>::serialize(*this, serializer);
+        return std::move(serializer).bytes();
+    }
+
+    inline BlackBoxOp::UserDefined BlackBoxOp::UserDefined::bincodeDeserialize(std::vector<uint8_t> input) {
+        auto deserializer = serde::BincodeDeserializer(input);
+        auto value = serde::Deserializable<BlackBoxOp::UserDefined>::deserialize(deserializer);
+        if (deserializer.get_buffer_offset() < input.size()) {
+            throw serde::deserialization_error("Some input bytes were not read");
+        }
+        return value;
+    }
+
+} // end of namespace Program
+
+template <>
+template <typename Serializer>
+void serde::Serializable<Program::BlackBoxOp::UserDefined>::serialize(const Program::BlackBoxOp::UserDefined &obj, Serializer &serializer) {
+    serde::Serializable<decltype(obj.name)>::serialize(obj.name, serializer);
+    serde::Serializable<decltype(obj.inputs)>::serialize(obj.inputs, serializer);
+    serde::Serializable<decltype(obj.outputs)>::serialize(obj.outputs, serializer);
+}
+
+template <>
+template <typename Deserializer>
+Program::BlackBoxOp::UserDefined serde::Deserializable<Program::BlackBoxOp::UserDefined>::deserialize(Deserializer &deserializer) {
+    Program::BlackBoxOp::UserDefined obj;
+    obj.name = serde::Deserializable<decltype(obj.name)>::deserialize(deserializer);
+    obj.inputs = serde::Deserializable<decltype(obj.inputs)>::deserialize(deserializer);
+    obj.outputs = serde::Deserializable<decltype(obj.outputs)>::deserialize(deserializer);
+    return obj;
+}
+
 namespace Program {
 
     inline bool operator==(const BlockId &lhs, const BlockId &rhs) {
@@ -0,0 +1,198 @@
+use std::{iter::Peekable, str::Chars};
+
+use super::{Location, ParserError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Token {
+    /// An identifier or keyword, e.g. `func`, `ASSERT_ZERO`, `_3` or `b0`
+    Ident(String),
+    /// A decimal integer
+    Int(String),
+    /// A hexadecimal integer, including its `0x` prefix
+    Hex(String),
+    Str(String),
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    LeftParen,
+    RightParen,
+    Comma,
+    Colon,
+    DoubleColon,
+    Dot,
+    Minus,
+    At,
+    Eof,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) => write!(f, "`{ident}`"),
+            Token::Int(int) | Token::Hex(int) => write!(f, "`{int}`"),
+            Token::Str(string) => write!(f, "{string:?}"),
+            Token::LeftBrace => write!(f, "`{{`"),
+            Token::RightBrace => write!(f, "`}}`"),
+            Token::LeftBracket => write!(f, "`[`"),
+            Token::RightBracket => write!(f, "`]`"),
+            Token::LeftParen => write!(f, "`(`"),
+            Token::RightParen => write!(f, "`)`"),
+            Token::Comma => write!(f, "`,`"),
+            Token::Colon => write!(f, "`:`"),
+            Token::DoubleColon => write!(f, "`::`"),
+            Token::Dot => write!(f, "`.`"),
+            Token::Minus => write!(f, "`-`"),
+            Token::At => write!(f, "`@`"),
+            Token::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SpannedToken {
+    pub(super) token: Token,
+    pub(super) location: Location,
+}
+
+pub(super) struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub(super) fn new(source: &'a str) -> Self {
+        Lexer { chars: source.chars().peekable(), line: 1, column: 1 }
+    }
+
+    /// Splits the source into tokens, ending with a single [`Token::Eof`].
+    pub(super) fn lex(mut self) -> Result<Vec<SpannedToken>, ParserError> {
+        let mut tokens = Vec::new();
+        loop {
+            let token = self.next_token()?;
+            let is_eof = token.token == Token::Eof;
+            tokens.push(token);
+            if is_eof {
+                return Ok(tokens);
+            }
+        }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let char = self.chars.next()?;
+        if char == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(char)
+    }
+
+    fn location(&self) -> Location {
+        Location { line: self.line, column: self.column }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            match self.chars.peek() {
+                Some(char) if char.is_whitespace() => {
+                    self.next_char();
+                }
+                Some('/') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    if lookahead.peek() != Some(&'/') {
+                        return;
+                    }
+                    while self.chars.peek().is_some_and(|char| *char != '\n') {
+                        self.next_char();
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<SpannedToken, ParserError> {
+        self.skip_whitespace_and_comments();
+
+        let location = self.location();
+        let Some(char) = self.next_char() else {
+            return Ok(SpannedToken { token: Token::Eof, location });
+        };
+
+        let token = match char {
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            '-' => Token::Minus,
+            '@' => Token::At,
+            ':' => {
+                if self.chars.peek() == Some(&':') {
+                    self.next_char();
+                    Token::DoubleColon
+                } else {
+                    Token::Colon
+                }
+            }
+            '"' => Token::Str(self.lex_string(location)?),
+            '0' if self.chars.peek() == Some(&'x') => {
+                self.next_char();
+                let digits = self.take_while(|char| char.is_ascii_hexdigit());
+                Token::Hex(format!("0x{digits}"))
+            }
+            char if char.is_ascii_digit() => {
+                let digits = self.take_while(|char| char.is_ascii_digit());
+                Token::Int(format!("{char}{digits}"))
+            }
+            char if char.is_ascii_alphabetic() || char == '_' => {
+                let rest = self.take_while(|char| char.is_ascii_alphanumeric() || char == '_');
+                Token::Ident(format!("{char}{rest}"))
+            }
+            character => return Err(ParserError::UnexpectedCharacter { character, location }),
+        };
+        Ok(SpannedToken { token, location })
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut result = String::new();
+        while let Some(char) = self.chars.peek().copied().filter(|char| predicate(*char)) {
+            result.push(char);
+            self.next_char();
+        }
+        result
+    }
+
+    fn lex_string(&mut self, start: Location) -> Result<String, ParserError> {
+        let mut string = String::new();
+        loop {
+            let location = self.location();
+            match self.next_char() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.next_char() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(character) => {
+                            return Err(ParserError::UnexpectedCharacter { character, location })
+                        }
+                        None => return Err(ParserError::UnterminatedString { location: start }),
+                    };
+                    string.push(escaped);
+                }
+                Some(char) => string.push(char),
+                None => return Err(ParserError::UnterminatedString { location: start }),
+            }
+        }
+    }
+}
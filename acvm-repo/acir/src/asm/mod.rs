@@ -0,0 +1,138 @@
+//! A textual assembly format for ACIR programs.
+//!
+//! The [`Display`][std::fmt::Display] implementations of the ACIR types are intended to be read by humans
+//! and abbreviate large opcodes, so their output cannot be read back in. The format defined here is lossless:
+//! [`print_program`] writes out a [`Program`] which [`parse_program`] will read back into an identical program.
+//! This allows ACIR regression tests to be written by hand and circuits to be diffed in code review.
+//!
+//! # Format
+//!
+//! A program consists of its ACIR functions and its unconstrained (Brillig) functions.
+//! Each function is labelled with its index in the program and functions of each kind must appear in order.
+//!
+//! ```text
+//! // Comments run until the end of the line.
+//! func 0 {
+//!     current_witness_index: 3
+//!     expression_width: bounded(4)
+//!     private_parameters: [_0, _1]
+//!     public_parameters: []
+//!     return_values: [_3]
+//!     recursive: false
+//!     assert_messages: [0: "sum is incorrect"]
+//!
+//!     ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+//!     BRILLIG_CALL { id: 0, inputs: [single([ (1, _2) 0 ])], outputs: [simple(_3)] }
+//!     ASSERT_ZERO [ (1, _2, _3) -1 ]
+//! }
+//!
+//! brillig func 0 {
+//!     const { destination: @0, bit_size: u32, value: 1 }
+//!     ...
+//! }
+//! ```
+//!
+//! Only `current_witness_index` is required within the header of an ACIR function. The remaining header items
+//! default to an unbounded expression width, no parameters, no return values, a non-recursive circuit and
+//! no assertion messages. Header items which are present must appear in the order shown above.
+//!
+//! ## Values
+//!
+//! - Field elements are written as decimal integers which fit into a `u128`, optionally negated with a leading `-`,
+//!   or as hexadecimal integers prefixed with `0x`.
+//! - `_N` is the witness with index `N`, and `bN` is the memory block with id `N`.
+//! - `[ (q, _i, _j) ... (q, _i) ... q_c ]` is an [`Expression`][crate::native_types::Expression] consisting of
+//!   its multiplication terms, followed by its linear terms, followed by its constant term.
+//! - `(_N, bits)` is a black box function input read from a witness, and `(value, bits)` is one with a constant value.
+//! - Opcode locations within assertion messages are written as `N` for ACIR opcodes, or `N.M` for Brillig opcodes.
+//!   Assertion payloads are either a string, or `dynamic(selector, [..])` where each item of the list is either
+//!   an expression or a memory block.
+//! - `@N` is the Brillig memory address `N`, `array(@p, size)` is a heap array and `vector(@p, @size)` is a heap vector.
+//! - Bit sizes are written as `field` or `uN` (`u0`, `u1`, `u8`, `u16`, `u32`, `u64` or `u128`).
+//! - Strings are double quoted and may contain the escape sequences `\"`, `\\`, `\n`, `\r` and `\t`.
+//!
+//! ## Opcodes
+//!
+//! ACIR opcodes are written in upper case as one of:
+//!
+//! - `ASSERT_ZERO expression`
+//! - `BLACKBOX::NAME { .. }` where `NAME` is the upper case name of the [`BlackBoxFunc`][crate::BlackBoxFunc]
+//! - `DIRECTIVE::TO_LE_RADIX { a: expression, b: [_N, ..], radix: N }`
+//! - `MEMORY_INIT { block_id: bN, init: [_N, ..], block_type: memory | call_data(N) | return_data }`
+//! - `MEMORY_OP { block_id: bN, operation: expression, index: expression, value: expression }`
+//! - `BRILLIG_CALL { id: N, inputs: [..], outputs: [..] }` where each input is one of `single(expression)`,
+//!   `array([expression, ..])` or `memory_array(bN)`, and each output is one of `simple(_N)` or `array([_N, ..])`
+//! - `CALL { id: N, inputs: [_N, ..], outputs: [_N, ..] }`
+//!
+//! `MEMORY_OP`, `BRILLIG_CALL` and `CALL` accept an optional `predicate: expression` as their last field.
+//!
+//! Brillig opcodes are written in snake case, e.g. `binary_int_op { .. }` or `return`, and black box operations
+//! within Brillig functions as `black_box::name { .. }`.
+//!
+//! The fields of each opcode share the names of the fields of its Rust definition and must appear in the same order.
+//! Fixed length tuples of witnesses are written as `(_N, _M)`.
+use std::str::FromStr;
+
+use acir_field::AcirField;
+use thiserror::Error;
+
+use crate::circuit::{opcodes::InvalidInputBitSize, Program};
+
+mod lexer;
+mod parser;
+mod printer;
+
+pub use printer::{print_brillig_opcode, print_program};
+
+/// A position within the source text of a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParserError {
+    #[error("{location}: unexpected character `{character}`")]
+    UnexpectedCharacter { character: char, location: Location },
+    #[error("{location}: unterminated string")]
+    UnterminatedString { location: Location },
+    #[error("{location}: expected {expected}, found {found}")]
+    UnexpectedToken { expected: String, found: String, location: Location },
+    #[error("{location}: invalid {kind} `{value}`")]
+    InvalidValue { kind: &'static str, value: String, location: Location },
+    #[error("{location}: expected {expected} elements, found {found}")]
+    UnexpectedLength { expected: usize, found: usize, location: Location },
+    #[error("{location}: expected {kind} function {expected}, found function {found}")]
+    UnexpectedFunctionIndex {
+        kind: &'static str,
+        expected: usize,
+        found: usize,
+        location: Location,
+    },
+    #[error("{location}: {error}")]
+    InvalidInputBitSize { error: InvalidInputBitSize, location: Location },
+}
+
+/// Parses a [`Program`] from its textual assembly format.
+pub fn parse_program<F: AcirField>(source: &str) -> Result<Program<F>, ParserError> {
+    let tokens = lexer::Lexer::new(source).lex()?;
+    parser::Parser::new(tokens).parse_program()
+}
+
+impl<F: AcirField> FromStr for Program<F> {
+    type Err = ParserError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        parse_program(source)
+    }
+}
+
+#[cfg(test)]
+mod tests;
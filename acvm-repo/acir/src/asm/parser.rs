@@ -0,0 +1,1137 @@
+//! Reads a [`Program`] from the textual assembly format described in the [module documentation][super].
+use std::collections::BTreeSet;
+
+use acir_field::AcirField;
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BitSize, BlackBoxOp, HeapArray, HeapValueType, HeapVector,
+    IntegerBitSize, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+};
+
+use super::{
+    lexer::{SpannedToken, Token},
+    Location, ParserError,
+};
+use crate::{
+    circuit::{
+        brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::{AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp},
+        AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, OpcodeLocation,
+        Program, PublicInputs,
+    },
+    native_types::{Expression, Witness},
+    BlackBoxFunc,
+};
+
+type ParseResult<T> = Result<T, ParserError>;
+
+pub(super) struct Parser {
+    tokens: Vec<SpannedToken>,
+    position: usize,
+}
+
+impl Parser {
+    /// Creates a parser over `tokens`, which must end with a [`Token::Eof`].
+    pub(super) fn new(tokens: Vec<SpannedToken>) -> Self {
+        Parser { tokens, position: 0 }
+    }
+
+    pub(super) fn parse_program<F: AcirField>(mut self) -> ParseResult<Program<F>> {
+        let mut program = Program::default();
+        loop {
+            let location = self.location();
+            if self.eat_keyword("func") {
+                let index = self.parse_usize()?;
+                check_function_index("ACIR", program.functions.len(), index, location)?;
+                program.functions.push(self.parse_circuit()?);
+            } else if self.eat_keyword("brillig") {
+                self.expect_keyword("func")?;
+                let index = self.parse_usize()?;
+                let expected = program.unconstrained_functions.len();
+                check_function_index("Brillig", expected, index, location)?;
+                program.unconstrained_functions.push(self.parse_brillig_function()?);
+            } else if self.peek() == &Token::Eof {
+                return Ok(program);
+            } else {
+                return Err(self.unexpected("`func` or `brillig func`"));
+            }
+        }
+    }
+
+    fn parse_circuit<F: AcirField>(&mut self) -> ParseResult<Circuit<F>> {
+        self.expect(Token::LeftBrace)?;
+        let current_witness_index =
+            self.parse_labelled("current_witness_index", Self::parse_u32)?;
+        let expression_width = self
+            .parse_optional_labelled("expression_width", Self::parse_expression_width)?
+            .unwrap_or_default();
+        let private_parameters = self
+            .parse_optional_labelled("private_parameters", Self::parse_witness_set)?
+            .unwrap_or_default();
+        let public_parameters = self
+            .parse_optional_labelled("public_parameters", Self::parse_witness_set)?
+            .unwrap_or_default();
+        let return_values = self
+            .parse_optional_labelled("return_values", Self::parse_witness_set)?
+            .unwrap_or_default();
+        let recursive =
+            self.parse_optional_labelled("recursive", Self::parse_bool)?.unwrap_or_default();
+        let assert_messages = self
+            .parse_optional_labelled("assert_messages", |parser| {
+                parser.parse_list(Self::parse_assert_message)
+            })?
+            .unwrap_or_default();
+
+        let mut opcodes = Vec::new();
+        while !self.eat(Token::RightBrace) {
+            opcodes.push(self.parse_opcode()?);
+        }
+
+        Ok(Circuit {
+            current_witness_index,
+            opcodes,
+            expression_width,
+            private_parameters,
+            public_parameters: PublicInputs(public_parameters),
+            return_values: PublicInputs(return_values),
+            assert_messages,
+            recursive,
+        })
+    }
+
+    fn parse_brillig_function<F: AcirField>(&mut self) -> ParseResult<BrilligBytecode<F>> {
+        self.expect(Token::LeftBrace)?;
+        let mut bytecode = Vec::new();
+        while !self.eat(Token::RightBrace) {
+            bytecode.push(self.parse_brillig_opcode()?);
+        }
+        Ok(BrilligBytecode { bytecode })
+    }
+
+    fn parse_expression_width(&mut self) -> ParseResult<ExpressionWidth> {
+        if self.eat_keyword("unbounded") {
+            Ok(ExpressionWidth::Unbounded)
+        } else if self.eat_keyword("bounded") {
+            let width = self.parse_parenthesized(Self::parse_usize)?;
+            Ok(ExpressionWidth::Bounded { width })
+        } else {
+            Err(self.unexpected("`unbounded` or `bounded`"))
+        }
+    }
+
+    fn parse_assert_message<F: AcirField>(
+        &mut self,
+    ) -> ParseResult<(OpcodeLocation, AssertionPayload<F>)> {
+        let acir_index = self.parse_usize()?;
+        let location = if self.eat(Token::Dot) {
+            let brillig_index = self.parse_usize()?;
+            OpcodeLocation::Brillig { acir_index, brillig_index }
+        } else {
+            OpcodeLocation::Acir(acir_index)
+        };
+        self.expect(Token::Colon)?;
+
+        let payload = if let Token::Str(message) = self.peek() {
+            let message = message.clone();
+            self.bump();
+            AssertionPayload::StaticString(message)
+        } else if self.eat_keyword("dynamic") {
+            self.expect(Token::LeftParen)?;
+            let selector = self.parse_u64()?;
+            self.expect(Token::Comma)?;
+            let items = self.parse_list(|parser| {
+                if parser.peek() == &Token::LeftBracket {
+                    Ok(ExpressionOrMemory::Expression(parser.parse_expression()?))
+                } else {
+                    Ok(ExpressionOrMemory::Memory(parser.parse_block_id()?))
+                }
+            })?;
+            self.expect(Token::RightParen)?;
+            AssertionPayload::Dynamic(selector, items)
+        } else {
+            return Err(self.unexpected("an assertion payload"));
+        };
+        Ok((location, payload))
+    }
+
+    fn parse_opcode<F: AcirField>(&mut self) -> ParseResult<Opcode<F>> {
+        let (name, location) = self.parse_ident()?;
+        let opcode = match name.as_str() {
+            "ASSERT_ZERO" => Opcode::AssertZero(self.parse_expression()?),
+            "BLACKBOX" => {
+                self.expect(Token::DoubleColon)?;
+                Opcode::BlackBoxFuncCall(self.parse_black_box_func_call()?)
+            }
+            "DIRECTIVE" => {
+                self.expect(Token::DoubleColon)?;
+                self.expect_keyword("TO_LE_RADIX")?;
+                self.expect(Token::LeftBrace)?;
+                let a = self.parse_labelled("a", Self::parse_expression)?;
+                let b = self.parse_labelled("b", Self::parse_witnesses)?;
+                let radix = self.parse_labelled("radix", Self::parse_u32)?;
+                self.expect(Token::RightBrace)?;
+                Opcode::Directive(Directive::ToLeRadix { a, b, radix })
+            }
+            "MEMORY_INIT" => {
+                self.expect(Token::LeftBrace)?;
+                let block_id = self.parse_labelled("block_id", Self::parse_block_id)?;
+                let init = self.parse_labelled("init", Self::parse_witnesses)?;
+                let block_type = self.parse_labelled("block_type", Self::parse_block_type)?;
+                self.expect(Token::RightBrace)?;
+                Opcode::MemoryInit { block_id, init, block_type }
+            }
+            "MEMORY_OP" => {
+                self.expect(Token::LeftBrace)?;
+                let block_id = self.parse_labelled("block_id", Self::parse_block_id)?;
+                let operation = self.parse_labelled("operation", Self::parse_expression)?;
+                let index = self.parse_labelled("index", Self::parse_expression)?;
+                let value = self.parse_labelled("value", Self::parse_expression)?;
+                let predicate = self.parse_predicate()?;
+                Opcode::MemoryOp { block_id, op: MemOp { operation, index, value }, predicate }
+            }
+            "BRILLIG_CALL" => {
+                self.expect(Token::LeftBrace)?;
+                let id = BrilligFunctionId(self.parse_labelled("id", Self::parse_u32)?);
+                let inputs = self.parse_labelled("inputs", |parser| {
+                    parser.parse_list(Self::parse_brillig_input)
+                })?;
+                let outputs = self.parse_labelled("outputs", |parser| {
+                    parser.parse_list(Self::parse_brillig_output)
+                })?;
+                let predicate = self.parse_predicate()?;
+                Opcode::BrilligCall { id, inputs, outputs, predicate }
+            }
+            "CALL" => {
+                self.expect(Token::LeftBrace)?;
+                let id = AcirFunctionId(self.parse_labelled("id", Self::parse_u32)?);
+                let inputs = self.parse_labelled("inputs", Self::parse_witnesses)?;
+                let outputs = self.parse_labelled("outputs", Self::parse_witnesses)?;
+                let predicate = self.parse_predicate()?;
+                Opcode::Call { id, inputs, outputs, predicate }
+            }
+            _ => {
+                return Err(ParserError::UnexpectedToken {
+                    expected: "an ACIR opcode".to_string(),
+                    found: Token::Ident(name).to_string(),
+                    location,
+                })
+            }
+        };
+        Ok(opcode)
+    }
+
+    /// Parses the optional predicate of an opcode along with the closing brace of its fields.
+    fn parse_predicate<F: AcirField>(&mut self) -> ParseResult<Option<Expression<F>>> {
+        let predicate = self.parse_optional_labelled("predicate", Self::parse_expression)?;
+        self.expect(Token::RightBrace)?;
+        Ok(predicate)
+    }
+
+    fn parse_block_type(&mut self) -> ParseResult<BlockType> {
+        if self.eat_keyword("memory") {
+            Ok(BlockType::Memory)
+        } else if self.eat_keyword("call_data") {
+            Ok(BlockType::CallData(self.parse_parenthesized(Self::parse_u32)?))
+        } else if self.eat_keyword("return_data") {
+            Ok(BlockType::ReturnData)
+        } else {
+            Err(self.unexpected("a block type"))
+        }
+    }
+
+    fn parse_brillig_input<F: AcirField>(&mut self) -> ParseResult<BrilligInputs<F>> {
+        if self.eat_keyword("single") {
+            Ok(BrilligInputs::Single(self.parse_parenthesized(Self::parse_expression)?))
+        } else if self.eat_keyword("array") {
+            let expressions =
+                self.parse_parenthesized(|parser| parser.parse_list(Self::parse_expression))?;
+            Ok(BrilligInputs::Array(expressions))
+        } else if self.eat_keyword("memory_array") {
+            Ok(BrilligInputs::MemoryArray(self.parse_parenthesized(Self::parse_block_id)?))
+        } else {
+            Err(self.unexpected("a Brillig input"))
+        }
+    }
+
+    fn parse_brillig_output(&mut self) -> ParseResult<BrilligOutputs> {
+        if self.eat_keyword("simple") {
+            Ok(BrilligOutputs::Simple(self.parse_parenthesized(Self::parse_witness)?))
+        } else if self.eat_keyword("array") {
+            Ok(BrilligOutputs::Array(self.parse_parenthesized(Self::parse_witnesses)?))
+        } else {
+            Err(self.unexpected("a Brillig output"))
+        }
+    }
+
+    fn parse_black_box_func_call<F: AcirField>(&mut self) -> ParseResult<BlackBoxFuncCall<F>> {
+        let (name, location) = self.parse_ident()?;
+        let Some(func) = BlackBoxFunc::lookup(&name.to_lowercase()) else {
+            return Err(ParserError::InvalidValue {
+                kind: "black box function",
+                value: name,
+                location,
+            });
+        };
+
+        self.expect(Token::LeftBrace)?;
+        let call = match func {
+            BlackBoxFunc::AES128Encrypt => BlackBoxFuncCall::AES128Encrypt {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                iv: self.parse_labelled("iv", Self::parse_function_input_array)?,
+                key: self.parse_labelled("key", Self::parse_function_input_array)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witnesses)?,
+            },
+            BlackBoxFunc::AND => BlackBoxFuncCall::AND {
+                lhs: self.parse_labelled("lhs", Self::parse_function_input)?,
+                rhs: self.parse_labelled("rhs", Self::parse_function_input)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::XOR => BlackBoxFuncCall::XOR {
+                lhs: self.parse_labelled("lhs", Self::parse_function_input)?,
+                rhs: self.parse_labelled("rhs", Self::parse_function_input)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::RANGE => BlackBoxFuncCall::RANGE {
+                input: self.parse_labelled("input", Self::parse_function_input)?,
+            },
+            BlackBoxFunc::Blake2s => BlackBoxFuncCall::Blake2s {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_array)?,
+            },
+            BlackBoxFunc::Blake3 => BlackBoxFuncCall::Blake3 {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_array)?,
+            },
+            BlackBoxFunc::SchnorrVerify => BlackBoxFuncCall::SchnorrVerify {
+                public_key_x: self.parse_labelled("public_key_x", Self::parse_function_input)?,
+                public_key_y: self.parse_labelled("public_key_y", Self::parse_function_input)?,
+                signature: self.parse_labelled("signature", Self::parse_function_input_array)?,
+                message: self.parse_labelled("message", Self::parse_function_inputs)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::PedersenCommitment => BlackBoxFuncCall::PedersenCommitment {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                domain_separator: self.parse_labelled("domain_separator", Self::parse_u32)?,
+                outputs: self.parse_labelled("outputs", |parser| {
+                    let [first, second] = parser.parse_witness_tuple()?;
+                    Ok((first, second))
+                })?,
+            },
+            BlackBoxFunc::PedersenHash => BlackBoxFuncCall::PedersenHash {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                domain_separator: self.parse_labelled("domain_separator", Self::parse_u32)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::EcdsaSecp256k1 => BlackBoxFuncCall::EcdsaSecp256k1 {
+                public_key_x: self
+                    .parse_labelled("public_key_x", Self::parse_function_input_array)?,
+                public_key_y: self
+                    .parse_labelled("public_key_y", Self::parse_function_input_array)?,
+                signature: self.parse_labelled("signature", Self::parse_function_input_array)?,
+                hashed_message: self
+                    .parse_labelled("hashed_message", Self::parse_function_input_array)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::EcdsaSecp256r1 => BlackBoxFuncCall::EcdsaSecp256r1 {
+                public_key_x: self
+                    .parse_labelled("public_key_x", Self::parse_function_input_array)?,
+                public_key_y: self
+                    .parse_labelled("public_key_y", Self::parse_function_input_array)?,
+                signature: self.parse_labelled("signature", Self::parse_function_input_array)?,
+                hashed_message: self
+                    .parse_labelled("hashed_message", Self::parse_function_input_array)?,
+                output: self.parse_labelled("output", Self::parse_witness)?,
+            },
+            BlackBoxFunc::MultiScalarMul => BlackBoxFuncCall::MultiScalarMul {
+                points: self.parse_labelled("points", Self::parse_function_inputs)?,
+                scalars: self.parse_labelled("scalars", Self::parse_function_inputs)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_triple)?,
+            },
+            BlackBoxFunc::EmbeddedCurveAdd => BlackBoxFuncCall::EmbeddedCurveAdd {
+                input1: self.parse_labelled("input1", Self::parse_function_input_array)?,
+                input2: self.parse_labelled("input2", Self::parse_function_input_array)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_triple)?,
+            },
+            BlackBoxFunc::Keccak256 => BlackBoxFuncCall::Keccak256 {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                var_message_size: self
+                    .parse_labelled("var_message_size", Self::parse_function_input)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_array)?,
+            },
+            BlackBoxFunc::Keccakf1600 => BlackBoxFuncCall::Keccakf1600 {
+                inputs: self.parse_labelled("inputs", Self::parse_function_input_array)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_array)?,
+            },
+            BlackBoxFunc::RecursiveAggregation => BlackBoxFuncCall::RecursiveAggregation {
+                verification_key: self
+                    .parse_labelled("verification_key", Self::parse_function_inputs)?,
+                proof: self.parse_labelled("proof", Self::parse_function_inputs)?,
+                public_inputs: self.parse_labelled("public_inputs", Self::parse_function_inputs)?,
+                key_hash: self.parse_labelled("key_hash", Self::parse_function_input)?,
+                proof_type: self.parse_labelled("proof_type", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntAdd => BlackBoxFuncCall::BigIntAdd {
+                lhs: self.parse_labelled("lhs", Self::parse_u32)?,
+                rhs: self.parse_labelled("rhs", Self::parse_u32)?,
+                output: self.parse_labelled("output", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntSub => BlackBoxFuncCall::BigIntSub {
+                lhs: self.parse_labelled("lhs", Self::parse_u32)?,
+                rhs: self.parse_labelled("rhs", Self::parse_u32)?,
+                output: self.parse_labelled("output", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntMul => BlackBoxFuncCall::BigIntMul {
+                lhs: self.parse_labelled("lhs", Self::parse_u32)?,
+                rhs: self.parse_labelled("rhs", Self::parse_u32)?,
+                output: self.parse_labelled("output", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntDiv => BlackBoxFuncCall::BigIntDiv {
+                lhs: self.parse_labelled("lhs", Self::parse_u32)?,
+                rhs: self.parse_labelled("rhs", Self::parse_u32)?,
+                output: self.parse_labelled("output", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntFromLeBytes => BlackBoxFuncCall::BigIntFromLeBytes {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                modulus: self
+                    .parse_labelled("modulus", |parser| parser.parse_list(Self::parse_u8))?,
+                output: self.parse_labelled("output", Self::parse_u32)?,
+            },
+            BlackBoxFunc::BigIntToLeBytes => BlackBoxFuncCall::BigIntToLeBytes {
+                input: self.parse_labelled("input", Self::parse_u32)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witnesses)?,
+            },
+            BlackBoxFunc::Poseidon2Permutation => BlackBoxFuncCall::Poseidon2Permutation {
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witnesses)?,
+                len: self.parse_labelled("len", Self::parse_u32)?,
+            },
+            BlackBoxFunc::Sha256Compression => BlackBoxFuncCall::Sha256Compression {
+                inputs: self.parse_labelled("inputs", Self::parse_function_input_array)?,
+                hash_values: self
+                    .parse_labelled("hash_values", Self::parse_function_input_array)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witness_array)?,
+            },
+            BlackBoxFunc::UserDefined => BlackBoxFuncCall::UserDefined {
+                name: self.parse_labelled("name", Self::parse_string)?,
+                inputs: self.parse_labelled("inputs", Self::parse_function_inputs)?,
+                outputs: self.parse_labelled("outputs", Self::parse_witnesses)?,
+            },
+        };
+        self.expect(Token::RightBrace)?;
+        Ok(call)
+    }
+
+    fn parse_brillig_opcode<F: AcirField>(&mut self) -> ParseResult<BrilligOpcode<F>> {
+        let (name, location) = self.parse_ident()?;
+        if name == "return" {
+            return Ok(BrilligOpcode::Return);
+        }
+        if name == "black_box" {
+            self.expect(Token::DoubleColon)?;
+            return Ok(BrilligOpcode::BlackBox(self.parse_black_box_op()?));
+        }
+
+        self.expect(Token::LeftBrace)?;
+        let opcode = match name.as_str() {
+            "binary_field_op" => BrilligOpcode::BinaryFieldOp {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                op: self.parse_labelled("op", Self::parse_binary_field_op)?,
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+            },
+            "binary_int_op" => BrilligOpcode::BinaryIntOp {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                op: self.parse_labelled("op", Self::parse_binary_int_op)?,
+                bit_size: self.parse_labelled("bit_size", Self::parse_integer_bit_size)?,
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+            },
+            "not" => BrilligOpcode::Not {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                source: self.parse_labelled("source", Self::parse_address)?,
+                bit_size: self.parse_labelled("bit_size", Self::parse_integer_bit_size)?,
+            },
+            "cast" => BrilligOpcode::Cast {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                source: self.parse_labelled("source", Self::parse_address)?,
+                bit_size: self.parse_labelled("bit_size", Self::parse_bit_size)?,
+            },
+            "jump_if_not" => BrilligOpcode::JumpIfNot {
+                condition: self.parse_labelled("condition", Self::parse_address)?,
+                location: self.parse_labelled("location", Self::parse_usize)?,
+            },
+            "jump_if" => BrilligOpcode::JumpIf {
+                condition: self.parse_labelled("condition", Self::parse_address)?,
+                location: self.parse_labelled("location", Self::parse_usize)?,
+            },
+            "jump" => BrilligOpcode::Jump {
+                location: self.parse_labelled("location", Self::parse_usize)?,
+            },
+            "calldata_copy" => BrilligOpcode::CalldataCopy {
+                destination_address: self
+                    .parse_labelled("destination_address", Self::parse_address)?,
+                size_address: self.parse_labelled("size_address", Self::parse_address)?,
+                offset_address: self.parse_labelled("offset_address", Self::parse_address)?,
+            },
+            "call" => BrilligOpcode::Call {
+                location: self.parse_labelled("location", Self::parse_usize)?,
+            },
+            "const" => BrilligOpcode::Const {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                bit_size: self.parse_labelled("bit_size", Self::parse_bit_size)?,
+                value: self.parse_labelled("value", Self::parse_field)?,
+            },
+            "indirect_const" => BrilligOpcode::IndirectConst {
+                destination_pointer: self
+                    .parse_labelled("destination_pointer", Self::parse_address)?,
+                bit_size: self.parse_labelled("bit_size", Self::parse_bit_size)?,
+                value: self.parse_labelled("value", Self::parse_field)?,
+            },
+            "foreign_call" => BrilligOpcode::ForeignCall {
+                function: self.parse_labelled("function", Self::parse_string)?,
+                destinations: self.parse_labelled("destinations", |parser| {
+                    parser.parse_list(Self::parse_value_or_array)
+                })?,
+                destination_value_types: self
+                    .parse_labelled("destination_value_types", |parser| {
+                        parser.parse_list(Self::parse_heap_value_type)
+                    })?,
+                inputs: self.parse_labelled("inputs", |parser| {
+                    parser.parse_list(Self::parse_value_or_array)
+                })?,
+                input_value_types: self.parse_labelled("input_value_types", |parser| {
+                    parser.parse_list(Self::parse_heap_value_type)
+                })?,
+            },
+            "mov" => BrilligOpcode::Mov {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                source: self.parse_labelled("source", Self::parse_address)?,
+            },
+            "conditional_mov" => BrilligOpcode::ConditionalMov {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                source_a: self.parse_labelled("source_a", Self::parse_address)?,
+                source_b: self.parse_labelled("source_b", Self::parse_address)?,
+                condition: self.parse_labelled("condition", Self::parse_address)?,
+            },
+            "load" => BrilligOpcode::Load {
+                destination: self.parse_labelled("destination", Self::parse_address)?,
+                source_pointer: self.parse_labelled("source_pointer", Self::parse_address)?,
+            },
+            "store" => BrilligOpcode::Store {
+                destination_pointer: self
+                    .parse_labelled("destination_pointer", Self::parse_address)?,
+                source: self.parse_labelled("source", Self::parse_address)?,
+            },
+            "trap" => BrilligOpcode::Trap {
+                revert_data: self.parse_labelled("revert_data", Self::parse_heap_array)?,
+            },
+            "stop" => BrilligOpcode::Stop {
+                return_data_offset: self.parse_labelled("return_data_offset", Self::parse_usize)?,
+                return_data_size: self.parse_labelled("return_data_size", Self::parse_usize)?,
+            },
+            _ => {
+                return Err(ParserError::UnexpectedToken {
+                    expected: "a Brillig opcode".to_string(),
+                    found: Token::Ident(name).to_string(),
+                    location,
+                })
+            }
+        };
+        self.expect(Token::RightBrace)?;
+        Ok(opcode)
+    }
+
+    fn parse_black_box_op(&mut self) -> ParseResult<BlackBoxOp> {
+        let (name, location) = self.parse_ident()?;
+        self.expect(Token::LeftBrace)?;
+        let op = match name.as_str() {
+            "aes128_encrypt" => BlackBoxOp::AES128Encrypt {
+                inputs: self.parse_labelled("inputs", Self::parse_heap_vector)?,
+                iv: self.parse_labelled("iv", Self::parse_heap_array)?,
+                key: self.parse_labelled("key", Self::parse_heap_array)?,
+                outputs: self.parse_labelled("outputs", Self::parse_heap_vector)?,
+            },
+            "blake2s" => BlackBoxOp::Blake2s {
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "blake3" => BlackBoxOp::Blake3 {
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "keccak256" => BlackBoxOp::Keccak256 {
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "keccakf1600" => BlackBoxOp::Keccakf1600 {
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "ecdsa_secp256k1" => BlackBoxOp::EcdsaSecp256k1 {
+                hashed_msg: self.parse_labelled("hashed_msg", Self::parse_heap_vector)?,
+                public_key_x: self.parse_labelled("public_key_x", Self::parse_heap_array)?,
+                public_key_y: self.parse_labelled("public_key_y", Self::parse_heap_array)?,
+                signature: self.parse_labelled("signature", Self::parse_heap_array)?,
+                result: self.parse_labelled("result", Self::parse_address)?,
+            },
+            "ecdsa_secp256r1" => BlackBoxOp::EcdsaSecp256r1 {
+                hashed_msg: self.parse_labelled("hashed_msg", Self::parse_heap_vector)?,
+                public_key_x: self.parse_labelled("public_key_x", Self::parse_heap_array)?,
+                public_key_y: self.parse_labelled("public_key_y", Self::parse_heap_array)?,
+                signature: self.parse_labelled("signature", Self::parse_heap_array)?,
+                result: self.parse_labelled("result", Self::parse_address)?,
+            },
+            "schnorr_verify" => BlackBoxOp::SchnorrVerify {
+                public_key_x: self.parse_labelled("public_key_x", Self::parse_address)?,
+                public_key_y: self.parse_labelled("public_key_y", Self::parse_address)?,
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                signature: self.parse_labelled("signature", Self::parse_heap_vector)?,
+                result: self.parse_labelled("result", Self::parse_address)?,
+            },
+            "pedersen_commitment" => BlackBoxOp::PedersenCommitment {
+                inputs: self.parse_labelled("inputs", Self::parse_heap_vector)?,
+                domain_separator: self.parse_labelled("domain_separator", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "pedersen_hash" => BlackBoxOp::PedersenHash {
+                inputs: self.parse_labelled("inputs", Self::parse_heap_vector)?,
+                domain_separator: self.parse_labelled("domain_separator", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "multi_scalar_mul" => BlackBoxOp::MultiScalarMul {
+                points: self.parse_labelled("points", Self::parse_heap_vector)?,
+                scalars: self.parse_labelled("scalars", Self::parse_heap_vector)?,
+                outputs: self.parse_labelled("outputs", Self::parse_heap_array)?,
+            },
+            "embedded_curve_add" => BlackBoxOp::EmbeddedCurveAdd {
+                input1_x: self.parse_labelled("input1_x", Self::parse_address)?,
+                input1_y: self.parse_labelled("input1_y", Self::parse_address)?,
+                input1_infinite: self.parse_labelled("input1_infinite", Self::parse_address)?,
+                input2_x: self.parse_labelled("input2_x", Self::parse_address)?,
+                input2_y: self.parse_labelled("input2_y", Self::parse_address)?,
+                input2_infinite: self.parse_labelled("input2_infinite", Self::parse_address)?,
+                result: self.parse_labelled("result", Self::parse_heap_array)?,
+            },
+            "bigint_add" => BlackBoxOp::BigIntAdd {
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "bigint_sub" => BlackBoxOp::BigIntSub {
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "bigint_mul" => BlackBoxOp::BigIntMul {
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "bigint_div" => BlackBoxOp::BigIntDiv {
+                lhs: self.parse_labelled("lhs", Self::parse_address)?,
+                rhs: self.parse_labelled("rhs", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "bigint_from_le_bytes" => BlackBoxOp::BigIntFromLeBytes {
+                inputs: self.parse_labelled("inputs", Self::parse_heap_vector)?,
+                modulus: self.parse_labelled("modulus", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_address)?,
+            },
+            "bigint_to_le_bytes" => BlackBoxOp::BigIntToLeBytes {
+                input: self.parse_labelled("input", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_heap_vector)?,
+            },
+            "poseidon2_permutation" => BlackBoxOp::Poseidon2Permutation {
+                message: self.parse_labelled("message", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+                len: self.parse_labelled("len", Self::parse_address)?,
+            },
+            "sha256_compression" => BlackBoxOp::Sha256Compression {
+                input: self.parse_labelled("input", Self::parse_heap_vector)?,
+                hash_values: self.parse_labelled("hash_values", Self::parse_heap_vector)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+            },
+            "to_radix" => BlackBoxOp::ToRadix {
+                input: self.parse_labelled("input", Self::parse_address)?,
+                radix: self.parse_labelled("radix", Self::parse_address)?,
+                output: self.parse_labelled("output", Self::parse_heap_array)?,
+                output_bits: self.parse_labelled("output_bits", Self::parse_bool)?,
+            },
+            "user_defined" => BlackBoxOp::UserDefined {
+                name: self.parse_labelled("name", Self::parse_string)?,
+                inputs: self.parse_labelled("inputs", |parser| {
+                    parser.parse_list(Self::parse_value_or_array)
+                })?,
+                outputs: self.parse_labelled("outputs", |parser| {
+                    parser.parse_list(Self::parse_value_or_array)
+                })?,
+            },
+            _ => {
+                return Err(ParserError::InvalidValue {
+                    kind: "black box operation",
+                    value: name,
+                    location,
+                })
+            }
+        };
+        self.expect(Token::RightBrace)?;
+        Ok(op)
+    }
+
+    fn parse_expression<F: AcirField>(&mut self) -> ParseResult<Expression<F>> {
+        self.expect(Token::LeftBracket)?;
+        let mut expression = Expression::default();
+        while self.eat(Token::LeftParen) {
+            let coefficient = self.parse_field()?;
+            self.expect(Token::Comma)?;
+            let lhs = self.parse_witness()?;
+            if self.eat(Token::Comma) {
+                let rhs = self.parse_witness()?;
+                expression.mul_terms.push((coefficient, lhs, rhs));
+            } else {
+                expression.linear_combinations.push((coefficient, lhs));
+            }
+            self.expect(Token::RightParen)?;
+        }
+        expression.q_c = self.parse_field()?;
+        self.expect(Token::RightBracket)?;
+        Ok(expression)
+    }
+
+    fn parse_function_input<F: AcirField>(&mut self) -> ParseResult<FunctionInput<F>> {
+        self.expect(Token::LeftParen)?;
+        let location = self.location();
+        let input = if self.peek_witness() {
+            let witness = self.parse_witness()?;
+            self.expect(Token::Comma)?;
+            FunctionInput::witness(witness, self.parse_u32()?)
+        } else {
+            let value = self.parse_field()?;
+            self.expect(Token::Comma)?;
+            FunctionInput::constant(value, self.parse_u32()?)
+                .map_err(|error| ParserError::InvalidInputBitSize { error, location })?
+        };
+        self.expect(Token::RightParen)?;
+        Ok(input)
+    }
+
+    fn parse_function_inputs<F: AcirField>(&mut self) -> ParseResult<Vec<FunctionInput<F>>> {
+        self.parse_list(Self::parse_function_input)
+    }
+
+    fn parse_function_input_array<F: AcirField, const N: usize>(
+        &mut self,
+    ) -> ParseResult<Box<[FunctionInput<F>; N]>> {
+        self.parse_array(Self::parse_function_input)
+    }
+
+    fn parse_witness_array<const N: usize>(&mut self) -> ParseResult<Box<[Witness; N]>> {
+        self.parse_array(Self::parse_witness)
+    }
+
+    /// Parses a list which must contain exactly `N` items.
+    fn parse_array<T, const N: usize>(
+        &mut self,
+        parse_item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Box<[T; N]>> {
+        let location = self.location();
+        let items = self.parse_list(parse_item)?;
+        let found = items.len();
+        let items: Box<[T; N]> = items
+            .into_boxed_slice()
+            .try_into()
+            .map_err(|_| ParserError::UnexpectedLength { expected: N, found, location })?;
+        Ok(items)
+    }
+
+    fn parse_witness_tuple<const N: usize>(&mut self) -> ParseResult<[Witness; N]> {
+        let location = self.location();
+        self.expect(Token::LeftParen)?;
+        let mut witnesses = Vec::new();
+        while !self.eat(Token::RightParen) {
+            witnesses.push(self.parse_witness()?);
+            if !self.eat(Token::Comma) {
+                self.expect(Token::RightParen)?;
+                break;
+            }
+        }
+        let found = witnesses.len();
+        witnesses.try_into().map_err(|_| ParserError::UnexpectedLength {
+            expected: N,
+            found,
+            location,
+        })
+    }
+
+    fn parse_witness_triple(&mut self) -> ParseResult<(Witness, Witness, Witness)> {
+        let [first, second, third] = self.parse_witness_tuple()?;
+        Ok((first, second, third))
+    }
+
+    fn parse_witness(&mut self) -> ParseResult<Witness> {
+        let index = self.parse_prefixed_index('_', "a witness")?;
+        Ok(Witness(index))
+    }
+
+    fn peek_witness(&self) -> bool {
+        matches!(self.peek(), Token::Ident(ident) if ident.starts_with('_'))
+    }
+
+    fn parse_witnesses(&mut self) -> ParseResult<Vec<Witness>> {
+        self.parse_list(Self::parse_witness)
+    }
+
+    fn parse_witness_set(&mut self) -> ParseResult<BTreeSet<Witness>> {
+        Ok(self.parse_witnesses()?.into_iter().collect())
+    }
+
+    fn parse_block_id(&mut self) -> ParseResult<BlockId> {
+        let index = self.parse_prefixed_index('b', "a block id")?;
+        Ok(BlockId(index))
+    }
+
+    /// Parses an identifier consisting of `prefix` followed by an index, e.g. `_3` or `b0`.
+    fn parse_prefixed_index(&mut self, prefix: char, expected: &str) -> ParseResult<u32> {
+        let location = self.location();
+        let index = match self.peek() {
+            Token::Ident(ident) => ident.strip_prefix(prefix).and_then(|index| index.parse().ok()),
+            _ => None,
+        };
+        match index {
+            Some(index) => {
+                self.bump();
+                Ok(index)
+            }
+            None => Err(ParserError::UnexpectedToken {
+                expected: expected.to_string(),
+                found: self.peek().to_string(),
+                location,
+            }),
+        }
+    }
+
+    fn parse_address(&mut self) -> ParseResult<MemoryAddress> {
+        self.expect(Token::At)?;
+        Ok(MemoryAddress(self.parse_usize()?))
+    }
+
+    fn parse_heap_array(&mut self) -> ParseResult<HeapArray> {
+        self.expect_keyword("array")?;
+        self.expect(Token::LeftParen)?;
+        let pointer = self.parse_address()?;
+        self.expect(Token::Comma)?;
+        let size = self.parse_usize()?;
+        self.expect(Token::RightParen)?;
+        Ok(HeapArray { pointer, size })
+    }
+
+    fn parse_heap_vector(&mut self) -> ParseResult<HeapVector> {
+        self.expect_keyword("vector")?;
+        self.expect(Token::LeftParen)?;
+        let pointer = self.parse_address()?;
+        self.expect(Token::Comma)?;
+        let size = self.parse_address()?;
+        self.expect(Token::RightParen)?;
+        Ok(HeapVector { pointer, size })
+    }
+
+    fn parse_value_or_array(&mut self) -> ParseResult<ValueOrArray> {
+        match self.peek() {
+            Token::At => Ok(ValueOrArray::MemoryAddress(self.parse_address()?)),
+            Token::Ident(ident) if ident == "array" => {
+                Ok(ValueOrArray::HeapArray(self.parse_heap_array()?))
+            }
+            Token::Ident(ident) if ident == "vector" => {
+                Ok(ValueOrArray::HeapVector(self.parse_heap_vector()?))
+            }
+            _ => Err(self.unexpected("a memory address, heap array or heap vector")),
+        }
+    }
+
+    fn parse_heap_value_type(&mut self) -> ParseResult<HeapValueType> {
+        if self.eat_keyword("array") {
+            self.expect(Token::LeftParen)?;
+            let value_types = self.parse_list(Self::parse_heap_value_type)?;
+            self.expect(Token::Comma)?;
+            let size = self.parse_usize()?;
+            self.expect(Token::RightParen)?;
+            Ok(HeapValueType::Array { value_types, size })
+        } else if self.eat_keyword("vector") {
+            let value_types =
+                self.parse_parenthesized(|parser| parser.parse_list(Self::parse_heap_value_type))?;
+            Ok(HeapValueType::Vector { value_types })
+        } else {
+            Ok(HeapValueType::Simple(self.parse_bit_size()?))
+        }
+    }
+
+    fn parse_bit_size(&mut self) -> ParseResult<BitSize> {
+        if self.eat_keyword("field") {
+            Ok(BitSize::Field)
+        } else {
+            Ok(BitSize::Integer(self.parse_integer_bit_size()?))
+        }
+    }
+
+    fn parse_integer_bit_size(&mut self) -> ParseResult<IntegerBitSize> {
+        let (name, location) = self.parse_ident()?;
+        name.strip_prefix('u')
+            .and_then(|bit_size| bit_size.parse::<u32>().ok())
+            .and_then(|bit_size| IntegerBitSize::try_from(bit_size).ok())
+            .ok_or(ParserError::InvalidValue { kind: "bit size", value: name, location })
+    }
+
+    fn parse_binary_field_op(&mut self) -> ParseResult<BinaryFieldOp> {
+        let (name, location) = self.parse_ident()?;
+        let op = match name.as_str() {
+            "add" => BinaryFieldOp::Add,
+            "sub" => BinaryFieldOp::Sub,
+            "mul" => BinaryFieldOp::Mul,
+            "div" => BinaryFieldOp::Div,
+            "integer_div" => BinaryFieldOp::IntegerDiv,
+            "equals" => BinaryFieldOp::Equals,
+            "less_than" => BinaryFieldOp::LessThan,
+            "less_than_equals" => BinaryFieldOp::LessThanEquals,
+            _ => {
+                return Err(ParserError::InvalidValue {
+                    kind: "binary field operation",
+                    value: name,
+                    location,
+                })
+            }
+        };
+        Ok(op)
+    }
+
+    fn parse_binary_int_op(&mut self) -> ParseResult<BinaryIntOp> {
+        let (name, location) = self.parse_ident()?;
+        let op = match name.as_str() {
+            "add" => BinaryIntOp::Add,
+            "sub" => BinaryIntOp::Sub,
+            "mul" => BinaryIntOp::Mul,
+            "div" => BinaryIntOp::Div,
+            "equals" => BinaryIntOp::Equals,
+            "less_than" => BinaryIntOp::LessThan,
+            "less_than_equals" => BinaryIntOp::LessThanEquals,
+            "and" => BinaryIntOp::And,
+            "or" => BinaryIntOp::Or,
+            "xor" => BinaryIntOp::Xor,
+            "shl" => BinaryIntOp::Shl,
+            "shr" => BinaryIntOp::Shr,
+            _ => {
+                return Err(ParserError::InvalidValue {
+                    kind: "binary integer operation",
+                    value: name,
+                    location,
+                })
+            }
+        };
+        Ok(op)
+    }
+
+    /// Parses a field element written as an optionally negated decimal integer, or as a hexadecimal integer.
+    fn parse_field<F: AcirField>(&mut self) -> ParseResult<F> {
+        let is_negative = self.eat(Token::Minus);
+        let location = self.location();
+        let value = match self.bump().token {
+            Token::Int(int) => int.parse::<u128>().map(F::from).map_err(|_| {
+                ParserError::InvalidValue { kind: "field element", value: int, location }
+            })?,
+            Token::Hex(hex) => F::from_hex(&hex).ok_or(ParserError::InvalidValue {
+                kind: "field element",
+                value: hex,
+                location,
+            })?,
+            token => {
+                return Err(ParserError::UnexpectedToken {
+                    expected: "a field element".to_string(),
+                    found: token.to_string(),
+                    location,
+                })
+            }
+        };
+        Ok(if is_negative { -value } else { value })
+    }
+
+    fn parse_bool(&mut self) -> ParseResult<bool> {
+        if self.eat_keyword("true") {
+            Ok(true)
+        } else if self.eat_keyword("false") {
+            Ok(false)
+        } else {
+            Err(self.unexpected("`true` or `false`"))
+        }
+    }
+
+    fn parse_string(&mut self) -> ParseResult<String> {
+        match self.peek().clone() {
+            Token::Str(string) => {
+                self.bump();
+                Ok(string)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    fn parse_u8(&mut self) -> ParseResult<u8> {
+        self.parse_int("u8")
+    }
+
+    fn parse_u32(&mut self) -> ParseResult<u32> {
+        self.parse_int("u32")
+    }
+
+    fn parse_u64(&mut self) -> ParseResult<u64> {
+        self.parse_int("u64")
+    }
+
+    fn parse_usize(&mut self) -> ParseResult<usize> {
+        self.parse_int("usize")
+    }
+
+    fn parse_int<T: std::str::FromStr>(&mut self, kind: &'static str) -> ParseResult<T> {
+        let location = self.location();
+        match self.peek().clone() {
+            Token::Int(int) => {
+                self.bump();
+                int.parse().map_err(|_| ParserError::InvalidValue { kind, value: int, location })
+            }
+            _ => Err(self.unexpected("an integer")),
+        }
+    }
+
+    fn parse_ident(&mut self) -> ParseResult<(String, Location)> {
+        let location = self.location();
+        match self.peek().clone() {
+            Token::Ident(ident) => {
+                self.bump();
+                Ok((ident, location))
+            }
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    /// Parses a comma separated list of items surrounded by square brackets.
+    fn parse_list<T>(
+        &mut self,
+        mut parse_item: impl FnMut(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Vec<T>> {
+        self.expect(Token::LeftBracket)?;
+        let mut items = Vec::new();
+        while !self.eat(Token::RightBracket) {
+            items.push(parse_item(self)?);
+            if !self.eat(Token::Comma) {
+                self.expect(Token::RightBracket)?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    fn parse_parenthesized<T>(
+        &mut self,
+        parse_item: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.expect(Token::LeftParen)?;
+        let item = parse_item(self)?;
+        self.expect(Token::RightParen)?;
+        Ok(item)
+    }
+
+    /// Parses a field of the form `label: value`, along with an optional trailing comma.
+    fn parse_labelled<T>(
+        &mut self,
+        label: &str,
+        parse_value: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.expect_keyword(label)?;
+        self.expect(Token::Colon)?;
+        let value = parse_value(self)?;
+        self.eat(Token::Comma);
+        Ok(value)
+    }
+
+    fn parse_optional_labelled<T>(
+        &mut self,
+        label: &str,
+        parse_value: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<Option<T>> {
+        if matches!(self.peek(), Token::Ident(ident) if ident == label) {
+            self.parse_labelled(label, parse_value).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].token
+    }
+
+    fn location(&self) -> Location {
+        self.tokens[self.position].location
+    }
+
+    fn bump(&mut self) -> SpannedToken {
+        let token = self.tokens[self.position].clone();
+        // The final `Eof` token is never consumed so that it can be peeked at repeatedly.
+        if self.position < self.tokens.len() - 1 {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        if self.peek() == &token {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if matches!(self.peek(), Token::Ident(ident) if ident == keyword) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> ParseResult<()> {
+        if self.eat(token.clone()) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&token.to_string()))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> ParseResult<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{keyword}`")))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> ParserError {
+        ParserError::UnexpectedToken {
+            expected: expected.to_string(),
+            found: self.peek().to_string(),
+            location: self.location(),
+        }
+    }
+}
+
+fn check_function_index(
+    kind: &'static str,
+    expected: usize,
+    found: usize,
+    location: Location,
+) -> ParseResult<()> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(ParserError::UnexpectedFunctionIndex { kind, expected, found, location })
+    }
+}
@@ -0,0 +1,708 @@
+//! Writes a [`Program`] out in the textual assembly format described in the [module documentation][super].
+use acir_field::AcirField;
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BitSize, BlackBoxOp, HeapArray, HeapValueType, HeapVector,
+    MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+};
+
+use crate::{
+    circuit::{
+        brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::{BlackBoxFuncCall, BlockId, BlockType, ConstantOrWitnessEnum, FunctionInput},
+        AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, Program,
+    },
+    native_types::{Expression, Witness},
+};
+
+/// Writes out a [`Program`] in the textual assembly format so that it can be read back in
+/// with [`parse_program`][super::parse_program].
+pub fn print_program<F: AcirField>(program: &Program<F>) -> String {
+    let mut functions = Vec::new();
+    for (index, circuit) in program.functions.iter().enumerate() {
+        functions.push(print_circuit(index, circuit));
+    }
+    for (index, function) in program.unconstrained_functions.iter().enumerate() {
+        functions.push(print_brillig_function(index, function));
+    }
+    functions.join("\n")
+}
+
+fn print_circuit<F: AcirField>(index: usize, circuit: &Circuit<F>) -> String {
+    let mut lines = vec![format!("func {index} {{")];
+    lines.push(format!("    current_witness_index: {}", circuit.current_witness_index));
+    let expression_width = match circuit.expression_width {
+        ExpressionWidth::Unbounded => "unbounded".to_string(),
+        ExpressionWidth::Bounded { width } => format!("bounded({width})"),
+    };
+    lines.push(format!("    expression_width: {expression_width}"));
+    lines.push(format!("    private_parameters: {}", witnesses(&circuit.private_parameters)));
+    lines.push(format!("    public_parameters: {}", witnesses(&circuit.public_parameters.0)));
+    lines.push(format!("    return_values: {}", witnesses(&circuit.return_values.0)));
+    lines.push(format!("    recursive: {}", circuit.recursive));
+    let assert_messages = list(&circuit.assert_messages, |(location, payload)| {
+        format!("{location}: {}", assertion_payload(payload))
+    });
+    lines.push(format!("    assert_messages: {assert_messages}"));
+    lines.push(String::new());
+    for opcode in &circuit.opcodes {
+        lines.push(format!("    {}", print_opcode(opcode)));
+    }
+    lines.push("}\n".to_string());
+    lines.join("\n")
+}
+
+fn print_brillig_function<F: AcirField>(index: usize, function: &BrilligBytecode<F>) -> String {
+    let mut lines = vec![format!("brillig func {index} {{")];
+    for opcode in &function.bytecode {
+        lines.push(format!("    {}", print_brillig_opcode(opcode)));
+    }
+    lines.push("}\n".to_string());
+    lines.join("\n")
+}
+
+fn print_opcode<F: AcirField>(opcode: &Opcode<F>) -> String {
+    match opcode {
+        Opcode::AssertZero(expr) => format!("ASSERT_ZERO {}", expression(expr)),
+        Opcode::BlackBoxFuncCall(call) => print_black_box_func_call(call),
+        Opcode::Directive(Directive::ToLeRadix { a, b, radix }) => format!(
+            "DIRECTIVE::TO_LE_RADIX {{ a: {}, b: {}, radix: {radix} }}",
+            expression(a),
+            witnesses(b)
+        ),
+        Opcode::MemoryOp { block_id, op, predicate } => fields(
+            "MEMORY_OP",
+            with_predicate(
+                vec![
+                    ("block_id", block(*block_id)),
+                    ("operation", expression(&op.operation)),
+                    ("index", expression(&op.index)),
+                    ("value", expression(&op.value)),
+                ],
+                predicate,
+            ),
+        ),
+        Opcode::MemoryInit { block_id, init, block_type } => {
+            let block_type = match block_type {
+                BlockType::Memory => "memory".to_string(),
+                BlockType::CallData(id) => format!("call_data({id})"),
+                BlockType::ReturnData => "return_data".to_string(),
+            };
+            fields(
+                "MEMORY_INIT",
+                vec![
+                    ("block_id", block(*block_id)),
+                    ("init", witnesses(init)),
+                    ("block_type", block_type),
+                ],
+            )
+        }
+        Opcode::BrilligCall { id, inputs, outputs, predicate } => {
+            let inputs = list(inputs, |input| match input {
+                BrilligInputs::Single(expr) => format!("single({})", expression(expr)),
+                BrilligInputs::Array(exprs) => format!("array({})", list(exprs, expression)),
+                BrilligInputs::MemoryArray(block_id) => {
+                    format!("memory_array({})", block(*block_id))
+                }
+            });
+            let outputs = list(outputs, |output| match output {
+                BrilligOutputs::Simple(output) => format!("simple({})", witness(*output)),
+                BrilligOutputs::Array(outputs) => format!("array({})", witnesses(outputs)),
+            });
+            fields(
+                "BRILLIG_CALL",
+                with_predicate(
+                    vec![("id", id.to_string()), ("inputs", inputs), ("outputs", outputs)],
+                    predicate,
+                ),
+            )
+        }
+        Opcode::Call { id, inputs, outputs, predicate } => fields(
+            "CALL",
+            with_predicate(
+                vec![
+                    ("id", id.to_string()),
+                    ("inputs", witnesses(inputs)),
+                    ("outputs", witnesses(outputs)),
+                ],
+                predicate,
+            ),
+        ),
+    }
+}
+
+fn print_black_box_func_call<F: AcirField>(call: &BlackBoxFuncCall<F>) -> String {
+    let name = format!("BLACKBOX::{}", call.get_black_box_func().name().to_uppercase());
+    let inputs = |inputs: &[FunctionInput<F>]| list(inputs, function_input);
+    let call_fields = match call {
+        BlackBoxFuncCall::AES128Encrypt { inputs: plaintext, iv, key, outputs } => vec![
+            ("inputs", inputs(plaintext)),
+            ("iv", inputs(iv.as_slice())),
+            ("key", inputs(key.as_slice())),
+            ("outputs", witnesses(outputs)),
+        ],
+        BlackBoxFuncCall::AND { lhs, rhs, output } | BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+            vec![
+                ("lhs", function_input(lhs)),
+                ("rhs", function_input(rhs)),
+                ("output", witness(*output)),
+            ]
+        }
+        BlackBoxFuncCall::RANGE { input } => vec![("input", function_input(input))],
+        BlackBoxFuncCall::Blake2s { inputs: message, outputs }
+        | BlackBoxFuncCall::Blake3 { inputs: message, outputs } => {
+            vec![("inputs", inputs(message)), ("outputs", witnesses(outputs.as_slice()))]
+        }
+        BlackBoxFuncCall::SchnorrVerify {
+            public_key_x,
+            public_key_y,
+            signature,
+            message,
+            output,
+        } => vec![
+            ("public_key_x", function_input(public_key_x)),
+            ("public_key_y", function_input(public_key_y)),
+            ("signature", inputs(signature.as_slice())),
+            ("message", inputs(message)),
+            ("output", witness(*output)),
+        ],
+        BlackBoxFuncCall::PedersenCommitment { inputs: values, domain_separator, outputs } => {
+            vec![
+                ("inputs", inputs(values)),
+                ("domain_separator", domain_separator.to_string()),
+                ("outputs", witness_tuple(&[outputs.0, outputs.1])),
+            ]
+        }
+        BlackBoxFuncCall::PedersenHash { inputs: values, domain_separator, output } => vec![
+            ("inputs", inputs(values)),
+            ("domain_separator", domain_separator.to_string()),
+            ("output", witness(*output)),
+        ],
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        }
+        | BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x,
+            public_key_y,
+            signature,
+            hashed_message,
+            output,
+        } => vec![
+            ("public_key_x", inputs(public_key_x.as_slice())),
+            ("public_key_y", inputs(public_key_y.as_slice())),
+            ("signature", inputs(signature.as_slice())),
+            ("hashed_message", inputs(hashed_message.as_slice())),
+            ("output", witness(*output)),
+        ],
+        BlackBoxFuncCall::MultiScalarMul { points, scalars, outputs } => vec![
+            ("points", inputs(points)),
+            ("scalars", inputs(scalars)),
+            ("outputs", witness_tuple(&[outputs.0, outputs.1, outputs.2])),
+        ],
+        BlackBoxFuncCall::EmbeddedCurveAdd { input1, input2, outputs } => vec![
+            ("input1", inputs(input1.as_slice())),
+            ("input2", inputs(input2.as_slice())),
+            ("outputs", witness_tuple(&[outputs.0, outputs.1, outputs.2])),
+        ],
+        BlackBoxFuncCall::Keccak256 { inputs: message, var_message_size, outputs } => vec![
+            ("inputs", inputs(message)),
+            ("var_message_size", function_input(var_message_size)),
+            ("outputs", witnesses(outputs.as_slice())),
+        ],
+        BlackBoxFuncCall::Keccakf1600 { inputs: state, outputs } => {
+            vec![("inputs", inputs(state.as_slice())), ("outputs", witnesses(outputs.as_slice()))]
+        }
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key,
+            proof,
+            public_inputs,
+            key_hash,
+            proof_type,
+        } => vec![
+            ("verification_key", inputs(verification_key)),
+            ("proof", inputs(proof)),
+            ("public_inputs", inputs(public_inputs)),
+            ("key_hash", function_input(key_hash)),
+            ("proof_type", proof_type.to_string()),
+        ],
+        BlackBoxFuncCall::BigIntAdd { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntSub { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntMul { lhs, rhs, output }
+        | BlackBoxFuncCall::BigIntDiv { lhs, rhs, output } => {
+            vec![("lhs", lhs.to_string()), ("rhs", rhs.to_string()), ("output", output.to_string())]
+        }
+        BlackBoxFuncCall::BigIntFromLeBytes { inputs: bytes, modulus, output } => vec![
+            ("inputs", inputs(bytes)),
+            ("modulus", list(modulus, u8::to_string)),
+            ("output", output.to_string()),
+        ],
+        BlackBoxFuncCall::BigIntToLeBytes { input, outputs } => {
+            vec![("input", input.to_string()), ("outputs", witnesses(outputs))]
+        }
+        BlackBoxFuncCall::Poseidon2Permutation { inputs: state, outputs, len } => vec![
+            ("inputs", inputs(state)),
+            ("outputs", witnesses(outputs)),
+            ("len", len.to_string()),
+        ],
+        BlackBoxFuncCall::Sha256Compression { inputs: message, hash_values, outputs } => vec![
+            ("inputs", inputs(message.as_slice())),
+            ("hash_values", inputs(hash_values.as_slice())),
+            ("outputs", witnesses(outputs.as_slice())),
+        ],
+        BlackBoxFuncCall::UserDefined { name, inputs: arguments, outputs } => vec![
+            ("name", string(name)),
+            ("inputs", inputs(arguments)),
+            ("outputs", witnesses(outputs)),
+        ],
+    };
+    fields(&name, call_fields)
+}
+
+/// Writes out a single Brillig opcode in the textual assembly format, e.g. `jump { location: 5 }`.
+pub fn print_brillig_opcode<F: AcirField>(opcode: &BrilligOpcode<F>) -> String {
+    let (name, opcode_fields) = match opcode {
+        BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs } => (
+            "binary_field_op",
+            vec![
+                ("destination", address(*destination)),
+                ("op", binary_field_op(*op).to_string()),
+                ("lhs", address(*lhs)),
+                ("rhs", address(*rhs)),
+            ],
+        ),
+        BrilligOpcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => (
+            "binary_int_op",
+            vec![
+                ("destination", address(*destination)),
+                ("op", binary_int_op(*op).to_string()),
+                ("bit_size", bit_size_name(BitSize::Integer(*bit_size))),
+                ("lhs", address(*lhs)),
+                ("rhs", address(*rhs)),
+            ],
+        ),
+        BrilligOpcode::Not { destination, source, bit_size } => (
+            "not",
+            vec![
+                ("destination", address(*destination)),
+                ("source", address(*source)),
+                ("bit_size", bit_size_name(BitSize::Integer(*bit_size))),
+            ],
+        ),
+        BrilligOpcode::Cast { destination, source, bit_size } => (
+            "cast",
+            vec![
+                ("destination", address(*destination)),
+                ("source", address(*source)),
+                ("bit_size", bit_size_name(*bit_size)),
+            ],
+        ),
+        BrilligOpcode::JumpIfNot { condition, location } => (
+            "jump_if_not",
+            vec![("condition", address(*condition)), ("location", location.to_string())],
+        ),
+        BrilligOpcode::JumpIf { condition, location } => (
+            "jump_if",
+            vec![("condition", address(*condition)), ("location", location.to_string())],
+        ),
+        BrilligOpcode::Jump { location } => ("jump", vec![("location", location.to_string())]),
+        BrilligOpcode::CalldataCopy { destination_address, size_address, offset_address } => (
+            "calldata_copy",
+            vec![
+                ("destination_address", address(*destination_address)),
+                ("size_address", address(*size_address)),
+                ("offset_address", address(*offset_address)),
+            ],
+        ),
+        BrilligOpcode::Call { location } => ("call", vec![("location", location.to_string())]),
+        BrilligOpcode::Const { destination, bit_size, value } => (
+            "const",
+            vec![
+                ("destination", address(*destination)),
+                ("bit_size", bit_size_name(*bit_size)),
+                ("value", field(*value)),
+            ],
+        ),
+        BrilligOpcode::IndirectConst { destination_pointer, bit_size, value } => (
+            "indirect_const",
+            vec![
+                ("destination_pointer", address(*destination_pointer)),
+                ("bit_size", bit_size_name(*bit_size)),
+                ("value", field(*value)),
+            ],
+        ),
+        BrilligOpcode::Return => return "return".to_string(),
+        BrilligOpcode::ForeignCall {
+            function,
+            destinations,
+            destination_value_types,
+            inputs,
+            input_value_types,
+        } => (
+            "foreign_call",
+            vec![
+                ("function", string(function)),
+                ("destinations", list(destinations, value_or_array)),
+                ("destination_value_types", list(destination_value_types, heap_value_type)),
+                ("inputs", list(inputs, value_or_array)),
+                ("input_value_types", list(input_value_types, heap_value_type)),
+            ],
+        ),
+        BrilligOpcode::Mov { destination, source } => {
+            ("mov", vec![("destination", address(*destination)), ("source", address(*source))])
+        }
+        BrilligOpcode::ConditionalMov { destination, source_a, source_b, condition } => (
+            "conditional_mov",
+            vec![
+                ("destination", address(*destination)),
+                ("source_a", address(*source_a)),
+                ("source_b", address(*source_b)),
+                ("condition", address(*condition)),
+            ],
+        ),
+        BrilligOpcode::Load { destination, source_pointer } => (
+            "load",
+            vec![
+                ("destination", address(*destination)),
+                ("source_pointer", address(*source_pointer)),
+            ],
+        ),
+        BrilligOpcode::Store { destination_pointer, source } => (
+            "store",
+            vec![
+                ("destination_pointer", address(*destination_pointer)),
+                ("source", address(*source)),
+            ],
+        ),
+        BrilligOpcode::BlackBox(op) => return print_black_box_op(op),
+        BrilligOpcode::Trap { revert_data } => {
+            ("trap", vec![("revert_data", heap_array(*revert_data))])
+        }
+        BrilligOpcode::Stop { return_data_offset, return_data_size } => (
+            "stop",
+            vec![
+                ("return_data_offset", return_data_offset.to_string()),
+                ("return_data_size", return_data_size.to_string()),
+            ],
+        ),
+    };
+    fields(name, opcode_fields)
+}
+
+fn print_black_box_op(op: &BlackBoxOp) -> String {
+    let op_fields = match op {
+        BlackBoxOp::AES128Encrypt { inputs, iv, key, outputs } => vec![
+            ("inputs", heap_vector(*inputs)),
+            ("iv", heap_array(*iv)),
+            ("key", heap_array(*key)),
+            ("outputs", heap_vector(*outputs)),
+        ],
+        BlackBoxOp::Blake2s { message, output }
+        | BlackBoxOp::Blake3 { message, output }
+        | BlackBoxOp::Keccak256 { message, output }
+        | BlackBoxOp::Keccakf1600 { message, output } => {
+            vec![("message", heap_vector(*message)), ("output", heap_array(*output))]
+        }
+        BlackBoxOp::EcdsaSecp256k1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        }
+        | BlackBoxOp::EcdsaSecp256r1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        } => vec![
+            ("hashed_msg", heap_vector(*hashed_msg)),
+            ("public_key_x", heap_array(*public_key_x)),
+            ("public_key_y", heap_array(*public_key_y)),
+            ("signature", heap_array(*signature)),
+            ("result", address(*result)),
+        ],
+        BlackBoxOp::SchnorrVerify { public_key_x, public_key_y, message, signature, result } => {
+            vec![
+                ("public_key_x", address(*public_key_x)),
+                ("public_key_y", address(*public_key_y)),
+                ("message", heap_vector(*message)),
+                ("signature", heap_vector(*signature)),
+                ("result", address(*result)),
+            ]
+        }
+        BlackBoxOp::PedersenCommitment { inputs, domain_separator, output } => vec![
+            ("inputs", heap_vector(*inputs)),
+            ("domain_separator", address(*domain_separator)),
+            ("output", heap_array(*output)),
+        ],
+        BlackBoxOp::PedersenHash { inputs, domain_separator, output } => vec![
+            ("inputs", heap_vector(*inputs)),
+            ("domain_separator", address(*domain_separator)),
+            ("output", address(*output)),
+        ],
+        BlackBoxOp::MultiScalarMul { points, scalars, outputs } => vec![
+            ("points", heap_vector(*points)),
+            ("scalars", heap_vector(*scalars)),
+            ("outputs", heap_array(*outputs)),
+        ],
+        BlackBoxOp::EmbeddedCurveAdd {
+            input1_x,
+            input1_y,
+            input1_infinite,
+            input2_x,
+            input2_y,
+            input2_infinite,
+            result,
+        } => vec![
+            ("input1_x", address(*input1_x)),
+            ("input1_y", address(*input1_y)),
+            ("input1_infinite", address(*input1_infinite)),
+            ("input2_x", address(*input2_x)),
+            ("input2_y", address(*input2_y)),
+            ("input2_infinite", address(*input2_infinite)),
+            ("result", heap_array(*result)),
+        ],
+        BlackBoxOp::BigIntAdd { lhs, rhs, output }
+        | BlackBoxOp::BigIntSub { lhs, rhs, output }
+        | BlackBoxOp::BigIntMul { lhs, rhs, output }
+        | BlackBoxOp::BigIntDiv { lhs, rhs, output } => {
+            vec![("lhs", address(*lhs)), ("rhs", address(*rhs)), ("output", address(*output))]
+        }
+        BlackBoxOp::BigIntFromLeBytes { inputs, modulus, output } => vec![
+            ("inputs", heap_vector(*inputs)),
+            ("modulus", heap_vector(*modulus)),
+            ("output", address(*output)),
+        ],
+        BlackBoxOp::BigIntToLeBytes { input, output } => {
+            vec![("input", address(*input)), ("output", heap_vector(*output))]
+        }
+        BlackBoxOp::Poseidon2Permutation { message, output, len } => vec![
+            ("message", heap_vector(*message)),
+            ("output", heap_array(*output)),
+            ("len", address(*len)),
+        ],
+        BlackBoxOp::Sha256Compression { input, hash_values, output } => vec![
+            ("input", heap_vector(*input)),
+            ("hash_values", heap_vector(*hash_values)),
+            ("output", heap_array(*output)),
+        ],
+        BlackBoxOp::ToRadix { input, radix, output, output_bits } => vec![
+            ("input", address(*input)),
+            ("radix", address(*radix)),
+            ("output", heap_array(*output)),
+            ("output_bits", output_bits.to_string()),
+        ],
+        BlackBoxOp::UserDefined { name, inputs, outputs } => vec![
+            ("name", string(name)),
+            ("inputs", list(inputs, value_or_array)),
+            ("outputs", list(outputs, value_or_array)),
+        ],
+    };
+    let name = format!("black_box::{}", black_box_op_name(op));
+    fields(&name, op_fields)
+}
+
+/// Returns the name of a Brillig black box operation within the textual format.
+fn black_box_op_name(op: &BlackBoxOp) -> &'static str {
+    match op {
+        BlackBoxOp::AES128Encrypt { .. } => "aes128_encrypt",
+        BlackBoxOp::Blake2s { .. } => "blake2s",
+        BlackBoxOp::Blake3 { .. } => "blake3",
+        BlackBoxOp::Keccak256 { .. } => "keccak256",
+        BlackBoxOp::Keccakf1600 { .. } => "keccakf1600",
+        BlackBoxOp::EcdsaSecp256k1 { .. } => "ecdsa_secp256k1",
+        BlackBoxOp::EcdsaSecp256r1 { .. } => "ecdsa_secp256r1",
+        BlackBoxOp::SchnorrVerify { .. } => "schnorr_verify",
+        BlackBoxOp::PedersenCommitment { .. } => "pedersen_commitment",
+        BlackBoxOp::PedersenHash { .. } => "pedersen_hash",
+        BlackBoxOp::MultiScalarMul { .. } => "multi_scalar_mul",
+        BlackBoxOp::EmbeddedCurveAdd { .. } => "embedded_curve_add",
+        BlackBoxOp::BigIntAdd { .. } => "bigint_add",
+        BlackBoxOp::BigIntSub { .. } => "bigint_sub",
+        BlackBoxOp::BigIntMul { .. } => "bigint_mul",
+        BlackBoxOp::BigIntDiv { .. } => "bigint_div",
+        BlackBoxOp::BigIntFromLeBytes { .. } => "bigint_from_le_bytes",
+        BlackBoxOp::BigIntToLeBytes { .. } => "bigint_to_le_bytes",
+        BlackBoxOp::Poseidon2Permutation { .. } => "poseidon2_permutation",
+        BlackBoxOp::Sha256Compression { .. } => "sha256_compression",
+        BlackBoxOp::ToRadix { .. } => "to_radix",
+        BlackBoxOp::UserDefined { .. } => "user_defined",
+    }
+}
+
+fn binary_field_op(op: BinaryFieldOp) -> &'static str {
+    match op {
+        BinaryFieldOp::Add => "add",
+        BinaryFieldOp::Sub => "sub",
+        BinaryFieldOp::Mul => "mul",
+        BinaryFieldOp::Div => "div",
+        BinaryFieldOp::IntegerDiv => "integer_div",
+        BinaryFieldOp::Equals => "equals",
+        BinaryFieldOp::LessThan => "less_than",
+        BinaryFieldOp::LessThanEquals => "less_than_equals",
+    }
+}
+
+fn binary_int_op(op: BinaryIntOp) -> &'static str {
+    match op {
+        BinaryIntOp::Add => "add",
+        BinaryIntOp::Sub => "sub",
+        BinaryIntOp::Mul => "mul",
+        BinaryIntOp::Div => "div",
+        BinaryIntOp::Equals => "equals",
+        BinaryIntOp::LessThan => "less_than",
+        BinaryIntOp::LessThanEquals => "less_than_equals",
+        BinaryIntOp::And => "and",
+        BinaryIntOp::Or => "or",
+        BinaryIntOp::Xor => "xor",
+        BinaryIntOp::Shl => "shl",
+        BinaryIntOp::Shr => "shr",
+    }
+}
+
+/// Writes out an item with named fields, e.g. `CALL { id: 0, inputs: [_1], outputs: [_2] }`.
+fn fields(name: &str, fields: Vec<(&str, String)>) -> String {
+    let fields = vecmap(fields, |(name, value)| format!("{name}: {value}"));
+    format!("{name} {{ {} }}", fields.join(", "))
+}
+
+/// Appends the predicate of an opcode to its fields, if it has one.
+fn with_predicate<'a, F: AcirField>(
+    mut fields: Vec<(&'a str, String)>,
+    predicate: &Option<Expression<F>>,
+) -> Vec<(&'a str, String)> {
+    if let Some(predicate) = predicate {
+        fields.push(("predicate", expression(predicate)));
+    }
+    fields
+}
+
+fn list<T>(items: &[T], f: impl Fn(&T) -> String) -> String {
+    format!("[{}]", vecmap(items, f).join(", "))
+}
+
+fn vecmap<T, U>(items: impl IntoIterator<Item = T>, f: impl Fn(T) -> U) -> Vec<U> {
+    items.into_iter().map(f).collect()
+}
+
+/// Writes out a field element as a decimal integer if it (or its negation) fits into a `u128`,
+/// otherwise as a hexadecimal integer.
+fn field<F: AcirField>(value: F) -> String {
+    if let Some(value) = value.try_into_u128() {
+        value.to_string()
+    } else if let Some(value) = (-value).try_into_u128() {
+        format!("-{value}")
+    } else {
+        format!("0x{}", value.to_hex().trim_start_matches('0'))
+    }
+}
+
+fn expression<F: AcirField>(expr: &Expression<F>) -> String {
+    let mut terms = Vec::new();
+    for (coefficient, lhs, rhs) in &expr.mul_terms {
+        terms.push(format!("({}, {}, {})", field(*coefficient), witness(*lhs), witness(*rhs)));
+    }
+    for (coefficient, term) in &expr.linear_combinations {
+        terms.push(format!("({}, {})", field(*coefficient), witness(*term)));
+    }
+    terms.push(field(expr.q_c));
+    format!("[ {} ]", terms.join(" "))
+}
+
+fn witness(witness: Witness) -> String {
+    format!("_{}", witness.witness_index())
+}
+
+fn witnesses<'a>(witnesses: impl IntoIterator<Item = &'a Witness>) -> String {
+    format!("[{}]", vecmap(witnesses, |w| witness(*w)).join(", "))
+}
+
+fn witness_tuple(witnesses: &[Witness]) -> String {
+    format!("({})", vecmap(witnesses, |w| witness(*w)).join(", "))
+}
+
+fn block(block_id: BlockId) -> String {
+    format!("b{}", block_id.0)
+}
+
+fn function_input<F: AcirField>(input: &FunctionInput<F>) -> String {
+    let value = match input.input_ref() {
+        ConstantOrWitnessEnum::Constant(constant) => field(*constant),
+        ConstantOrWitnessEnum::Witness(input) => witness(*input),
+    };
+    format!("({value}, {})", input.num_bits())
+}
+
+fn assertion_payload<F: AcirField>(payload: &AssertionPayload<F>) -> String {
+    match payload {
+        AssertionPayload::StaticString(message) => string(message),
+        AssertionPayload::Dynamic(selector, items) => {
+            let items = list(items, |item| match item {
+                ExpressionOrMemory::Expression(expr) => expression(expr),
+                ExpressionOrMemory::Memory(block_id) => block(*block_id),
+            });
+            format!("dynamic({selector}, {items})")
+        }
+    }
+}
+
+fn string(string: &str) -> String {
+    let mut escaped = String::with_capacity(string.len() + 2);
+    escaped.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char => escaped.push(char),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn address(address: MemoryAddress) -> String {
+    format!("@{}", address.to_usize())
+}
+
+fn heap_array(array: HeapArray) -> String {
+    format!("array({}, {})", address(array.pointer), array.size)
+}
+
+fn heap_vector(vector: HeapVector) -> String {
+    format!("vector({}, {})", address(vector.pointer), address(vector.size))
+}
+
+fn bit_size_name(bit_size: BitSize) -> String {
+    match bit_size {
+        BitSize::Field => "field".to_string(),
+        BitSize::Integer(bit_size) => format!("u{}", u32::from(bit_size)),
+    }
+}
+
+fn value_or_array(value: &ValueOrArray) -> String {
+    match value {
+        ValueOrArray::MemoryAddress(value) => address(*value),
+        ValueOrArray::HeapArray(array) => heap_array(*array),
+        ValueOrArray::HeapVector(vector) => heap_vector(*vector),
+    }
+}
+
+fn heap_value_type(typ: &HeapValueType) -> String {
+    match typ {
+        HeapValueType::Simple(bit_size) => bit_size_name(*bit_size),
+        HeapValueType::Array { value_types, size } => {
+            format!("array({}, {size})", list(value_types, heap_value_type))
+        }
+        HeapValueType::Vector { value_types } => {
+            format!("vector({})", list(value_types, heap_value_type))
+        }
+    }
+}
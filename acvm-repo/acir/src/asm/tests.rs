@@ -0,0 +1,476 @@
+use std::collections::BTreeSet;
+
+use acir_field::{AcirField, FieldElement};
+use brillig::{
+    BinaryFieldOp, BinaryIntOp, BitSize, BlackBoxOp, HeapArray, HeapValueType, HeapVector,
+    IntegerBitSize, MemoryAddress, Opcode as BrilligOpcode, ValueOrArray,
+};
+
+use super::{parse_program, print_program, Location, ParserError};
+use crate::{
+    circuit::{
+        brillig::{BrilligBytecode, BrilligFunctionId, BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::{AcirFunctionId, BlackBoxFuncCall, BlockId, BlockType, FunctionInput, MemOp},
+        AssertionPayload, Circuit, ExpressionOrMemory, ExpressionWidth, Opcode, OpcodeLocation,
+        Program, PublicInputs,
+    },
+    native_types::{Expression, Witness},
+};
+
+fn assert_roundtrip(program: Program<FieldElement>) {
+    let text = print_program(&program);
+    let parsed: Program<FieldElement> = parse_program(&text)
+        .unwrap_or_else(|error| panic!("failed to parse printed program: {error}\n{text}"));
+    assert_eq!(parsed, program);
+}
+
+fn witness_input(index: u32, num_bits: u32) -> FunctionInput<FieldElement> {
+    FunctionInput::witness(Witness(index), num_bits)
+}
+
+fn witness_inputs<const N: usize>(start: u32) -> Box<[FunctionInput<FieldElement>; N]> {
+    Box::new(std::array::from_fn(|i| witness_input(start + i as u32, 8)))
+}
+
+fn witnesses<const N: usize>(start: u32) -> Box<[Witness; N]> {
+    Box::new(std::array::from_fn(|i| Witness(start + i as u32)))
+}
+
+#[test]
+fn parses_hand_written_program() {
+    let src = "
+        // Checks that the sum of two inputs is returned.
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0, _1]
+            return_values: [_2]
+            assert_messages: [0: \"sum is incorrect\"]
+
+            ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _2) 0 ])], outputs: [simple(_3)] }
+            ASSERT_ZERO [ (1, _2, _3) -1 ]
+        }
+
+        brillig func 0 {
+            calldata_copy { destination_address: @0, size_address: @1, offset_address: @2 }
+            binary_field_op { destination: @0, op: div, lhs: @1, rhs: @0 }
+            stop { return_data_offset: 0, return_data_size: 1 }
+        }
+    ";
+    let program: Program<FieldElement> = src.parse().unwrap();
+
+    let circuit = Circuit {
+        current_witness_index: 3,
+        opcodes: vec![
+            Opcode::AssertZero(Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(0)),
+                    (FieldElement::one(), Witness(1)),
+                    (-FieldElement::one(), Witness(2)),
+                ],
+                q_c: FieldElement::zero(),
+            }),
+            Opcode::BrilligCall {
+                id: BrilligFunctionId(0),
+                inputs: vec![BrilligInputs::Single(Witness(2).into())],
+                outputs: vec![BrilligOutputs::Simple(Witness(3))],
+                predicate: None,
+            },
+            Opcode::AssertZero(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(2), Witness(3))],
+                linear_combinations: Vec::new(),
+                q_c: -FieldElement::one(),
+            }),
+        ],
+        private_parameters: BTreeSet::from([Witness(0), Witness(1)]),
+        return_values: PublicInputs(BTreeSet::from([Witness(2)])),
+        assert_messages: vec![(
+            OpcodeLocation::Acir(0),
+            AssertionPayload::StaticString("sum is incorrect".to_string()),
+        )],
+        ..Circuit::default()
+    };
+    let brillig = BrilligBytecode {
+        bytecode: vec![
+            BrilligOpcode::CalldataCopy {
+                destination_address: MemoryAddress(0),
+                size_address: MemoryAddress(1),
+                offset_address: MemoryAddress(2),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: MemoryAddress(0),
+                op: BinaryFieldOp::Div,
+                lhs: MemoryAddress(1),
+                rhs: MemoryAddress(0),
+            },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 },
+        ],
+    };
+    assert_eq!(
+        program,
+        Program { functions: vec![circuit], unconstrained_functions: vec![brillig] }
+    );
+}
+
+#[test]
+fn roundtrips_acir_opcodes() {
+    let large_constant = FieldElement::from_hex("0x1234567890abcdef1234567890abcdef1234").unwrap();
+    let opcodes = vec![
+        Opcode::AssertZero(Expression {
+            mul_terms: vec![(large_constant, Witness(1), Witness(2))],
+            linear_combinations: vec![(-large_constant, Witness(3))],
+            q_c: FieldElement::from(5u128),
+        }),
+        Opcode::Directive(Directive::ToLeRadix {
+            a: Witness(1).into(),
+            b: vec![Witness(4), Witness(5)],
+            radix: 2,
+        }),
+        Opcode::MemoryInit {
+            block_id: BlockId(0),
+            init: vec![Witness(1), Witness(2)],
+            block_type: BlockType::CallData(1),
+        },
+        Opcode::MemoryInit { block_id: BlockId(1), init: vec![], block_type: BlockType::Memory },
+        Opcode::MemoryInit {
+            block_id: BlockId(2),
+            init: vec![Witness(3)],
+            block_type: BlockType::ReturnData,
+        },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(6)),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(Witness(1).into(), Witness(2).into()),
+            predicate: Some(Witness(3).into()),
+        },
+        Opcode::BrilligCall {
+            id: BrilligFunctionId(0),
+            inputs: vec![
+                BrilligInputs::Single(Witness(1).into()),
+                BrilligInputs::Array(vec![Witness(2).into(), FieldElement::from(3u128).into()]),
+                BrilligInputs::MemoryArray(BlockId(1)),
+            ],
+            outputs: vec![
+                BrilligOutputs::Simple(Witness(7)),
+                BrilligOutputs::Array(vec![Witness(8), Witness(9)]),
+            ],
+            predicate: Some(Expression::one()),
+        },
+        Opcode::Call {
+            id: AcirFunctionId(1),
+            inputs: vec![Witness(1), Witness(2)],
+            outputs: vec![Witness(10)],
+            predicate: None,
+        },
+    ];
+
+    let circuit = Circuit {
+        current_witness_index: 10,
+        opcodes,
+        expression_width: ExpressionWidth::Bounded { width: 4 },
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        public_parameters: PublicInputs(BTreeSet::from([Witness(3)])),
+        return_values: PublicInputs(BTreeSet::from([Witness(10)])),
+        assert_messages: vec![
+            (
+                OpcodeLocation::Acir(0),
+                AssertionPayload::StaticString("a \"quoted\"\nmessage\\".to_string()),
+            ),
+            (
+                OpcodeLocation::Brillig { acir_index: 7, brillig_index: 3 },
+                AssertionPayload::Dynamic(
+                    12345,
+                    vec![
+                        ExpressionOrMemory::Expression(Witness(1).into()),
+                        ExpressionOrMemory::Memory(BlockId(1)),
+                    ],
+                ),
+            ),
+        ],
+        recursive: true,
+    };
+    let callee = Circuit { current_witness_index: 2, ..Circuit::default() };
+    assert_roundtrip(Program { functions: vec![circuit, callee], unconstrained_functions: vec![] });
+}
+
+#[test]
+fn roundtrips_black_box_func_calls() {
+    let opcodes = vec![
+        BlackBoxFuncCall::AES128Encrypt {
+            inputs: vec![witness_input(1, 8)],
+            iv: witness_inputs(2),
+            key: witness_inputs(18),
+            outputs: vec![Witness(34)],
+        },
+        BlackBoxFuncCall::AND {
+            lhs: witness_input(1, 4),
+            rhs: FunctionInput::constant(FieldElement::from(3u128), 4).unwrap(),
+            output: Witness(3),
+        },
+        BlackBoxFuncCall::XOR {
+            lhs: witness_input(1, 4),
+            rhs: witness_input(2, 4),
+            output: Witness(3),
+        },
+        BlackBoxFuncCall::RANGE { input: witness_input(1, 8) },
+        BlackBoxFuncCall::Blake2s { inputs: vec![witness_input(1, 8)], outputs: witnesses(2) },
+        BlackBoxFuncCall::Blake3 { inputs: vec![witness_input(1, 8)], outputs: witnesses(2) },
+        BlackBoxFuncCall::SchnorrVerify {
+            public_key_x: witness_input(1, 254),
+            public_key_y: witness_input(2, 254),
+            signature: witness_inputs(3),
+            message: vec![witness_input(67, 8)],
+            output: Witness(68),
+        },
+        BlackBoxFuncCall::PedersenCommitment {
+            inputs: vec![witness_input(1, 254)],
+            domain_separator: 1,
+            outputs: (Witness(2), Witness(3)),
+        },
+        BlackBoxFuncCall::PedersenHash {
+            inputs: vec![witness_input(1, 254)],
+            domain_separator: 1,
+            output: Witness(2),
+        },
+        BlackBoxFuncCall::EcdsaSecp256k1 {
+            public_key_x: witness_inputs(1),
+            public_key_y: witness_inputs(33),
+            signature: witness_inputs(65),
+            hashed_message: witness_inputs(129),
+            output: Witness(161),
+        },
+        BlackBoxFuncCall::EcdsaSecp256r1 {
+            public_key_x: witness_inputs(1),
+            public_key_y: witness_inputs(33),
+            signature: witness_inputs(65),
+            hashed_message: witness_inputs(129),
+            output: Witness(161),
+        },
+        BlackBoxFuncCall::MultiScalarMul {
+            points: vec![witness_input(1, 254), witness_input(2, 254), witness_input(3, 1)],
+            scalars: vec![witness_input(4, 254), witness_input(5, 254)],
+            outputs: (Witness(6), Witness(7), Witness(8)),
+        },
+        BlackBoxFuncCall::EmbeddedCurveAdd {
+            input1: witness_inputs(1),
+            input2: witness_inputs(4),
+            outputs: (Witness(7), Witness(8), Witness(9)),
+        },
+        BlackBoxFuncCall::Keccak256 {
+            inputs: vec![witness_input(1, 8)],
+            var_message_size: witness_input(2, 32),
+            outputs: witnesses(3),
+        },
+        BlackBoxFuncCall::Keccakf1600 { inputs: witness_inputs(1), outputs: witnesses(26) },
+        BlackBoxFuncCall::RecursiveAggregation {
+            verification_key: vec![witness_input(1, 254)],
+            proof: vec![witness_input(2, 254)],
+            public_inputs: vec![witness_input(3, 254)],
+            key_hash: witness_input(4, 254),
+            proof_type: 1,
+        },
+        BlackBoxFuncCall::BigIntAdd { lhs: 0, rhs: 1, output: 2 },
+        BlackBoxFuncCall::BigIntSub { lhs: 0, rhs: 1, output: 2 },
+        BlackBoxFuncCall::BigIntMul { lhs: 0, rhs: 1, output: 2 },
+        BlackBoxFuncCall::BigIntDiv { lhs: 0, rhs: 1, output: 2 },
+        BlackBoxFuncCall::BigIntFromLeBytes {
+            inputs: vec![witness_input(1, 8)],
+            modulus: vec![1, 2, 255],
+            output: 0,
+        },
+        BlackBoxFuncCall::BigIntToLeBytes { input: 0, outputs: vec![Witness(1), Witness(2)] },
+        BlackBoxFuncCall::Poseidon2Permutation {
+            inputs: vec![witness_input(1, 254), witness_input(2, 254)],
+            outputs: vec![Witness(3), Witness(4)],
+            len: 2,
+        },
+        BlackBoxFuncCall::Sha256Compression {
+            inputs: witness_inputs(1),
+            hash_values: witness_inputs(17),
+            outputs: witnesses(25),
+        },
+        BlackBoxFuncCall::UserDefined {
+            name: "my_hash".to_string(),
+            inputs: vec![witness_input(1, 254), witness_input(2, 254)],
+            outputs: vec![Witness(3)],
+        },
+    ];
+
+    let circuit = Circuit {
+        current_witness_index: 161,
+        opcodes: opcodes.into_iter().map(Opcode::BlackBoxFuncCall).collect(),
+        ..Circuit::default()
+    };
+    assert_roundtrip(Program { functions: vec![circuit], unconstrained_functions: vec![] });
+}
+
+#[test]
+fn roundtrips_brillig_opcodes() {
+    let array = HeapArray { pointer: MemoryAddress(1), size: 4 };
+    let vector = HeapVector { pointer: MemoryAddress(2), size: MemoryAddress(3) };
+    let address = MemoryAddress(4);
+    let black_box_ops = vec![
+        BlackBoxOp::AES128Encrypt { inputs: vector, iv: array, key: array, outputs: vector },
+        BlackBoxOp::Blake2s { message: vector, output: array },
+        BlackBoxOp::Blake3 { message: vector, output: array },
+        BlackBoxOp::Keccak256 { message: vector, output: array },
+        BlackBoxOp::Keccakf1600 { message: vector, output: array },
+        BlackBoxOp::EcdsaSecp256k1 {
+            hashed_msg: vector,
+            public_key_x: array,
+            public_key_y: array,
+            signature: array,
+            result: address,
+        },
+        BlackBoxOp::EcdsaSecp256r1 {
+            hashed_msg: vector,
+            public_key_x: array,
+            public_key_y: array,
+            signature: array,
+            result: address,
+        },
+        BlackBoxOp::SchnorrVerify {
+            public_key_x: address,
+            public_key_y: address,
+            message: vector,
+            signature: vector,
+            result: address,
+        },
+        BlackBoxOp::PedersenCommitment { inputs: vector, domain_separator: address, output: array },
+        BlackBoxOp::PedersenHash { inputs: vector, domain_separator: address, output: address },
+        BlackBoxOp::MultiScalarMul { points: vector, scalars: vector, outputs: array },
+        BlackBoxOp::EmbeddedCurveAdd {
+            input1_x: address,
+            input1_y: address,
+            input1_infinite: address,
+            input2_x: address,
+            input2_y: address,
+            input2_infinite: address,
+            result: array,
+        },
+        BlackBoxOp::BigIntAdd { lhs: address, rhs: address, output: address },
+        BlackBoxOp::BigIntSub { lhs: address, rhs: address, output: address },
+        BlackBoxOp::BigIntMul { lhs: address, rhs: address, output: address },
+        BlackBoxOp::BigIntDiv { lhs: address, rhs: address, output: address },
+        BlackBoxOp::BigIntFromLeBytes { inputs: vector, modulus: vector, output: address },
+        BlackBoxOp::BigIntToLeBytes { input: address, output: vector },
+        BlackBoxOp::Poseidon2Permutation { message: vector, output: array, len: address },
+        BlackBoxOp::Sha256Compression { input: vector, hash_values: vector, output: array },
+        BlackBoxOp::ToRadix { input: address, radix: address, output: array, output_bits: true },
+        BlackBoxOp::UserDefined {
+            name: "my_hash".to_string(),
+            inputs: vec![ValueOrArray::MemoryAddress(address), ValueOrArray::HeapArray(array)],
+            outputs: vec![ValueOrArray::HeapVector(vector)],
+        },
+    ];
+
+    let mut bytecode = vec![
+        BrilligOpcode::BinaryFieldOp {
+            destination: address,
+            op: BinaryFieldOp::IntegerDiv,
+            lhs: address,
+            rhs: address,
+        },
+        BrilligOpcode::BinaryIntOp {
+            destination: address,
+            op: BinaryIntOp::Shr,
+            bit_size: IntegerBitSize::U64,
+            lhs: address,
+            rhs: address,
+        },
+        BrilligOpcode::Not { destination: address, source: address, bit_size: IntegerBitSize::U1 },
+        BrilligOpcode::Cast { destination: address, source: address, bit_size: BitSize::Field },
+        BrilligOpcode::JumpIfNot { condition: address, location: 3 },
+        BrilligOpcode::JumpIf { condition: address, location: 4 },
+        BrilligOpcode::Jump { location: 5 },
+        BrilligOpcode::Call { location: 6 },
+        BrilligOpcode::Const {
+            destination: address,
+            bit_size: BitSize::Integer(IntegerBitSize::U128),
+            value: FieldElement::from(u128::MAX),
+        },
+        BrilligOpcode::IndirectConst {
+            destination_pointer: address,
+            bit_size: BitSize::Field,
+            value: -FieldElement::from(2u128),
+        },
+        BrilligOpcode::Return,
+        BrilligOpcode::ForeignCall {
+            function: "print".to_string(),
+            destinations: vec![ValueOrArray::MemoryAddress(address)],
+            destination_value_types: vec![HeapValueType::Simple(BitSize::Field)],
+            inputs: vec![ValueOrArray::HeapArray(array), ValueOrArray::HeapVector(vector)],
+            input_value_types: vec![
+                HeapValueType::Array {
+                    value_types: vec![
+                        HeapValueType::field(),
+                        HeapValueType::Simple(BitSize::Integer(IntegerBitSize::U8)),
+                    ],
+                    size: 2,
+                },
+                HeapValueType::Vector {
+                    value_types: vec![HeapValueType::Vector { value_types: vec![] }],
+                },
+            ],
+        },
+        BrilligOpcode::Mov { destination: address, source: address },
+        BrilligOpcode::ConditionalMov {
+            destination: address,
+            source_a: address,
+            source_b: address,
+            condition: address,
+        },
+        BrilligOpcode::Load { destination: address, source_pointer: address },
+        BrilligOpcode::Store { destination_pointer: address, source: address },
+        BrilligOpcode::Trap { revert_data: array },
+        BrilligOpcode::Stop { return_data_offset: 1, return_data_size: 2 },
+    ];
+    bytecode.extend(black_box_ops.into_iter().map(BrilligOpcode::BlackBox));
+
+    let circuit = Circuit::default();
+    let brillig = BrilligBytecode { bytecode };
+    assert_roundtrip(Program { functions: vec![circuit], unconstrained_functions: vec![brillig] });
+}
+
+#[test]
+fn reports_location_of_unexpected_token() {
+    let src = "func 0 {\n    current_witness_index: 1\n    ASSERT_ZERO [ (1, _0) ]\n}";
+    let error = parse_program::<FieldElement>(src).unwrap_err();
+    assert_eq!(
+        error,
+        ParserError::UnexpectedToken {
+            expected: "a field element".to_string(),
+            found: "`]`".to_string(),
+            location: Location { line: 3, column: 27 },
+        }
+    );
+}
+
+#[test]
+fn rejects_fixed_length_inputs_of_wrong_length() {
+    let src = "
+        func 0 {
+            current_witness_index: 2
+            BLACKBOX::EMBEDDED_CURVE_ADD {
+                input1: [(_0, 254), (_1, 254)],
+                input2: [(_0, 254), (_1, 254), (_2, 1)],
+                outputs: (_3, _4, _5)
+            }
+        }
+    ";
+    let error = parse_program::<FieldElement>(src).unwrap_err();
+    assert!(matches!(error, ParserError::UnexpectedLength { expected: 3, found: 2, .. }));
+}
+
+#[test]
+fn rejects_out_of_order_functions() {
+    let src = "func 1 { current_witness_index: 0 }";
+    let error = parse_program::<FieldElement>(src).unwrap_err();
+    assert!(matches!(error, ParserError::UnexpectedFunctionIndex { expected: 0, found: 1, .. }));
+}
@@ -195,6 +195,14 @@ pub enum BlackBoxFunc {
     /// - state: [(witness, 32); 8]
     /// - output: [(witness, 32); 8]
     Sha256Compression,
+
+    /// A function which is not built into ACIR, solved by an implementation registered at runtime.
+    /// - inputs: a vector of (witness, max_num_bits) with the arity it was registered with
+    /// - outputs: a vector of witnesses with the arity it was registered with
+    ///
+    /// Each call carries the name of the function it refers to, which is declared in Noir through
+    /// `#[foreign(name)]`.
+    UserDefined,
 }
 
 impl std::fmt::Display for BlackBoxFunc {
@@ -230,6 +238,7 @@ impl BlackBoxFunc {
             BlackBoxFunc::Sha256Compression => "sha256_compression",
             BlackBoxFunc::PedersenCommitment => "pedersen_commitment",
             BlackBoxFunc::PedersenHash => "pedersen_hash",
+            BlackBoxFunc::UserDefined => "user_defined",
         }
     }
 
@@ -259,6 +268,7 @@ impl BlackBoxFunc {
             "sha256_compression" => Some(BlackBoxFunc::Sha256Compression),
             "pedersen_commitment" => Some(BlackBoxFunc::PedersenCommitment),
             "pedersen_hash" => Some(BlackBoxFunc::PedersenHash),
+            "user_defined" => Some(BlackBoxFunc::UserDefined),
             _ => None,
         }
     }
@@ -266,6 +276,20 @@ impl BlackBoxFunc {
     pub fn is_valid_black_box_func_name(op_name: &str) -> bool {
         BlackBoxFunc::lookup(op_name).is_some()
     }
+
+    /// Returns `true` if this function is defined over the Grumpkin curve embedded in BN254,
+    /// or uses parameters which only exist for BN254, and so is unavailable on other fields.
+    pub fn is_bn254_specific(&self) -> bool {
+        matches!(
+            self,
+            BlackBoxFunc::SchnorrVerify
+                | BlackBoxFunc::PedersenCommitment
+                | BlackBoxFunc::PedersenHash
+                | BlackBoxFunc::MultiScalarMul
+                | BlackBoxFunc::EmbeddedCurveAdd
+                | BlackBoxFunc::Poseidon2Permutation
+        )
+    }
 }
 
 #[cfg(test)]
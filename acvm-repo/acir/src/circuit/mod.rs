@@ -249,8 +249,61 @@ impl<F> Circuit<F> {
     }
 }
 
+/// Magic bytes which begin every program serialized by [`Program::serialize_program`].
+pub const PROGRAM_MAGIC: [u8; 4] = *b"ACIR";
+
+/// The version of the serialization format written by [`Program::serialize_program`].
+///
+/// This must be incremented whenever a change to the ACIR types alters their serialized representation,
+/// with [`Program::migrate`] being extended to read programs serialized with the previous version.
+///
+/// - Version 1 introduced the format header.
+/// - Version 2 added user-defined blackbox function calls to ACIR and Brillig.
+pub const PROGRAM_FORMAT_VERSION: u32 = 2;
+
+/// Programs serialized before the introduction of the format header consist of only the gzipped bincode payload.
+/// These are treated as version 0 of the serialization format.
+const LEGACY_FORMAT_VERSION: u32 = 0;
+/// The version which introduced the format header.
+const HEADER_FORMAT_VERSION: u32 = 1;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Error)]
+pub enum ProgramDeserializationError {
+    #[error("Serialized program does not begin with a valid ACIR format header")]
+    InvalidHeader,
+    #[error("Program was serialized with format version {found} but version {expected} is expected. Use `Program::migrate` to read programs serialized with older versions")]
+    OutdatedVersion { found: u32, expected: u32 },
+    #[error("Program was serialized with format version {found} which is newer than the latest supported version {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error("Failed to decompress program: {0}")]
+    Decompression(#[from] std::io::Error),
+    #[error("Failed to deserialize program: {0}")]
+    Deserialization(#[from] bincode::Error),
+}
+
+/// Splits a serialized program into the version of the format it was serialized with and its payload.
+fn split_format_header(
+    serialized_program: &[u8],
+) -> Result<(u32, &[u8]), ProgramDeserializationError> {
+    if serialized_program.starts_with(&GZIP_MAGIC) {
+        return Ok((LEGACY_FORMAT_VERSION, serialized_program));
+    }
+
+    let header_length = PROGRAM_MAGIC.len() + PROGRAM_FORMAT_VERSION.to_le_bytes().len();
+    if serialized_program.len() < header_length || !serialized_program.starts_with(&PROGRAM_MAGIC) {
+        return Err(ProgramDeserializationError::InvalidHeader);
+    }
+    let (header, payload) = serialized_program.split_at(header_length);
+    let version = u32::from_le_bytes(header[PROGRAM_MAGIC.len()..].try_into().unwrap());
+    Ok((version, payload))
+}
+
 impl<F: Serialize> Program<F> {
-    fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+    fn write<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(&PROGRAM_MAGIC)?;
+        writer.write_all(&PROGRAM_FORMAT_VERSION.to_le_bytes())?;
+
         let buf = bincode::serialize(self).unwrap();
         let mut encoder = flate2::write::GzEncoder::new(writer, Compression::default());
         encoder.write_all(&buf)?;
@@ -276,19 +329,55 @@ impl<F: Serialize> Program<F> {
 }
 
 impl<F: for<'a> Deserialize<'a>> Program<F> {
-    fn read<R: Read>(reader: R) -> std::io::Result<Self> {
+    fn read<R: Read>(reader: R) -> Result<Self, ProgramDeserializationError> {
         let mut gz_decoder = flate2::read::GzDecoder::new(reader);
         let mut buf_d = Vec::new();
         gz_decoder.read_to_end(&mut buf_d)?;
-        bincode::deserialize(&buf_d)
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))
+        Ok(bincode::deserialize(&buf_d)?)
     }
 
-    pub fn deserialize_program(serialized_circuit: &[u8]) -> std::io::Result<Self> {
-        Program::read(serialized_circuit)
+    /// Deserializes a program which was serialized with the current [`PROGRAM_FORMAT_VERSION`].
+    ///
+    /// Programs serialized with an older version of the format are rejected and must be read using [`Program::migrate`].
+    pub fn deserialize_program(
+        serialized_circuit: &[u8],
+    ) -> Result<Self, ProgramDeserializationError> {
+        let (version, payload) = split_format_header(serialized_circuit)?;
+        match version {
+            PROGRAM_FORMAT_VERSION => Program::read(payload),
+            found if found < PROGRAM_FORMAT_VERSION => {
+                Err(ProgramDeserializationError::OutdatedVersion {
+                    found,
+                    expected: PROGRAM_FORMAT_VERSION,
+                })
+            }
+            found => Err(ProgramDeserializationError::UnsupportedVersion {
+                found,
+                expected: PROGRAM_FORMAT_VERSION,
+            }),
+        }
     }
 
-    // Deserialize and base64 decode program
+    /// Deserializes a program which was serialized with the current or any older version of the format,
+    /// converting it into the current representation.
+    ///
+    /// Reserializing the returned program with [`Program::serialize_program`] upgrades it to the current version.
+    pub fn migrate(serialized_circuit: &[u8]) -> Result<Self, ProgramDeserializationError> {
+        let (version, payload) = split_format_header(serialized_circuit)?;
+        match version {
+            // Version 1 only introduced the format header so the payload is unchanged from the legacy format.
+            // Version 2 only appended new variants to the opcode enums, so older payloads still decode as the current version.
+            LEGACY_FORMAT_VERSION | HEADER_FORMAT_VERSION | PROGRAM_FORMAT_VERSION => {
+                Program::read(payload)
+            }
+            found => Err(ProgramDeserializationError::UnsupportedVersion {
+                found,
+                expected: PROGRAM_FORMAT_VERSION,
+            }),
+        }
+    }
+
+    // Deserialize and base64 decode program, migrating it from older versions of the format
     pub fn deserialize_program_base64<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
@@ -297,7 +386,7 @@ impl<F: for<'a> Deserialize<'a>> Program<F> {
         let program_bytes = base64::engine::general_purpose::STANDARD
             .decode(bytecode_b64)
             .map_err(D::Error::custom)?;
-        let circuit = Self::deserialize_program(&program_bytes).map_err(D::Error::custom)?;
+        let circuit = Self::migrate(&program_bytes).map_err(D::Error::custom)?;
         Ok(circuit)
     }
 }
@@ -387,7 +476,8 @@ mod tests {
 
     use super::{
         opcodes::{BlackBoxFuncCall, FunctionInput},
-        Circuit, Compression, Opcode, PublicInputs,
+        Circuit, Compression, Opcode, ProgramDeserializationError, PublicInputs,
+        HEADER_FORMAT_VERSION, PROGRAM_FORMAT_VERSION, PROGRAM_MAGIC,
     };
     use crate::{
         circuit::{ExpressionWidth, Program},
@@ -499,6 +589,8 @@ mod tests {
 
         // We expect to load circuits as compressed artifacts so we compress the junk circuit.
         let mut zipped_bad_circuit = Vec::new();
+        zipped_bad_circuit.extend(PROGRAM_MAGIC);
+        zipped_bad_circuit.extend(PROGRAM_FORMAT_VERSION.to_le_bytes());
         let mut encoder =
             flate2::write::GzEncoder::new(&mut zipped_bad_circuit, Compression::default());
         encoder.write_all(bad_circuit).unwrap();
@@ -506,6 +598,92 @@ mod tests {
 
         let deserialization_result: Result<Program<FieldElement>, _> =
             Program::deserialize_program(&zipped_bad_circuit);
-        assert!(deserialization_result.is_err());
+        assert!(matches!(
+            deserialization_result,
+            Err(ProgramDeserializationError::Deserialization(_))
+        ));
+    }
+
+    fn simple_program() -> Program<FieldElement> {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![and_opcode(), range_opcode()],
+            ..Circuit::default()
+        };
+        Program { functions: vec![circuit], unconstrained_functions: Vec::new() }
+    }
+
+    #[test]
+    fn migrates_programs_without_format_header() {
+        use std::io::Write;
+
+        let program = simple_program();
+
+        // Programs serialized before the format header was introduced are only the gzipped bincode payload.
+        let mut legacy_bytes = Vec::new();
+        let mut encoder = flate2::write::GzEncoder::new(&mut legacy_bytes, Compression::default());
+        encoder.write_all(&bincode::serialize(&program).unwrap()).unwrap();
+        encoder.finish().unwrap();
+
+        let deserialization_result = Program::<FieldElement>::deserialize_program(&legacy_bytes);
+        assert!(matches!(
+            deserialization_result,
+            Err(ProgramDeserializationError::OutdatedVersion {
+                found: 0,
+                expected: PROGRAM_FORMAT_VERSION
+            })
+        ));
+
+        let migrated_program = Program::migrate(&legacy_bytes).unwrap();
+        assert_eq!(migrated_program, program);
+
+        let upgraded_bytes = Program::serialize_program(&migrated_program);
+        assert_eq!(Program::deserialize_program(&upgraded_bytes).unwrap(), program);
+    }
+
+    #[test]
+    fn migrates_programs_from_first_header_version() {
+        let program = simple_program();
+        let mut bytes = Program::serialize_program(&program);
+        bytes[PROGRAM_MAGIC.len()..PROGRAM_MAGIC.len() + 4]
+            .copy_from_slice(&HEADER_FORMAT_VERSION.to_le_bytes());
+
+        assert!(matches!(
+            Program::<FieldElement>::deserialize_program(&bytes),
+            Err(ProgramDeserializationError::OutdatedVersion {
+                found: HEADER_FORMAT_VERSION,
+                expected: PROGRAM_FORMAT_VERSION
+            })
+        ));
+        assert_eq!(Program::migrate(&bytes).unwrap(), program);
+    }
+
+    #[test]
+    fn rejects_programs_from_newer_format_versions() {
+        let mut bytes = Program::serialize_program(&simple_program());
+        let newer_version = PROGRAM_FORMAT_VERSION + 1;
+        bytes[PROGRAM_MAGIC.len()..PROGRAM_MAGIC.len() + 4]
+            .copy_from_slice(&newer_version.to_le_bytes());
+
+        for result in [
+            Program::<FieldElement>::deserialize_program(&bytes),
+            Program::<FieldElement>::migrate(&bytes),
+        ] {
+            assert!(matches!(
+                result,
+                Err(ProgramDeserializationError::UnsupportedVersion { found, expected })
+                    if found == newer_version && expected == PROGRAM_FORMAT_VERSION
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_programs_without_valid_header() {
+        for bytes in [&b""[..], b"ACI", b"ACIR\x01", b"not an ACIR program"] {
+            assert!(matches!(
+                Program::<FieldElement>::deserialize_program(bytes),
+                Err(ProgramDeserializationError::InvalidHeader)
+            ));
+        }
     }
 }
@@ -238,6 +238,18 @@ pub enum BlackBoxFuncCall<F> {
         /// Output of the compression, represented by 8 u32s
         outputs: Box<[Witness; 8]>,
     },
+    /// Calls a function registered with the solver under `name`
+    ///
+    /// The backend is expected to provide its own constraints for the function, so its inputs
+    /// and outputs are passed through as flat vectors of field elements.
+    UserDefined {
+        /// Name under which the function is registered
+        name: String,
+        /// Flattened arguments of the function
+        inputs: Vec<FunctionInput<F>>,
+        /// Flattened return values of the function
+        outputs: Vec<Witness>,
+    },
 }
 
 impl<F: Copy> BlackBoxFuncCall<F> {
@@ -267,11 +279,15 @@ impl<F: Copy> BlackBoxFuncCall<F> {
             BlackBoxFuncCall::Sha256Compression { .. } => BlackBoxFunc::Sha256Compression,
             BlackBoxFuncCall::PedersenCommitment { .. } => BlackBoxFunc::PedersenCommitment,
             BlackBoxFuncCall::PedersenHash { .. } => BlackBoxFunc::PedersenHash,
+            BlackBoxFuncCall::UserDefined { .. } => BlackBoxFunc::UserDefined,
         }
     }
 
     pub fn name(&self) -> &str {
-        self.get_black_box_func().name()
+        match self {
+            BlackBoxFuncCall::UserDefined { name, .. } => name,
+            _ => self.get_black_box_func().name(),
+        }
     }
 
     pub fn get_inputs_vec(&self) -> Vec<FunctionInput<F>> {
@@ -282,7 +298,8 @@ impl<F: Copy> BlackBoxFuncCall<F> {
             | BlackBoxFuncCall::BigIntFromLeBytes { inputs, .. }
             | BlackBoxFuncCall::PedersenCommitment { inputs, .. }
             | BlackBoxFuncCall::PedersenHash { inputs, .. }
-            | BlackBoxFuncCall::Poseidon2Permutation { inputs, .. } => inputs.to_vec(),
+            | BlackBoxFuncCall::Poseidon2Permutation { inputs, .. }
+            | BlackBoxFuncCall::UserDefined { inputs, .. } => inputs.to_vec(),
 
             BlackBoxFuncCall::Keccakf1600 { inputs, .. } => inputs.to_vec(),
 
@@ -394,7 +411,8 @@ impl<F: Copy> BlackBoxFuncCall<F> {
             BlackBoxFuncCall::Sha256Compression { outputs, .. } => outputs.to_vec(),
 
             BlackBoxFuncCall::AES128Encrypt { outputs, .. }
-            | BlackBoxFuncCall::Poseidon2Permutation { outputs, .. } => outputs.to_vec(),
+            | BlackBoxFuncCall::Poseidon2Permutation { outputs, .. }
+            | BlackBoxFuncCall::UserDefined { outputs, .. } => outputs.to_vec(),
 
             BlackBoxFuncCall::AND { output, .. }
             | BlackBoxFuncCall::XOR { output, .. }
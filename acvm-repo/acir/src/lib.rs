@@ -5,6 +5,7 @@
 
 // Arbitrary Circuit Intermediate Representation
 
+pub mod asm;
 pub mod circuit;
 pub mod native_types;
 
@@ -0,0 +1,540 @@
+//! Checks a complete [`WitnessStack`] against the constraints of a [`Program`].
+//!
+//! Unlike the [ACVM][crate::pwg::ACVM], which derives a witness from a program's inputs, the checker takes
+//! a witness which was produced elsewhere and checks every opcode against it without solving for any new values.
+//!
+//! [`Opcode::BrilligCall`] and [`Opcode::Directive`] opcodes only provide hints for solving the witness
+//! and are not constraints, so their outputs are not checked.
+
+use std::collections::HashMap;
+
+use acir::{
+    circuit::{
+        opcodes::{AcirFunctionId, BlockId, ConstantOrWitnessEnum},
+        Circuit, Opcode, OpcodeLocation, Program,
+    },
+    native_types::{Expression, Witness, WitnessMap, WitnessStack},
+    AcirField,
+};
+use thiserror::Error;
+
+use crate::{
+    pwg::{
+        arithmetic::ExpressionSolver, blackbox, blackbox::bigint::AcvmBigIntSolver,
+        is_predicate_false, memory_op::MemoryOpSolver, resolve_assertion_payload, witness_to_value,
+        ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError,
+    },
+    BlackBoxFunctionSolver,
+};
+
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum WitnessCheckError<F> {
+    #[error("Function {function_id}, opcode {opcode_location}: {error}")]
+    UnsatisfiedOpcode {
+        function_id: AcirFunctionId,
+        opcode_location: OpcodeLocation,
+        error: OpcodeResolutionError<F>,
+    },
+    #[error("Expected the witness of function {expected} in the witness stack but found the witness of function {found}")]
+    UnexpectedStackItem { expected: AcirFunctionId, found: u32 },
+    #[error("Witness stack is missing the witness of function {0}")]
+    MissingStackItem(AcirFunctionId),
+    #[error("Witness stack contains {0} witnesses which do not correspond to any ACIR call")]
+    UnusedStackItems(usize),
+}
+
+/// Checks that `witness_stack` satisfies every constraint of `program`.
+///
+/// The witness stack must have the layout produced by executing the program, i.e. with the witness of the `main`
+/// function at the top of the stack, preceded by the witnesses of each function called in the order of execution.
+///
+/// Returns every violated constraint in the order in which the opcodes would be executed. If the witness stack
+/// does not match the [calls][Opcode::Call] made by the program then only this inconsistency is returned.
+pub fn check_witness<F: AcirField, B: BlackBoxFunctionSolver<F>>(
+    program: &Program<F>,
+    witness_stack: &WitnessStack<F>,
+    blackbox_solver: &B,
+) -> Result<(), Vec<WitnessCheckError<F>>> {
+    let mut witness_stack = witness_stack.clone();
+    let call_tree =
+        CallTree::pop(program, &mut witness_stack, AcirFunctionId(0)).map_err(|err| vec![err])?;
+    if witness_stack.length() > 0 {
+        return Err(vec![WitnessCheckError::UnusedStackItems(witness_stack.length())]);
+    }
+
+    let mut checker = WitnessChecker { program, blackbox_solver, errors: Vec::new() };
+    checker.check_function(&call_tree);
+
+    if checker.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(checker.errors)
+    }
+}
+
+/// The witness of a single execution of an ACIR function, along with the witnesses of the functions which it called.
+struct CallTree<F> {
+    function_id: AcirFunctionId,
+    witness: WitnessMap<F>,
+    /// The calls made by the function, in the order in which they were executed.
+    callees: Vec<CallTree<F>>,
+}
+
+impl<F: AcirField> CallTree<F> {
+    /// Pops the witness of the function `function_id` from the top of the stack,
+    /// followed by the witnesses of the functions which it called.
+    ///
+    /// Each call pushes the witness of the callee onto the stack once it completes, so the witnesses of the
+    /// functions called by a function sit directly beneath its own witness with the most recent call on top.
+    fn pop(
+        program: &Program<F>,
+        witness_stack: &mut WitnessStack<F>,
+        function_id: AcirFunctionId,
+    ) -> Result<Self, WitnessCheckError<F>> {
+        let stack_item =
+            witness_stack.pop().ok_or(WitnessCheckError::MissingStackItem(function_id))?;
+        if stack_item.index != function_id.0 {
+            return Err(WitnessCheckError::UnexpectedStackItem {
+                expected: function_id,
+                found: stack_item.index,
+            });
+        }
+
+        let circuit = &program.functions[function_id.as_usize()];
+        let mut callees = Vec::new();
+        for opcode in circuit.opcodes.iter().rev() {
+            if let Opcode::Call { id, predicate, .. } = opcode {
+                if is_call_executed(&stack_item.witness, predicate) {
+                    callees.push(Self::pop(program, witness_stack, *id)?);
+                }
+            }
+        }
+        callees.reverse();
+
+        Ok(CallTree { function_id, witness: stack_item.witness, callees })
+    }
+}
+
+/// Returns whether a call with the given predicate was executed.
+///
+/// Calls with a false predicate are skipped during execution and so have no witness. We also assume that a call
+/// whose predicate cannot be evaluated was skipped, as the missing assignment will be reported when checking it.
+fn is_call_executed<F: AcirField>(
+    witness: &WitnessMap<F>,
+    predicate: &Option<Expression<F>>,
+) -> bool {
+    !is_predicate_false(witness, predicate).unwrap_or(true)
+}
+
+struct WitnessChecker<'a, F, B> {
+    program: &'a Program<F>,
+    blackbox_solver: &'a B,
+    errors: Vec<WitnessCheckError<F>>,
+}
+
+impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> WitnessChecker<'a, F, B> {
+    /// Checks the witness of a function call, and those of the calls which it makes, against their functions' opcodes.
+    fn check_function(&mut self, call: &CallTree<F>) {
+        let circuit = &self.program.functions[call.function_id.as_usize()];
+        let mut witness = call.witness.clone();
+        let mut block_solvers = HashMap::new();
+        let mut bigint_solver = AcvmBigIntSolver::default();
+        let mut callees = call.callees.iter();
+
+        for (index, opcode) in circuit.opcodes.iter().enumerate() {
+            let location = OpcodeLocation::Acir(index);
+            let mut result =
+                self.check_opcode(opcode, &mut witness, &mut block_solvers, &mut bigint_solver);
+
+            if let Opcode::Call { id, inputs, outputs, predicate } = opcode {
+                if is_call_executed(&call.witness, predicate) {
+                    let callee =
+                        callees.next().expect("Call tree should contain every executed call");
+                    self.check_function(callee);
+
+                    let callee_circuit = &self.program.functions[id.as_usize()];
+                    result = result.and_then(|()| {
+                        check_call(inputs, outputs, &call.witness, callee_circuit, &callee.witness)
+                    });
+                }
+            }
+
+            if let Err(error) = result {
+                let error =
+                    resolve_error_location(error, location, circuit, &call.witness, &block_solvers);
+                self.errors.push(WitnessCheckError::UnsatisfiedOpcode {
+                    function_id: call.function_id,
+                    opcode_location: location,
+                    error,
+                });
+                // A failing solver may have overwritten the provided value of a witness
+                // with the one which it computed, so we restore the provided values.
+                witness = call.witness.clone();
+            }
+        }
+    }
+
+    fn check_opcode(
+        &self,
+        opcode: &Opcode<F>,
+        witness: &mut WitnessMap<F>,
+        block_solvers: &mut HashMap<BlockId, MemoryOpSolver<F>>,
+        bigint_solver: &mut AcvmBigIntSolver,
+    ) -> Result<(), OpcodeResolutionError<F>> {
+        // The solvers will assign values to any unassigned witnesses rather than reporting them,
+        // so we must first check that the witness contains every witness which the opcode constrains.
+        if let Some(witness_index) =
+            constrained_witnesses(opcode).into_iter().find(|w| !witness.contains_key(w))
+        {
+            return Err(OpcodeNotSolvable::MissingAssignment(witness_index.0).into());
+        }
+
+        // As every witness is assigned, the solvers will now only compare the values
+        // which they compute against the existing assignments.
+        match opcode {
+            Opcode::AssertZero(expr) => ExpressionSolver::solve(witness, expr),
+            Opcode::BlackBoxFuncCall(bb_func) => {
+                blackbox::solve(self.blackbox_solver, witness, bb_func, bigint_solver)
+            }
+            Opcode::MemoryInit { block_id, init, .. } => {
+                block_solvers.entry(*block_id).or_default().init(init, witness)
+            }
+            Opcode::MemoryOp { block_id, op, predicate } => {
+                block_solvers.entry(*block_id).or_default().solve_memory_op(op, witness, predicate)
+            }
+            // Calls are checked against the witness of the callee once the caller has been checked.
+            Opcode::Call { .. } => Ok(()),
+            Opcode::Directive(_) | Opcode::BrilligCall { .. } => Ok(()),
+        }
+    }
+}
+
+/// Checks that the inputs and outputs of a call match the parameters and return values of the callee's witness.
+fn check_call<F: AcirField>(
+    inputs: &[Witness],
+    outputs: &[Witness],
+    witness: &WitnessMap<F>,
+    callee: &Circuit<F>,
+    callee_witness: &WitnessMap<F>,
+) -> Result<(), OpcodeResolutionError<F>> {
+    let return_values = callee.return_values.indices();
+    if outputs.len() != return_values.len() {
+        return Err(OpcodeResolutionError::AcirCallOutputsMismatch {
+            opcode_location: ErrorLocation::Unresolved,
+            results_size: return_values.len() as u32,
+            outputs_size: outputs.len() as u32,
+        });
+    }
+
+    let arguments = inputs.iter().enumerate().map(|(index, input)| (*input, Witness(index as u32)));
+    let results =
+        outputs.iter().zip(return_values).map(|(output, index)| (*output, Witness(index)));
+    for (caller_witness, callee_witness_index) in arguments.chain(results) {
+        let caller_value = witness_to_value(witness, caller_witness)?;
+        let callee_value = witness_to_value(callee_witness, callee_witness_index)?;
+        if caller_value != callee_value {
+            return Err(OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Unresolved,
+                payload: None,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Attaches the location of the failing opcode to `error`, along with its assertion payload if it has one.
+fn resolve_error_location<F: AcirField>(
+    mut error: OpcodeResolutionError<F>,
+    location: OpcodeLocation,
+    circuit: &Circuit<F>,
+    witness: &WitnessMap<F>,
+    block_solvers: &HashMap<BlockId, MemoryOpSolver<F>>,
+) -> OpcodeResolutionError<F> {
+    match &mut error {
+        OpcodeResolutionError::UnsatisfiedConstrain { opcode_location, payload } => {
+            *opcode_location = ErrorLocation::Resolved(location);
+            match resolve_assertion_payload(
+                location,
+                &circuit.assert_messages,
+                witness,
+                block_solvers,
+            ) {
+                Ok(resolved_payload) => *payload = resolved_payload,
+                // The provided witness may not encode a valid message, which we report in place of the failure.
+                Err(payload_error) => return payload_error,
+            }
+        }
+        OpcodeResolutionError::IndexOutOfBounds { opcode_location, .. }
+        | OpcodeResolutionError::InvalidInputBitSize { opcode_location, .. }
+        | OpcodeResolutionError::AcirCallOutputsMismatch { opcode_location, .. } => {
+            *opcode_location = ErrorLocation::Resolved(location);
+        }
+        _ => (),
+    }
+    error
+}
+
+/// Returns the witnesses which must be assigned in order to check `opcode`.
+fn constrained_witnesses<F: AcirField>(opcode: &Opcode<F>) -> Vec<Witness> {
+    fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+        let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+        mul_witnesses.chain(expr.linear_combinations.iter().map(|(_, witness)| *witness))
+    }
+
+    match opcode {
+        Opcode::AssertZero(expr) => expression_witnesses(expr).collect(),
+        Opcode::BlackBoxFuncCall(bb_func) => {
+            let inputs =
+                bb_func.get_inputs_vec().into_iter().filter_map(|input| match input.input() {
+                    ConstantOrWitnessEnum::Witness(witness) => Some(witness),
+                    ConstantOrWitnessEnum::Constant(_) => None,
+                });
+            inputs.chain(bb_func.get_outputs_vec()).collect()
+        }
+        Opcode::MemoryInit { init, .. } => init.clone(),
+        Opcode::MemoryOp { op, predicate, .. } => expression_witnesses(&op.operation)
+            .chain(expression_witnesses(&op.index))
+            .chain(expression_witnesses(&op.value))
+            .chain(predicate.iter().flat_map(expression_witnesses))
+            .collect(),
+        Opcode::Call { inputs, outputs, predicate, .. } => {
+            let predicate_witnesses = predicate.iter().flat_map(expression_witnesses);
+            inputs.iter().chain(outputs).copied().chain(predicate_witnesses).collect()
+        }
+        Opcode::Directive(_) | Opcode::BrilligCall { .. } => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir::{
+        circuit::{opcodes::AcirFunctionId, OpcodeLocation, Program, ResolvedAssertionPayload},
+        native_types::{Witness, WitnessMap, WitnessStack},
+        FieldElement,
+    };
+    use acvm_blackbox_solver::StubbedBlackBoxSolver;
+
+    use super::{check_witness, WitnessCheckError};
+    use crate::pwg::{ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError};
+
+    fn witness_map(values: &[(u32, u128)]) -> WitnessMap<FieldElement> {
+        WitnessMap::from(BTreeMap::from_iter(
+            values.iter().map(|(index, value)| (Witness(*index), FieldElement::from(*value))),
+        ))
+    }
+
+    fn check(
+        program: &str,
+        witness_stack: &[(u32, &[(u32, u128)])],
+    ) -> Result<(), Vec<WitnessCheckError<FieldElement>>> {
+        let program: Program<FieldElement> = program.parse().unwrap();
+        let mut stack = WitnessStack::default();
+        for (index, values) in witness_stack {
+            stack.push(*index, witness_map(values));
+        }
+        check_witness(&program, &stack, &StubbedBlackBoxSolver)
+    }
+
+    fn unsatisfied_opcodes(
+        errors: Vec<WitnessCheckError<FieldElement>>,
+    ) -> Vec<(u32, OpcodeLocation)> {
+        errors
+            .into_iter()
+            .map(|error| match error {
+                WitnessCheckError::UnsatisfiedOpcode { function_id, opcode_location, .. } => {
+                    (function_id.0, opcode_location)
+                }
+                error => panic!("Expected an unsatisfied opcode, found {error:?}"),
+            })
+            .collect()
+    }
+
+    const ADDITION: &str = "
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0, _1]
+            return_values: [_2]
+            assert_messages: [1: \"sum is not even\"]
+            ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+            ASSERT_ZERO [ (1, _2) (-2, _3) 0 ]
+        }
+    ";
+
+    #[test]
+    fn accepts_satisfying_witness() {
+        assert_eq!(check(ADDITION, &[(0, &[(0, 1), (1, 3), (2, 4), (3, 2)])]), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_unsatisfied_opcode() {
+        let errors = check(ADDITION, &[(0, &[(0, 1), (1, 3), (2, 5), (3, 2)])]).unwrap_err();
+        assert_eq!(
+            unsatisfied_opcodes(errors.clone()),
+            vec![(0, OpcodeLocation::Acir(0)), (0, OpcodeLocation::Acir(1))]
+        );
+
+        let WitnessCheckError::UnsatisfiedOpcode { error, .. } = &errors[1] else { unreachable!() };
+        assert_eq!(
+            error,
+            &OpcodeResolutionError::UnsatisfiedConstrain {
+                opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+                payload: Some(ResolvedAssertionPayload::String("sum is not even".to_string())),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_missing_assignments() {
+        // The solver would otherwise solve for `_3` rather than reporting it as missing.
+        let errors = check(ADDITION, &[(0, &[(0, 1), (1, 3), (2, 4)])]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![WitnessCheckError::UnsatisfiedOpcode {
+                function_id: AcirFunctionId(0),
+                opcode_location: OpcodeLocation::Acir(1),
+                error: OpcodeNotSolvable::MissingAssignment(3).into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_memory_indices_which_do_not_fit_in_a_block() {
+        let program = "
+            func 0 {
+                current_witness_index: 2
+                MEMORY_INIT { block_id: b0, init: [_0, _1], block_type: memory }
+                MEMORY_OP { block_id: b0, operation: [ 0 ], index: [ (1, _2) 0 ], value: [ (1, _0) 0 ] }
+            }
+        ";
+        let index = u128::from(u64::MAX) + 1;
+        let errors = check(program, &[(0, &[(0, 1), (1, 2), (2, index)])]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![WitnessCheckError::UnsatisfiedOpcode {
+                function_id: AcirFunctionId(0),
+                opcode_location: OpcodeLocation::Acir(1),
+                error: OpcodeResolutionError::IndexOutOfBounds {
+                    opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+                    index: FieldElement::from(index),
+                    array_size: 2,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_string_assertion_payloads() {
+        // The message of the failing constraint is the string held in `_1`, which is not a valid character.
+        let program = "
+            func 0 {
+                current_witness_index: 1
+                assert_messages: [0: dynamic(0, [[ (1, _1) 0 ]])]
+                ASSERT_ZERO [ (1, _0) 0 ]
+            }
+        ";
+        let errors = check(program, &[(0, &[(0, 1), (1, 256)])]).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![WitnessCheckError::UnsatisfiedOpcode {
+                function_id: AcirFunctionId(0),
+                opcode_location: OpcodeLocation::Acir(0),
+                error: OpcodeResolutionError::InvalidAssertionPayload {
+                    opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+                    value: FieldElement::from(256u128),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn checks_range_and_memory_constraints() {
+        let program = "
+            func 0 {
+                current_witness_index: 4
+                BLACKBOX::RANGE { input: (_0, 8) }
+                MEMORY_INIT { block_id: b0, init: [_0, _1], block_type: memory }
+                MEMORY_OP { block_id: b0, operation: [ 1 ], index: [ 1 ], value: [ (1, _2) 0 ] }
+                MEMORY_OP { block_id: b0, operation: [ 0 ], index: [ 1 ], value: [ (1, _3) 0 ] }
+                // Unconstrained hints are not checked.
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_4)] }
+            }
+
+            brillig func 0 {
+                stop { return_data_offset: 0, return_data_size: 0 }
+            }
+        ";
+        assert_eq!(check(program, &[(0, &[(0, 255), (1, 1), (2, 7), (3, 7), (4, 0)])]), Ok(()));
+
+        let errors =
+            check(program, &[(0, &[(0, 256), (1, 1), (2, 7), (3, 1), (4, 0)])]).unwrap_err();
+        assert_eq!(
+            unsatisfied_opcodes(errors),
+            vec![(0, OpcodeLocation::Acir(0)), (0, OpcodeLocation::Acir(3))]
+        );
+    }
+
+    const NESTED_CALLS: &str = "
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0]
+            CALL { id: 1, inputs: [_0], outputs: [_1] }
+            CALL { id: 1, inputs: [_1], outputs: [_2] }
+            CALL { id: 1, inputs: [_2], outputs: [_3], predicate: [ 0 ] }
+        }
+
+        func 1 {
+            current_witness_index: 1
+            private_parameters: [_0]
+            return_values: [_1]
+            ASSERT_ZERO [ (2, _0) (-1, _1) 0 ]
+        }
+    ";
+
+    #[test]
+    fn checks_calls_against_callee_witnesses() {
+        let first_call: &[(u32, u128)] = &[(0, 3), (1, 6)];
+        let second_call: &[(u32, u128)] = &[(0, 6), (1, 12)];
+        let main: &[(u32, u128)] = &[(0, 3), (1, 6), (2, 12), (3, 0)];
+        assert_eq!(check(NESTED_CALLS, &[(1, first_call), (1, second_call), (0, main)]), Ok(()));
+
+        // The second call returns a value which differs from the output of the call in `main`.
+        let main: &[(u32, u128)] = &[(0, 3), (1, 6), (2, 13), (3, 0)];
+        let errors =
+            check(NESTED_CALLS, &[(1, first_call), (1, second_call), (0, main)]).unwrap_err();
+        assert_eq!(unsatisfied_opcodes(errors), vec![(0, OpcodeLocation::Acir(1))]);
+
+        // The witness of the first call does not satisfy its constraints.
+        let first_call: &[(u32, u128)] = &[(0, 3), (1, 7)];
+        let main: &[(u32, u128)] = &[(0, 3), (1, 7), (2, 12), (3, 0)];
+        let errors =
+            check(NESTED_CALLS, &[(1, first_call), (1, second_call), (0, main)]).unwrap_err();
+        assert_eq!(
+            unsatisfied_opcodes(errors),
+            vec![(1, OpcodeLocation::Acir(0)), (0, OpcodeLocation::Acir(1))]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_witness_stacks() {
+        let call: &[(u32, u128)] = &[(0, 3), (1, 6)];
+        let main: &[(u32, u128)] = &[(0, 3), (1, 6), (2, 12), (3, 0)];
+
+        assert_eq!(
+            check(NESTED_CALLS, &[(1, call), (0, main)]),
+            Err(vec![WitnessCheckError::MissingStackItem(AcirFunctionId(1))])
+        );
+        assert_eq!(
+            check(NESTED_CALLS, &[(1, call), (1, call), (1, call), (0, main)]),
+            Err(vec![WitnessCheckError::UnusedStackItems(1)])
+        );
+        assert_eq!(
+            check(NESTED_CALLS, &[(1, call), (0, main), (1, call)]),
+            Err(vec![WitnessCheckError::UnexpectedStackItem {
+                expected: AcirFunctionId(0),
+                found: 1
+            }])
+        );
+    }
+}
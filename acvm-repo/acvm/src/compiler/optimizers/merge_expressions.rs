@@ -0,0 +1,433 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use acir::{
+    circuit::{
+        brillig::{BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::ConstantOrWitnessEnum,
+        AssertionPayload, Circuit, ExpressionOrMemory, Opcode, OpcodeLocation,
+    },
+    native_types::{Expression, Witness},
+    AcirField,
+};
+
+/// `MergeExpressionsOptimizer` removes redundancy between [`Opcode::AssertZero`] opcodes.
+///
+/// It performs two simplifications:
+/// - Common subexpression elimination: an `AssertZero` opcode which is a multiple of an earlier one
+///   enforces the same constraint, so it is dropped.
+/// - Linear substitution: an intermediate witness `w` which is solved by a linear opcode `k*w + L = 0`
+///   and then only read by a single later `AssertZero` opcode is replaced in that opcode by `-L/k`.
+///   The opcode which defined `w` is then dropped.
+///
+/// Witnesses which are circuit arguments or return values, or which are used by any other kind of opcode
+/// or by an assertion payload, are never substituted away. Opcodes which have an assertion payload attached
+/// are never dropped so that a failing constraint keeps its error message.
+pub(crate) struct MergeExpressionsOptimizer<F> {
+    circuit: Circuit<F>,
+}
+
+impl<F: AcirField> MergeExpressionsOptimizer<F> {
+    pub(crate) fn new(circuit: Circuit<F>) -> Self {
+        Self { circuit }
+    }
+
+    /// Returns a `Circuit` where duplicate `AssertZero` opcodes and single-use linear intermediate witnesses
+    /// have been removed.
+    pub(crate) fn merge_expressions(self, order_list: Vec<usize>) -> (Circuit<F>, Vec<usize>) {
+        let protected_opcodes = opcodes_with_assertion_payloads(&self.circuit.assert_messages);
+
+        let (circuit, order_list) =
+            remove_duplicate_expressions(self.circuit, order_list, &protected_opcodes);
+        let (circuit, order_list) =
+            substitute_linear_definitions(circuit, order_list, &protected_opcodes);
+        // Substitution can turn two distinct opcodes into duplicates of each other.
+        remove_duplicate_expressions(circuit, order_list, &protected_opcodes)
+    }
+}
+
+/// Returns the original indices of the opcodes which have an assertion payload attached.
+fn opcodes_with_assertion_payloads<F>(
+    assert_messages: &[(OpcodeLocation, AssertionPayload<F>)],
+) -> HashSet<usize> {
+    assert_messages
+        .iter()
+        .filter_map(|(location, _)| match location {
+            OpcodeLocation::Acir(index) => Some(*index),
+            OpcodeLocation::Brillig { .. } => None,
+        })
+        .collect()
+}
+
+/// Drops every `AssertZero` opcode which is trivially satisfied or which is a multiple of an earlier opcode.
+///
+/// All witnesses of a duplicate are known once the earlier opcode has been solved, so the duplicate
+/// can never assign a witness and only repeats a check which has already been made.
+fn remove_duplicate_expressions<F: AcirField>(
+    circuit: Circuit<F>,
+    order_list: Vec<usize>,
+    protected_opcodes: &HashSet<usize>,
+) -> (Circuit<F>, Vec<usize>) {
+    let mut seen_expressions = HashSet::new();
+
+    let mut new_order_list = Vec::with_capacity(order_list.len());
+    let mut optimized_opcodes = Vec::with_capacity(circuit.opcodes.len());
+    for (idx, opcode) in circuit.opcodes.into_iter().enumerate() {
+        if let Opcode::AssertZero(expr) = &opcode {
+            let is_new = seen_expressions.insert(normalize(expr));
+            if (expr.is_zero() || !is_new) && !protected_opcodes.contains(&order_list[idx]) {
+                continue;
+            }
+        }
+        new_order_list.push(order_list[idx]);
+        optimized_opcodes.push(opcode);
+    }
+
+    (Circuit { opcodes: optimized_opcodes, ..circuit }, new_order_list)
+}
+
+/// Returns `expr` scaled such that its leading coefficient is one, with its terms in a canonical order.
+fn normalize<F: AcirField>(expr: &Expression<F>) -> Expression<F> {
+    let mut expr = expr.clone();
+    for (_, lhs, rhs) in expr.mul_terms.iter_mut() {
+        if lhs > rhs {
+            std::mem::swap(lhs, rhs);
+        }
+    }
+    expr.sort();
+
+    let leading_coefficient = expr
+        .mul_terms
+        .first()
+        .map(|(coefficient, _, _)| *coefficient)
+        .or_else(|| expr.linear_combinations.first().map(|(coefficient, _)| *coefficient))
+        .unwrap_or(expr.q_c);
+    if leading_coefficient.is_zero() {
+        expr
+    } else {
+        &expr * leading_coefficient.inverse()
+    }
+}
+
+/// Substitutes away intermediate witnesses which are defined by a linear `AssertZero` opcode
+/// and read by exactly one later `AssertZero` opcode.
+///
+/// If an opcode is the first to mention a witness then that witness is unknown when the opcode is solved,
+/// so every other witness in the opcode must already be known. Substituting the definition into the later
+/// opcode therefore only introduces witnesses which are known by the time it is solved.
+fn substitute_linear_definitions<F: AcirField>(
+    circuit: Circuit<F>,
+    order_list: Vec<usize>,
+    protected_opcodes: &HashSet<usize>,
+) -> (Circuit<F>, Vec<usize>) {
+    let witness_uses = collect_assert_zero_uses(&circuit);
+    let pinned_witnesses = collect_pinned_witnesses(&circuit);
+
+    // Maps each witness which has been substituted away to the linear expression which defined it.
+    let mut definitions: HashMap<Witness, Expression<F>> = HashMap::new();
+
+    let mut new_order_list = Vec::with_capacity(order_list.len());
+    let mut optimized_opcodes = Vec::with_capacity(circuit.opcodes.len());
+    for (idx, opcode) in circuit.opcodes.iter().enumerate() {
+        let Opcode::AssertZero(expr) = opcode else {
+            new_order_list.push(order_list[idx]);
+            optimized_opcodes.push(opcode.clone());
+            continue;
+        };
+
+        let mut expr = expr.clone();
+        expr.sort();
+        for (_, witness) in expr.linear_combinations.clone() {
+            if let Some(definition) = definitions.remove(&witness) {
+                let coefficient = linear_coefficient(&expr, witness);
+                let definition_coefficient = linear_coefficient(&definition, witness);
+                expr = expr.add_mul(-coefficient / definition_coefficient, &definition);
+            }
+        }
+
+        let defined_witness = if expr.is_linear() && !protected_opcodes.contains(&order_list[idx]) {
+            expr.linear_combinations.iter().map(|(_, witness)| *witness).find(|witness| {
+                if pinned_witnesses.contains(witness) {
+                    return false;
+                }
+                match witness_uses.get(witness).map(Vec::as_slice) {
+                    Some(&[definition, usage]) if definition == idx => {
+                        let Opcode::AssertZero(usage) = &circuit.opcodes[usage] else {
+                            unreachable!("only `AssertZero` opcodes are recorded as uses");
+                        };
+                        // Substituting into a multiplication term could leave the opcode with
+                        // several terms in the witness which it solves.
+                        !usage
+                            .mul_terms
+                            .iter()
+                            .any(|(_, lhs, rhs)| lhs == witness || rhs == witness)
+                    }
+                    _ => false,
+                }
+            })
+        } else {
+            None
+        };
+
+        match defined_witness {
+            Some(witness) => {
+                definitions.insert(witness, expr);
+            }
+            None => {
+                new_order_list.push(order_list[idx]);
+                optimized_opcodes.push(Opcode::AssertZero(expr));
+            }
+        }
+    }
+    debug_assert!(definitions.is_empty(), "every substituted witness should have been used");
+
+    (Circuit { opcodes: optimized_opcodes, ..circuit }, new_order_list)
+}
+
+fn linear_coefficient<F: AcirField>(expr: &Expression<F>, witness: Witness) -> F {
+    expr.linear_combinations
+        .iter()
+        .find_map(|(coefficient, term)| (*term == witness).then_some(*coefficient))
+        .expect("expression should contain the witness")
+}
+
+/// Maps each witness to the indices of the `AssertZero` opcodes which mention it.
+fn collect_assert_zero_uses<F>(circuit: &Circuit<F>) -> BTreeMap<Witness, Vec<usize>> {
+    let mut witness_uses: BTreeMap<Witness, Vec<usize>> = BTreeMap::new();
+    for (idx, opcode) in circuit.opcodes.iter().enumerate() {
+        if let Opcode::AssertZero(expr) = opcode {
+            for witness in expression_witnesses(expr) {
+                let uses = witness_uses.entry(witness).or_default();
+                if uses.last() != Some(&idx) {
+                    uses.push(idx);
+                }
+            }
+        }
+    }
+    witness_uses
+}
+
+/// Returns the witnesses which must be kept in the circuit.
+///
+/// These are the circuit's arguments and return values along with any witness which is used
+/// by an assertion payload or by an opcode other than [`Opcode::AssertZero`].
+fn collect_pinned_witnesses<F: AcirField>(circuit: &Circuit<F>) -> HashSet<Witness> {
+    let mut pinned_witnesses: HashSet<Witness> = circuit.circuit_arguments().into_iter().collect();
+    pinned_witnesses.extend(circuit.return_values.0.iter().copied());
+
+    for (_, payload) in &circuit.assert_messages {
+        if let AssertionPayload::Dynamic(_, values) = payload {
+            for value in values {
+                if let ExpressionOrMemory::Expression(expr) = value {
+                    pinned_witnesses.extend(expression_witnesses(expr));
+                }
+            }
+        }
+    }
+
+    for opcode in &circuit.opcodes {
+        match opcode {
+            Opcode::AssertZero(_) => (),
+            Opcode::BlackBoxFuncCall(bb_func) => {
+                for input in bb_func.get_inputs_vec() {
+                    if let ConstantOrWitnessEnum::Witness(witness) = input.input() {
+                        pinned_witnesses.insert(witness);
+                    }
+                }
+                pinned_witnesses.extend(bb_func.get_outputs_vec());
+            }
+            Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+                pinned_witnesses.extend(expression_witnesses(a));
+                pinned_witnesses.extend(b.iter().copied());
+            }
+            Opcode::MemoryInit { init, .. } => pinned_witnesses.extend(init.iter().copied()),
+            Opcode::MemoryOp { op, predicate, .. } => {
+                pinned_witnesses.extend(expression_witnesses(&op.operation));
+                pinned_witnesses.extend(expression_witnesses(&op.index));
+                pinned_witnesses.extend(expression_witnesses(&op.value));
+                pinned_witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+            }
+            Opcode::BrilligCall { inputs, outputs, predicate, .. } => {
+                for input in inputs {
+                    match input {
+                        BrilligInputs::Single(expr) => {
+                            pinned_witnesses.extend(expression_witnesses(expr));
+                        }
+                        BrilligInputs::Array(exprs) => {
+                            pinned_witnesses.extend(exprs.iter().flat_map(expression_witnesses));
+                        }
+                        BrilligInputs::MemoryArray(_) => (),
+                    }
+                }
+                for output in outputs {
+                    match output {
+                        BrilligOutputs::Simple(witness) => {
+                            pinned_witnesses.insert(*witness);
+                        }
+                        BrilligOutputs::Array(witnesses) => {
+                            pinned_witnesses.extend(witnesses.iter().copied());
+                        }
+                    }
+                }
+                pinned_witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+            }
+            Opcode::Call { inputs, outputs, predicate, .. } => {
+                pinned_witnesses.extend(inputs.iter().chain(outputs).copied());
+                pinned_witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+            }
+        }
+    }
+    pinned_witnesses
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+    let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+    mul_witnesses.chain(expr.linear_combinations.iter().map(|(_, witness)| *witness))
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{Circuit, Opcode, Program},
+        native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
+    use acvm_blackbox_solver::StubbedBlackBoxSolver;
+
+    use super::MergeExpressionsOptimizer;
+    use crate::pwg::{ACVMStatus, ACVM};
+
+    fn parse_circuit(source: &str) -> Circuit<FieldElement> {
+        let mut program: Program<FieldElement> = source.parse().unwrap();
+        program.functions.remove(0)
+    }
+
+    fn merge_expressions(circuit: Circuit<FieldElement>) -> (Circuit<FieldElement>, Vec<usize>) {
+        let acir_opcode_positions = (0..circuit.opcodes.len()).collect();
+        MergeExpressionsOptimizer::new(circuit).merge_expressions(acir_opcode_positions)
+    }
+
+    fn solve(circuit: &Circuit<FieldElement>, inputs: &[(u32, u128)]) -> WitnessMap<FieldElement> {
+        let initial_witness = WitnessMap::from(
+            inputs
+                .iter()
+                .map(|(index, value)| (Witness(*index), FieldElement::from(*value)))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        );
+        let mut acvm = ACVM::new(
+            &StubbedBlackBoxSolver,
+            &circuit.opcodes,
+            initial_witness,
+            &[],
+            &circuit.assert_messages,
+        );
+        assert_eq!(acvm.solve(), ACVMStatus::Solved);
+        acvm.finalize()
+    }
+
+    #[test]
+    fn removes_duplicate_expressions() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 2
+                private_parameters: [_0, _1]
+                return_values: [_2]
+
+                ASSERT_ZERO [ (1, _0, _1) (-1, _2) 0 ]
+                ASSERT_ZERO [ (-2, _1, _0) (2, _2) 0 ]
+                ASSERT_ZERO [ (1, _0, _1) (-1, _2) 0 ]
+            }",
+        );
+
+        let (optimized_circuit, acir_opcode_positions) = merge_expressions(circuit.clone());
+        assert_eq!(optimized_circuit.opcodes, vec![circuit.opcodes[0].clone()]);
+        assert_eq!(acir_opcode_positions, vec![0]);
+    }
+
+    #[test]
+    fn substitutes_single_use_linear_definitions() {
+        // _3 = _0 + _1 and _4 = 2 * _3 are only used to compute the return value _5 = _2 * _2 + _4 + 1.
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 5
+                private_parameters: [_0, _1, _2]
+                return_values: [_5]
+
+                ASSERT_ZERO [ (1, _0) (1, _1) (-1, _3) 0 ]
+                ASSERT_ZERO [ (2, _3) (-1, _4) 0 ]
+                ASSERT_ZERO [ (1, _2, _2) (1, _4) (-1, _5) 1 ]
+            }",
+        );
+
+        let (optimized_circuit, acir_opcode_positions) = merge_expressions(circuit.clone());
+        assert_eq!(optimized_circuit.opcodes.len(), 1);
+        assert_eq!(acir_opcode_positions, vec![2]);
+
+        let Opcode::AssertZero(expr) = &optimized_circuit.opcodes[0] else {
+            panic!("expected an `AssertZero` opcode");
+        };
+        let witnesses: Vec<_> =
+            expr.linear_combinations.iter().map(|(_, witness)| *witness).collect();
+        assert_eq!(witnesses, vec![Witness(0), Witness(1), Witness(5)]);
+
+        let expected = solve(&circuit, &[(0, 3), (1, 4), (2, 5)]);
+        let actual = solve(&optimized_circuit, &[(0, 3), (1, 4), (2, 5)]);
+        assert_eq!(actual[&Witness(5)], expected[&Witness(5)]);
+        assert_eq!(actual[&Witness(5)], FieldElement::from(40_u128));
+    }
+
+    #[test]
+    fn keeps_witnesses_used_outside_of_assert_zero_opcodes() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 3
+                private_parameters: [_0, _1]
+                return_values: [_3]
+
+                ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+                BLACKBOX::RANGE { input: (_2, 32) }
+                ASSERT_ZERO [ (1, _2) (-1, _3) 5 ]
+            }",
+        );
+
+        let (optimized_circuit, _) = merge_expressions(circuit.clone());
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+
+    #[test]
+    fn keeps_witnesses_used_in_multiplication_terms() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 3
+                private_parameters: [_0, _1]
+                return_values: [_3]
+
+                ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+                ASSERT_ZERO [ (1, _2, _3) (1, _3) -1 ]
+            }",
+        );
+
+        let (optimized_circuit, _) = merge_expressions(circuit.clone());
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+    }
+
+    #[test]
+    fn keeps_opcodes_with_assertion_payloads() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 2
+                private_parameters: [_0]
+                return_values: [_2]
+                assert_messages: [0: \"definition\", 2: \"duplicate\"]
+
+                ASSERT_ZERO [ (1, _0) (-1, _1) 1 ]
+                ASSERT_ZERO [ (1, _1) (-1, _2) 0 ]
+                ASSERT_ZERO [ (2, _1) (-2, _2) 0 ]
+            }",
+        );
+
+        let (optimized_circuit, acir_opcode_positions) = merge_expressions(circuit.clone());
+        assert_eq!(optimized_circuit.opcodes, circuit.opcodes);
+        assert_eq!(acir_opcode_positions, vec![0, 1, 2]);
+    }
+}
@@ -5,6 +5,7 @@ use acir::{
 
 // mod constant_backpropagation;
 mod general;
+mod merge_expressions;
 mod redundant_range;
 mod unused_memory;
 
@@ -13,6 +14,7 @@ pub(crate) use redundant_range::RangeOptimizer;
 use tracing::info;
 
 // use self::constant_backpropagation::ConstantBackpropagationOptimizer;
+use self::merge_expressions::MergeExpressionsOptimizer;
 use self::unused_memory::UnusedMemoryOptimizer;
 
 use super::{transform_assert_messages, AcirTransformationMap};
@@ -64,6 +66,10 @@ pub(super) fn optimize_internal<F: AcirField>(acir: Circuit<F>) -> (Circuit<F>,
     // let (acir, acir_opcode_positions) =
     // ConstantBackpropagationOptimizer::backpropagate_constants(acir, acir_opcode_positions);
 
+    // Common subexpression elimination and linear substitution pass
+    let merge_optimizer = MergeExpressionsOptimizer::new(acir);
+    let (acir, acir_opcode_positions) = merge_optimizer.merge_expressions(acir_opcode_positions);
+
     // Range optimization pass
     let range_optimizer = RangeOptimizer::new(acir);
     let (acir, acir_opcode_positions) =
@@ -0,0 +1,266 @@
+//! Backend-agnostic estimates of the number of gates needed to prove a [`Circuit`].
+//!
+//! The estimates are produced in-process by a [`GateEstimator`], so they are available without installing
+//! a proving backend. They are only approximations of what a backend will report.
+
+use std::collections::{BTreeSet, HashMap};
+
+use acir::{
+    circuit::{Circuit, ExpressionWidth, Opcode},
+    native_types::Expression,
+    AcirField, BlackBoxFunc,
+};
+
+/// Width of the PLONK arithmetisation assumed when a circuit does not bound its expression width.
+pub const DEFAULT_PLONK_WIDTH: usize = 4;
+
+/// Smallest width for which gates can be chained, as each gate passes on an intermediate witness.
+pub const MIN_PLONK_WIDTH: usize = 3;
+
+/// A cost model which estimates the number of gates required by each ACIR opcode.
+pub trait GateEstimator<F> {
+    /// Returns the estimated number of gates needed to constrain `opcode`.
+    fn opcode_gates(&self, opcode: &Opcode<F>) -> usize;
+
+    /// Returns the estimated number of gates needed to constrain each opcode of `circuit`.
+    fn gates_per_opcode(&self, circuit: &Circuit<F>) -> Vec<usize> {
+        circuit.opcodes.iter().map(|opcode| self.opcode_gates(opcode)).collect()
+    }
+
+    /// Returns the estimated number of gates needed to constrain `circuit`.
+    fn circuit_gates(&self, circuit: &Circuit<F>) -> usize {
+        self.gates_per_opcode(circuit).into_iter().sum()
+    }
+}
+
+/// The cost of a single call to a blackbox function.
+///
+/// A call costs `fixed + per_input * inputs` gates, plus one gate for every `bits_per_gate` bits
+/// spanned by its inputs if `bits_per_gate` is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlackBoxCost {
+    pub fixed: usize,
+    pub per_input: usize,
+    pub bits_per_gate: Option<u32>,
+}
+
+impl BlackBoxCost {
+    pub const fn fixed(gates: usize) -> Self {
+        BlackBoxCost { fixed: gates, per_input: 0, bits_per_gate: None }
+    }
+
+    pub const fn per_input(gates: usize) -> Self {
+        BlackBoxCost { fixed: 0, per_input: gates, bits_per_gate: None }
+    }
+
+    pub const fn per_bits(bits_per_gate: u32) -> Self {
+        BlackBoxCost { fixed: 0, per_input: 0, bits_per_gate: Some(bits_per_gate) }
+    }
+
+    fn gates(&self, num_inputs: usize, num_input_bits: u32) -> usize {
+        let bit_gates = self
+            .bits_per_gate
+            .map_or(0, |bits_per_gate| num_input_bits.div_ceil(bits_per_gate.max(1)) as usize);
+        self.fixed + self.per_input * num_inputs + bit_gates
+    }
+}
+
+/// Estimates gates for a width-N PLONK arithmetisation.
+///
+/// Each gate can hold a single multiplication term and up to `width` witnesses. Longer [`Opcode::AssertZero`]
+/// expressions are split into several gates which are chained through intermediate witnesses.
+/// Blackbox functions are costed using a table which can be overridden with [`PlonkGateEstimator::with_blackbox_cost`],
+/// while Brillig calls and directives are unconstrained and so do not cost any gates.
+#[derive(Debug, Clone)]
+pub struct PlonkGateEstimator {
+    width: usize,
+    blackbox_costs: HashMap<BlackBoxFunc, BlackBoxCost>,
+    /// Gates needed for every element of a memory block when it is initialized.
+    pub memory_init_gates_per_element: usize,
+    /// Gates needed for every read from or write to a memory block.
+    pub memory_op_gates: usize,
+}
+
+impl PlonkGateEstimator {
+    /// Creates an estimator for gates which hold `width` witnesses, using the default blackbox cost table.
+    ///
+    /// Widths below [`MIN_PLONK_WIDTH`] are raised to it, as a circuit may be compiled with any expression width.
+    pub fn new(width: usize) -> Self {
+        PlonkGateEstimator {
+            width: width.max(MIN_PLONK_WIDTH),
+            blackbox_costs: default_blackbox_costs(),
+            memory_init_gates_per_element: 1,
+            memory_op_gates: 2,
+        }
+    }
+
+    /// Creates an estimator for the arithmetisation targeted by `expression_width`.
+    pub fn for_expression_width(expression_width: ExpressionWidth) -> Self {
+        match expression_width {
+            ExpressionWidth::Unbounded => Self::new(DEFAULT_PLONK_WIDTH),
+            ExpressionWidth::Bounded { width } => Self::new(width),
+        }
+    }
+
+    /// Replaces the cost of calls to `func`.
+    pub fn with_blackbox_cost(mut self, func: BlackBoxFunc, cost: BlackBoxCost) -> Self {
+        self.blackbox_costs.insert(func, cost);
+        self
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    fn expression_gates<F>(&self, expr: &Expression<F>) -> usize {
+        let witnesses: BTreeSet<_> = expr
+            .mul_terms
+            .iter()
+            .flat_map(|(_, lhs, rhs)| [*lhs, *rhs])
+            .chain(expr.linear_combinations.iter().map(|(_, witness)| *witness))
+            .collect();
+
+        // The first gate holds `width` witnesses and defines an intermediate witness which is carried into the
+        // next gate. Every following gate takes one intermediate witness and passes another one on.
+        let width_gates = witnesses.len().saturating_sub(2).div_ceil(self.width - 2);
+        width_gates.max(expr.mul_terms.len()).max(1)
+    }
+}
+
+impl<F: AcirField> GateEstimator<F> for PlonkGateEstimator {
+    fn opcode_gates(&self, opcode: &Opcode<F>) -> usize {
+        match opcode {
+            Opcode::AssertZero(expr) => self.expression_gates(expr),
+            Opcode::BlackBoxFuncCall(bb_func) => {
+                let Some(cost) = self.blackbox_costs.get(&bb_func.get_black_box_func()) else {
+                    return 0;
+                };
+                let inputs = bb_func.get_inputs_vec();
+                let num_input_bits = inputs.iter().map(|input| input.num_bits()).sum();
+                cost.gates(inputs.len(), num_input_bits)
+            }
+            Opcode::MemoryInit { init, .. } => self.memory_init_gates_per_element * init.len(),
+            Opcode::MemoryOp { .. } => self.memory_op_gates,
+            // Unconstrained execution and calls to other circuits do not add gates to this circuit.
+            Opcode::Directive(_) | Opcode::BrilligCall { .. } | Opcode::Call { .. } => 0,
+        }
+    }
+}
+
+/// Approximate costs of blackbox functions in an UltraPlonk-style arithmetisation with lookup tables.
+fn default_blackbox_costs() -> HashMap<BlackBoxFunc, BlackBoxCost> {
+    HashMap::from([
+        (BlackBoxFunc::AES128Encrypt, BlackBoxCost::per_input(250)),
+        (BlackBoxFunc::AND, BlackBoxCost::per_bits(8)),
+        (BlackBoxFunc::XOR, BlackBoxCost::per_bits(8)),
+        (BlackBoxFunc::RANGE, BlackBoxCost::per_bits(14)),
+        (BlackBoxFunc::Blake2s, BlackBoxCost { fixed: 500, per_input: 40, bits_per_gate: None }),
+        (BlackBoxFunc::Blake3, BlackBoxCost { fixed: 500, per_input: 40, bits_per_gate: None }),
+        (BlackBoxFunc::SchnorrVerify, BlackBoxCost::fixed(5_000)),
+        (BlackBoxFunc::PedersenCommitment, BlackBoxCost::per_input(25)),
+        (BlackBoxFunc::PedersenHash, BlackBoxCost::per_input(30)),
+        (BlackBoxFunc::EcdsaSecp256k1, BlackBoxCost::fixed(36_000)),
+        (BlackBoxFunc::EcdsaSecp256r1, BlackBoxCost::fixed(70_000)),
+        (BlackBoxFunc::MultiScalarMul, BlackBoxCost::per_input(800)),
+        (BlackBoxFunc::Keccak256, BlackBoxCost::per_input(150)),
+        (BlackBoxFunc::Keccakf1600, BlackBoxCost::fixed(16_000)),
+        (BlackBoxFunc::RecursiveAggregation, BlackBoxCost::fixed(0)),
+        (BlackBoxFunc::EmbeddedCurveAdd, BlackBoxCost::fixed(4)),
+        (BlackBoxFunc::BigIntAdd, BlackBoxCost::fixed(10)),
+        (BlackBoxFunc::BigIntSub, BlackBoxCost::fixed(10)),
+        (BlackBoxFunc::BigIntMul, BlackBoxCost::fixed(50)),
+        (BlackBoxFunc::BigIntDiv, BlackBoxCost::fixed(100)),
+        (BlackBoxFunc::BigIntFromLeBytes, BlackBoxCost::per_input(1)),
+        (BlackBoxFunc::BigIntToLeBytes, BlackBoxCost::fixed(20)),
+        (BlackBoxFunc::Poseidon2Permutation, BlackBoxCost::fixed(73)),
+        (BlackBoxFunc::Sha256Compression, BlackBoxCost::fixed(7_000)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{Circuit, ExpressionWidth, Program},
+        BlackBoxFunc, FieldElement,
+    };
+
+    use super::{BlackBoxCost, GateEstimator, PlonkGateEstimator, MIN_PLONK_WIDTH};
+
+    fn parse_circuit(source: &str) -> Circuit<FieldElement> {
+        let mut program: Program<FieldElement> = source.parse().unwrap();
+        program.functions.remove(0)
+    }
+
+    #[test]
+    fn splits_wide_expressions_across_gates() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 6
+                ASSERT_ZERO [ (1, _0, _1) (1, _2) (-1, _3) 0 ]
+                ASSERT_ZERO [ (1, _0) (1, _1) (1, _2) (1, _3) (1, _4) (1, _5) (-1, _6) 0 ]
+                ASSERT_ZERO [ (1, _0, _1) (1, _2, _3) (1, _4, _5) 0 ]
+                ASSERT_ZERO [ 0 ]
+            }",
+        );
+
+        let estimator = PlonkGateEstimator::new(4);
+        assert_eq!(estimator.gates_per_opcode(&circuit), vec![1, 3, 3, 1]);
+
+        let estimator = PlonkGateEstimator::new(7);
+        assert_eq!(estimator.gates_per_opcode(&circuit), vec![1, 1, 3, 1]);
+    }
+
+    #[test]
+    fn raises_narrow_widths_to_the_minimum() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 3
+                ASSERT_ZERO [ (1, _0) (1, _1) (1, _2) (-1, _3) 0 ]
+            }",
+        );
+
+        for width in 0..MIN_PLONK_WIDTH {
+            let estimator =
+                PlonkGateEstimator::for_expression_width(ExpressionWidth::Bounded { width });
+            assert_eq!(estimator.width(), MIN_PLONK_WIDTH);
+            assert_eq!(estimator.gates_per_opcode(&circuit), vec![2]);
+        }
+    }
+
+    #[test]
+    fn costs_blackbox_calls_from_table() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 2
+                BLACKBOX::RANGE { input: (_0, 32) }
+                BLACKBOX::AND { lhs: (_0, 32), rhs: (_1, 32), output: _2 }
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_2)] }
+            }
+
+            brillig func 0 {
+                stop { return_data_offset: 0, return_data_size: 0 }
+            }",
+        );
+
+        let estimator = PlonkGateEstimator::new(4);
+        assert_eq!(estimator.gates_per_opcode(&circuit), vec![3, 8, 0]);
+
+        let estimator = PlonkGateEstimator::new(4)
+            .with_blackbox_cost(BlackBoxFunc::RANGE, BlackBoxCost::fixed(1));
+        assert_eq!(estimator.circuit_gates(&circuit), 9);
+    }
+
+    #[test]
+    fn costs_memory_by_block_size() {
+        let circuit = parse_circuit(
+            "func 0 {
+                current_witness_index: 3
+                MEMORY_INIT { block_id: b0, init: [_0, _1, _2], block_type: memory }
+                MEMORY_OP { block_id: b0, operation: [ 0 ], index: [ 1 ], value: [ (1, _3) 0 ] }
+            }",
+        );
+
+        let estimator = PlonkGateEstimator::new(4);
+        assert_eq!(estimator.gates_per_opcode(&circuit), vec![3, 2]);
+    }
+}
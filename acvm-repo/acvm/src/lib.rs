@@ -3,9 +3,13 @@
 #![warn(clippy::semicolon_if_nothing_returned)]
 #![cfg_attr(not(test), warn(unused_crate_dependencies, unused_extern_crates))]
 
+mod check;
 pub mod compiler;
+pub mod gates;
 pub mod pwg;
 
+pub use check::{check_witness, WitnessCheckError};
+
 pub use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 use pwg::OpcodeResolutionError;
 
@@ -21,6 +21,7 @@ mod logic;
 mod pedersen;
 mod range;
 mod signature;
+mod user_defined;
 pub(crate) mod utils;
 
 use embedded_curve_ops::{embedded_curve_add, multi_scalar_mul};
@@ -33,6 +34,7 @@ use signature::{
     ecdsa::{secp256k1_prehashed, secp256r1_prehashed},
     schnorr::schnorr_verify,
 };
+use user_defined::solve_user_defined_opcode;
 
 /// Check if all of the inputs to the function have assignments
 ///
@@ -190,5 +192,8 @@ pub(crate) fn solve<F: AcirField>(
         BlackBoxFuncCall::Poseidon2Permutation { inputs, outputs, len } => {
             solve_poseidon2_permutation_opcode(backend, initial_witness, inputs, outputs, *len)
         }
+        BlackBoxFuncCall::UserDefined { name, inputs, outputs } => {
+            solve_user_defined_opcode(backend, initial_witness, name, inputs, outputs)
+        }
     }
 }
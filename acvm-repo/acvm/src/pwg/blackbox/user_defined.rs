@@ -0,0 +1,39 @@
+use acir::{
+    circuit::opcodes::FunctionInput,
+    native_types::{Witness, WitnessMap},
+    AcirField, BlackBoxFunc,
+};
+
+use crate::pwg::{input_to_value, insert_value, OpcodeResolutionError};
+use crate::BlackBoxFunctionSolver;
+
+/// Solves a call to a blackbox function which was registered with `backend` under `name`.
+pub(super) fn solve_user_defined_opcode<F: AcirField>(
+    backend: &impl BlackBoxFunctionSolver<F>,
+    initial_witness: &mut WitnessMap<F>,
+    name: &str,
+    inputs: &[FunctionInput<F>],
+    outputs: &[Witness],
+) -> Result<(), OpcodeResolutionError<F>> {
+    let inputs = inputs
+        .iter()
+        .map(|input| input_to_value(initial_witness, *input, false))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let values = backend.user_defined(name, &inputs)?;
+    if values.len() != outputs.len() {
+        return Err(OpcodeResolutionError::BlackBoxFunctionFailed(
+            BlackBoxFunc::UserDefined,
+            format!(
+                "{name}: the number of outputs does not match the call. {} != {}",
+                values.len(),
+                outputs.len()
+            ),
+        ));
+    }
+
+    for (output_witness, value) in outputs.iter().zip(values) {
+        insert_value(output_witness, value, initial_witness)?;
+    }
+    Ok(())
+}
@@ -12,7 +12,9 @@ use acir::{
     AcirField,
 };
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
-use brillig_vm::{FailureReason, MemoryValue, VMStatus, VM};
+use brillig_vm::{
+    BrilligObserver, DecodedBytecode, FailureReason, MemoryValue, VMLimits, VMStatus, VM,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{pwg::OpcodeNotSolvable, OpcodeResolutionError};
@@ -26,6 +28,16 @@ pub enum BrilligSolverStatus<F> {
     ForeignCallWait(ForeignCallWaitInfo<F>),
 }
 
+/// The Brillig function being called by an ACIR `BrilligCall` opcode, along with the
+/// settings the VM should be run with.
+pub(crate) struct BrilligCall<'b, F> {
+    pub(crate) bytecode: DecodedBytecode<'b, F>,
+    /// The index of the calling opcode within the ACIR circuit.
+    pub(crate) acir_index: usize,
+    pub(crate) function_id: BrilligFunctionId,
+    pub(crate) vm_limits: VMLimits,
+}
+
 pub struct BrilligSolver<'b, F, B: BlackBoxFunctionSolver<F>> {
     vm: VM<'b, F, B>,
     acir_index: usize,
@@ -62,23 +74,32 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
         initial_witness: &WitnessMap<F>,
         memory: &HashMap<BlockId, MemoryOpSolver<F>>,
         inputs: &'b [BrilligInputs<F>],
-        brillig_bytecode: &'b [BrilligOpcode<F>],
         bb_solver: &'b B,
-        acir_index: usize,
-        brillig_function_id: BrilligFunctionId,
+        call: BrilligCall<'b, F>,
     ) -> Result<Self, OpcodeResolutionError<F>> {
-        let vm =
-            Self::setup_brillig_vm(initial_witness, memory, inputs, brillig_bytecode, bb_solver)?;
-        Ok(Self { vm, acir_index, function_id: brillig_function_id })
+        let calldata = Self::read_calldata(initial_witness, memory, inputs)?;
+        Ok(Self::with_calldata(calldata, bb_solver, call))
+    }
+
+    /// Constructs a solver for a Brillig block given the bytecode and its already evaluated inputs.
+    pub(crate) fn with_calldata(
+        calldata: Vec<F>,
+        bb_solver: &'b B,
+        call: BrilligCall<'b, F>,
+    ) -> Self {
+        // Instantiate a Brillig VM given the solved calldata
+        // along with the Brillig bytecode.
+        let mut vm = VM::with_decoded_bytecode(calldata, call.bytecode, vec![], bb_solver);
+        vm.set_limits(call.vm_limits);
+        Self { vm, acir_index: call.acir_index, function_id: call.function_id }
     }
 
-    fn setup_brillig_vm(
+    /// Evaluates the inputs to a Brillig call into the calldata for the Brillig VM.
+    pub(crate) fn read_calldata(
         initial_witness: &WitnessMap<F>,
         memory: &HashMap<BlockId, MemoryOpSolver<F>>,
         inputs: &[BrilligInputs<F>],
-        brillig_bytecode: &'b [BrilligOpcode<F>],
-        bb_solver: &'b B,
-    ) -> Result<VM<'b, F, B>, OpcodeResolutionError<F>> {
+    ) -> Result<Vec<F>, OpcodeResolutionError<F>> {
         // Set input values
         let mut calldata: Vec<F> = Vec::new();
         // Each input represents an expression or array of expressions to evaluate.
@@ -122,11 +143,7 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
                 }
             }
         }
-
-        // Instantiate a Brillig VM given the solved calldata
-        // along with the Brillig bytecode.
-        let vm = VM::new(calldata, brillig_bytecode, vec![], bb_solver);
-        Ok(vm)
+        Ok(calldata)
     }
 
     pub fn get_memory(&self) -> &[MemoryValue<F>] {
@@ -141,8 +158,11 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
         self.vm.get_call_stack()
     }
 
-    pub(crate) fn solve(&mut self) -> Result<BrilligSolverStatus<F>, OpcodeResolutionError<F>> {
-        let status = self.vm.process_opcodes();
+    pub(crate) fn solve<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+    ) -> Result<BrilligSolverStatus<F>, OpcodeResolutionError<F>> {
+        let status = self.vm.process_opcodes_with_observer(observer);
         self.handle_vm_status(status)
     }
 
@@ -174,6 +194,13 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
                         brillig_index: *brillig_index,
                     })
                     .collect();
+                // Exceeding a limit is not a failure of the program itself, so it must not be
+                // reported as an assertion which a test could expect to fail.
+                let limit_exceeded = |reason: String| OpcodeResolutionError::BrilligLimitExceeded {
+                    function_id: self.function_id,
+                    call_stack: call_stack.clone(),
+                    reason,
+                };
                 let payload = match reason {
                     FailureReason::RuntimeError { message } => {
                         Some(ResolvedAssertionPayload::String(message))
@@ -185,6 +212,16 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
                             revert_data_size,
                         )
                     }
+                    FailureReason::MemoryLimitExceeded { max_memory_size, requested_size } => {
+                        return Err(limit_exceeded(format!(
+                            "memory limit exceeded: attempted to grow memory to {requested_size} slots but the limit is {max_memory_size}"
+                        )));
+                    }
+                    FailureReason::StepLimitExceeded { max_steps } => {
+                        return Err(limit_exceeded(format!(
+                            "step limit exceeded: execution did not finish within {max_steps} opcodes"
+                        )));
+                    }
                 };
 
                 Err(OpcodeResolutionError::BrilligFunctionFailed {
@@ -205,47 +242,19 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
         outputs: &[BrilligOutputs],
     ) -> Result<(), OpcodeResolutionError<F>> {
         // Finish the Brillig execution by writing the outputs to the witness map
-        let vm_status = self.vm.get_status();
-        match vm_status {
-            VMStatus::Finished { return_data_offset, return_data_size } => {
-                self.write_brillig_outputs(witness, return_data_offset, return_data_size, outputs)?;
-                Ok(())
-            }
-            _ => panic!("Brillig VM has not completed execution"),
-        }
+        write_brillig_outputs(witness, &self.return_values(), outputs)
     }
 
-    fn write_brillig_outputs(
-        &self,
-        witness_map: &mut WitnessMap<F>,
-        return_data_offset: usize,
-        return_data_size: usize,
-        outputs: &[BrilligOutputs],
-    ) -> Result<(), OpcodeResolutionError<F>> {
-        // Write VM execution results into the witness map
-        let memory = self.vm.get_memory();
-        let mut current_ret_data_idx = return_data_offset;
-        for output in outputs.iter() {
-            match output {
-                BrilligOutputs::Simple(witness) => {
-                    insert_value(witness, memory[current_ret_data_idx].to_field(), witness_map)?;
-                    current_ret_data_idx += 1;
-                }
-                BrilligOutputs::Array(witness_arr) => {
-                    for witness in witness_arr.iter() {
-                        let value = &memory[current_ret_data_idx];
-                        insert_value(witness, value.to_field(), witness_map)?;
-                        current_ret_data_idx += 1;
-                    }
-                }
-            }
+    /// Returns the values returned by the Brillig VM once it has completed execution.
+    pub(crate) fn return_values(&self) -> Vec<F> {
+        match self.vm.get_status() {
+            VMStatus::Finished { return_data_offset, return_data_size } => self.vm.get_memory()
+                [return_data_offset..return_data_offset + return_data_size]
+                .iter()
+                .map(|value| value.to_field())
+                .collect(),
+            _ => panic!("Brillig VM has not completed execution"),
         }
-
-        assert!(
-            current_ret_data_idx == return_data_offset + return_data_size,
-            "Brillig VM did not write the expected number of return values"
-        );
-        Ok(())
     }
 
     pub fn resolve_pending_foreign_call(&mut self, foreign_call_result: ForeignCallResult<F>) {
@@ -256,6 +265,223 @@ impl<'b, B: BlackBoxFunctionSolver<F>, F: AcirField> BrilligSolver<'b, F, B> {
     }
 }
 
+/// Writes the values returned by a Brillig call into the witness map.
+pub(super) fn write_brillig_outputs<F: AcirField>(
+    witness_map: &mut WitnessMap<F>,
+    return_values: &[F],
+    outputs: &[BrilligOutputs],
+) -> Result<(), OpcodeResolutionError<F>> {
+    let mut return_values = return_values.iter();
+    for output in outputs.iter() {
+        let witnesses = match output {
+            BrilligOutputs::Simple(witness) => std::slice::from_ref(witness),
+            BrilligOutputs::Array(witness_arr) => witness_arr.as_slice(),
+        };
+        for witness in witnesses {
+            let value =
+                return_values.next().expect("Brillig VM did not write enough return values");
+            insert_value(witness, *value, witness_map)?;
+        }
+    }
+
+    assert!(
+        return_values.next().is_none(),
+        "Brillig VM did not write the expected number of return values"
+    );
+    Ok(())
+}
+
+/// The decoded bytecode of the Brillig functions of a program, each decoded the first time it is called.
+///
+/// Each [ACVM][super::ACVM] decodes the functions it calls itself, but these can be lent between the ACVMs
+/// executing the circuits of a program with [ACVM::set_decoded_brillig_functions][super::ACVM::set_decoded_brillig_functions]
+/// so that each function is decoded once per execution of the program.
+#[derive(Debug, Clone)]
+pub struct DecodedBrilligFunctions<'a, F> {
+    functions: HashMap<BrilligFunctionId, DecodedBytecode<'a, F>>,
+}
+
+impl<'a, F> Default for DecodedBrilligFunctions<'a, F> {
+    fn default() -> Self {
+        Self { functions: HashMap::new() }
+    }
+}
+
+impl<'a, F: AcirField> DecodedBrilligFunctions<'a, F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the decoded `bytecode` of the function `id`, decoding it if it hasn't been called before.
+    pub(super) fn get_or_decode(
+        &mut self,
+        id: BrilligFunctionId,
+        bytecode: &'a [BrilligOpcode<F>],
+    ) -> DecodedBytecode<'a, F> {
+        self.functions.entry(id).or_insert_with(|| DecodedBytecode::new(bytecode)).clone()
+    }
+}
+
+/// A cache of the results of calls to Brillig functions, keyed by the function and its inputs.
+///
+/// Brillig functions which make foreign calls are never cached, as the results of the foreign calls may differ
+/// between calls. All other Brillig functions are deterministic, so a repeated call with the same inputs can
+/// reuse the results of the first call rather than running the Brillig VM again.
+///
+/// Results are held for the lifetime of the cache, so a single cache should not be shared between unrelated programs.
+/// The size of the cache is bounded by the number of values it holds, after which further results are not cached.
+#[derive(Debug)]
+pub struct BrilligCallCache<F> {
+    results: HashMap<(BrilligFunctionId, Vec<F>), Vec<F>>,
+    /// Whether each Brillig function seen so far can be cached.
+    cacheable_functions: HashMap<BrilligFunctionId, bool>,
+    /// The number of values held across the calldata and return values of all cached calls.
+    size: usize,
+    /// The maximum `size` of the cache.
+    max_values: usize,
+    stats: BrilligCallCacheStats,
+}
+
+impl<F> Default for BrilligCallCache<F> {
+    fn default() -> Self {
+        Self::with_max_values(Self::DEFAULT_MAX_VALUES)
+    }
+}
+
+impl<F> BrilligCallCache<F> {
+    /// The default bound on the number of values held by a cache.
+    pub const DEFAULT_MAX_VALUES: usize = 1 << 20;
+
+    /// Creates a cache holding at most `max_values` values across the calldata and return values of cached calls.
+    pub fn with_max_values(max_values: usize) -> Self {
+        Self {
+            results: HashMap::new(),
+            cacheable_functions: HashMap::new(),
+            size: 0,
+            max_values,
+            stats: BrilligCallCacheStats::default(),
+        }
+    }
+}
+
+impl<F: AcirField> BrilligCallCache<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the maximum number of values this cache may hold.
+    pub fn max_values(&self) -> usize {
+        self.max_values
+    }
+
+    /// Returns statistics on how calls were resolved using this cache.
+    pub fn stats(&self) -> BrilligCallCacheStats {
+        self.stats
+    }
+
+    /// Returns whether calls to the function `id` with the given `bytecode` can be cached.
+    /// Calls which cannot be cached are counted towards [`BrilligCallCacheStats::uncacheable`].
+    pub(super) fn is_cacheable(
+        &mut self,
+        id: BrilligFunctionId,
+        bytecode: &[BrilligOpcode<F>],
+    ) -> bool {
+        let cacheable = *self.cacheable_functions.entry(id).or_insert_with(|| {
+            !bytecode.iter().any(|opcode| matches!(opcode, BrilligOpcode::ForeignCall { .. }))
+        });
+        if !cacheable {
+            self.stats.uncacheable += 1;
+        }
+        cacheable
+    }
+
+    /// Looks up the results of a previous call to the function `id` with the same `calldata`,
+    /// handing back `calldata` on a miss so that it can be used to run the call.
+    pub(super) fn get(&mut self, id: BrilligFunctionId, calldata: Vec<F>) -> Result<&[F], Vec<F>> {
+        let key = (id, calldata);
+        if self.results.contains_key(&key) {
+            self.stats.hits += 1;
+            Ok(&self.results[&key])
+        } else {
+            self.stats.misses += 1;
+            Err(key.1)
+        }
+    }
+
+    /// Adds the results of a call, unless the cache is too full to hold them.
+    /// Results which are not added are counted towards [`BrilligCallCacheStats::dropped`].
+    pub(super) fn insert(
+        &mut self,
+        id: BrilligFunctionId,
+        calldata: Vec<F>,
+        return_values: Vec<F>,
+    ) {
+        let key = (id, calldata);
+        if self.results.contains_key(&key) {
+            return;
+        }
+        let size = key.1.len() + return_values.len();
+        if self.size + size > self.max_values {
+            self.stats.dropped += 1;
+            return;
+        }
+        self.size += size;
+        self.results.insert(key, return_values);
+    }
+
+    /// Adds the results and statistics of `other`, a cache which was used to execute calls separately
+    /// from this one, so that later calls through this cache can reuse its results.
+    pub fn merge(&mut self, other: BrilligCallCache<F>) {
+        for ((id, calldata), return_values) in other.results {
+            self.insert(id, calldata, return_values);
+        }
+        self.cacheable_functions.extend(other.cacheable_functions);
+        self.stats.hits += other.stats.hits;
+        self.stats.misses += other.stats.misses;
+        self.stats.uncacheable += other.stats.uncacheable;
+        self.stats.dropped += other.stats.dropped;
+    }
+}
+
+/// Counts of how Brillig calls were resolved by a [`BrilligCallCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BrilligCallCacheStats {
+    /// Calls which were resolved using the results of an earlier call.
+    pub hits: usize,
+    /// Calls which were executed by the Brillig VM as no earlier results could be reused.
+    pub misses: usize,
+    /// Calls to functions which make foreign calls, and so were executed without using the cache.
+    pub uncacheable: usize,
+    /// Calls whose results were not added to the cache as it was full.
+    pub dropped: usize,
+}
+
+impl BrilligCallCacheStats {
+    /// Returns the proportion of cacheable calls which were resolved using the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let cacheable_calls = self.hits + self.misses;
+        if cacheable_calls == 0 {
+            0.0
+        } else {
+            self.hits as f64 / cacheable_calls as f64
+        }
+    }
+}
+
+impl std::fmt::Display for BrilligCallCacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} uncacheable calls, {} results dropped as the cache was full",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.uncacheable,
+            self.dropped
+        )
+    }
+}
+
 /// Extracts a `ResolvedAssertionPayload` from a block of memory of a Brillig VM instance.
 ///
 /// Returns `None` if the amount of memory requested is zero.
@@ -21,6 +21,19 @@ pub(crate) struct MemoryOpSolver<F> {
 }
 
 impl<F: AcirField> MemoryOpSolver<F> {
+    /// Converts the value of a memory operation's index into a [`MemoryIndex`].
+    ///
+    /// Values which do not fit are necessarily out of bounds, as no block can hold that many elements.
+    fn index_from_field(&self, index: F) -> Result<MemoryIndex, OpcodeResolutionError<F>> {
+        index.try_to_u64().and_then(|index| MemoryIndex::try_from(index).ok()).ok_or(
+            OpcodeResolutionError::IndexOutOfBounds {
+                opcode_location: ErrorLocation::Unresolved,
+                index,
+                array_size: self.block_len,
+            },
+        )
+    }
+
     fn write_memory_index(
         &mut self,
         index: MemoryIndex,
@@ -29,7 +42,7 @@ impl<F: AcirField> MemoryOpSolver<F> {
         if index >= self.block_len {
             return Err(OpcodeResolutionError::IndexOutOfBounds {
                 opcode_location: ErrorLocation::Unresolved,
-                index,
+                index: F::from(u128::from(index)),
                 array_size: self.block_len,
             });
         }
@@ -40,7 +53,7 @@ impl<F: AcirField> MemoryOpSolver<F> {
     fn read_memory_index(&self, index: MemoryIndex) -> Result<F, OpcodeResolutionError<F>> {
         self.block_value.get(&index).copied().ok_or(OpcodeResolutionError::IndexOutOfBounds {
             opcode_location: ErrorLocation::Unresolved,
-            index,
+            index: F::from(u128::from(index)),
             array_size: self.block_len,
         })
     }
@@ -61,17 +74,20 @@ impl<F: AcirField> MemoryOpSolver<F> {
         Ok(())
     }
 
+    /// Solves a [`MemoryOp`][`acir::circuit::Opcode::MemoryOp`] opcode.
+    ///
+    /// Returns the index and value written to memory, unless the opcode is a read or a skipped write.
     pub(crate) fn solve_memory_op(
         &mut self,
         op: &MemOp<F>,
         initial_witness: &mut WitnessMap<F>,
         predicate: &Option<Expression<F>>,
-    ) -> Result<(), OpcodeResolutionError<F>> {
+    ) -> Result<Option<(MemoryIndex, F)>, OpcodeResolutionError<F>> {
         let operation = get_value(&op.operation, initial_witness)?;
 
         // Find the memory index associated with this memory operation.
         let index = get_value(&op.index, initial_witness)?;
-        let memory_index = index.try_to_u64().unwrap() as MemoryIndex;
+        let memory_index = self.index_from_field(index)?;
 
         // Calculate the value associated with this memory operation.
         //
@@ -89,8 +105,9 @@ impl<F: AcirField> MemoryOpSolver<F> {
             // `value_read = arr[memory_index]`
             //
             // This is the value that we want to read into; i.e. copy from the memory block
-            // into this value.
-            let value_read_witness = value.to_witness().expect(
+            // into this value. We take the witness from the unevaluated expression so that a witness
+            // which has already been assigned is checked against the value read from memory.
+            let value_read_witness = op.value.to_witness().expect(
                 "Memory must be read into a specified witness index, encountered an Expression",
             );
 
@@ -98,7 +115,8 @@ impl<F: AcirField> MemoryOpSolver<F> {
             // and zero out the operation's output.
             let value_in_array =
                 if skip_operation { F::zero() } else { self.read_memory_index(memory_index)? };
-            insert_value(&value_read_witness, value_in_array, initial_witness)
+            insert_value(&value_read_witness, value_in_array, initial_witness)?;
+            Ok(None)
         } else {
             // `arr[memory_index] = value_write`
             //
@@ -110,10 +128,11 @@ impl<F: AcirField> MemoryOpSolver<F> {
             if skip_operation {
                 // We only want to write to already initialized memory.
                 // Do nothing if the predicate is zero.
-                Ok(())
+                Ok(None)
             } else {
                 let value_to_write = get_value(&value_write, initial_witness)?;
-                self.write_memory_index(memory_index, value_to_write)
+                self.write_memory_index(memory_index, value_to_write)?;
+                Ok(Some((memory_index, value_to_write)))
             }
         }
     }
@@ -183,9 +202,9 @@ mod tests {
             err,
             Some(crate::pwg::OpcodeResolutionError::IndexOutOfBounds {
                 opcode_location: _,
-                index: 2,
+                index,
                 array_size: 2
-            })
+            }) if index == FieldElement::from(2u128)
         ));
     }
 
@@ -16,9 +16,13 @@ use acir::{
     AcirField, BlackBoxFunc,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
+pub use brillig_vm::{BrilligObserver, NoopObserver, VMLimits};
 
 use self::{
-    arithmetic::ExpressionSolver, blackbox::bigint::AcvmBigIntSolver, directives::solve_directives,
+    arithmetic::ExpressionSolver,
+    blackbox::bigint::AcvmBigIntSolver,
+    brillig::{write_brillig_outputs, BrilligCall},
+    directives::solve_directives,
     memory_op::MemoryOpSolver,
 };
 use crate::BlackBoxFunctionSolver;
@@ -33,10 +37,36 @@ pub(crate) mod brillig;
 pub(crate) mod directives;
 // black box functions
 pub(crate) mod blackbox;
-mod memory_op;
+pub(crate) mod memory_op;
+// profiling
+pub(crate) mod profiling;
 
-pub use self::brillig::{BrilligSolver, BrilligSolverStatus};
+pub use self::brillig::{
+    BrilligCallCache, BrilligCallCacheStats, BrilligSolver, BrilligSolverStatus,
+    DecodedBrilligFunctions,
+};
 pub use brillig::ForeignCallWaitInfo;
+pub use profiling::{ProfilingObserver, ProfilingSample, ProfilingSamples};
+
+/// Receives callbacks as the [ACVM] executes a circuit, in addition to those of any Brillig calls it makes.
+///
+/// As with [BrilligObserver], every method has an empty default implementation and executing with
+/// [NoopObserver] has no runtime cost.
+pub trait ACVMObserver<F>: BrilligObserver<F> {
+    /// Called before the ACIR opcode at `instruction_pointer` is solved.
+    fn on_acir_opcode_start(&mut self, _instruction_pointer: usize, _opcode: &Opcode<F>) {}
+
+    /// Called when the ACVM pauses to wait for the result of an ACIR call.
+    fn on_acir_call(&mut self, _call: &AcirCallWaitInfo<F>) {}
+
+    /// Called after `value` has been written to `index` of the memory block `block_id` by a `MemoryOp` opcode.
+    fn on_acir_memory_write(&mut self, _block_id: BlockId, _index: u32, _value: &F) {}
+
+    /// Called when an opcode fails to solve, including when a Brillig call fails.
+    fn on_acir_failure(&mut self, _error: &OpcodeResolutionError<F>) {}
+}
+
+impl<F> ACVMObserver<F> for NoopObserver {}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ACVMStatus<F> {
@@ -129,7 +159,9 @@ pub enum OpcodeResolutionError<F> {
         payload: Option<ResolvedAssertionPayload<F>>,
     },
     #[error("Index out of bounds, array has size {array_size:?}, but index was {index:?}")]
-    IndexOutOfBounds { opcode_location: ErrorLocation, index: u32, array_size: u32 },
+    IndexOutOfBounds { opcode_location: ErrorLocation, index: F, array_size: u32 },
+    #[error("Cannot resolve assertion payload: {value} is not a valid string character")]
+    InvalidAssertionPayload { opcode_location: ErrorLocation, value: F },
     #[error("Cannot solve opcode: {invalid_input_bit_size}")]
     InvalidInputBitSize {
         opcode_location: ErrorLocation,
@@ -143,6 +175,12 @@ pub enum OpcodeResolutionError<F> {
         call_stack: Vec<OpcodeLocation>,
         payload: Option<ResolvedAssertionPayload<F>>,
     },
+    #[error("Brillig function exceeded its resource limits: {reason}")]
+    BrilligLimitExceeded {
+        function_id: BrilligFunctionId,
+        call_stack: Vec<OpcodeLocation>,
+        reason: String,
+    },
     #[error("Attempted to call `main` with a `Call` opcode")]
     AcirMainCallAttempted { opcode_location: ErrorLocation },
     #[error("{results_size:?} result values were provided for {outputs_size:?} call output witnesses, most likely due to bad ACIR codegen")]
@@ -187,6 +225,12 @@ pub struct ACVM<'a, F, B: BlackBoxFunctionSolver<F>> {
 
     brillig_solver: Option<BrilligSolver<'a, F, B>>,
 
+    /// Results of earlier Brillig calls, if caching has been enabled with [`ACVM::set_brillig_call_cache`].
+    brillig_call_cache: Option<BrilligCallCache<F>>,
+
+    /// The decoded bytecode of the Brillig functions called so far.
+    decoded_brillig_functions: DecodedBrilligFunctions<'a, F>,
+
     /// A counter maintained throughout an ACVM process that determines
     /// whether the caller has resolved the results of an ACIR [call][Opcode::Call].
     acir_call_counter: usize,
@@ -198,6 +242,9 @@ pub struct ACVM<'a, F, B: BlackBoxFunctionSolver<F>> {
     unconstrained_functions: &'a [BrilligBytecode<F>],
 
     assertion_payloads: &'a [(OpcodeLocation, AssertionPayload<F>)],
+
+    /// Limits on the resources used by the Brillig VM of each Brillig call.
+    brillig_vm_limits: VMLimits,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
@@ -218,10 +265,13 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             instruction_pointer: 0,
             witness_map: initial_witness,
             brillig_solver: None,
+            brillig_call_cache: None,
+            decoded_brillig_functions: DecodedBrilligFunctions::new(),
             acir_call_counter: 0,
             acir_call_results: Vec::default(),
             unconstrained_functions,
             assertion_payloads,
+            brillig_vm_limits: VMLimits::default(),
         }
     }
 
@@ -236,6 +286,40 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
         self.witness_map.insert(witness, value)
     }
 
+    /// Enables caching of the results of Brillig calls which do not make foreign calls.
+    ///
+    /// The cache may be carried over from the execution of another circuit of the same program.
+    pub fn set_brillig_call_cache(&mut self, cache: BrilligCallCache<F>) {
+        self.brillig_call_cache = Some(cache);
+    }
+
+    /// Removes the Brillig call cache from the ACVM, disabling caching for the rest of its execution.
+    pub fn take_brillig_call_cache(&mut self) -> Option<BrilligCallCache<F>> {
+        self.brillig_call_cache.take()
+    }
+
+    /// Reuses Brillig functions decoded by the ACVM of another circuit of the same program,
+    /// so that they aren't decoded again when called from this circuit.
+    pub fn set_decoded_brillig_functions(
+        &mut self,
+        decoded_brillig_functions: DecodedBrilligFunctions<'a, F>,
+    ) {
+        self.decoded_brillig_functions = decoded_brillig_functions;
+    }
+
+    /// Removes the Brillig functions which have been decoded so far, so that they can be reused by another ACVM.
+    pub fn take_decoded_brillig_functions(&mut self) -> DecodedBrilligFunctions<'a, F> {
+        std::mem::take(&mut self.decoded_brillig_functions)
+    }
+
+    /// Sets the limits on memory size and executed opcodes enforced on each Brillig call.
+    ///
+    /// Each Brillig call is limited separately, and a call which exceeds a limit fails with
+    /// [`OpcodeResolutionError::BrilligLimitExceeded`].
+    pub fn set_brillig_vm_limits(&mut self, vm_limits: VMLimits) {
+        self.brillig_vm_limits = vm_limits;
+    }
+
     /// Returns a slice containing the opcodes of the circuit being executed.
     pub fn opcodes(&self) -> &[Opcode<F>] {
         self.opcodes
@@ -331,14 +415,29 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     /// 2. The circuit has been found to be unsatisfiable.
     /// 2. A Brillig [foreign call][`ForeignCallWaitInfo`] has been encountered and must be resolved.
     pub fn solve(&mut self) -> ACVMStatus<F> {
+        self.solve_with_observer(&mut NoopObserver)
+    }
+
+    /// Executes the ACVM's circuit until execution halts, reporting execution events to `observer`.
+    ///
+    /// See [`ACVM::solve`] for the conditions under which execution halts.
+    pub fn solve_with_observer<O: ACVMObserver<F>>(&mut self, observer: &mut O) -> ACVMStatus<F> {
         while self.status == ACVMStatus::InProgress {
-            self.solve_opcode();
+            self.solve_opcode_with_observer(observer);
         }
         self.status.clone()
     }
 
     pub fn solve_opcode(&mut self) -> ACVMStatus<F> {
+        self.solve_opcode_with_observer(&mut NoopObserver)
+    }
+
+    pub fn solve_opcode_with_observer<O: ACVMObserver<F>>(
+        &mut self,
+        observer: &mut O,
+    ) -> ACVMStatus<F> {
         let opcode = &self.opcodes[self.instruction_pointer];
+        observer.on_acir_opcode_start(self.instruction_pointer, opcode);
 
         let resolution = match opcode {
             Opcode::AssertZero(expr) => ExpressionSolver::solve(&mut self.witness_map, expr),
@@ -355,18 +454,29 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             }
             Opcode::MemoryOp { block_id, op, predicate } => {
                 let solver = self.block_solvers.entry(*block_id).or_default();
-                solver.solve_memory_op(op, &mut self.witness_map, predicate)
+                solver.solve_memory_op(op, &mut self.witness_map, predicate).map(|write| {
+                    if let Some((index, value)) = write {
+                        observer.on_acir_memory_write(*block_id, index, &value);
+                    }
+                })
             }
-            Opcode::BrilligCall { .. } => match self.solve_brillig_call_opcode() {
+            Opcode::BrilligCall { .. } => match self.solve_brillig_call_opcode(observer) {
                 Ok(Some(foreign_call)) => return self.wait_for_foreign_call(foreign_call),
                 res => res.map(|_| ()),
             },
             Opcode::Call { .. } => match self.solve_call_opcode() {
-                Ok(Some(input_values)) => return self.wait_for_acir_call(input_values),
+                Ok(Some(input_values)) => {
+                    observer.on_acir_call(&input_values);
+                    return self.wait_for_acir_call(input_values);
+                }
                 res => res.map(|_| ()),
             },
         };
-        self.handle_opcode_resolution(resolution)
+        let status = self.handle_opcode_resolution(resolution);
+        if let ACVMStatus::Failure(error) = &status {
+            observer.on_acir_failure(error);
+        }
+        status
     }
 
     fn handle_opcode_resolution(
@@ -401,7 +511,10 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
                     } => {
                         let location = OpcodeLocation::Acir(self.instruction_pointer());
                         *opcode_index = ErrorLocation::Resolved(location);
-                        *assertion_payload = self.extract_assertion_payload(location);
+                        match self.extract_assertion_payload(location) {
+                            Ok(payload) => *assertion_payload = payload,
+                            Err(payload_error) => return self.fail(payload_error),
+                        }
                     }
                     OpcodeResolutionError::InvalidInputBitSize {
                         opcode_location: opcode_index,
@@ -421,64 +534,18 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
     fn extract_assertion_payload(
         &self,
         location: OpcodeLocation,
-    ) -> Option<ResolvedAssertionPayload<F>> {
-        let (_, found_assertion_payload) =
-            self.assertion_payloads.iter().find(|(loc, _)| location == *loc)?;
-        match found_assertion_payload {
-            AssertionPayload::StaticString(string) => {
-                Some(ResolvedAssertionPayload::String(string.clone()))
-            }
-            AssertionPayload::Dynamic(error_selector, expression) => {
-                let mut fields = vec![];
-                for expr in expression {
-                    match expr {
-                        ExpressionOrMemory::Expression(expr) => {
-                            let value = get_value(expr, &self.witness_map).ok()?;
-                            fields.push(value);
-                        }
-                        ExpressionOrMemory::Memory(block_id) => {
-                            let memory_block = self.block_solvers.get(block_id)?;
-                            fields.extend((0..memory_block.block_len).map(|memory_index| {
-                                *memory_block
-                                    .block_value
-                                    .get(&memory_index)
-                                    .expect("All memory is initialized on creation")
-                            }));
-                        }
-                    }
-                }
-                let error_selector = ErrorSelector::new(*error_selector);
-
-                Some(match error_selector {
-                    STRING_ERROR_SELECTOR => {
-                        // If the error selector is 0, it means the error is a string
-                        let string = fields
-                            .iter()
-                            .map(|field| {
-                                let as_u8: u8 = field
-                                    .try_to_u64()
-                                    .expect("String character doesn't fit in u64")
-                                    .try_into()
-                                    .expect("String character doesn't fit in u8");
-                                as_u8 as char
-                            })
-                            .collect();
-                        ResolvedAssertionPayload::String(string)
-                    }
-                    _ => {
-                        // If the error selector is not 0, it means the error is a custom error
-                        ResolvedAssertionPayload::Raw(RawAssertionPayload {
-                            selector: error_selector,
-                            data: fields,
-                        })
-                    }
-                })
-            }
-        }
+    ) -> Result<Option<ResolvedAssertionPayload<F>>, OpcodeResolutionError<F>> {
+        resolve_assertion_payload(
+            location,
+            self.assertion_payloads,
+            &self.witness_map,
+            &self.block_solvers,
+        )
     }
 
-    fn solve_brillig_call_opcode(
+    fn solve_brillig_call_opcode<O: ACVMObserver<F>>(
         &mut self,
+        observer: &mut O,
     ) -> Result<Option<ForeignCallWaitInfo<F>>, OpcodeResolutionError<F>> {
         let Opcode::BrilligCall { id, inputs, outputs, predicate } =
             &self.opcodes[self.instruction_pointer]
@@ -491,22 +558,62 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
                 .map(|_| None);
         }
 
+        let bytecode = &self.unconstrained_functions[id.as_usize()].bytecode;
+        // Calldata of a call whose results should be added to the Brillig call cache once it completes.
+        let mut uncached_calldata = None;
+
         // If we're resuming execution after resolving a foreign call then
         // there will be a cached `BrilligSolver` to avoid recomputation.
         let mut solver: BrilligSolver<'_, F, B> = match self.brillig_solver.take() {
             Some(solver) => solver,
+            None if self
+                .brillig_call_cache
+                .as_mut()
+                .is_some_and(|cache| cache.is_cacheable(*id, bytecode)) =>
+            {
+                let calldata = BrilligSolver::<F, B>::read_calldata(
+                    &self.witness_map,
+                    &self.block_solvers,
+                    inputs,
+                )?;
+                let cache = self.brillig_call_cache.as_mut().expect("Cacheable calls have a cache");
+                match cache.get(*id, calldata) {
+                    Ok(return_values) => {
+                        write_brillig_outputs(&mut self.witness_map, return_values, outputs)?;
+                        return Ok(None);
+                    }
+                    Err(calldata) => {
+                        uncached_calldata = Some(calldata.clone());
+                        BrilligSolver::with_calldata(
+                            calldata,
+                            self.backend,
+                            BrilligCall {
+                                bytecode: self
+                                    .decoded_brillig_functions
+                                    .get_or_decode(*id, bytecode),
+                                acir_index: self.instruction_pointer,
+                                function_id: *id,
+                                vm_limits: self.brillig_vm_limits,
+                            },
+                        )
+                    }
+                }
+            }
             None => BrilligSolver::new_call(
                 &self.witness_map,
                 &self.block_solvers,
                 inputs,
-                &self.unconstrained_functions[id.as_usize()].bytecode,
                 self.backend,
-                self.instruction_pointer,
-                *id,
+                BrilligCall {
+                    bytecode: self.decoded_brillig_functions.get_or_decode(*id, bytecode),
+                    acir_index: self.instruction_pointer,
+                    function_id: *id,
+                    vm_limits: self.brillig_vm_limits,
+                },
             )?,
         };
 
-        let result = solver.solve().map_err(|err| self.map_brillig_error(err))?;
+        let result = solver.solve(observer).map_err(|err| self.map_brillig_error(err))?;
 
         match result {
             BrilligSolverStatus::ForeignCallWait(foreign_call) => {
@@ -518,6 +625,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
                 unreachable!("Brillig solver still in progress")
             }
             BrilligSolverStatus::Finished => {
+                if let (Some(cache), Some(calldata)) =
+                    (self.brillig_call_cache.as_mut(), uncached_calldata)
+                {
+                    cache.insert(*id, calldata, solver.return_values());
+                }
                 // Write execution outputs
                 solver.finalize(&mut self.witness_map, outputs)?;
                 Ok(None)
@@ -567,14 +679,18 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> ACVM<'a, F, B> {
             return StepResult::Status(self.handle_opcode_resolution(resolution));
         }
 
+        let bytecode = &self.unconstrained_functions[id.as_usize()].bytecode;
         let solver = BrilligSolver::new_call(
             witness,
             &self.block_solvers,
             inputs,
-            &self.unconstrained_functions[id.as_usize()].bytecode,
             self.backend,
-            self.instruction_pointer,
-            *id,
+            BrilligCall {
+                bytecode: self.decoded_brillig_functions.get_or_decode(*id, bytecode),
+                acir_index: self.instruction_pointer,
+                function_id: *id,
+                vm_limits: self.brillig_vm_limits,
+            },
         );
         match solver {
             Ok(solver) => StepResult::IntoBrillig(solver),
@@ -741,6 +857,80 @@ fn any_witness_from_expression<F>(expr: &Expression<F>) -> Option<Witness> {
     }
 }
 
+/// Resolves the assertion payload associated with the opcode at `location`, if there is one,
+/// using the values of the witnesses and memory blocks which it references.
+///
+/// Returns an error if a string payload contains a value which is not a valid character.
+pub(crate) fn resolve_assertion_payload<F: AcirField>(
+    location: OpcodeLocation,
+    assertion_payloads: &[(OpcodeLocation, AssertionPayload<F>)],
+    witness_map: &WitnessMap<F>,
+    block_solvers: &HashMap<BlockId, MemoryOpSolver<F>>,
+) -> Result<Option<ResolvedAssertionPayload<F>>, OpcodeResolutionError<F>> {
+    let Some((_, found_assertion_payload)) =
+        assertion_payloads.iter().find(|(loc, _)| location == *loc)
+    else {
+        return Ok(None);
+    };
+    match found_assertion_payload {
+        AssertionPayload::StaticString(string) => {
+            Ok(Some(ResolvedAssertionPayload::String(string.clone())))
+        }
+        AssertionPayload::Dynamic(error_selector, expression) => {
+            let mut fields = vec![];
+            for expr in expression {
+                match expr {
+                    ExpressionOrMemory::Expression(expr) => {
+                        let Ok(value) = get_value(expr, witness_map) else {
+                            return Ok(None);
+                        };
+                        fields.push(value);
+                    }
+                    ExpressionOrMemory::Memory(block_id) => {
+                        let Some(memory_block) = block_solvers.get(block_id) else {
+                            return Ok(None);
+                        };
+                        fields.extend((0..memory_block.block_len).map(|memory_index| {
+                            *memory_block
+                                .block_value
+                                .get(&memory_index)
+                                .expect("All memory is initialized on creation")
+                        }));
+                    }
+                }
+            }
+            let error_selector = ErrorSelector::new(*error_selector);
+
+            Ok(Some(match error_selector {
+                STRING_ERROR_SELECTOR => {
+                    // If the error selector is 0, it means the error is a string
+                    let string = fields
+                        .iter()
+                        .map(|field| {
+                            field
+                                .try_to_u64()
+                                .and_then(|value| u8::try_from(value).ok())
+                                .map(char::from)
+                                .ok_or(OpcodeResolutionError::InvalidAssertionPayload {
+                                    opcode_location: ErrorLocation::Resolved(location),
+                                    value: *field,
+                                })
+                        })
+                        .collect::<Result<_, _>>()?;
+                    ResolvedAssertionPayload::String(string)
+                }
+                _ => {
+                    // If the error selector is not 0, it means the error is a custom error
+                    ResolvedAssertionPayload::Raw(RawAssertionPayload {
+                        selector: error_selector,
+                        data: fields,
+                    })
+                }
+            }))
+        }
+    }
+}
+
 /// Returns `true` if the predicate is zero
 /// A predicate is used to indicate whether we should skip a certain operation.
 /// If we have a zero predicate it means the operation should be skipped.
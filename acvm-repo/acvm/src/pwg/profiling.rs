@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use acir::{
+    brillig::Opcode as BrilligOpcode,
+    circuit::{brillig::BrilligFunctionId, Opcode, OpcodeLocation},
+};
+
+use super::{ACVMObserver, BrilligObserver};
+
+/// The call stack at the time a Brillig opcode was executed while profiling with a [`ProfilingObserver`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProfilingSample {
+    /// The location of the ACIR opcode being executed, followed by the Brillig call stack if it is a Brillig call
+    pub call_stack: Vec<OpcodeLocation>,
+    /// The Brillig function which was executing, if any
+    pub brillig_function_id: Option<BrilligFunctionId>,
+}
+
+/// The number of Brillig opcodes executed while profiling, keyed by the call stack they were executed at.
+pub type ProfilingSamples = BTreeMap<ProfilingSample, usize>;
+
+/// An [`ACVMObserver`] which counts the Brillig opcodes executed at each call stack.
+///
+/// Counts are kept against the Brillig call stack of each opcode and only turned into [`ProfilingSample`]s
+/// by [`ProfilingObserver::into_samples`], so that long running programs don't allocate for every opcode.
+/// Brillig calls whose results are read from the Brillig call cache execute no opcodes and so are not sampled.
+#[derive(Debug, Default)]
+pub struct ProfilingObserver {
+    /// The index of the ACIR opcode being solved and the function it calls, if it is a Brillig call.
+    current_brillig_call: Option<(usize, BrilligFunctionId)>,
+    /// The number of opcodes executed by each Brillig call, keyed by the Brillig call stack of each opcode.
+    brillig_samples: BTreeMap<(usize, BrilligFunctionId), BTreeMap<Vec<usize>, usize>>,
+    /// Reused to look up the Brillig call stack of each opcode without allocating.
+    call_stack: Vec<usize>,
+}
+
+impl ProfilingObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the samples taken so far, with call stacks relative to the circuit being executed.
+    pub fn into_samples(self) -> ProfilingSamples {
+        let mut samples = ProfilingSamples::new();
+        for ((acir_index, brillig_function_id), counts) in self.brillig_samples {
+            for (brillig_call_stack, count) in counts {
+                let brillig_call_stack = brillig_call_stack
+                    .into_iter()
+                    .map(|brillig_index| OpcodeLocation::Brillig { acir_index, brillig_index });
+                let sample = ProfilingSample {
+                    call_stack: std::iter::once(OpcodeLocation::Acir(acir_index))
+                        .chain(brillig_call_stack)
+                        .collect(),
+                    brillig_function_id: Some(brillig_function_id),
+                };
+                samples.insert(sample, count);
+            }
+        }
+        samples
+    }
+}
+
+impl<F> BrilligObserver<F> for ProfilingObserver {
+    fn on_brillig_opcode_start(
+        &mut self,
+        program_counter: usize,
+        _opcode: &BrilligOpcode<F>,
+        call_stack: &[usize],
+    ) {
+        let Some(brillig_call) = self.current_brillig_call else {
+            return;
+        };
+        self.call_stack.clear();
+        self.call_stack.extend_from_slice(call_stack);
+        self.call_stack.push(program_counter);
+
+        let counts = self.brillig_samples.entry(brillig_call).or_default();
+        match counts.get_mut(self.call_stack.as_slice()) {
+            Some(count) => *count += 1,
+            None => {
+                counts.insert(self.call_stack.clone(), 1);
+            }
+        }
+    }
+}
+
+impl<F> ACVMObserver<F> for ProfilingObserver {
+    fn on_acir_opcode_start(&mut self, instruction_pointer: usize, opcode: &Opcode<F>) {
+        self.current_brillig_call = match opcode {
+            Opcode::BrilligCall { id, .. } => Some((instruction_pointer, *id)),
+            _ => None,
+        };
+    }
+}
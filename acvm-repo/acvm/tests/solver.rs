@@ -14,10 +14,14 @@ use acir::{
     AcirField, FieldElement,
 };
 
-use acvm::pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM};
-use acvm_blackbox_solver::StubbedBlackBoxSolver;
+use acvm::pwg::{
+    ACVMObserver, ACVMStatus, BrilligCallCache, BrilligCallCacheStats, BrilligObserver,
+    ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, VMLimits, ACVM,
+};
+use acvm_blackbox_solver::{StubbedBlackBoxSolver, UserBlackBoxRegistry, UserBlackBoxSolver};
 use bn254_blackbox_solver::{field_from_hex, Bn254BlackBoxSolver, POSEIDON2_CONFIG};
 use brillig_vm::brillig::HeapValueType;
+use brillig_vm::MemoryValue;
 
 use num_bigint::BigUint;
 use proptest::arbitrary::any;
@@ -790,6 +794,315 @@ fn memory_operations() {
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
 
+#[test]
+fn brillig_call_cache_reuses_results() {
+    let program: acir::circuit::Program<FieldElement> = "
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0]
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_1)] }
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_2)] }
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _1) 0 ])], outputs: [simple(_3)] }
+        }
+
+        brillig func 0 {
+            const { destination: @1, bit_size: u32, value: 1 }
+            const { destination: @2, bit_size: u32, value: 0 }
+            calldata_copy { destination_address: @0, size_address: @1, offset_address: @2 }
+            binary_field_op { destination: @0, op: mul, lhs: @0, rhs: @0 }
+            stop { return_data_offset: 0, return_data_size: 1 }
+        }
+    "
+    .parse()
+    .unwrap();
+    let circuit = &program.functions[0];
+
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(0), FieldElement::from(3u128))]));
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        initial_witness,
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    acvm.set_brillig_call_cache(BrilligCallCache::new());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+
+    let cache = acvm.take_brillig_call_cache().unwrap();
+    assert_eq!(
+        cache.stats(),
+        BrilligCallCacheStats { hits: 1, misses: 2, uncacheable: 0, dropped: 0 }
+    );
+
+    let witness_map = acvm.finalize();
+    assert_eq!(witness_map[&Witness(1)], FieldElement::from(9u128));
+    assert_eq!(witness_map[&Witness(2)], FieldElement::from(9u128));
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(81u128));
+}
+
+#[test]
+fn brillig_call_cache_drops_results_once_full() {
+    let program: acir::circuit::Program<FieldElement> = "
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0]
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_1)] }
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _1) 0 ])], outputs: [simple(_2)] }
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _1) 0 ])], outputs: [simple(_3)] }
+        }
+
+        brillig func 0 {
+            const { destination: @1, bit_size: u32, value: 1 }
+            const { destination: @2, bit_size: u32, value: 0 }
+            calldata_copy { destination_address: @0, size_address: @1, offset_address: @2 }
+            binary_field_op { destination: @0, op: mul, lhs: @0, rhs: @0 }
+            stop { return_data_offset: 0, return_data_size: 1 }
+        }
+    "
+    .parse()
+    .unwrap();
+    let circuit = &program.functions[0];
+
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(0), FieldElement::from(3u128))]));
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        initial_witness,
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    // The cache only has room for the calldata and return value of a single call.
+    acvm.set_brillig_call_cache(BrilligCallCache::with_max_values(2));
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+
+    let cache = acvm.take_brillig_call_cache().unwrap();
+    assert_eq!(
+        cache.stats(),
+        BrilligCallCacheStats { hits: 0, misses: 3, uncacheable: 0, dropped: 2 }
+    );
+
+    let witness_map = acvm.finalize();
+    assert_eq!(witness_map[&Witness(2)], FieldElement::from(81u128));
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(81u128));
+}
+
+#[test]
+fn brillig_vm_limits_fail_brillig_calls() {
+    let program: acir::circuit::Program<FieldElement> = "
+        func 0 {
+            current_witness_index: 0
+            BRILLIG_CALL { id: 0, inputs: [], outputs: [] }
+        }
+
+        brillig func 0 {
+            jump { location: 0 }
+        }
+    "
+    .parse()
+    .unwrap();
+    let circuit = &program.functions[0];
+
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        WitnessMap::new(),
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    acvm.set_brillig_vm_limits(VMLimits { max_steps: Some(100), ..Default::default() });
+
+    let ACVMStatus::Failure(OpcodeResolutionError::BrilligLimitExceeded { reason, .. }) =
+        acvm.solve()
+    else {
+        panic!("Expected the Brillig call to exceed its limits");
+    };
+    assert_eq!(reason, "step limit exceeded: execution did not finish within 100 opcodes");
+}
+
+#[test]
+fn solves_user_defined_black_box_calls() {
+    let program: acir::circuit::Program<FieldElement> = "
+        func 0 {
+            current_witness_index: 3
+            private_parameters: [_0, _1]
+            BLACKBOX::USER_DEFINED { name: \"sum_and_product\", inputs: [(_0, 254), (_1, 254)], outputs: [_2, _3] }
+        }
+    "
+    .parse()
+    .unwrap();
+    let circuit = &program.functions[0];
+
+    let mut registry = UserBlackBoxRegistry::new();
+    registry.register("sum_and_product", 2, 2, |inputs: &[FieldElement]| {
+        Ok(vec![inputs[0] + inputs[1], inputs[0] * inputs[1]])
+    });
+    let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry);
+
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(0), FieldElement::from(3u128)),
+        (Witness(1), FieldElement::from(4u128)),
+    ]));
+    let mut acvm = ACVM::new(
+        &solver,
+        &circuit.opcodes,
+        initial_witness.clone(),
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+    assert_eq!(witness_map[&Witness(2)], FieldElement::from(7u128));
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(12u128));
+
+    // Solvers without the function registered fail to solve the call.
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        initial_witness,
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BlackBoxFunctionFailed(
+            acir::BlackBoxFunc::UserDefined,
+            "sum_and_product: no implementation has been registered".to_string()
+        ))
+    );
+}
+
+#[derive(Default)]
+struct RecordingObserver {
+    acir_opcodes: Vec<usize>,
+    brillig_opcodes: Vec<usize>,
+    memory_writes: Vec<(MemoryAddress, MemoryValue<FieldElement>)>,
+    acir_memory_writes: Vec<(BlockId, u32, FieldElement)>,
+    acir_failures: Vec<OpcodeResolutionError<FieldElement>>,
+}
+
+impl BrilligObserver<FieldElement> for RecordingObserver {
+    fn on_brillig_opcode_start(
+        &mut self,
+        program_counter: usize,
+        _: &BrilligOpcode<FieldElement>,
+        _: &[usize],
+    ) {
+        self.brillig_opcodes.push(program_counter);
+    }
+
+    fn on_memory_write(&mut self, address: MemoryAddress, value: &MemoryValue<FieldElement>) {
+        self.memory_writes.push((address, *value));
+    }
+}
+
+impl ACVMObserver<FieldElement> for RecordingObserver {
+    fn on_acir_opcode_start(&mut self, instruction_pointer: usize, _: &Opcode<FieldElement>) {
+        self.acir_opcodes.push(instruction_pointer);
+    }
+
+    fn on_acir_memory_write(&mut self, block_id: BlockId, index: u32, value: &FieldElement) {
+        self.acir_memory_writes.push((block_id, index, *value));
+    }
+
+    fn on_acir_failure(&mut self, error: &OpcodeResolutionError<FieldElement>) {
+        self.acir_failures.push(error.clone());
+    }
+}
+
+#[test]
+fn observer_receives_acir_and_brillig_events() {
+    let program: acir::circuit::Program<FieldElement> = "
+        func 0 {
+            current_witness_index: 1
+            private_parameters: [_0]
+            BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_1)] }
+            ASSERT_ZERO [ (1, _1) -10 ]
+        }
+
+        brillig func 0 {
+            const { destination: @1, bit_size: u32, value: 1 }
+            const { destination: @2, bit_size: u32, value: 0 }
+            calldata_copy { destination_address: @0, size_address: @1, offset_address: @2 }
+            binary_field_op { destination: @0, op: mul, lhs: @0, rhs: @0 }
+            stop { return_data_offset: 0, return_data_size: 1 }
+        }
+    "
+    .parse()
+    .unwrap();
+    let circuit = &program.functions[0];
+
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(0), FieldElement::from(3u128))]));
+    let mut acvm = ACVM::new(
+        &StubbedBlackBoxSolver,
+        &circuit.opcodes,
+        initial_witness,
+        &program.unconstrained_functions,
+        &circuit.assert_messages,
+    );
+    let mut observer = RecordingObserver::default();
+    let status = acvm.solve_with_observer(&mut observer);
+
+    assert_eq!(observer.acir_opcodes, vec![0, 1]);
+    assert_eq!(observer.brillig_opcodes, vec![0, 1, 2, 3, 4]);
+    assert_eq!(
+        observer.memory_writes,
+        vec![
+            (MemoryAddress(1), MemoryValue::new_integer(1, IntegerBitSize::U32)),
+            (MemoryAddress(2), MemoryValue::new_integer(0, IntegerBitSize::U32)),
+            (MemoryAddress(0), MemoryValue::new_field(FieldElement::from(3u128))),
+            (MemoryAddress(0), MemoryValue::new_field(FieldElement::from(9u128))),
+        ]
+    );
+    let ACVMStatus::Failure(error) = status else {
+        panic!("expected the assertion to fail, got {status:?}");
+    };
+    assert_eq!(observer.acir_failures, vec![error]);
+}
+
+#[test]
+fn observer_receives_acir_memory_writes() {
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(0), FieldElement::from(1u128)),
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+    let block_id = BlockId(0);
+    let index = |index: u128| Expression::from_field(FieldElement::from(index));
+
+    let opcodes = vec![
+        Opcode::MemoryInit {
+            block_id,
+            init: vec![Witness(0), Witness(1)],
+            block_type: BlockType::Memory,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(index(1), Witness(2).into()),
+            predicate: None,
+        },
+        // Skipped writes and reads are not reported.
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(index(0), Witness(2).into()),
+            predicate: Some(Expression::zero()),
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(index(1), Witness(3)),
+            predicate: None,
+        },
+    ];
+    let mut acvm = ACVM::new(&StubbedBlackBoxSolver, &opcodes, initial_witness, &[], &[]);
+    let mut observer = RecordingObserver::default();
+    assert_eq!(acvm.solve_with_observer(&mut observer), ACVMStatus::Solved);
+
+    assert_eq!(observer.acir_memory_writes, vec![(block_id, 1, FieldElement::from(3u128))]);
+    assert_eq!(acvm.finalize()[&Witness(3)], FieldElement::from(3u128));
+}
+
 fn allowed_bigint_moduli() -> Vec<Vec<u8>> {
     let bn254_fq: Vec<u8> = vec![
         0x47, 0xFD, 0x7C, 0xD8, 0x16, 0x8C, 0x20, 0x3C, 0x8d, 0xca, 0x71, 0x68, 0x91, 0x6a, 0x81,
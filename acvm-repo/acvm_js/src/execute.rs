@@ -5,7 +5,7 @@ use acvm::acir::circuit::ResolvedAssertionPayload;
 use acvm::{
     acir::circuit::{Circuit, Program},
     acir::native_types::{WitnessMap, WitnessStack},
-    pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, ACVM},
+    pwg::{ACVMStatus, ErrorLocation, OpcodeResolutionError, VMLimits, ACVM},
 };
 use acvm::{BlackBoxFunctionSolver, FieldElement};
 use bn254_blackbox_solver::Bn254BlackBoxSolver;
@@ -33,9 +33,13 @@ pub async fn execute_circuit(
 ) -> Result<JsWitnessMap, Error> {
     console_error_panic_hook::set_once();
 
-    let mut witness_stack =
-        execute_program_with_native_type_return(program, initial_witness, &foreign_call_handler)
-            .await?;
+    let mut witness_stack = execute_program_with_native_type_return(
+        program,
+        initial_witness,
+        &foreign_call_handler,
+        VMLimits::default(),
+    )
+    .await?;
     let witness_map =
         witness_stack.pop().expect("Should have at least one witness on the stack").witness;
     Ok(witness_map.into())
@@ -56,13 +60,14 @@ pub async fn execute_circuit_with_return_witness(
 ) -> Result<JsSolvedAndReturnWitness, Error> {
     console_error_panic_hook::set_once();
 
-    let program: Program<FieldElement> = Program::deserialize_program(&program)
+    let program: Program<FieldElement> = Program::migrate(&program)
     .map_err(|_| JsExecutionError::new("Failed to deserialize circuit. This is likely due to differing serialization formats between ACVM_JS and your compiler".to_string(), None, None, None))?;
 
     let mut witness_stack = execute_program_with_native_program_and_return(
         &program,
         initial_witness,
         &foreign_call_handler,
+        VMLimits::default(),
     )
     .await?;
     let solved_witness =
@@ -90,9 +95,48 @@ pub async fn execute_program(
 ) -> Result<JsWitnessStack, Error> {
     console_error_panic_hook::set_once();
 
-    let witness_stack =
-        execute_program_with_native_type_return(program, initial_witness, &foreign_call_handler)
-            .await?;
+    let witness_stack = execute_program_with_native_type_return(
+        program,
+        initial_witness,
+        &foreign_call_handler,
+        VMLimits::default(),
+    )
+    .await?;
+
+    Ok(witness_stack.into())
+}
+
+/// Executes an ACIR program as `executeProgram` does, failing any Brillig call which exceeds the given limits.
+///
+/// This allows untrusted programs to be executed without exhausting the memory or time of the host.
+///
+/// @param {Uint8Array} program - A serialized representation of an ACIR program
+/// @param {WitnessMap} initial_witness - The initial witness map defining all of the inputs to `program`.
+/// @param {ForeignCallHandler} foreign_call_handler - A callback to process any foreign calls from the program.
+/// @param {number | undefined} max_memory_size - The maximum number of memory slots each Brillig call may use.
+/// @param {number | undefined} max_steps - The maximum number of opcodes each Brillig call may execute.
+/// @returns {WitnessStack} The solved witness calculated by executing the program on the provided inputs.
+#[wasm_bindgen(js_name = executeProgramWithLimits, skip_jsdoc)]
+pub async fn execute_program_with_limits(
+    program: Vec<u8>,
+    initial_witness: JsWitnessMap,
+    foreign_call_handler: ForeignCallHandler,
+    max_memory_size: Option<u32>,
+    max_steps: Option<u32>,
+) -> Result<JsWitnessStack, Error> {
+    console_error_panic_hook::set_once();
+
+    let brillig_vm_limits = VMLimits {
+        max_memory_size: max_memory_size.map(|size| size as usize),
+        max_steps: max_steps.map(|steps| steps as usize),
+    };
+    let witness_stack = execute_program_with_native_type_return(
+        program,
+        initial_witness,
+        &foreign_call_handler,
+        brillig_vm_limits,
+    )
+    .await?;
 
     Ok(witness_stack.into())
 }
@@ -101,22 +145,29 @@ async fn execute_program_with_native_type_return(
     program: Vec<u8>,
     initial_witness: JsWitnessMap,
     foreign_call_executor: &ForeignCallHandler,
+    brillig_vm_limits: VMLimits,
 ) -> Result<WitnessStack<FieldElement>, Error> {
-    let program: Program<FieldElement> = Program::deserialize_program(&program)
+    let program: Program<FieldElement> = Program::migrate(&program)
     .map_err(|_| JsExecutionError::new(
         "Failed to deserialize circuit. This is likely due to differing serialization formats between ACVM_JS and your compiler".to_string(), 
         None,
         None,
     None))?;
 
-    execute_program_with_native_program_and_return(&program, initial_witness, foreign_call_executor)
-        .await
+    execute_program_with_native_program_and_return(
+        &program,
+        initial_witness,
+        foreign_call_executor,
+        brillig_vm_limits,
+    )
+    .await
 }
 
 async fn execute_program_with_native_program_and_return(
     program: &Program<FieldElement>,
     initial_witness: JsWitnessMap,
     foreign_call_executor: &ForeignCallHandler,
+    brillig_vm_limits: VMLimits,
 ) -> Result<WitnessStack<FieldElement>, Error> {
     let blackbox_solver = Bn254BlackBoxSolver;
     let executor = ProgramExecutor::new(
@@ -124,6 +175,7 @@ async fn execute_program_with_native_program_and_return(
         &program.unconstrained_functions,
         &blackbox_solver,
         foreign_call_executor,
+        brillig_vm_limits,
     );
     let witness_stack = executor.execute(initial_witness.into()).await?;
 
@@ -138,6 +190,8 @@ struct ProgramExecutor<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     blackbox_solver: &'a B,
 
     foreign_call_handler: &'a ForeignCallHandler,
+
+    brillig_vm_limits: VMLimits,
 }
 
 impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
@@ -146,12 +200,14 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
         blackbox_solver: &'a B,
         foreign_call_handler: &'a ForeignCallHandler,
+        brillig_vm_limits: VMLimits,
     ) -> Self {
         ProgramExecutor {
             functions,
             unconstrained_functions,
             blackbox_solver,
             foreign_call_handler,
+            brillig_vm_limits,
         }
     }
 
@@ -181,6 +237,7 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
                 self.unconstrained_functions,
                 &circuit.assert_messages,
             );
+            acvm.set_brillig_vm_limits(self.brillig_vm_limits);
 
             loop {
                 let solver_status = acvm.solve();
@@ -204,8 +261,13 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
                             OpcodeResolutionError::InvalidInputBitSize {
                                 opcode_location: ErrorLocation::Resolved(opcode_location),
                                 ..
+                            }
+                            | OpcodeResolutionError::InvalidAssertionPayload {
+                                opcode_location: ErrorLocation::Resolved(opcode_location),
+                                ..
                             } => Some(vec![*opcode_location]),
-                            OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+                            OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. }
+                            | OpcodeResolutionError::BrilligLimitExceeded { call_stack, .. } => {
                                 Some(call_stack.clone())
                             }
                             _ => None,
@@ -214,7 +276,10 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ProgramExecutor<'a, B> {
                         let brillig_function_id = match &error {
                             OpcodeResolutionError::BrilligFunctionFailed {
                                 function_id, ..
-                            } => Some(*function_id),
+                            }
+                            | OpcodeResolutionError::BrilligLimitExceeded { function_id, .. } => {
+                                Some(*function_id)
+                            }
                             _ => None,
                         };
 
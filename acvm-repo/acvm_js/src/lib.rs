@@ -24,7 +24,10 @@ pub use build_info::build_info;
 pub use compression::{
     compress_witness, compress_witness_stack, decompress_witness, decompress_witness_stack,
 };
-pub use execute::{execute_circuit, execute_circuit_with_return_witness, execute_program};
+pub use execute::{
+    execute_circuit, execute_circuit_with_return_witness, execute_program,
+    execute_program_with_limits,
+};
 pub use js_execution_error::JsExecutionError;
 pub use js_witness_map::JsSolvedAndReturnWitness;
 pub use js_witness_map::JsWitnessMap;
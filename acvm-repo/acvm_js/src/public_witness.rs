@@ -39,8 +39,8 @@ pub fn get_return_witness(
     witness_map: JsWitnessMap,
 ) -> Result<JsWitnessMap, JsString> {
     console_error_panic_hook::set_once();
-    let program: Program<FieldElement> =
-        Program::deserialize_program(&program).expect("Failed to deserialize circuit");
+    let program: Program<FieldElement> = Program::migrate(&program)
+        .map_err(|err| JsString::from(format!("Failed to deserialize circuit: {err}")))?;
     let circuit = match program.functions.len() {
         0 => return Ok(JsWitnessMap::from(WitnessMap::new())),
         1 => &program.functions[0],
@@ -66,8 +66,8 @@ pub fn get_public_parameters_witness(
     solved_witness: JsWitnessMap,
 ) -> Result<JsWitnessMap, JsString> {
     console_error_panic_hook::set_once();
-    let program: Program<FieldElement> =
-        Program::deserialize_program(&program).expect("Failed to deserialize circuit");
+    let program: Program<FieldElement> = Program::migrate(&program)
+        .map_err(|err| JsString::from(format!("Failed to deserialize circuit: {err}")))?;
     let circuit = match program.functions.len() {
         0 => return Ok(JsWitnessMap::from(WitnessMap::new())),
         1 => &program.functions[0],
@@ -93,8 +93,8 @@ pub fn get_public_witness(
     solved_witness: JsWitnessMap,
 ) -> Result<JsWitnessMap, JsString> {
     console_error_panic_hook::set_once();
-    let program: Program<FieldElement> =
-        Program::deserialize_program(&program).expect("Failed to deserialize circuit");
+    let program: Program<FieldElement> = Program::migrate(&program)
+        .map_err(|err| JsString::from(format!("Failed to deserialize circuit: {err}")))?;
     let circuit = match program.functions.len() {
         0 => return Ok(JsWitnessMap::from(WitnessMap::new())),
         1 => &program.functions[0],
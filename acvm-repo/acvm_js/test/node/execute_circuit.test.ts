@@ -4,6 +4,7 @@ import {
   WitnessMap,
   ForeignCallHandler,
   executeProgram,
+  getReturnWitness,
   WitnessStack,
   StackItem,
 } from '@noir-lang/acvm_js';
@@ -24,6 +25,18 @@ it('successfully executes circuit and extracts return value', async () => {
   expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
 });
 
+it('migrates and executes a circuit serialized without the format header', async () => {
+  const { legacyBytecode, initialWitnessMap, resultWitness, expectedResult } = await import('../shared/addition');
+
+  const solvedWitness: WitnessMap = await executeCircuit(legacyBytecode, initialWitnessMap, () => {
+    throw Error('unexpected oracle');
+  });
+  expect(solvedWitness.get(resultWitness)).to.be.eq(expectedResult);
+
+  const returnWitness: WitnessMap = getReturnWitness(legacyBytecode, solvedWitness);
+  expect(returnWitness).to.be.deep.eq(new Map([[resultWitness, expectedResult]]));
+});
+
 it('successfully processes simple brillig foreign call opcodes', async () => {
   const { bytecode, initialWitnessMap, expectedWitnessMap, oracleResponse, oracleCallName, oracleCallInputs } =
     await import('../shared/foreign_call');
@@ -130,6 +143,16 @@ it('executeProgram: successfully executes program and extracts return value', as
   expect(solvedWitnessMap.get(resultWitness)).to.be.eq(expectedResult);
 });
 
+it('executeProgram: migrates and executes a program serialized without the format header', async () => {
+  const { legacyBytecode, initialWitnessMap, resultWitness, expectedResult } = await import('../shared/addition');
+
+  const witnessStack: WitnessStack = await executeProgram(legacyBytecode, initialWitnessMap, () => {
+    throw Error('unexpected oracle');
+  });
+
+  expect(witnessStack[0].witness.get(resultWitness)).to.be.eq(expectedResult);
+});
+
 it('executeProgram: successfully process a program of acir functions with a nested call', async () => {
   const { bytecode, initialWitnessMap, expectedWitnessStack } = await import('../shared/nested_acir_call');
 
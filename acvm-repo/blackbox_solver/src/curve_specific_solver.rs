@@ -44,6 +44,17 @@ pub trait BlackBoxFunctionSolver<F> {
         _inputs: &[F],
         _len: u32,
     ) -> Result<Vec<F>, BlackBoxResolutionError>;
+
+    /// Solves a call to a blackbox function which is not built into ACIR.
+    ///
+    /// Solvers do not know of any such functions by default; see [`crate::UserBlackBoxSolver`]
+    /// for a way to register them.
+    fn user_defined(&self, name: &str, _inputs: &[F]) -> Result<Vec<F>, BlackBoxResolutionError> {
+        Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::UserDefined,
+            format!("{name}: no implementation has been registered"),
+        ))
+    }
 }
 
 pub struct StubbedBlackBoxSolver;
@@ -109,3 +120,79 @@ impl<F> BlackBoxFunctionSolver<F> for StubbedBlackBoxSolver {
         Err(Self::fail(BlackBoxFunc::Poseidon2Permutation))
     }
 }
+
+/// Solver for programs compiled over the BLS12-381 scalar field.
+///
+/// The curve-specific blackbox functions are defined over Grumpkin, the curve embedded in BN254, or use
+/// parameters which only exist for BN254. None of them are available on BLS12-381, so each call fails with
+/// an error explaining why.
+#[derive(Default)]
+pub struct Bls12381BlackBoxSolver;
+
+impl Bls12381BlackBoxSolver {
+    fn fail(black_box_function: BlackBoxFunc) -> BlackBoxResolutionError {
+        let reason = match black_box_function {
+            BlackBoxFunc::Poseidon2Permutation => "its parameters are only defined for bn254",
+            _ => "it relies on the Grumpkin curve which is only embedded in bn254",
+        };
+        BlackBoxResolutionError::Failed(
+            black_box_function,
+            format!(
+                "{} is not available on the bls12_381 field as {reason}",
+                black_box_function.name()
+            ),
+        )
+    }
+}
+
+impl<F> BlackBoxFunctionSolver<F> for Bls12381BlackBoxSolver {
+    fn schnorr_verify(
+        &self,
+        _public_key_x: &F,
+        _public_key_y: &F,
+        _signature: &[u8; 64],
+        _message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::SchnorrVerify))
+    }
+    fn pedersen_commitment(
+        &self,
+        _inputs: &[F],
+        _domain_separator: u32,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::PedersenCommitment))
+    }
+    fn pedersen_hash(
+        &self,
+        _inputs: &[F],
+        _domain_separator: u32,
+    ) -> Result<F, BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::PedersenHash))
+    }
+    fn multi_scalar_mul(
+        &self,
+        _points: &[F],
+        _scalars_lo: &[F],
+        _scalars_hi: &[F],
+    ) -> Result<(F, F, F), BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::MultiScalarMul))
+    }
+    fn ec_add(
+        &self,
+        _input1_x: &F,
+        _input1_y: &F,
+        _input1_infinite: &F,
+        _input2_x: &F,
+        _input2_y: &F,
+        _input2_infinite: &F,
+    ) -> Result<(F, F, F), BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::EmbeddedCurveAdd))
+    }
+    fn poseidon2_permutation(
+        &self,
+        _inputs: &[F],
+        _len: u32,
+    ) -> Result<Vec<F>, BlackBoxResolutionError> {
+        Err(Self::fail(BlackBoxFunc::Poseidon2Permutation))
+    }
+}
@@ -16,13 +16,17 @@ mod curve_specific_solver;
 mod ecdsa;
 mod hash;
 mod logic;
+mod user_defined;
 
 pub use aes128::aes128_encrypt;
 pub use bigint::BigIntSolver;
-pub use curve_specific_solver::{BlackBoxFunctionSolver, StubbedBlackBoxSolver};
+pub use curve_specific_solver::{
+    BlackBoxFunctionSolver, Bls12381BlackBoxSolver, StubbedBlackBoxSolver,
+};
 pub use ecdsa::{ecdsa_secp256k1_verify, ecdsa_secp256r1_verify};
 pub use hash::{blake2s, blake3, keccak256, keccakf1600, sha256_compression};
 pub use logic::{bit_and, bit_xor};
+pub use user_defined::{UserBlackBox, UserBlackBoxRegistry, UserBlackBoxSolver};
 
 #[derive(Clone, PartialEq, Eq, Debug, Error)]
 pub enum BlackBoxResolutionError {
@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use acir::BlackBoxFunc;
+
+use crate::{BlackBoxFunctionSolver, BlackBoxResolutionError};
+
+type UserBlackBoxFn<F> = dyn Fn(&[F]) -> Result<Vec<F>, String> + Send + Sync;
+
+/// A blackbox function which is not built into ACIR, along with the number of field elements
+/// it takes and returns.
+pub struct UserBlackBox<F> {
+    num_inputs: usize,
+    num_outputs: usize,
+    function: Box<UserBlackBoxFn<F>>,
+}
+
+impl<F> UserBlackBox<F> {
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+}
+
+impl<F> std::fmt::Debug for UserBlackBox<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserBlackBox")
+            .field("num_inputs", &self.num_inputs)
+            .field("num_outputs", &self.num_outputs)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Implementations of the blackbox functions declared in Noir with a `#[foreign(name)]` attribute
+/// whose name is not one of the [`BlackBoxFunc`]s built into ACIR.
+///
+/// Calls to these functions are carried through ACIR and Brillig by name, and solved by looking
+/// that name up in the registry.
+///
+/// Registration is only available to users of the ACVM as a library: `nargo` solves programs with
+/// an empty registry, so any call to a user-defined blackbox function fails to execute there.
+#[derive(Debug)]
+pub struct UserBlackBoxRegistry<F> {
+    functions: HashMap<String, UserBlackBox<F>>,
+}
+
+impl<F> Default for UserBlackBoxRegistry<F> {
+    fn default() -> Self {
+        UserBlackBoxRegistry { functions: HashMap::new() }
+    }
+}
+
+impl<F> UserBlackBoxRegistry<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `function` as the implementation of the blackbox function called `name`,
+    /// replacing any function previously registered under that name.
+    ///
+    /// `function` is passed `num_inputs` field elements and must return `num_outputs` of them,
+    /// or a message explaining why the inputs could not be processed.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        num_inputs: usize,
+        num_outputs: usize,
+        function: impl Fn(&[F]) -> Result<Vec<F>, String> + Send + Sync + 'static,
+    ) {
+        let function = UserBlackBox { num_inputs, num_outputs, function: Box::new(function) };
+        self.functions.insert(name.into(), function);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&UserBlackBox<F>> {
+        self.functions.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Calls the function registered under `name`, checking that the number of inputs and outputs
+    /// match those it was registered with.
+    pub fn solve(&self, name: &str, inputs: &[F]) -> Result<Vec<F>, BlackBoxResolutionError> {
+        let fail = |reason: String| {
+            BlackBoxResolutionError::Failed(BlackBoxFunc::UserDefined, format!("{name}: {reason}"))
+        };
+
+        let Some(user_black_box) = self.functions.get(name) else {
+            return Err(fail("no implementation has been registered".to_string()));
+        };
+        if inputs.len() != user_black_box.num_inputs {
+            return Err(fail(format!(
+                "expected {} inputs but got {}",
+                user_black_box.num_inputs,
+                inputs.len()
+            )));
+        }
+
+        let outputs = (user_black_box.function)(inputs).map_err(fail)?;
+        if outputs.len() != user_black_box.num_outputs {
+            return Err(fail(format!(
+                "expected {} outputs but got {}",
+                user_black_box.num_outputs,
+                outputs.len()
+            )));
+        }
+        Ok(outputs)
+    }
+}
+
+/// Extends a [`BlackBoxFunctionSolver`] with the functions of a [`UserBlackBoxRegistry`].
+#[derive(Debug, Default)]
+pub struct UserBlackBoxSolver<F, B> {
+    solver: B,
+    registry: UserBlackBoxRegistry<F>,
+}
+
+impl<F, B> UserBlackBoxSolver<F, B> {
+    pub fn new(solver: B, registry: UserBlackBoxRegistry<F>) -> Self {
+        UserBlackBoxSolver { solver, registry }
+    }
+
+    pub fn registry(&self) -> &UserBlackBoxRegistry<F> {
+        &self.registry
+    }
+
+    pub fn registry_mut(&mut self) -> &mut UserBlackBoxRegistry<F> {
+        &mut self.registry
+    }
+}
+
+impl<F, B: BlackBoxFunctionSolver<F>> BlackBoxFunctionSolver<F> for UserBlackBoxSolver<F, B> {
+    fn schnorr_verify(
+        &self,
+        public_key_x: &F,
+        public_key_y: &F,
+        signature: &[u8; 64],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        self.solver.schnorr_verify(public_key_x, public_key_y, signature, message)
+    }
+    fn pedersen_commitment(
+        &self,
+        inputs: &[F],
+        domain_separator: u32,
+    ) -> Result<(F, F), BlackBoxResolutionError> {
+        self.solver.pedersen_commitment(inputs, domain_separator)
+    }
+    fn pedersen_hash(
+        &self,
+        inputs: &[F],
+        domain_separator: u32,
+    ) -> Result<F, BlackBoxResolutionError> {
+        self.solver.pedersen_hash(inputs, domain_separator)
+    }
+    fn multi_scalar_mul(
+        &self,
+        points: &[F],
+        scalars_lo: &[F],
+        scalars_hi: &[F],
+    ) -> Result<(F, F, F), BlackBoxResolutionError> {
+        self.solver.multi_scalar_mul(points, scalars_lo, scalars_hi)
+    }
+    fn ec_add(
+        &self,
+        input1_x: &F,
+        input1_y: &F,
+        input1_infinite: &F,
+        input2_x: &F,
+        input2_y: &F,
+        input2_infinite: &F,
+    ) -> Result<(F, F, F), BlackBoxResolutionError> {
+        self.solver.ec_add(input1_x, input1_y, input1_infinite, input2_x, input2_y, input2_infinite)
+    }
+    fn poseidon2_permutation(
+        &self,
+        inputs: &[F],
+        len: u32,
+    ) -> Result<Vec<F>, BlackBoxResolutionError> {
+        self.solver.poseidon2_permutation(inputs, len)
+    }
+    fn user_defined(&self, name: &str, inputs: &[F]) -> Result<Vec<F>, BlackBoxResolutionError> {
+        if self.registry.contains(name) {
+            self.registry.solve(name, inputs)
+        } else {
+            self.solver.user_defined(name, inputs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{AcirField, BlackBoxFunc, FieldElement};
+
+    use super::{UserBlackBoxRegistry, UserBlackBoxSolver};
+    use crate::{BlackBoxFunctionSolver, BlackBoxResolutionError, StubbedBlackBoxSolver};
+
+    fn registry() -> UserBlackBoxRegistry<FieldElement> {
+        let mut registry = UserBlackBoxRegistry::new();
+        registry.register("sum_and_product", 2, 2, |inputs: &[FieldElement]| {
+            Ok(vec![inputs[0] + inputs[1], inputs[0] * inputs[1]])
+        });
+        registry.register("broken", 1, 2, |inputs: &[FieldElement]| Ok(inputs.to_vec()));
+        registry.register("non_zero", 1, 1, |inputs: &[FieldElement]| {
+            if inputs[0].is_zero() {
+                Err("input must not be zero".to_string())
+            } else {
+                Ok(vec![inputs[0].inverse()])
+            }
+        });
+        registry
+    }
+
+    fn failure(reason: &str) -> BlackBoxResolutionError {
+        BlackBoxResolutionError::Failed(BlackBoxFunc::UserDefined, reason.to_string())
+    }
+
+    #[test]
+    fn solves_registered_functions() {
+        let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry());
+        let inputs = [FieldElement::from(3u128), FieldElement::from(4u128)];
+        assert_eq!(
+            solver.user_defined("sum_and_product", &inputs),
+            Ok(vec![FieldElement::from(7u128), FieldElement::from(12u128)])
+        );
+    }
+
+    #[test]
+    fn checks_arity_of_calls() {
+        let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry());
+        assert_eq!(
+            solver.user_defined("sum_and_product", &[FieldElement::one()]),
+            Err(failure("sum_and_product: expected 2 inputs but got 1"))
+        );
+        assert_eq!(
+            solver.user_defined("broken", &[FieldElement::one()]),
+            Err(failure("broken: expected 2 outputs but got 1"))
+        );
+    }
+
+    #[test]
+    fn reports_failures() {
+        let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry());
+        assert_eq!(
+            solver.user_defined("non_zero", &[FieldElement::zero()]),
+            Err(failure("non_zero: input must not be zero"))
+        );
+        assert_eq!(
+            solver.user_defined("unknown", &[]),
+            Err(failure("unknown: no implementation has been registered"))
+        );
+    }
+}
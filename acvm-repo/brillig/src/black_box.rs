@@ -1,9 +1,9 @@
-use crate::{opcodes::HeapVector, HeapArray, MemoryAddress};
+use crate::{opcodes::HeapVector, HeapArray, MemoryAddress, ValueOrArray};
 use serde::{Deserialize, Serialize};
 
 /// These opcodes provide an equivalent of ACIR blackbox functions.
 /// They are implemented as native functions in the VM.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BlackBoxOp {
     /// Encrypts a message using AES128.
     AES128Encrypt {
@@ -129,4 +129,12 @@ pub enum BlackBoxOp {
         output: HeapArray,
         output_bits: bool,
     },
+    /// Calls a function registered with the solver under `name`.
+    /// The values of `inputs` are flattened before being passed to the function, and its results
+    /// are written in order to `outputs`.
+    UserDefined {
+        name: String,
+        inputs: Vec<ValueOrArray>,
+        outputs: Vec<ValueOrArray>,
+    },
 }
@@ -0,0 +1,72 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::time::Duration;
+
+use acir::{
+    brillig::{BitSize, MemoryAddress, Opcode},
+    FieldElement,
+};
+use acvm_blackbox_solver::StubbedBlackBoxSolver;
+use brillig_vm::{DecodedBytecode, VMStatus, VM};
+
+const SIZES: [usize; 4] = [10, 100, 1000, 10000];
+
+/// A function of `num_opcodes` opcodes which jumps straight to its final `stop`,
+/// so that the cost of a call is dominated by preparing the bytecode rather than executing it.
+fn sample_bytecode(num_opcodes: usize) -> Vec<Opcode<FieldElement>> {
+    let constants = (0..num_opcodes - 2).map(|i| Opcode::Const {
+        destination: MemoryAddress(i),
+        bit_size: BitSize::Field,
+        value: FieldElement::from(i),
+    });
+    std::iter::once(Opcode::Jump { location: num_opcodes - 1 })
+        .chain(constants)
+        .chain(std::iter::once(Opcode::Stop { return_data_offset: 0, return_data_size: 0 }))
+        .collect()
+}
+
+fn bench_calls(c: &mut Criterion) {
+    let mut group = c.benchmark_group("brillig_call_decoding_bytecode");
+    for size in SIZES.iter() {
+        let bytecode = sample_bytecode(*size);
+
+        group.throughput(Throughput::Elements(*size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), &bytecode, |b, bytecode| {
+            b.iter(|| {
+                let mut vm = VM::new(vec![], bytecode, vec![], &StubbedBlackBoxSolver);
+                assert!(matches!(vm.process_opcodes(), VMStatus::Finished { .. }));
+            });
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("brillig_call_predecoded_bytecode");
+    for size in SIZES.iter() {
+        let bytecode = sample_bytecode(*size);
+        let decoded_bytecode = DecodedBytecode::new(&bytecode);
+
+        group.throughput(Throughput::Elements(*size as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &decoded_bytecode,
+            |b, decoded_bytecode| {
+                b.iter(|| {
+                    let mut vm = VM::with_decoded_bytecode(
+                        vec![],
+                        decoded_bytecode.clone(),
+                        vec![],
+                        &StubbedBlackBoxSolver,
+                    );
+                    assert!(matches!(vm.process_opcodes(), VMStatus::Finished { .. }));
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(40).measurement_time(Duration::from_secs(10));
+    targets = bench_calls
+);
+criterion_main!(benches);
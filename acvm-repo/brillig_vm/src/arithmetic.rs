@@ -62,138 +62,147 @@ pub(crate) fn evaluate_binary_field_op<F: AcirField>(
     })
 }
 
-/// Evaluate a binary operation on two unsigned big integers with a given bit size.
-pub(crate) fn evaluate_binary_int_op<F: AcirField>(
-    op: &BinaryIntOp,
-    lhs: MemoryValue<F>,
-    rhs: MemoryValue<F>,
+/// Evaluates a [BinaryIntOp] on the values of two integers of a fixed bit size.
+type IntegerOpFn = fn(u128, u128) -> Result<u128, BrilligArithmeticError>;
+
+/// A binary operation on two unsigned integers, specialised for the bit size of its operands.
+///
+/// The implementation is resolved once when the operation is created, so evaluating it does not
+/// need to dispatch on the operation or its bit size.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IntegerOperation {
+    op: BinaryIntOp,
     bit_size: IntegerBitSize,
-) -> Result<MemoryValue<F>, BrilligArithmeticError> {
-    let lhs = lhs.expect_integer_with_bit_size(bit_size).map_err(|err| match err {
-        MemoryTypeError::MismatchedBitSize { value_bit_size, expected_bit_size } => {
-            BrilligArithmeticError::MismatchedLhsBitSize {
-                lhs_bit_size: value_bit_size,
-                op_bit_size: expected_bit_size,
-            }
-        }
-    })?;
+    rhs_bit_size: IntegerBitSize,
+    result_bit_size: IntegerBitSize,
+    evaluate: IntegerOpFn,
+}
 
-    let rhs_bit_size = if op == &BinaryIntOp::Shl || op == &BinaryIntOp::Shr {
-        IntegerBitSize::U8
-    } else {
-        bit_size
-    };
+impl PartialEq for IntegerOperation {
+    fn eq(&self, other: &Self) -> bool {
+        self.op == other.op && self.bit_size == other.bit_size
+    }
+}
 
-    let rhs = rhs.expect_integer_with_bit_size(rhs_bit_size).map_err(|err| match err {
-        MemoryTypeError::MismatchedBitSize { value_bit_size, expected_bit_size } => {
-            BrilligArithmeticError::MismatchedRhsBitSize {
-                rhs_bit_size: value_bit_size,
-                op_bit_size: expected_bit_size,
+impl Eq for IntegerOperation {}
+
+impl IntegerOperation {
+    pub(crate) fn new(op: BinaryIntOp, bit_size: IntegerBitSize) -> Self {
+        let evaluate = match bit_size {
+            IntegerBitSize::U0 => specialise::<0>(op),
+            IntegerBitSize::U1 => specialise::<1>(op),
+            IntegerBitSize::U8 => specialise::<8>(op),
+            IntegerBitSize::U16 => specialise::<16>(op),
+            IntegerBitSize::U32 => specialise::<32>(op),
+            IntegerBitSize::U64 => specialise::<64>(op),
+            IntegerBitSize::U128 => specialise::<128>(op),
+        };
+        let rhs_bit_size = match op {
+            BinaryIntOp::Shl | BinaryIntOp::Shr => IntegerBitSize::U8,
+            _ => bit_size,
+        };
+        let result_bit_size = match op {
+            BinaryIntOp::Equals | BinaryIntOp::LessThan | BinaryIntOp::LessThanEquals => {
+                IntegerBitSize::U1
             }
-        }
-    })?;
+            _ => bit_size,
+        };
+        IntegerOperation { op, bit_size, rhs_bit_size, result_bit_size, evaluate }
+    }
+
+    /// Evaluates the operation on two integer memory values, checking that they have the expected bit sizes.
+    pub(crate) fn evaluate<F: AcirField>(
+        &self,
+        lhs: MemoryValue<F>,
+        rhs: MemoryValue<F>,
+    ) -> Result<MemoryValue<F>, BrilligArithmeticError> {
+        let lhs = lhs.expect_integer_with_bit_size(self.bit_size).map_err(|err| match err {
+            MemoryTypeError::MismatchedBitSize { value_bit_size, expected_bit_size } => {
+                BrilligArithmeticError::MismatchedLhsBitSize {
+                    lhs_bit_size: value_bit_size,
+                    op_bit_size: expected_bit_size,
+                }
+            }
+        })?;
+
+        let rhs = rhs.expect_integer_with_bit_size(self.rhs_bit_size).map_err(|err| match err {
+            MemoryTypeError::MismatchedBitSize { value_bit_size, expected_bit_size } => {
+                BrilligArithmeticError::MismatchedRhsBitSize {
+                    rhs_bit_size: value_bit_size,
+                    op_bit_size: expected_bit_size,
+                }
+            }
+        })?;
 
-    let result = if bit_size == IntegerBitSize::U128 {
-        evaluate_binary_int_op_128(op, lhs, rhs)?
+        let result = (self.evaluate)(lhs, rhs)?;
+        Ok(MemoryValue::new_integer(result, self.result_bit_size))
+    }
+}
+
+/// Returns the implementation of `op` on integers of `BITS` bits.
+fn specialise<const BITS: u32>(op: BinaryIntOp) -> IntegerOpFn {
+    match op {
+        BinaryIntOp::Add => add::<BITS>,
+        BinaryIntOp::Sub => sub::<BITS>,
+        BinaryIntOp::Mul => mul::<BITS>,
+        BinaryIntOp::Div => div,
+        BinaryIntOp::Equals => |lhs, rhs| Ok((lhs == rhs) as u128),
+        BinaryIntOp::LessThan => |lhs, rhs| Ok((lhs < rhs) as u128),
+        BinaryIntOp::LessThanEquals => |lhs, rhs| Ok((lhs <= rhs) as u128),
+        // The operands of bitwise operations already fit within the bit size, and so does their result.
+        BinaryIntOp::And => |lhs, rhs| Ok(lhs & rhs),
+        BinaryIntOp::Or => |lhs, rhs| Ok(lhs | rhs),
+        BinaryIntOp::Xor => |lhs, rhs| Ok(lhs ^ rhs),
+        BinaryIntOp::Shl => shl::<BITS>,
+        BinaryIntOp::Shr => shr::<BITS>,
+    }
+}
+
+/// Returns a mask of the lowest `bits` bits, used to keep a result within the bit size.
+const fn mask(bits: u32) -> u128 {
+    if bits >= u128::BITS {
+        u128::MAX
     } else {
-        evaluate_binary_int_op_generic(op, lhs, rhs, bit_size)?
-    };
+        (1 << bits) - 1
+    }
+}
 
-    Ok(match op {
-        BinaryIntOp::Equals | BinaryIntOp::LessThan | BinaryIntOp::LessThanEquals => {
-            MemoryValue::new_integer(result, IntegerBitSize::U1)
-        }
-        _ => MemoryValue::new_integer(result, bit_size),
-    })
+// Arithmetic wraps modulo 2^128, which leaves the lowest bits of the result unaffected,
+// so masking afterwards gives the result modulo 2^BITS.
+fn add<const BITS: u32>(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    Ok(lhs.wrapping_add(rhs) & mask(BITS))
 }
 
-fn evaluate_binary_int_op_128(
-    op: &BinaryIntOp,
-    lhs: u128,
-    rhs: u128,
-) -> Result<u128, BrilligArithmeticError> {
-    let result = match op {
-        BinaryIntOp::Add => lhs.wrapping_add(rhs),
-        BinaryIntOp::Sub => lhs.wrapping_sub(rhs),
-        BinaryIntOp::Mul => lhs.wrapping_mul(rhs),
-        BinaryIntOp::Div => {
-            if rhs == 0 {
-                return Err(BrilligArithmeticError::DivisionByZero);
-            } else {
-                lhs / rhs
-            }
-        }
-        BinaryIntOp::Equals => (lhs == rhs) as u128,
-        BinaryIntOp::LessThan => (lhs < rhs) as u128,
-        BinaryIntOp::LessThanEquals => (lhs <= rhs) as u128,
-        BinaryIntOp::And => lhs & rhs,
-        BinaryIntOp::Or => lhs | rhs,
-        BinaryIntOp::Xor => lhs ^ rhs,
-        BinaryIntOp::Shl => {
-            if rhs >= 128 {
-                0
-            } else {
-                lhs.wrapping_shl(rhs as u32)
-            }
-        }
-        BinaryIntOp::Shr => {
-            if rhs >= 128 {
-                0
-            } else {
-                lhs.wrapping_shr(rhs as u32)
-            }
-        }
-    };
-    Ok(result)
+fn sub<const BITS: u32>(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    Ok(lhs.wrapping_sub(rhs) & mask(BITS))
 }
 
-fn evaluate_binary_int_op_generic(
-    op: &BinaryIntOp,
-    lhs: u128,
-    rhs: u128,
-    bit_size: IntegerBitSize,
-) -> Result<u128, BrilligArithmeticError> {
-    let bit_size: u32 = bit_size.into();
-    let bit_modulo = 1 << bit_size;
-    let result = match op {
-        // Perform addition, subtraction, and multiplication, applying a modulo operation to keep the result within the bit size.
-        BinaryIntOp::Add => (lhs + rhs) % bit_modulo,
-        BinaryIntOp::Sub => (bit_modulo + lhs - rhs) % bit_modulo,
-        BinaryIntOp::Mul => (lhs * rhs) % bit_modulo,
-        // Perform unsigned division using the modulo operation on a and b.
-        BinaryIntOp::Div => {
-            if rhs == 0 {
-                return Err(BrilligArithmeticError::DivisionByZero);
-            } else {
-                lhs / rhs
-            }
-        }
-        // Perform a == operation, returning 0 or 1
-        BinaryIntOp::Equals => (lhs == rhs) as u128,
-        // Perform a < operation, returning 0 or 1
-        BinaryIntOp::LessThan => (lhs < rhs) as u128,
-        // Perform a <= operation, returning 0 or 1
-        BinaryIntOp::LessThanEquals => (lhs <= rhs) as u128,
-        // Perform bitwise AND, OR, XOR, left shift, and right shift operations, applying a modulo operation to keep the result within the bit size.
-        BinaryIntOp::And => lhs & rhs,
-        BinaryIntOp::Or => lhs | rhs,
-        BinaryIntOp::Xor => lhs ^ rhs,
-        BinaryIntOp::Shl => {
-            if rhs >= (bit_size as u128) {
-                0
-            } else {
-                (lhs << rhs) % bit_modulo
-            }
-        }
-        BinaryIntOp::Shr => {
-            if rhs >= (bit_size as u128) {
-                0
-            } else {
-                lhs >> rhs
-            }
-        }
-    };
-    Ok(result)
+fn mul<const BITS: u32>(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    Ok(lhs.wrapping_mul(rhs) & mask(BITS))
+}
+
+fn div(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    if rhs == 0 {
+        Err(BrilligArithmeticError::DivisionByZero)
+    } else {
+        Ok(lhs / rhs)
+    }
+}
+
+fn shl<const BITS: u32>(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    if rhs >= BITS as u128 {
+        Ok(0)
+    } else {
+        Ok((lhs << rhs) & mask(BITS))
+    }
+}
+
+fn shr<const BITS: u32>(lhs: u128, rhs: u128) -> Result<u128, BrilligArithmeticError> {
+    if rhs >= BITS as u128 {
+        Ok(0)
+    } else {
+        Ok(lhs >> rhs)
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +216,16 @@ mod tests {
         result: u128,
     }
 
+    /// Evaluates `op` through an [IntegerOperation] specialised for `bit_size`.
+    fn evaluate_binary_int_op<F: AcirField>(
+        op: &BinaryIntOp,
+        lhs: MemoryValue<F>,
+        rhs: MemoryValue<F>,
+        bit_size: IntegerBitSize,
+    ) -> Result<MemoryValue<F>, BrilligArithmeticError> {
+        IntegerOperation::new(*op, bit_size).evaluate(lhs, rhs)
+    }
+
     fn evaluate_u128(op: &BinaryIntOp, a: u128, b: u128, bit_size: IntegerBitSize) -> u128 {
         let result_value: MemoryValue<FieldElement> = evaluate_binary_int_op(
             op,
@@ -320,4 +339,47 @@ mod tests {
 
         evaluate_int_ops(test_ops, BinaryIntOp::Div, bit_size);
     }
+
+    #[test]
+    fn shift_test() {
+        let shift = |op, a, b, bit_size| {
+            let result: MemoryValue<FieldElement> = IntegerOperation::new(op, bit_size)
+                .evaluate(
+                    MemoryValue::new_integer(a, bit_size),
+                    MemoryValue::new_integer(b, IntegerBitSize::U8),
+                )
+                .unwrap();
+            result.to_field().to_u128()
+        };
+
+        assert_eq!(shift(BinaryIntOp::Shl, 0b1010_0001, 1, IntegerBitSize::U8), 0b0100_0010);
+        assert_eq!(shift(BinaryIntOp::Shl, 1, 8, IntegerBitSize::U8), 0);
+        assert_eq!(shift(BinaryIntOp::Shl, 1, 127, IntegerBitSize::U128), 1 << 127);
+        assert_eq!(shift(BinaryIntOp::Shr, 0b1010_0001, 4, IntegerBitSize::U8), 0b1010);
+        assert_eq!(shift(BinaryIntOp::Shr, u128::MAX, 128, IntegerBitSize::U128), 0);
+    }
+
+    #[test]
+    fn comparisons_return_booleans() {
+        let result: MemoryValue<FieldElement> =
+            IntegerOperation::new(BinaryIntOp::LessThan, IntegerBitSize::U64)
+                .evaluate(
+                    MemoryValue::new_integer(1, IntegerBitSize::U64),
+                    MemoryValue::new_integer(2, IntegerBitSize::U64),
+                )
+                .unwrap();
+        assert_eq!(result, MemoryValue::new_integer(1, IntegerBitSize::U1));
+    }
+
+    #[test]
+    fn mismatched_bit_sizes_are_rejected() {
+        let result = IntegerOperation::new(BinaryIntOp::Add, IntegerBitSize::U32).evaluate(
+            MemoryValue::<FieldElement>::new_integer(1, IntegerBitSize::U32),
+            MemoryValue::new_integer(2, IntegerBitSize::U64),
+        );
+        assert!(matches!(
+            result,
+            Err(BrilligArithmeticError::MismatchedRhsBitSize { rhs_bit_size: 64, op_bit_size: 32 })
+        ));
+    }
 }
@@ -1,4 +1,6 @@
-use acir::brillig::{BlackBoxOp, HeapArray, HeapVector, IntegerBitSize};
+use acir::brillig::{
+    BlackBoxOp, HeapArray, HeapVector, IntegerBitSize, MemoryAddress, ValueOrArray,
+};
 use acir::{AcirField, BlackBoxFunc};
 use acvm_blackbox_solver::BigIntSolver;
 use acvm_blackbox_solver::{
@@ -8,8 +10,8 @@ use acvm_blackbox_solver::{
 use num_bigint::BigUint;
 use num_traits::Zero;
 
-use crate::memory::MemoryValue;
-use crate::Memory;
+use crate::memory::{MemoryValue, ObservedMemory};
+use crate::{BrilligObserver, Memory};
 
 fn read_heap_vector<'a, F: AcirField>(
     memory: &'a Memory<F>,
@@ -26,6 +28,20 @@ fn read_heap_array<'a, F: AcirField>(
     memory.read_slice(memory.read_ref(array.pointer), array.size)
 }
 
+/// Returns the address and number of values held by `value`
+fn value_or_array_location<F: AcirField>(
+    memory: &Memory<F>,
+    value: &ValueOrArray,
+) -> (MemoryAddress, usize) {
+    match value {
+        ValueOrArray::MemoryAddress(address) => (*address, 1),
+        ValueOrArray::HeapArray(array) => (memory.read_ref(array.pointer), array.size),
+        ValueOrArray::HeapVector(vector) => {
+            (memory.read_ref(vector.pointer), memory.read(vector.size).to_usize())
+        }
+    }
+}
+
 /// Extracts the last byte of every value
 fn to_u8_vec<F: AcirField>(inputs: &[MemoryValue<F>]) -> Vec<u8> {
     let mut result = Vec::with_capacity(inputs.len());
@@ -39,10 +55,14 @@ fn to_value_vec<F: AcirField>(input: &[u8]) -> Vec<MemoryValue<F>> {
     input.iter().map(|&x| x.into()).collect()
 }
 
-pub(crate) fn evaluate_black_box<F: AcirField, Solver: BlackBoxFunctionSolver<F>>(
+pub(crate) fn evaluate_black_box<
+    F: AcirField,
+    Solver: BlackBoxFunctionSolver<F>,
+    O: BrilligObserver<F>,
+>(
     op: &BlackBoxOp,
     solver: &Solver,
-    memory: &mut Memory<F>,
+    memory: &mut ObservedMemory<F, O>,
     bigint_solver: &mut BrilligBigintSolver,
 ) -> Result<(), BlackBoxResolutionError> {
     match op {
@@ -391,6 +411,34 @@ pub(crate) fn evaluate_black_box<F: AcirField, Solver: BlackBoxFunctionSolver<F>
 
             Ok(())
         }
+        BlackBoxOp::UserDefined { name, inputs, outputs } => {
+            let mut input_values = Vec::new();
+            for input in inputs {
+                let (address, size) = value_or_array_location(memory, input);
+                input_values.extend(memory.read_slice(address, size).iter().map(|x| x.to_field()));
+            }
+
+            let values = solver.user_defined(name, &input_values)?;
+            let output_locations: Vec<_> =
+                outputs.iter().map(|output| value_or_array_location(memory, output)).collect();
+            let num_outputs: usize = output_locations.iter().map(|(_, size)| size).sum();
+            if values.len() != num_outputs {
+                return Err(BlackBoxResolutionError::Failed(
+                    BlackBoxFunc::UserDefined,
+                    format!(
+                        "{name}: the number of outputs does not match the call. {} != {num_outputs}",
+                        values.len()
+                    ),
+                ));
+            }
+
+            let mut values = values.into_iter().map(MemoryValue::new_field);
+            for (address, size) in output_locations {
+                let output_values: Vec<_> = values.by_ref().take(size).collect();
+                memory.write_slice(address, &output_values);
+            }
+            Ok(())
+        }
     }
 }
 
@@ -465,5 +513,6 @@ fn black_box_function_from_op(op: &BlackBoxOp) -> BlackBoxFunc {
         BlackBoxOp::ToRadix { .. } => unreachable!("ToRadix is not an ACIR BlackBoxFunc"),
         BlackBoxOp::PedersenCommitment { .. } => BlackBoxFunc::PedersenCommitment,
         BlackBoxOp::PedersenHash { .. } => BlackBoxFunc::PedersenHash,
+        BlackBoxOp::UserDefined { .. } => BlackBoxFunc::UserDefined,
     }
 }
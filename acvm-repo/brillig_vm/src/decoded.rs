@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use acir::brillig::{BinaryFieldOp, BitSize, IntegerBitSize, MemoryAddress, Opcode};
+use acir::AcirField;
+
+use crate::arithmetic::IntegerOperation;
+use crate::memory::MemoryValue;
+
+/// A compact form of an [Opcode], decoded once before execution begins.
+///
+/// Operands which would otherwise be interpreted each time the opcode is processed are resolved
+/// ahead of time: constants are converted into memory values and integer operations are
+/// specialised for their bit size.
+/// Opcodes with variable sized operands, such as foreign calls, are left to be processed in their
+/// original form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DecodedOpcode<F> {
+    BinaryFieldOp {
+        op: BinaryFieldOp,
+        lhs: MemoryAddress,
+        rhs: MemoryAddress,
+        destination: MemoryAddress,
+    },
+    BinaryIntOp {
+        operation: IntegerOperation,
+        lhs: MemoryAddress,
+        rhs: MemoryAddress,
+        destination: MemoryAddress,
+    },
+    Not {
+        source: MemoryAddress,
+        destination: MemoryAddress,
+        bit_size: IntegerBitSize,
+    },
+    Cast {
+        source: MemoryAddress,
+        destination: MemoryAddress,
+        bit_size: BitSize,
+    },
+    Jump {
+        location: usize,
+    },
+    JumpIf {
+        condition: MemoryAddress,
+        location: usize,
+    },
+    JumpIfNot {
+        condition: MemoryAddress,
+        location: usize,
+    },
+    Call {
+        location: usize,
+    },
+    Return,
+    Mov {
+        source: MemoryAddress,
+        destination: MemoryAddress,
+    },
+    ConditionalMov {
+        source_a: MemoryAddress,
+        source_b: MemoryAddress,
+        condition: MemoryAddress,
+        destination: MemoryAddress,
+    },
+    Load {
+        source_pointer: MemoryAddress,
+        destination: MemoryAddress,
+    },
+    Store {
+        source: MemoryAddress,
+        destination_pointer: MemoryAddress,
+    },
+    Const {
+        value: MemoryValue<F>,
+        destination: MemoryAddress,
+    },
+    IndirectConst {
+        value: MemoryValue<F>,
+        destination_pointer: MemoryAddress,
+    },
+    Stop {
+        return_data_offset: usize,
+        return_data_size: usize,
+    },
+    /// An opcode which is processed in its original form.
+    Undecoded,
+}
+
+impl<F: AcirField> DecodedOpcode<F> {
+    pub(crate) fn decode(opcode: &Opcode<F>) -> Self {
+        match opcode {
+            Opcode::BinaryFieldOp { destination, op, lhs, rhs } => DecodedOpcode::BinaryFieldOp {
+                op: *op,
+                lhs: *lhs,
+                rhs: *rhs,
+                destination: *destination,
+            },
+            Opcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => {
+                DecodedOpcode::BinaryIntOp {
+                    operation: IntegerOperation::new(*op, *bit_size),
+                    lhs: *lhs,
+                    rhs: *rhs,
+                    destination: *destination,
+                }
+            }
+            Opcode::Not { destination, source, bit_size } => DecodedOpcode::Not {
+                source: *source,
+                destination: *destination,
+                bit_size: *bit_size,
+            },
+            Opcode::Cast { destination, source, bit_size } => DecodedOpcode::Cast {
+                source: *source,
+                destination: *destination,
+                bit_size: *bit_size,
+            },
+            Opcode::Jump { location } => DecodedOpcode::Jump { location: *location },
+            Opcode::JumpIf { condition, location } => {
+                DecodedOpcode::JumpIf { condition: *condition, location: *location }
+            }
+            Opcode::JumpIfNot { condition, location } => {
+                DecodedOpcode::JumpIfNot { condition: *condition, location: *location }
+            }
+            Opcode::Call { location } => DecodedOpcode::Call { location: *location },
+            Opcode::Return => DecodedOpcode::Return,
+            Opcode::Mov { destination, source } => {
+                DecodedOpcode::Mov { source: *source, destination: *destination }
+            }
+            Opcode::ConditionalMov { destination, source_a, source_b, condition } => {
+                DecodedOpcode::ConditionalMov {
+                    source_a: *source_a,
+                    source_b: *source_b,
+                    condition: *condition,
+                    destination: *destination,
+                }
+            }
+            Opcode::Load { destination, source_pointer } => {
+                DecodedOpcode::Load { source_pointer: *source_pointer, destination: *destination }
+            }
+            Opcode::Store { destination_pointer, source } => {
+                DecodedOpcode::Store { source: *source, destination_pointer: *destination_pointer }
+            }
+            // Consts are not checked to fit in the bit size, since they can safely be checked statically.
+            Opcode::Const { destination, bit_size, value } => DecodedOpcode::Const {
+                value: MemoryValue::new_from_field(*value, *bit_size),
+                destination: *destination,
+            },
+            Opcode::IndirectConst { destination_pointer, bit_size, value } => {
+                DecodedOpcode::IndirectConst {
+                    value: MemoryValue::new_from_field(*value, *bit_size),
+                    destination_pointer: *destination_pointer,
+                }
+            }
+            Opcode::Stop { return_data_offset, return_data_size } => DecodedOpcode::Stop {
+                return_data_offset: *return_data_offset,
+                return_data_size: *return_data_size,
+            },
+            Opcode::CalldataCopy { .. }
+            | Opcode::ForeignCall { .. }
+            | Opcode::Trap { .. }
+            | Opcode::BlackBox(_) => DecodedOpcode::Undecoded,
+        }
+    }
+}
+
+/// The bytecode of a Brillig function along with its decoded opcodes, ready to be executed by a [VM][crate::VM].
+///
+/// The bytecode is decoded once when this is created and clones share the decoded opcodes,
+/// so a function which is called many times only needs to be decoded once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedBytecode<'a, F> {
+    bytecode: &'a [Opcode<F>],
+    /// The decoded opcode at each position corresponds to the original opcode at that position.
+    decoded: Arc<[DecodedOpcode<F>]>,
+}
+
+impl<'a, F: AcirField> DecodedBytecode<'a, F> {
+    pub fn new(bytecode: &'a [Opcode<F>]) -> Self {
+        Self { bytecode, decoded: bytecode.iter().map(DecodedOpcode::decode).collect() }
+    }
+}
+
+impl<'a, F> DecodedBytecode<'a, F> {
+    /// Returns the original opcodes of the bytecode.
+    pub fn bytecode(&self) -> &'a [Opcode<F>] {
+        self.bytecode
+    }
+
+    pub(crate) fn decoded(&self) -> &[DecodedOpcode<F>] {
+        &self.decoded
+    }
+}
@@ -12,21 +12,27 @@
 //! [acvm]: https://crates.io/crates/acvm
 
 use acir::brillig::{
-    BinaryFieldOp, BinaryIntOp, BitSize, ForeignCallParam, ForeignCallResult, HeapArray,
-    HeapValueType, HeapVector, IntegerBitSize, MemoryAddress, Opcode, ValueOrArray,
+    BinaryFieldOp, BitSize, ForeignCallParam, ForeignCallResult, HeapArray, HeapValueType,
+    HeapVector, IntegerBitSize, MemoryAddress, Opcode, ValueOrArray,
 };
 use acir::AcirField;
 use acvm_blackbox_solver::BlackBoxFunctionSolver;
-use arithmetic::{evaluate_binary_field_op, evaluate_binary_int_op, BrilligArithmeticError};
+use arithmetic::{evaluate_binary_field_op, BrilligArithmeticError};
 use black_box::{evaluate_black_box, BrilligBigintSolver};
+use decoded::DecodedOpcode;
+use memory::ObservedMemory;
 
 // Re-export `brillig`.
 pub use acir::brillig;
+pub use decoded::DecodedBytecode;
 pub use memory::{Memory, MemoryValue, MEMORY_ADDRESSING_BIT_SIZE};
+pub use observer::{BrilligObserver, NoopObserver};
 
 mod arithmetic;
 mod black_box;
+mod decoded;
 mod memory;
+mod observer;
 
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
@@ -35,6 +41,22 @@ pub type ErrorCallStack = Vec<usize>;
 pub enum FailureReason {
     Trap { revert_data_offset: usize, revert_data_size: usize },
     RuntimeError { message: String },
+    MemoryLimitExceeded { max_memory_size: usize, requested_size: usize },
+    StepLimitExceeded { max_steps: usize },
+}
+
+/// Limits on the resources which a [VM] may use, allowing untrusted programs to be sandboxed.
+///
+/// An opcode which would grow memory past `max_memory_size` fails with a [FailureReason::MemoryLimitExceeded],
+/// leaving memory untouched, and processing more than `max_steps` opcodes fails with a
+/// [FailureReason::StepLimitExceeded]. No limits are set by default.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct VMLimits {
+    /// The maximum number of values the VM's memory may hold.
+    pub max_memory_size: Option<usize>,
+    /// The maximum number of opcodes the VM may process, including opcodes which are processed
+    /// again after a foreign call has been resolved.
+    pub max_steps: Option<usize>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -76,8 +98,8 @@ pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
     /// Represents the outputs of all foreign calls during a Brillig process
     /// List is appended onto by the caller upon reaching a [VMStatus::ForeignCallWait]
     foreign_call_results: Vec<ForeignCallResult<F>>,
-    /// Executable opcodes
-    bytecode: &'a [Opcode<F>],
+    /// Executable opcodes, decoded ahead of execution
+    bytecode: DecodedBytecode<'a, F>,
     /// Status of the VM
     status: VMStatus<F>,
     /// Memory of the VM
@@ -88,6 +110,10 @@ pub struct VM<'a, F, B: BlackBoxFunctionSolver<F>> {
     black_box_solver: &'a B,
     // The solver for big integers
     bigint_solver: BrilligBigintSolver,
+    /// Limits on the resources used by the VM
+    limits: VMLimits,
+    /// The number of opcodes processed so far
+    steps: usize,
 }
 
 impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
@@ -97,6 +123,22 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         bytecode: &'a [Opcode<F>],
         foreign_call_results: Vec<ForeignCallResult<F>>,
         black_box_solver: &'a B,
+    ) -> Self {
+        Self::with_decoded_bytecode(
+            calldata,
+            DecodedBytecode::new(bytecode),
+            foreign_call_results,
+            black_box_solver,
+        )
+    }
+
+    /// Constructs a new VM instance for bytecode which has already been decoded,
+    /// saving the cost of decoding it again for each call to the same function.
+    pub fn with_decoded_bytecode(
+        calldata: Vec<F>,
+        bytecode: DecodedBytecode<'a, F>,
+        foreign_call_results: Vec<ForeignCallResult<F>>,
+        black_box_solver: &'a B,
     ) -> Self {
         Self {
             calldata,
@@ -109,9 +151,17 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             call_stack: Vec::new(),
             black_box_solver,
             bigint_solver: Default::default(),
+            limits: VMLimits::default(),
+            steps: 0,
         }
     }
 
+    /// Sets the limits on the resources which the VM may use while processing opcodes.
+    pub fn set_limits(&mut self, limits: VMLimits) {
+        self.limits = limits;
+        self.memory.set_max_size(limits.max_memory_size);
+    }
+
     /// Updates the current status of the VM.
     /// Returns the given status.
     fn status(&mut self, status: VMStatus<F>) -> VMStatus<F> {
@@ -164,17 +214,26 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
     }
 
     fn fail(&mut self, message: String) -> VMStatus<F> {
-        self.status(VMStatus::Failure {
-            call_stack: self.get_error_stack(),
-            reason: FailureReason::RuntimeError { message },
-        });
+        self.fail_with_reason(FailureReason::RuntimeError { message })
+    }
+
+    fn fail_with_reason(&mut self, reason: FailureReason) -> VMStatus<F> {
+        self.status(VMStatus::Failure { call_stack: self.get_error_stack(), reason });
         self.status.clone()
     }
 
     /// Loop over the bytecode and update the program counter
     pub fn process_opcodes(&mut self) -> VMStatus<F> {
+        self.process_opcodes_with_observer(&mut NoopObserver)
+    }
+
+    /// Loop over the bytecode and update the program counter, reporting execution events to `observer`.
+    pub fn process_opcodes_with_observer<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+    ) -> VMStatus<F> {
         while !matches!(
-            self.process_opcode(),
+            self.process_opcode_with_observer(observer),
             VMStatus::Finished { .. } | VMStatus::Failure { .. } | VMStatus::ForeignCallWait { .. }
         ) {}
         self.status.clone()
@@ -196,53 +255,173 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
 
     /// Process a single opcode and modify the program counter.
     pub fn process_opcode(&mut self) -> VMStatus<F> {
-        let opcode = &self.bytecode[self.program_counter];
+        self.process_opcode_with_observer(&mut NoopObserver)
+    }
+
+    /// Process a single opcode and modify the program counter, reporting execution events to `observer`.
+    pub fn process_opcode_with_observer<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+    ) -> VMStatus<F> {
+        let status = match self.limits.max_steps {
+            Some(max_steps) if self.steps >= max_steps => {
+                self.fail_with_reason(FailureReason::StepLimitExceeded { max_steps })
+            }
+            _ => {
+                self.steps += 1;
+                self.process_opcode_within_memory_limit(observer)
+            }
+        };
+        match &status {
+            VMStatus::ForeignCallWait { function, inputs } => {
+                observer.on_foreign_call(function, inputs);
+            }
+            VMStatus::Failure { reason, call_stack } => {
+                observer.on_brillig_failure(reason, call_stack);
+            }
+            VMStatus::Finished { .. } | VMStatus::InProgress => {}
+        }
+        status
+    }
+
+    /// Processes the current opcode, failing if it attempted to grow memory past its maximum size.
+    fn process_opcode_within_memory_limit<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+    ) -> VMStatus<F> {
+        let program_counter = self.program_counter;
+        observer.on_brillig_opcode_start(
+            program_counter,
+            &self.bytecode.bytecode()[program_counter],
+            &self.call_stack,
+        );
+
+        let status = self.process_opcode_internal(observer);
+        match self.memory.take_exceeded_size() {
+            Some(requested_size) => {
+                // Report the failure at the opcode which exceeded the limit rather than the one after it.
+                self.program_counter = program_counter;
+                let max_memory_size =
+                    self.limits.max_memory_size.expect("Memory can only exceed a set limit");
+                self.fail_with_reason(FailureReason::MemoryLimitExceeded {
+                    max_memory_size,
+                    requested_size,
+                })
+            }
+            None => status,
+        }
+    }
+
+    fn process_opcode_internal<O: BrilligObserver<F>>(&mut self, observer: &mut O) -> VMStatus<F> {
+        let opcode = self.bytecode.decoded()[self.program_counter];
         match opcode {
-            Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
-                if let Err(error) = self.process_binary_field_op(*op, *lhs, *rhs, *result) {
+            DecodedOpcode::BinaryFieldOp { op, lhs, rhs, destination } => {
+                if let Err(error) =
+                    self.process_binary_field_op(observer, op, lhs, rhs, destination)
+                {
                     self.fail(error.to_string())
                 } else {
                     self.increment_program_counter()
                 }
             }
-            Opcode::BinaryIntOp { op, bit_size, lhs, rhs, destination: result } => {
-                if let Err(error) = self.process_binary_int_op(*op, *bit_size, *lhs, *rhs, *result)
-                {
-                    self.fail(error.to_string())
-                } else {
-                    self.increment_program_counter()
+            DecodedOpcode::BinaryIntOp { operation, lhs, rhs, destination } => {
+                let result = operation.evaluate(self.memory.read(lhs), self.memory.read(rhs));
+                match result {
+                    Ok(value) => {
+                        self.write_memory(observer, destination, value);
+                        self.increment_program_counter()
+                    }
+                    Err(error) => self.fail(error.to_string()),
                 }
             }
-            Opcode::Not { destination, source, bit_size } => {
-                if let Err(error) = self.process_not(*source, *destination, *bit_size) {
+            DecodedOpcode::Not { source, destination, bit_size } => {
+                if let Err(error) = self.process_not(observer, source, destination, bit_size) {
                     self.fail(error)
                 } else {
                     self.increment_program_counter()
                 }
             }
-            Opcode::Cast { destination: destination_address, source: source_address, bit_size } => {
-                let source_value = self.memory.read(*source_address);
-                let casted_value = self.cast(*bit_size, source_value);
-                self.memory.write(*destination_address, casted_value);
+            DecodedOpcode::Cast { source, destination, bit_size } => {
+                let source_value = self.memory.read(source);
+                let casted_value = self.cast(bit_size, source_value);
+                self.write_memory(observer, destination, casted_value);
                 self.increment_program_counter()
             }
-            Opcode::Jump { location: destination } => self.set_program_counter(*destination),
-            Opcode::JumpIf { condition, location: destination } => {
-                // Check if condition is true
+            DecodedOpcode::Jump { location } => self.set_program_counter(location),
+            DecodedOpcode::JumpIf { condition, location } => {
                 // We use 0 to mean false and any other value to mean true
-                let condition_value = self.memory.read(*condition);
-                if condition_value.try_into().expect("condition value is not a boolean") {
-                    return self.set_program_counter(*destination);
+                if self.read_condition(condition) {
+                    return self.set_program_counter(location);
                 }
                 self.increment_program_counter()
             }
-            Opcode::JumpIfNot { condition, location: destination } => {
-                let condition_value = self.memory.read(*condition);
-                if condition_value.try_into().expect("condition value is not a boolean") {
+            DecodedOpcode::JumpIfNot { condition, location } => {
+                if self.read_condition(condition) {
                     return self.increment_program_counter();
                 }
-                self.set_program_counter(*destination)
+                self.set_program_counter(location)
+            }
+            DecodedOpcode::Call { location } => {
+                // Push a return location
+                self.call_stack.push(self.program_counter);
+                self.set_program_counter(location)
             }
+            DecodedOpcode::Return => {
+                if let Some(return_location) = self.call_stack.pop() {
+                    self.set_program_counter(return_location + 1)
+                } else {
+                    self.fail("return opcode hit, but callstack already empty".to_string())
+                }
+            }
+            DecodedOpcode::Mov { source, destination } => {
+                self.write_memory(observer, destination, self.memory.read(source));
+                self.increment_program_counter()
+            }
+            DecodedOpcode::ConditionalMov { source_a, source_b, condition, destination } => {
+                let source = if self.read_condition(condition) { source_a } else { source_b };
+                self.write_memory(observer, destination, self.memory.read(source));
+                self.increment_program_counter()
+            }
+            DecodedOpcode::Load { source_pointer, destination } => {
+                // Convert our source_pointer to an address
+                let source = self.memory.read_ref(source_pointer);
+                // Use our usize source index to lookup the value in memory
+                self.write_memory(observer, destination, self.memory.read(source));
+                self.increment_program_counter()
+            }
+            DecodedOpcode::Store { source, destination_pointer } => {
+                // Convert our destination_pointer to an address
+                let destination = self.memory.read_ref(destination_pointer);
+                // Use our usize destination index to set the value in memory
+                self.write_memory(observer, destination, self.memory.read(source));
+                self.increment_program_counter()
+            }
+            DecodedOpcode::Const { value, destination } => {
+                self.write_memory(observer, destination, value);
+                self.increment_program_counter()
+            }
+            DecodedOpcode::IndirectConst { value, destination_pointer } => {
+                let destination = self.memory.read_ref(destination_pointer);
+                self.write_memory(observer, destination, value);
+                self.increment_program_counter()
+            }
+            DecodedOpcode::Stop { return_data_offset, return_data_size } => {
+                self.finish(return_data_offset, return_data_size)
+            }
+            DecodedOpcode::Undecoded => {
+                let opcode = &self.bytecode.bytecode()[self.program_counter];
+                self.process_undecoded_opcode(opcode, observer)
+            }
+        }
+    }
+
+    /// Processes an opcode which was left in its original form when the bytecode was decoded.
+    fn process_undecoded_opcode<O: BrilligObserver<F>>(
+        &mut self,
+        opcode: &Opcode<F>,
+        observer: &mut O,
+    ) -> VMStatus<F> {
+        match opcode {
             Opcode::CalldataCopy { destination_address, size_address, offset_address } => {
                 let size = self.memory.read(*size_address).to_usize();
                 let offset = self.memory.read(*offset_address).to_usize();
@@ -250,16 +429,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                     .iter()
                     .map(|value| MemoryValue::new_field(*value))
                     .collect();
-                self.memory.write_slice(*destination_address, &values);
+                self.write_memory_slice(observer, *destination_address, &values);
                 self.increment_program_counter()
             }
-            Opcode::Return => {
-                if let Some(return_location) = self.call_stack.pop() {
-                    self.set_program_counter(return_location + 1)
-                } else {
-                    self.fail("return opcode hit, but callstack already empty".to_string())
-                }
-            }
             Opcode::ForeignCall {
                 function,
                 destinations,
@@ -287,6 +459,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                 }
 
                 let write_result = self.write_foreign_call_result(
+                    observer,
                     destinations,
                     destination_value_types,
                     self.foreign_call_counter,
@@ -299,20 +472,6 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                 self.foreign_call_counter += 1;
                 self.increment_program_counter()
             }
-            Opcode::Mov { destination: destination_address, source: source_address } => {
-                let source_value = self.memory.read(*source_address);
-                self.memory.write(*destination_address, source_value);
-                self.increment_program_counter()
-            }
-            Opcode::ConditionalMov { destination, source_a, source_b, condition } => {
-                let condition_value = self.memory.read(*condition);
-                if condition_value.try_into().expect("condition value is not a boolean") {
-                    self.memory.write(*destination, self.memory.read(*source_a));
-                } else {
-                    self.memory.write(*destination, self.memory.read(*source_b));
-                }
-                self.increment_program_counter()
-            }
             Opcode::Trap { revert_data } => {
                 if revert_data.size > 0 {
                     self.trap(self.memory.read_ref(revert_data.pointer).0, revert_data.size)
@@ -320,55 +479,51 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                     self.trap(0, 0)
                 }
             }
-            Opcode::Stop { return_data_offset, return_data_size } => {
-                self.finish(*return_data_offset, *return_data_size)
-            }
-            Opcode::Load { destination: destination_address, source_pointer } => {
-                // Convert our source_pointer to an address
-                let source = self.memory.read_ref(*source_pointer);
-                // Use our usize source index to lookup the value in memory
-                let value = self.memory.read(source);
-                self.memory.write(*destination_address, value);
-                self.increment_program_counter()
-            }
-            Opcode::Store { destination_pointer, source: source_address } => {
-                // Convert our destination_pointer to an address
-                let destination = self.memory.read_ref(*destination_pointer);
-                // Use our usize destination index to set the value in memory
-                self.memory.write(destination, self.memory.read(*source_address));
-                self.increment_program_counter()
-            }
-            Opcode::Call { location } => {
-                // Push a return location
-                self.call_stack.push(self.program_counter);
-                self.set_program_counter(*location)
-            }
-            Opcode::Const { destination, value, bit_size } => {
-                // Consts are not checked in runtime to fit in the bit size, since they can safely be checked statically.
-                self.memory.write(*destination, MemoryValue::new_from_field(*value, *bit_size));
-                self.increment_program_counter()
-            }
-            Opcode::IndirectConst { destination_pointer, bit_size, value } => {
-                // Convert our destination_pointer to an address
-                let destination = self.memory.read_ref(*destination_pointer);
-                // Use our usize destination index to set the value in memory
-                self.memory.write(destination, MemoryValue::new_from_field(*value, *bit_size));
-                self.increment_program_counter()
-            }
             Opcode::BlackBox(black_box_op) => {
                 match evaluate_black_box(
                     black_box_op,
                     self.black_box_solver,
-                    &mut self.memory,
+                    &mut ObservedMemory::new(&mut self.memory, observer),
                     &mut self.bigint_solver,
                 ) {
                     Ok(()) => self.increment_program_counter(),
                     Err(e) => self.fail(e.to_string()),
                 }
             }
+            _ => unreachable!("ICE: {opcode:?} should have been decoded"),
+        }
+    }
+
+    /// Reads a boolean condition from memory.
+    fn read_condition(&self, condition: MemoryAddress) -> bool {
+        match self.memory.read(condition) {
+            MemoryValue::Integer(value, IntegerBitSize::U1) => value != 0,
+            value => panic!("condition value is not a boolean: {value:?}"),
         }
     }
 
+    /// Writes `value` to `address`, reporting the write to `observer`.
+    fn write_memory<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+        address: MemoryAddress,
+        value: MemoryValue<F>,
+    ) {
+        if self.memory.try_write(address, value) {
+            observer.on_memory_write(address, &value);
+        }
+    }
+
+    /// Writes `values` starting at `address`, reporting each write to `observer`.
+    fn write_memory_slice<O: BrilligObserver<F>>(
+        &mut self,
+        observer: &mut O,
+        address: MemoryAddress,
+        values: &[MemoryValue<F>],
+    ) {
+        ObservedMemory::new(&mut self.memory, observer).write_slice(address, values);
+    }
+
     /// Returns the current value of the program counter.
     pub fn program_counter(&self) -> usize {
         self.program_counter
@@ -383,9 +538,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
     /// If the program counter no longer points to an opcode
     /// in the bytecode, then the VMStatus reports halted.
     fn set_program_counter(&mut self, value: usize) -> VMStatus<F> {
-        assert!(self.program_counter < self.bytecode.len());
+        assert!(self.program_counter < self.bytecode.bytecode().len());
         self.program_counter = value;
-        if self.program_counter >= self.bytecode.len() {
+        if self.program_counter >= self.bytecode.bytecode().len() {
             self.status = VMStatus::Finished { return_data_offset: 0, return_data_size: 0 };
         }
         self.status.clone()
@@ -476,8 +631,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         }
     }
 
-    fn write_foreign_call_result(
+    fn write_foreign_call_result<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         destinations: &[ValueOrArray],
         destination_value_types: &[HeapValueType],
         foreign_call_index: usize,
@@ -499,7 +655,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             (ValueOrArray::MemoryAddress(value_index), HeapValueType::Simple(bit_size)) => {
                 match output {
                     ForeignCallParam::Single(value) => {
-                        self.write_value_to_memory(*value_index, value, *bit_size)?;
+                        self.write_value_to_memory(observer, *value_index, value, *bit_size)?;
                     }
                     _ => return Err(format!(
                         "Function result size does not match brillig bytecode. Expected 1 result but got {output:?}")
@@ -518,9 +674,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                                let destination = self.memory.read_ref(*pointer_index);
                                let return_type = value_type;
                                let mut flatten_values_idx = 0; //index of values read from flatten_values
-                               self.write_slice_of_values_to_memory(destination, &output.fields(), &mut flatten_values_idx, return_type)?;
+                               self.write_slice_of_values_to_memory(observer, destination, &output.fields(), &mut flatten_values_idx, return_type)?;
                             } else {
-                                self.write_values_to_memory_slice(*pointer_index, values, value_types)?;
+                                self.write_values_to_memory_slice(observer, *pointer_index, values, value_types)?;
                             }
                         }
                         _ => {
@@ -532,7 +688,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                     let destination = self.memory.read_ref(*pointer_index);
                     let return_type = value_type;
                     let mut flatten_values_idx = 0; //index of values read from flatten_values
-                    self.write_slice_of_values_to_memory(destination, &output.fields(), &mut flatten_values_idx, return_type)?;
+                    self.write_slice_of_values_to_memory(observer, destination, &output.fields(), &mut flatten_values_idx, return_type)?;
             }
         }
             (
@@ -543,8 +699,8 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                     match output {
                         ForeignCallParam::Array(values) => {
                             // Set our size in the size address
-                            self.memory.write(*size_index, values.len().into());
-                            self.write_values_to_memory_slice(*pointer_index, values, value_types)?;
+                            self.write_memory(observer, *size_index, values.len().into());
+                            self.write_values_to_memory_slice(observer, *pointer_index, values, value_types)?;
 
                         }
                         _ => {
@@ -567,8 +723,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         Ok(())
     }
 
-    fn write_value_to_memory(
+    fn write_value_to_memory<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         destination: MemoryAddress,
         value: &F,
         value_bit_size: BitSize,
@@ -576,7 +733,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         let memory_value = MemoryValue::new_checked(*value, value_bit_size);
 
         if let Some(memory_value) = memory_value {
-            self.memory.write(destination, memory_value);
+            self.write_memory(observer, destination, memory_value);
         } else {
             return Err(format!(
                 "Foreign call result value {} does not fit in bit size {:?}",
@@ -586,8 +743,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         Ok(())
     }
 
-    fn write_values_to_memory_slice(
+    fn write_values_to_memory_slice<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         pointer_index: MemoryAddress,
         values: &[F],
         value_types: &[HeapValueType],
@@ -609,7 +767,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             .map(|(value, bit_size)| MemoryValue::new_checked(*value, bit_size))
             .collect();
         if let Some(memory_values) = memory_values {
-            self.memory.write_slice(destination, &memory_values);
+            self.write_memory_slice(observer, destination, &memory_values);
         } else {
             return Err(format!(
                 "Foreign call result values {:?} do not match expected bit sizes",
@@ -624,8 +782,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
     /// values_idx is the current index in the values vector and is incremented every time
     /// a value is written to memory
     /// The function returns the address of the next value to be written
-    fn write_slice_of_values_to_memory(
+    fn write_slice_of_values_to_memory<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         destination: MemoryAddress,
         values: &Vec<F>,
         values_idx: &mut usize,
@@ -634,7 +793,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
         let mut current_pointer = destination;
         match value_type {
             HeapValueType::Simple(bit_size) => {
-                self.write_value_to_memory(destination, &values[*values_idx], *bit_size)?;
+                self.write_value_to_memory(observer, destination, &values[*values_idx], *bit_size)?;
                 *values_idx += 1;
                 Ok(MemoryAddress(destination.to_usize() + 1))
             }
@@ -644,6 +803,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                         match typ {
                             HeapValueType::Simple(len) => {
                                 self.write_value_to_memory(
+                                    observer,
                                     current_pointer,
                                     &values[*values_idx],
                                     *len,
@@ -655,6 +815,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
                                 let destination =
                                     MemoryAddress(self.memory.read_ref(current_pointer).0 + 1);
                                 self.write_slice_of_values_to_memory(
+                                    observer,
                                     destination,
                                     values,
                                     values_idx,
@@ -681,8 +842,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
 
     /// Process a binary operation.
     /// This method will not modify the program counter.
-    fn process_binary_field_op(
+    fn process_binary_field_op<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         op: BinaryFieldOp,
         lhs: MemoryAddress,
         rhs: MemoryAddress,
@@ -693,31 +855,14 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
 
         let result_value = evaluate_binary_field_op(&op, lhs_value, rhs_value)?;
 
-        self.memory.write(result, result_value);
+        self.write_memory(observer, result, result_value);
 
         Ok(())
     }
 
-    /// Process a binary operation.
-    /// This method will not modify the program counter.
-    fn process_binary_int_op(
-        &mut self,
-        op: BinaryIntOp,
-        bit_size: IntegerBitSize,
-        lhs: MemoryAddress,
-        rhs: MemoryAddress,
-        result: MemoryAddress,
-    ) -> Result<(), BrilligArithmeticError> {
-        let lhs_value = self.memory.read(lhs);
-        let rhs_value = self.memory.read(rhs);
-
-        let result_value = evaluate_binary_int_op(&op, lhs_value, rhs_value, bit_size)?;
-        self.memory.write(result, result_value);
-        Ok(())
-    }
-
-    fn process_not(
+    fn process_not<O: BrilligObserver<F>>(
         &mut self,
+        observer: &mut O,
         source: MemoryAddress,
         destination: MemoryAddress,
         op_bit_size: IntegerBitSize,
@@ -742,7 +887,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
             let mask = (1_u128 << bit_size as u128) - 1;
             (!value) & mask
         };
-        self.memory.write(destination, MemoryValue::new_integer(negated_value, bit_size));
+        self.write_memory(observer, destination, MemoryValue::new_integer(negated_value, bit_size));
         Ok(())
     }
 
@@ -785,8 +930,9 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>> VM<'a, F, B> {
 #[cfg(test)]
 mod tests {
     use crate::memory::MEMORY_ADDRESSING_BIT_SIZE;
+    use acir::brillig::{BinaryIntOp, BlackBoxOp};
     use acir::{AcirField, FieldElement};
-    use acvm_blackbox_solver::StubbedBlackBoxSolver;
+    use acvm_blackbox_solver::{StubbedBlackBoxSolver, UserBlackBoxRegistry, UserBlackBoxSolver};
 
     use super::*;
 
@@ -2227,4 +2373,256 @@ mod tests {
         // Ensure the foreign call counter has been incremented
         assert_eq!(vm.foreign_call_counter, 1);
     }
+
+    #[test]
+    fn user_defined_black_box_op() {
+        let mut registry = UserBlackBoxRegistry::new();
+        registry.register("running_sums", 3, 3, |inputs: &[FieldElement]| {
+            Ok(vec![inputs[0], inputs[0] + inputs[1], inputs[0] + inputs[1] + inputs[2]])
+        });
+        let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry);
+
+        let r_input = MemoryAddress::from(0);
+        let r_input_pointer = MemoryAddress::from(1);
+        let r_output = MemoryAddress::from(2);
+        let r_output_pointer = MemoryAddress::from(3);
+        let field_const = |destination: usize, value: u128| Opcode::Const {
+            destination: MemoryAddress::from(destination),
+            bit_size: BitSize::Field,
+            value: FieldElement::from(value),
+        };
+        let pointer_const = |destination: MemoryAddress, value: u128| Opcode::Const {
+            destination,
+            bit_size: BitSize::Integer(MEMORY_ADDRESSING_BIT_SIZE),
+            value: FieldElement::from(value),
+        };
+
+        let opcodes = vec![
+            field_const(0, 3),
+            pointer_const(r_input_pointer, 10),
+            field_const(10, 5),
+            field_const(11, 6),
+            pointer_const(r_output_pointer, 20),
+            Opcode::BlackBox(BlackBoxOp::UserDefined {
+                name: "running_sums".to_string(),
+                inputs: vec![
+                    ValueOrArray::MemoryAddress(r_input),
+                    ValueOrArray::HeapArray(HeapArray { pointer: r_input_pointer, size: 2 }),
+                ],
+                outputs: vec![
+                    ValueOrArray::MemoryAddress(r_output),
+                    ValueOrArray::HeapArray(HeapArray { pointer: r_output_pointer, size: 2 }),
+                ],
+            }),
+        ];
+
+        let mut vm = VM::new(vec![], &opcodes, vec![], &solver);
+        let status = vm.process_opcodes();
+        assert_eq!(status, VMStatus::Finished { return_data_offset: 0, return_data_size: 0 });
+
+        let memory = vm.get_memory();
+        assert_eq!(memory[2], MemoryValue::new_field(FieldElement::from(3u128)));
+        assert_eq!(
+            &memory[20..22],
+            &[
+                MemoryValue::new_field(FieldElement::from(8u128)),
+                MemoryValue::new_field(FieldElement::from(14u128))
+            ]
+        );
+
+        // Calls with the wrong number of outputs are rejected rather than partially written.
+        let mut opcodes = opcodes;
+        let Opcode::BlackBox(BlackBoxOp::UserDefined { outputs, .. }) = opcodes.last_mut().unwrap()
+        else {
+            unreachable!()
+        };
+        outputs.pop();
+        let mut vm = VM::new(vec![], &opcodes, vec![], &solver);
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                reason: FailureReason::RuntimeError {
+                    message: "failed to solve blackbox function: user_defined, reason: running_sums: the number of outputs does not match the call. 3 != 1".to_string()
+                },
+                call_stack: vec![5]
+            }
+        );
+    }
+
+    #[test]
+    fn observer_receives_the_call_stack_of_each_opcode() {
+        let r_value = MemoryAddress::from(0);
+        let opcodes: Vec<Opcode<FieldElement>> = vec![
+            Opcode::Call { location: 2 },
+            Opcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            Opcode::Const {
+                destination: r_value,
+                value: FieldElement::from(1u128),
+                bit_size: BitSize::Field,
+            },
+            Opcode::Return,
+        ];
+
+        let mut observer = RecordingObserver::default();
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver);
+        assert_eq!(
+            vm.process_opcodes_with_observer(&mut observer),
+            VMStatus::Finished { return_data_offset: 0, return_data_size: 0 }
+        );
+
+        let call_stacks: Vec<_> = observer
+            .events
+            .into_iter()
+            .filter_map(|event| match event {
+                ObservedEvent::OpcodeStart(call_stack) => Some(call_stack),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(call_stacks, vec![vec![0], vec![0, 2], vec![0, 3], vec![1]]);
+    }
+
+    #[test]
+    fn step_limit_stops_infinite_loops() {
+        let opcodes: Vec<Opcode<FieldElement>> = vec![Opcode::Jump { location: 0 }];
+
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver);
+        vm.set_limits(VMLimits { max_steps: Some(10), ..Default::default() });
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                reason: FailureReason::StepLimitExceeded { max_steps: 10 },
+                call_stack: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn memory_limit_rejects_writes_past_the_limit() {
+        let r_pointer = MemoryAddress::from(0);
+        let r_value = MemoryAddress::from(1);
+        let opcodes: Vec<Opcode<FieldElement>> = vec![
+            Opcode::Const {
+                destination: r_pointer,
+                value: FieldElement::from(1u128 << 30),
+                bit_size: BitSize::Integer(MEMORY_ADDRESSING_BIT_SIZE),
+            },
+            Opcode::Const {
+                destination: r_value,
+                value: FieldElement::from(1u128),
+                bit_size: BitSize::Field,
+            },
+            Opcode::Store { destination_pointer: r_pointer, source: r_value },
+            Opcode::Stop { return_data_offset: 0, return_data_size: 0 },
+        ];
+
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver);
+        vm.set_limits(VMLimits { max_memory_size: Some(1024), ..Default::default() });
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                reason: FailureReason::MemoryLimitExceeded {
+                    max_memory_size: 1024,
+                    requested_size: (1 << 30) + 1
+                },
+                call_stack: vec![2]
+            }
+        );
+        assert_eq!(vm.get_memory().len(), 2);
+    }
+
+    #[test]
+    fn memory_limit_does_not_apply_to_public_writes() {
+        let one = MemoryValue::new_field(FieldElement::from(1u128));
+        let two = MemoryValue::new_field(FieldElement::from(2u128));
+        let mut memory = Memory::default();
+        memory.set_max_size(Some(4));
+
+        memory.write(MemoryAddress::from(7), one);
+        memory.write_slice(MemoryAddress::from(8), &[two]);
+
+        assert_eq!(memory.values().len(), 9);
+        assert_eq!(memory.read_slice(MemoryAddress::from(7), 2), &[one, two]);
+        assert_eq!(memory.take_exceeded_size(), None);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum ObservedEvent {
+        /// The call stack at the start of the opcode, followed by its program counter
+        OpcodeStart(Vec<usize>),
+        MemoryWrite(usize, MemoryValue<FieldElement>),
+        ForeignCall(String, Vec<ForeignCallParam<FieldElement>>),
+        Failure(FailureReason, Vec<usize>),
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Vec<ObservedEvent>,
+    }
+
+    impl BrilligObserver<FieldElement> for RecordingObserver {
+        fn on_brillig_opcode_start(
+            &mut self,
+            program_counter: usize,
+            _opcode: &Opcode<FieldElement>,
+            call_stack: &[usize],
+        ) {
+            let call_stack = call_stack.iter().copied().chain(std::iter::once(program_counter));
+            self.events.push(ObservedEvent::OpcodeStart(call_stack.collect()));
+        }
+
+        fn on_memory_write(&mut self, address: MemoryAddress, value: &MemoryValue<FieldElement>) {
+            self.events.push(ObservedEvent::MemoryWrite(address.to_usize(), *value));
+        }
+
+        fn on_foreign_call(&mut self, function: &str, inputs: &[ForeignCallParam<FieldElement>]) {
+            self.events.push(ObservedEvent::ForeignCall(function.to_string(), inputs.to_vec()));
+        }
+
+        fn on_brillig_failure(&mut self, reason: &FailureReason, call_stack: &[usize]) {
+            self.events.push(ObservedEvent::Failure(reason.clone(), call_stack.to_vec()));
+        }
+    }
+
+    #[test]
+    fn observer_receives_execution_events() {
+        let r_input = MemoryAddress::from(0);
+        let r_result = MemoryAddress::from(1);
+        let bit_size = BitSize::Integer(MEMORY_ADDRESSING_BIT_SIZE);
+
+        let opcodes = vec![
+            Opcode::Const { destination: r_input, value: (5u128).into(), bit_size },
+            Opcode::ForeignCall {
+                function: "double".into(),
+                destinations: vec![ValueOrArray::MemoryAddress(r_result)],
+                destination_value_types: vec![HeapValueType::Simple(bit_size)],
+                inputs: vec![ValueOrArray::MemoryAddress(r_input)],
+                input_value_types: vec![HeapValueType::Simple(bit_size)],
+            },
+            Opcode::Trap { revert_data: HeapArray { pointer: r_input, size: 0 } },
+        ];
+
+        let mut observer = RecordingObserver::default();
+        let mut vm = VM::new(vec![], &opcodes, vec![], &StubbedBlackBoxSolver);
+        vm.process_opcodes_with_observer(&mut observer);
+        vm.resolve_foreign_call(FieldElement::from(10u128).into());
+        let status = vm.process_opcodes_with_observer(&mut observer);
+        assert!(matches!(status, VMStatus::Failure { .. }));
+
+        let five = MemoryValue::new_integer(5, MEMORY_ADDRESSING_BIT_SIZE);
+        let ten = MemoryValue::new_integer(10, MEMORY_ADDRESSING_BIT_SIZE);
+        let trap = FailureReason::Trap { revert_data_offset: 0, revert_data_size: 0 };
+        assert_eq!(
+            observer.events,
+            vec![
+                ObservedEvent::OpcodeStart(vec![0]),
+                ObservedEvent::MemoryWrite(0, five),
+                ObservedEvent::OpcodeStart(vec![1]),
+                ObservedEvent::ForeignCall("double".into(), vec![FieldElement::from(5u128).into()]),
+                ObservedEvent::OpcodeStart(vec![1]),
+                ObservedEvent::MemoryWrite(1, ten),
+                ObservedEvent::OpcodeStart(vec![2]),
+                ObservedEvent::Failure(trap, vec![2]),
+            ]
+        );
+    }
 }
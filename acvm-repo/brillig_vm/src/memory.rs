@@ -3,6 +3,9 @@ use acir::{
     AcirField,
 };
 use num_traits::{One, Zero};
+use std::ops::Deref;
+
+use crate::BrilligObserver;
 
 pub const MEMORY_ADDRESSING_BIT_SIZE: IntegerBitSize = IntegerBitSize::U32;
 
@@ -230,6 +233,10 @@ pub struct Memory<F> {
     // Memory is a vector of values.
     // We grow the memory when values past the end are set, extending with 0s.
     inner: Vec<MemoryValue<F>>,
+    /// The maximum number of values which the memory may hold, if it is limited.
+    max_size: Option<usize>,
+    /// The size which the first write past `max_size` required the memory to grow to.
+    exceeded_size: Option<usize>,
 }
 
 impl<F: AcirField> Memory<F> {
@@ -253,11 +260,34 @@ impl<F: AcirField> Memory<F> {
     }
 
     /// Sets the value at pointer `ptr` to `value`
+    ///
+    /// The maximum size only limits the VM's own writes, so this always succeeds.
     pub fn write(&mut self, ptr: MemoryAddress, value: MemoryValue<F>) {
         self.resize_to_fit(ptr.to_usize() + 1);
         self.inner[ptr.to_usize()] = value;
     }
 
+    /// Sets the value at pointer `ptr` to `value`, returning whether the write fit within the maximum size.
+    ///
+    /// A write which doesn't fit is not performed, and the size it required is recorded for the VM to report.
+    pub(crate) fn try_write(&mut self, ptr: MemoryAddress, value: MemoryValue<F>) -> bool {
+        if !self.fits_within_max_size(ptr.to_usize() + 1) {
+            return false;
+        }
+        self.write(ptr, value);
+        true
+    }
+
+    /// Checks whether the memory may grow to hold `size` values, recording the size if it may not.
+    fn fits_within_max_size(&mut self, size: usize) -> bool {
+        if self.max_size.is_some_and(|max_size| size > max_size) {
+            self.exceeded_size.get_or_insert(size);
+            return false;
+        }
+        true
+    }
+
+    /// Grows the memory to hold at least `size` values.
     fn resize_to_fit(&mut self, size: usize) {
         // Calculate new memory size
         let new_size = std::cmp::max(self.inner.len(), size);
@@ -266,13 +296,80 @@ impl<F: AcirField> Memory<F> {
     }
 
     /// Sets the values after pointer `ptr` to `values`
+    ///
+    /// The maximum size only limits the VM's own writes, so this always succeeds.
     pub fn write_slice(&mut self, ptr: MemoryAddress, values: &[MemoryValue<F>]) {
         self.resize_to_fit(ptr.to_usize() + values.len());
         self.inner[ptr.to_usize()..(ptr.to_usize() + values.len())].copy_from_slice(values);
     }
 
+    /// Sets the values after pointer `ptr` to `values`, returning whether the write fit within the maximum size.
+    ///
+    /// A write which doesn't fit is not performed, and the size it required is recorded for the VM to report.
+    pub(crate) fn try_write_slice(
+        &mut self,
+        ptr: MemoryAddress,
+        values: &[MemoryValue<F>],
+    ) -> bool {
+        if !self.fits_within_max_size(ptr.to_usize() + values.len()) {
+            return false;
+        }
+        self.write_slice(ptr, values);
+        true
+    }
+
+    /// Limits the number of values which the memory may hold, or removes the limit if `max_size` is `None`.
+    pub fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+    }
+
+    /// Returns the size which the memory would have needed to grow to for a write which exceeded
+    /// its maximum size, clearing it so that later writes are checked afresh.
+    pub(crate) fn take_exceeded_size(&mut self) -> Option<usize> {
+        self.exceeded_size.take()
+    }
+
     /// Returns the values of the memory
     pub fn values(&self) -> &[MemoryValue<F>] {
         &self.inner
     }
 }
+
+/// A view over [Memory] which reports every write to a [BrilligObserver].
+///
+/// Reads go straight through to the underlying memory via [Deref].
+pub(crate) struct ObservedMemory<'m, F, O> {
+    memory: &'m mut Memory<F>,
+    observer: &'m mut O,
+}
+
+impl<'m, F: AcirField, O: BrilligObserver<F>> ObservedMemory<'m, F, O> {
+    pub(crate) fn new(memory: &'m mut Memory<F>, observer: &'m mut O) -> Self {
+        Self { memory, observer }
+    }
+
+    /// Sets the value at pointer `ptr` to `value`
+    pub(crate) fn write(&mut self, ptr: MemoryAddress, value: MemoryValue<F>) {
+        if self.memory.try_write(ptr, value) {
+            self.observer.on_memory_write(ptr, &value);
+        }
+    }
+
+    /// Sets the values after pointer `ptr` to `values`
+    pub(crate) fn write_slice(&mut self, ptr: MemoryAddress, values: &[MemoryValue<F>]) {
+        if !self.memory.try_write_slice(ptr, values) {
+            return;
+        }
+        for (offset, value) in values.iter().enumerate() {
+            self.observer.on_memory_write(MemoryAddress(ptr.to_usize() + offset), value);
+        }
+    }
+}
+
+impl<'m, F, O> Deref for ObservedMemory<'m, F, O> {
+    type Target = Memory<F>;
+
+    fn deref(&self) -> &Memory<F> {
+        self.memory
+    }
+}
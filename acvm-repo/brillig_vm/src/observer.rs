@@ -0,0 +1,36 @@
+use acir::brillig::{ForeignCallParam, MemoryAddress, Opcode};
+
+use crate::{FailureReason, MemoryValue};
+
+/// Receives callbacks as the [VM][crate::VM] executes Brillig bytecode.
+///
+/// Every method has an empty default implementation so implementors only need to override the events they
+/// are interested in. The VM is generic over the observer, so executing with [NoopObserver] compiles
+/// down to the same code as executing without one.
+pub trait BrilligObserver<F> {
+    /// Called before the opcode at `program_counter` is processed.
+    ///
+    /// `call_stack` holds the location of each `Call` opcode which has not yet returned, outermost first.
+    fn on_brillig_opcode_start(
+        &mut self,
+        _program_counter: usize,
+        _opcode: &Opcode<F>,
+        _call_stack: &[usize],
+    ) {
+    }
+
+    /// Called after `value` has been written to `address`.
+    fn on_memory_write(&mut self, _address: MemoryAddress, _value: &MemoryValue<F>) {}
+
+    /// Called when the VM pauses to wait for the result of a foreign call.
+    fn on_foreign_call(&mut self, _function: &str, _inputs: &[ForeignCallParam<F>]) {}
+
+    /// Called when the VM fails, either by reaching a `Trap` opcode or by hitting a runtime error.
+    fn on_brillig_failure(&mut self, _reason: &FailureReason, _call_stack: &[usize]) {}
+}
+
+/// An observer which ignores every event.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopObserver;
+
+impl<F> BrilligObserver<F> for NoopObserver {}
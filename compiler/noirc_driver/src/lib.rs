@@ -14,13 +14,16 @@ use noirc_evaluator::create_program;
 use noirc_evaluator::errors::RuntimeError;
 use noirc_evaluator::ssa::SsaProgramArtifact;
 use noirc_frontend::debug::build_debug_crate_file;
+use noirc_frontend::hir::def_collector::dc_crate::CompilationError;
 use noirc_frontend::hir::def_map::{Contract, CrateDefMap};
-use noirc_frontend::hir::Context;
+use noirc_frontend::hir::resolution::errors::ResolverError;
+use noirc_frontend::hir::{Context, FunctionNameMatch};
 use noirc_frontend::monomorphization::{
     errors::MonomorphizationError, monomorphize, monomorphize_debug,
 };
 use noirc_frontend::node_interner::FuncId;
 use noirc_frontend::token::SecondaryAttribute;
+use noirc_frontend::usage_tracker::unreachable_items;
 use std::path::Path;
 use tracing::info;
 
@@ -49,12 +52,24 @@ pub const NOIRC_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const NOIR_ARTIFACT_VERSION_STRING: &str =
     concat!(env!("CARGO_PKG_VERSION"), "+", env!("GIT_COMMIT"));
 
+/// The field which programs are compiled over.
+/// This is chosen when the compiler is built, through its `bn254` and `bls12_381` features.
+pub const NATIVE_FIELD: &str = noirc_frontend::hir::def_collector::dc_mod::CHOSEN_FIELD;
+
+/// The fields which the compiler can be built for.
+pub const SUPPORTED_FIELDS: [&str; 2] = ["bn254", "bls12_381"];
+
 #[derive(Args, Clone, Debug, Default)]
 pub struct CompileOptions {
     /// Specify the backend expression width that should be targeted
     #[arg(long, value_parser = parse_expression_width)]
     pub expression_width: Option<ExpressionWidth>,
 
+    /// Specify the field which the program should be compiled over, either `bn254` or `bls12_381`.
+    /// Overrides the `field` set in the package's Nargo.toml.
+    #[arg(long, value_parser = parse_field)]
+    pub field: Option<String>,
+
     /// Generate ACIR with the target backend expression width.
     /// The default is to generate ACIR without a bound and split expressions after code generation.
     /// Activating this flag can sometimes provide optimizations for certain programs.
@@ -138,6 +153,32 @@ pub fn parse_expression_width(input: &str) -> Result<ExpressionWidth, std::io::E
     }
 }
 
+pub fn parse_field(input: &str) -> Result<String, std::io::Error> {
+    use std::io::{Error, ErrorKind};
+    if SUPPORTED_FIELDS.contains(&input) {
+        Ok(input.to_string())
+    } else {
+        let message =
+            format!("unknown field `{input}`, expected one of: {}", SUPPORTED_FIELDS.join(", "));
+        Err(Error::new(ErrorKind::InvalidInput, message))
+    }
+}
+
+/// Checks that programs can be compiled over `field` by this compiler.
+///
+/// The field is fixed when the compiler is built, so each field is selected by running the build for it,
+/// which nargo does for the `--field` option and the `field` of a package.
+pub fn check_field(field: &str) -> Result<(), String> {
+    if field == NATIVE_FIELD {
+        Ok(())
+    } else {
+        Err(format!(
+            "cannot compile for the {field} field as this compiler was built for the {NATIVE_FIELD} field. \
+            Use a compiler built with `--no-default-features --features {field}` to target it"
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub enum CompileError {
     MonomorphizationError(MonomorphizationError),
@@ -277,6 +318,12 @@ pub fn check_crate(
     crate_id: CrateId,
     options: &CompileOptions,
 ) -> CompilationResult<()> {
+    if let Some(field) = &options.field {
+        check_field(field).map_err(|message| {
+            vec![CustomDiagnostic::from_message(&message).in_file(FileId::default())]
+        })?;
+    }
+
     let mut errors = vec![];
     let error_on_unused_imports = true;
     let diagnostics = CrateDefMap::collect_defs(
@@ -297,6 +344,75 @@ pub fn check_crate(
     }
 }
 
+/// Returns a warning for each function or global in the crate which can never be reached
+/// from one of its entry points: `main`, contract functions, exported functions and tests.
+///
+/// If `public_items_are_entry_points` is set, as should be done for libraries, each public item
+/// is also treated as an entry point. Items which [check_crate] already reports as being unused
+/// are not reported again.
+pub fn check_unreachable_items(
+    context: &Context,
+    crate_id: CrateId,
+    public_items_are_entry_points: bool,
+    options: &CompileOptions,
+) -> CompilationResult<()> {
+    let def_map = context.def_map(&crate_id).expect("The local crate should be analyzed already");
+
+    let tests = context
+        .get_all_test_functions_in_crate_matching(&crate_id, FunctionNameMatch::Anything)
+        .into_iter()
+        .map(|(_, test_function)| test_function.get_id());
+    let exported_functions =
+        context.get_all_exported_functions_in_crate(&crate_id).into_iter().map(|(_, id)| id);
+    let contract_functions =
+        context.get_all_contracts(&crate_id).into_iter().flat_map(|contract| {
+            contract
+                .functions
+                .into_iter()
+                .filter(|function| function.is_entry_point)
+                .map(|function| function.function_id)
+        });
+    let entry_points = context
+        .get_main_function(&crate_id)
+        .into_iter()
+        .chain(tests)
+        .chain(exported_functions)
+        .chain(contract_functions);
+
+    let unreachable_items = unreachable_items(
+        &context.def_interner,
+        def_map,
+        entry_points,
+        public_items_are_entry_points,
+    );
+    let unused_items = context.def_interner.unused_items();
+
+    let mut items = Vec::new();
+    for (module_id, module_items) in unreachable_items {
+        let file_id = def_map.modules()[module_id.local_id.0].location.file;
+        let unused_in_module = unused_items.get(&module_id);
+
+        items.extend(module_items.into_iter().filter_map(|(ident, item)| {
+            let is_unused = unused_in_module.map_or(false, |unused| unused.contains_key(&ident));
+            (!is_unused).then_some((file_id, ident, item))
+        }));
+    }
+
+    // Report items in the order they appear in the source code.
+    items.sort_by_key(|(file_id, ident, _)| (*file_id, ident.span().start()));
+
+    let warnings = vecmap(items, |(file_id, ident, item)| {
+        let error = CompilationError::from(ResolverError::UnreachableItem { ident, item });
+        CustomDiagnostic::from(&error).in_file(file_id)
+    });
+
+    if has_errors(&warnings, options.deny_warnings) {
+        Err(warnings)
+    } else {
+        Ok(((), warnings))
+    }
+}
+
 pub fn compute_function_abi(
     context: &Context,
     crate_id: &CrateId,
@@ -0,0 +1,41 @@
+use std::path::Path;
+
+use fm::FileId;
+use noirc_driver::{
+    check_crate, check_field, file_manager_with_stdlib, prepare_crate, CompileOptions,
+    NATIVE_FIELD, SUPPORTED_FIELDS,
+};
+use noirc_errors::CustomDiagnostic;
+use noirc_frontend::hir::{def_map::parse_file, Context};
+
+#[test]
+fn reject_crates_compiled_for_another_field() {
+    let other_field = SUPPORTED_FIELDS
+        .into_iter()
+        .find(|field| *field != NATIVE_FIELD)
+        .expect("compiler should support more than one field");
+    assert_eq!(check_field(NATIVE_FIELD), Ok(()));
+    let message = check_field(other_field).unwrap_err();
+
+    let root = Path::new("");
+    let file_name = Path::new("main.nr");
+    let mut file_manager = file_manager_with_stdlib(root);
+    file_manager.add_file_with_source(file_name, "fn main() {}".to_owned()).expect(
+        "Adding source buffer to file manager should never fail when file manager is empty",
+    );
+    let parsed_files = file_manager
+        .as_file_map()
+        .all_file_ids()
+        .map(|&file_id| (file_id, parse_file(&file_manager, file_id)))
+        .collect();
+
+    let mut context = Context::new(file_manager, parsed_files);
+    let root_crate_id = prepare_crate(&mut context, file_name);
+
+    let options = CompileOptions { field: Some(other_field.to_string()), ..Default::default() };
+    let errors = check_crate(&mut context, root_crate_id, &options).unwrap_err();
+    assert_eq!(errors, vec![CustomDiagnostic::from_message(&message).in_file(FileId::default())]);
+
+    let options = CompileOptions { field: Some(NATIVE_FIELD.to_string()), ..Default::default() };
+    assert!(check_crate(&mut context, root_crate_id, &options).is_ok());
+}
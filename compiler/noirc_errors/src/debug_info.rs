@@ -104,6 +104,12 @@ pub struct DebugInfo {
     pub variables: DebugVariables,
     pub functions: DebugFunctions,
     pub types: DebugTypes,
+    /// Number of opcodes removed from the ACIR circuit by ACIR-level optimizations.
+    #[serde(default)]
+    pub acir_opcodes_optimized_away: usize,
+    /// Number of opcodes removed from each Brillig function called by the circuit by Brillig-level optimizations.
+    #[serde(default)]
+    pub brillig_opcodes_optimized_away: BTreeMap<BrilligFunctionId, usize>,
 }
 
 /// Holds OpCodes Counts for Acir and Brillig Opcodes
@@ -125,7 +131,15 @@ impl DebugInfo {
         functions: DebugFunctions,
         types: DebugTypes,
     ) -> Self {
-        Self { locations, brillig_locations, variables, functions, types }
+        Self {
+            locations,
+            brillig_locations,
+            variables,
+            functions,
+            types,
+            acir_opcodes_optimized_away: 0,
+            brillig_opcodes_optimized_away: BTreeMap::new(),
+        }
     }
 
     /// Updates the locations map when the [`Circuit`][acvm::acir::circuit::Circuit] is modified.
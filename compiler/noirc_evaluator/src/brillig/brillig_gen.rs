@@ -5,8 +5,11 @@ pub(crate) mod brillig_directive;
 pub(crate) mod brillig_fn;
 pub(crate) mod brillig_slice_ops;
 mod constant_allocation;
+mod register_allocation;
 mod variable_liveness;
 
+pub(crate) use register_allocation::MAX_VARIABLE_REGISTERS;
+
 use acvm::FieldElement;
 
 use self::{brillig_block::BrilligBlock, brillig_fn::FunctionContext};
@@ -17,13 +20,16 @@ use super::brillig_ir::{
 use crate::ssa::ir::function::Function;
 
 /// Converting an SSA function into Brillig bytecode.
+///
+/// At most `max_registers` registers are assigned to the function's variables before they are spilled to memory.
 pub(crate) fn convert_ssa_function(
     func: &Function,
     enable_debug_trace: bool,
+    max_registers: usize,
 ) -> BrilligArtifact<FieldElement> {
     let mut brillig_context = BrilligContext::new(enable_debug_trace);
 
-    let mut function_context = FunctionContext::new(func);
+    let mut function_context = FunctionContext::with_max_registers(func, max_registers);
 
     brillig_context.enter_context(Label::function(func.id()));
 
@@ -477,6 +477,11 @@ pub(crate) fn convert_black_box_call<F: AcirField + DebugToString, Registers: Re
                 unreachable!("ICE: AES128Encrypt expects three array arguments, one array result")
             }
         }
+        BlackBoxFunc::UserDefined => {
+            unreachable!(
+                "ICE: user-defined blackbox functions are called through `Value::UserBlackBox`"
+            )
+        }
     }
 }
 
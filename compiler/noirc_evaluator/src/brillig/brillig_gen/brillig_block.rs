@@ -1,4 +1,4 @@
-use crate::brillig::brillig_ir::artifact::Label;
+use crate::brillig::brillig_ir::artifact::{BrilligParameter, Label};
 use crate::brillig::brillig_ir::brillig_variable::{
     type_to_heap_value_type, BrilligArray, BrilligVariable, SingleAddrVariable,
 };
@@ -18,7 +18,7 @@ use crate::ssa::ir::{
     types::{NumericType, Type},
     value::{Value, ValueId},
 };
-use acvm::acir::brillig::{MemoryAddress, ValueOrArray};
+use acvm::acir::brillig::{BlackBoxOp, HeapArray, MemoryAddress, ValueOrArray};
 use acvm::{acir::AcirField, FieldElement};
 use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use iter_extended::vecmap;
@@ -29,6 +29,8 @@ use super::brillig_black_box::convert_black_box_call;
 use super::brillig_block_variables::BlockVariables;
 use super::brillig_fn::FunctionContext;
 use super::constant_allocation::InstructionLocation;
+use super::register_allocation::VariableLocation;
+use super::variable_liveness::{collect_variables_of_value, variables_used_in_instruction};
 
 /// Generate the compilation artifacts for compiling a function into brillig bytecode.
 pub(crate) struct BrilligBlock<'block> {
@@ -54,13 +56,8 @@ impl<'block> BrilligBlock<'block> {
         let live_in = function_context.liveness.get_live_in(&block_id);
         let variables = BlockVariables::new(live_in.clone());
 
-        brillig_context.set_allocated_registers(
-            variables
-                .get_available_variables(function_context)
-                .into_iter()
-                .map(|variable| variable.extract_register())
-                .collect(),
-        );
+        brillig_context
+            .set_allocated_registers(variables.get_allocated_registers(function_context));
         let last_uses = function_context.liveness.get_last_uses(&block_id).clone();
 
         let mut brillig_block =
@@ -76,6 +73,14 @@ impl<'block> BrilligBlock<'block> {
 
         self.convert_block_params(dfg);
 
+        // Variables which did not fit in registers are held in memory allocated on entry to the function.
+        if let Some(spill_area) = self.function_context.register_allocation.spill_area_pointer() {
+            if self.function_context.blocks.first() == Some(&self.block_id) {
+                let spill_slots = self.function_context.register_allocation.spill_slots();
+                self.brillig_context.codegen_allocate_immediate_mem(spill_area, spill_slots);
+            }
+        }
+
         let block = &dfg[self.block_id];
 
         // Convert all of the instructions into the block
@@ -117,13 +122,18 @@ impl<'block> BrilligBlock<'block> {
         terminator_instruction: &TerminatorInstruction,
         dfg: &DataFlowGraph,
     ) {
-        self.initialize_constants(
-            &self
-                .function_context
-                .constant_allocation
-                .allocated_at_location(self.block_id, InstructionLocation::Terminator),
-            dfg,
-        );
+        let constants = self
+            .function_context
+            .constant_allocation
+            .allocated_at_location(self.block_id, InstructionLocation::Terminator);
+        let mut used_variables = Vec::new();
+        terminator_instruction.for_each_value(|value_id| {
+            used_variables.extend(collect_variables_of_value(value_id, dfg));
+        });
+        self.reload_spilled_variables(used_variables, &constants, dfg);
+        self.initialize_constants(&constants, dfg);
+        self.variables.store_spilled_variables(self.function_context, self.brillig_context);
+
         match terminator_instruction {
             TerminatorInstruction::JmpIf {
                 condition,
@@ -146,14 +156,31 @@ impl<'block> BrilligBlock<'block> {
                 call_stack: _,
             } => {
                 let target_block = &dfg[*destination_block];
+                let mut sources = Vec::with_capacity(arguments.len());
+                let mut destinations = Vec::with_capacity(arguments.len());
                 for (src, dest) in arguments.iter().zip(target_block.parameters()) {
+                    let source = self.convert_ssa_value(*src, dfg).extract_register();
+                    // Spilled block parameters are written straight to memory,
+                    // before any of the registers holding the arguments is overwritten.
+                    if let Some(VariableLocation::Spilled(slot)) =
+                        self.function_context.register_allocation.location(dfg.resolve(*dest))
+                    {
+                        let spill_area = self
+                            .function_context
+                            .register_allocation
+                            .spill_area_pointer()
+                            .expect("ICE: Spilled variables need a spill area");
+                        self.brillig_context
+                            .codegen_store_with_immediate_offset(spill_area, slot, source);
+                        continue;
+                    }
                     // Destinations are block parameters so they should have been allocated previously.
                     let destination =
                         self.variables.get_allocation(self.function_context, *dest, dfg);
-                    let source = self.convert_ssa_value(*src, dfg);
-                    self.brillig_context
-                        .mov_instruction(destination.extract_register(), source.extract_register());
+                    sources.push(source);
+                    destinations.push(destination.extract_register());
                 }
+                self.brillig_context.codegen_mov_registers_to_registers(sources, destinations);
                 self.brillig_context.jump_instruction(
                     self.create_block_label_for_current_function(*destination_block),
                 );
@@ -189,7 +216,7 @@ impl<'block> BrilligBlock<'block> {
                 // Be a valid pointer to the array.
                 // For slices, two registers are passed, the pointer to the data and a register holding the size of the slice.
                 Type::Numeric(_) | Type::Array(..) | Type::Slice(..) | Type::Reference(_) => {
-                    self.variables.define_variable(
+                    self.variables.define_block_param(
                         self.function_context,
                         self.brillig_context,
                         param_id,
@@ -206,13 +233,16 @@ impl<'block> BrilligBlock<'block> {
         let instruction = &dfg[instruction_id];
         self.brillig_context.set_call_stack(dfg.get_call_stack(instruction_id));
 
-        self.initialize_constants(
-            &self.function_context.constant_allocation.allocated_at_location(
-                self.block_id,
-                InstructionLocation::Instruction(instruction_id),
-            ),
+        let constants = self
+            .function_context
+            .constant_allocation
+            .allocated_at_location(self.block_id, InstructionLocation::Instruction(instruction_id));
+        self.reload_spilled_variables(
+            variables_used_in_instruction(instruction, dfg),
+            &constants,
             dfg,
         );
+        self.initialize_constants(&constants, dfg);
         match instruction {
             Instruction::Binary(binary) => {
                 let result_var = self.variables.define_single_addr_variable(
@@ -425,6 +455,103 @@ impl<'block> BrilligBlock<'block> {
                         }
                     }
                 }
+                Value::UserBlackBox(name) => {
+                    let result_ids = dfg.instruction_results(instruction_id);
+
+                    // As in ACIR, user-defined blackbox functions take and return nested arrays flattened,
+                    // so these are copied into temporary flat arrays before the call and out of them after it.
+                    let inputs = vecmap(arguments, |value_id| {
+                        let variable = self.convert_ssa_value(*value_id, dfg);
+                        match dfg.type_of_value(*value_id) {
+                            typ @ Type::Array(..) => {
+                                self.flatten_user_black_box_input(variable.extract_array(), typ)
+                            }
+                            Type::Slice(item_types) => {
+                                assert!(
+                                    item_types.iter().all(|typ| matches!(typ, Type::Numeric(_))),
+                                    "ICE: slices of arrays cannot be passed to user-defined blackbox functions"
+                                );
+                                self.brillig_context.variable_to_value_or_array(variable)
+                            }
+                            _ => self.brillig_context.variable_to_value_or_array(variable),
+                        }
+                    });
+
+                    let mut nested_outputs = Vec::new();
+                    let outputs = vecmap(result_ids, |value_id| {
+                        let typ = dfg.type_of_value(*value_id);
+                        assert!(
+                            !matches!(typ, Type::Slice(_)),
+                            "ICE: user-defined blackbox functions cannot return slices"
+                        );
+                        match FunctionContext::ssa_type_to_parameter(&typ) {
+                            BrilligParameter::Array(item_types, item_count)
+                                if BrilligContext::<FieldElement, Stack>::has_nested_arrays(
+                                    &item_types,
+                                ) =>
+                            {
+                                let array = self
+                                    .variables
+                                    .define_variable(
+                                        self.function_context,
+                                        self.brillig_context,
+                                        *value_id,
+                                        dfg,
+                                    )
+                                    .extract_array();
+                                let flattened_output = HeapArray {
+                                    pointer: self.brillig_context.allocate_register(),
+                                    size: typ.flattened_size(),
+                                };
+                                self.brillig_context.codegen_allocate_immediate_mem(
+                                    flattened_output.pointer,
+                                    flattened_output.size,
+                                );
+                                nested_outputs.push((
+                                    array,
+                                    item_types,
+                                    item_count,
+                                    flattened_output,
+                                ));
+                                ValueOrArray::HeapArray(flattened_output)
+                            }
+                            _ => {
+                                let variable = self.allocate_external_call_result(*value_id, dfg);
+                                self.brillig_context.variable_to_value_or_array(variable)
+                            }
+                        }
+                    });
+
+                    self.brillig_context.black_box_op_instruction(BlackBoxOp::UserDefined {
+                        name: name.clone(),
+                        inputs: inputs.clone(),
+                        outputs: outputs.clone(),
+                    });
+
+                    for (array, item_types, item_count, flattened_output) in nested_outputs {
+                        let deflattened_array = self.brillig_context.deflatten_array(
+                            &item_types,
+                            item_count,
+                            flattened_output.pointer,
+                            false,
+                        );
+                        self.brillig_context.mov_instruction(array.pointer, deflattened_array);
+                        self.brillig_context.deallocate_register(deflattened_array);
+                    }
+
+                    // Deallocate the temporary heap arrays and vectors of the inputs and outputs
+                    for value in inputs.into_iter().chain(outputs) {
+                        match value {
+                            ValueOrArray::HeapArray(array) => {
+                                self.brillig_context.deallocate_heap_array(array);
+                            }
+                            ValueOrArray::HeapVector(vector) => {
+                                self.brillig_context.deallocate_heap_vector(vector);
+                            }
+                            ValueOrArray::MemoryAddress(_) => {}
+                        }
+                    }
+                }
                 Value::Function(func_id) => {
                     let result_ids = dfg.instruction_results(instruction_id);
                     self.convert_ssa_function_call(*func_id, arguments, dfg, result_ids);
@@ -648,7 +775,7 @@ impl<'block> BrilligBlock<'block> {
 
                 let index_variable = self.convert_ssa_single_addr_value(*index, dfg);
 
-                if !dfg.is_safe_index(*index, *array) {
+                if !dfg.is_safe_array_access(instruction_id) {
                     self.validate_array_index(array_variable, index_variable);
                 }
 
@@ -676,7 +803,7 @@ impl<'block> BrilligBlock<'block> {
                     dfg,
                 );
 
-                if !dfg.is_safe_index(*index, *array) {
+                if !dfg.is_safe_array_access(instruction_id) {
                     self.validate_array_index(source_variable, index_register);
                 }
 
@@ -761,6 +888,9 @@ impl<'block> BrilligBlock<'block> {
             }
         };
 
+        self.variables.store_spilled_variables(self.function_context, self.brillig_context);
+        self.variables.release_reloaded_variables(self.brillig_context);
+
         let dead_variables = self
             .last_uses
             .get(&instruction_id)
@@ -789,7 +919,12 @@ impl<'block> BrilligBlock<'block> {
             .map(|argument_id| self.convert_ssa_value(*argument_id, dfg).extract_register())
             .collect();
 
-        let variables_to_save = self.variables.get_available_variables(self.function_context);
+        let mut variables_to_save = self.variables.get_available_variables(self.function_context);
+        // The spill area of this function needs to be found again after the call, like any variable held in a register.
+        if let Some(spill_area) = self.function_context.register_allocation.spill_area_pointer() {
+            variables_to_save
+                .push(BrilligVariable::SingleAddr(SingleAddrVariable::new_usize(spill_area)));
+        }
 
         let saved_registers = self
             .brillig_context
@@ -827,8 +962,7 @@ impl<'block> BrilligBlock<'block> {
             .codegen_post_call_prep_returns_load_registers(&returned_registers, &saved_registers);
 
         // Reset the register state to the one needed to hold the current available variables
-        let variables = self.variables.get_available_variables(self.function_context);
-        let registers = variables.into_iter().map(|variable| variable.extract_register()).collect();
+        let registers = self.variables.get_allocated_registers(self.function_context);
         self.brillig_context.set_allocated_registers(registers);
     }
 
@@ -1530,6 +1664,24 @@ impl<'block> BrilligBlock<'block> {
         }
     }
 
+    /// Loads the spilled variables used at the current location into registers,
+    /// including the ones needed to build the constants allocated there.
+    fn reload_spilled_variables(
+        &mut self,
+        used_variables: impl IntoIterator<Item = ValueId>,
+        constants: &[ValueId],
+        dfg: &DataFlowGraph,
+    ) {
+        let constant_variables =
+            constants.iter().flat_map(|constant_id| collect_variables_of_value(*constant_id, dfg));
+        self.variables.reload_spilled_variables(
+            used_variables.into_iter().chain(constant_variables),
+            self.function_context,
+            self.brillig_context,
+            dfg,
+        );
+    }
+
     fn initialize_constants(&mut self, constants: &[ValueId], dfg: &DataFlowGraph) {
         for &constant_id in constants {
             self.convert_ssa_value(constant_id, dfg);
@@ -1627,6 +1779,9 @@ impl<'block> BrilligBlock<'block> {
             Value::Intrinsic(_) | Value::ForeignFunction(_) => {
                 todo!("ICE: Cannot convert value {value:?}")
             }
+            Value::UserBlackBox(name) => unreachable!(
+                "ICE: user-defined blackbox function `{name}` used as a value should have been rejected by `check_for_user_black_box_values`"
+            ),
         }
     }
 
@@ -1793,6 +1948,40 @@ impl<'block> BrilligBlock<'block> {
         variable.extract_single_addr()
     }
 
+    /// Converts an array argument of a user-defined blackbox function into a [`HeapArray`] of its flattened items.
+    ///
+    /// Arrays without nested arrays are already laid out flat in memory, so only nested arrays are copied.
+    fn flatten_user_black_box_input(&mut self, array: BrilligArray, typ: Type) -> ValueOrArray {
+        let BrilligParameter::Array(item_types, item_count) =
+            FunctionContext::ssa_type_to_parameter(&typ)
+        else {
+            unreachable!("ICE: expected an array parameter")
+        };
+        if !BrilligContext::<FieldElement, Stack>::has_nested_arrays(&item_types) {
+            return ValueOrArray::HeapArray(
+                self.brillig_context.codegen_brillig_array_to_heap_array(array),
+            );
+        }
+
+        let flattened_input = HeapArray {
+            pointer: self.brillig_context.allocate_register(),
+            size: typ.flattened_size(),
+        };
+        self.brillig_context
+            .codegen_allocate_immediate_mem(flattened_input.pointer, flattened_input.size);
+
+        let items_pointer = self.brillig_context.codegen_make_array_items_pointer(array);
+        self.brillig_context.flatten_array(
+            &item_types,
+            item_count,
+            flattened_input.pointer,
+            items_pointer,
+        );
+        self.brillig_context.deallocate_register(items_pointer);
+
+        ValueOrArray::HeapArray(flattened_input)
+    }
+
     fn allocate_external_call_result(
         &mut self,
         result: ValueId,
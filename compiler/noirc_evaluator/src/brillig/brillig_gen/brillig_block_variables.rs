@@ -1,5 +1,5 @@
-use acvm::FieldElement;
-use fxhash::FxHashSet as HashSet;
+use acvm::{acir::brillig::MemoryAddress, FieldElement};
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 
 use crate::{
     brillig::brillig_ir::{
@@ -17,26 +17,32 @@ use crate::{
     },
 };
 
-use super::brillig_fn::FunctionContext;
+use super::{brillig_fn::FunctionContext, register_allocation::VariableLocation};
 
 #[derive(Debug, Default)]
 pub(crate) struct BlockVariables {
     available_variables: HashSet<ValueId>,
+    /// The temporary registers holding spilled variables while the current instruction is converted.
+    reloaded_variables: HashMap<ValueId, BrilligVariable>,
+    /// The spilled variables defined by the current instruction, which have yet to be written to memory.
+    pending_spills: Vec<ValueId>,
 }
 
 impl BlockVariables {
     /// Creates a BlockVariables instance. It uses the variables that are live in to the block and the global available variables (block parameters)
     pub(crate) fn new(live_in: HashSet<ValueId>) -> Self {
-        BlockVariables { available_variables: live_in }
+        BlockVariables { available_variables: live_in, ..Default::default() }
     }
 
-    /// Returns all variables that have not been removed at this point.
+    /// Returns all variables held in registers that have not been removed at this point.
+    /// Spilled variables are left out, since they are kept in memory.
     pub(crate) fn get_available_variables(
         &self,
         function_context: &FunctionContext,
     ) -> Vec<BrilligVariable> {
         self.available_variables
             .iter()
+            .filter(|value_id| !function_context.register_allocation.is_spilled(**value_id))
             .map(|value_id| {
                 function_context
                     .ssa_value_allocations
@@ -47,6 +53,25 @@ impl BlockVariables {
             .collect()
     }
 
+    /// Returns the registers that can't be used as temporaries at this point: the ones reserved for variables
+    /// by the register allocation, the ones of variables allocated during the codegen and the ones holding
+    /// reloaded spilled variables.
+    pub(crate) fn get_allocated_registers(
+        &self,
+        function_context: &FunctionContext,
+    ) -> Vec<MemoryAddress> {
+        let mut registers: Vec<_> =
+            function_context.register_allocation.variable_registers().collect();
+        registers.extend(
+            self.get_available_variables(function_context)
+                .into_iter()
+                .map(|variable| variable.extract_register()),
+        );
+        registers
+            .extend(self.reloaded_variables.values().map(|variable| variable.extract_register()));
+        registers
+    }
+
     /// For a given SSA value id, define the variable and return the corresponding cached allocation.
     pub(crate) fn define_variable(
         &mut self,
@@ -56,7 +81,24 @@ impl BlockVariables {
         dfg: &DataFlowGraph,
     ) -> BrilligVariable {
         let value_id = dfg.resolve(value_id);
-        let variable = allocate_value(value_id, brillig_context, dfg);
+        let variable = match function_context.register_allocation.location(value_id) {
+            Some(VariableLocation::Register(register)) => {
+                value_in_register(value_id, register, dfg)
+            }
+            Some(VariableLocation::Spilled(_)) => {
+                // Spilled variables are computed into a temporary register,
+                // and written to memory once the instruction defining them has been converted.
+                let variable = allocate_value(value_id, brillig_context, dfg);
+                if self.reloaded_variables.insert(value_id, variable).is_some() {
+                    unreachable!("ICE: ValueId {value_id:?} was already defined");
+                }
+                self.pending_spills.push(value_id);
+                self.available_variables.insert(value_id);
+                return variable;
+            }
+            // Values which are not tracked by the liveness analysis are allocated on the fly.
+            None => allocate_value(value_id, brillig_context, dfg),
+        };
 
         if function_context.ssa_value_allocations.insert(value_id, variable).is_some() {
             unreachable!("ICE: ValueId {value_id:?} was already in cache");
@@ -67,6 +109,23 @@ impl BlockVariables {
         variable
     }
 
+    /// Defines a block parameter. Spilled parameters are only made available,
+    /// since their value is written to memory by the jumps to their block.
+    pub(crate) fn define_block_param(
+        &mut self,
+        function_context: &mut FunctionContext,
+        brillig_context: &mut BrilligContext<FieldElement, Stack>,
+        param_id: ValueId,
+        dfg: &DataFlowGraph,
+    ) {
+        let param_id = dfg.resolve(param_id);
+        if function_context.register_allocation.is_spilled(param_id) {
+            self.available_variables.insert(param_id);
+        } else {
+            self.define_variable(function_context, brillig_context, param_id, dfg);
+        }
+    }
+
     /// Defines a variable that fits in a single register and returns the allocated register.
     pub(crate) fn define_single_addr_variable(
         &mut self,
@@ -87,6 +146,11 @@ impl BlockVariables {
         brillig_context: &mut BrilligContext<FieldElement, Stack>,
     ) {
         assert!(self.available_variables.remove(value_id), "ICE: Variable is not available");
+        // Registers reserved by the register allocation are never handed out as temporaries,
+        // so only the variables allocated on the fly need to be deallocated.
+        if function_context.register_allocation.location(*value_id).is_some() {
+            return;
+        }
         let variable = function_context
             .ssa_value_allocations
             .get(value_id)
@@ -113,11 +177,85 @@ impl BlockVariables {
             "ICE: ValueId {value_id:?} is not available"
         );
 
+        if let Some(variable) = self.reloaded_variables.get(&value_id) {
+            return *variable;
+        }
+
         *function_context
             .ssa_value_allocations
             .get(&value_id)
             .unwrap_or_else(|| panic!("ICE: Value not found in cache {value_id}"))
     }
+
+    /// Loads the available spilled variables among the given values into temporary registers,
+    /// so that they can be used by the instruction being converted.
+    pub(crate) fn reload_spilled_variables(
+        &mut self,
+        value_ids: impl IntoIterator<Item = ValueId>,
+        function_context: &FunctionContext,
+        brillig_context: &mut BrilligContext<FieldElement, Stack>,
+        dfg: &DataFlowGraph,
+    ) {
+        let Some(spill_area) = function_context.register_allocation.spill_area_pointer() else {
+            return;
+        };
+        for value_id in value_ids {
+            let value_id = dfg.resolve(value_id);
+            let Some(VariableLocation::Spilled(slot)) =
+                function_context.register_allocation.location(value_id)
+            else {
+                continue;
+            };
+            if !self.available_variables.contains(&value_id)
+                || self.reloaded_variables.contains_key(&value_id)
+            {
+                continue;
+            }
+            let variable = allocate_value(value_id, brillig_context, dfg);
+            brillig_context.codegen_load_with_immediate_offset(
+                spill_area,
+                slot,
+                variable.extract_register(),
+            );
+            self.reloaded_variables.insert(value_id, variable);
+        }
+    }
+
+    /// Writes the spilled variables defined by the instruction being converted to their slot in memory.
+    pub(crate) fn store_spilled_variables(
+        &mut self,
+        function_context: &FunctionContext,
+        brillig_context: &mut BrilligContext<FieldElement, Stack>,
+    ) {
+        for value_id in std::mem::take(&mut self.pending_spills) {
+            let Some(VariableLocation::Spilled(slot)) =
+                function_context.register_allocation.location(value_id)
+            else {
+                unreachable!("ICE: Only spilled variables are pending to be stored");
+            };
+            let spill_area = function_context
+                .register_allocation
+                .spill_area_pointer()
+                .expect("ICE: Spilled variables need a spill area");
+            let variable = self.reloaded_variables[&value_id];
+            brillig_context.codegen_store_with_immediate_offset(
+                spill_area,
+                slot,
+                variable.extract_register(),
+            );
+        }
+    }
+
+    /// Frees the temporary registers holding spilled variables once the instruction using them has been converted.
+    pub(crate) fn release_reloaded_variables(
+        &mut self,
+        brillig_context: &mut BrilligContext<FieldElement, Stack>,
+    ) {
+        assert!(self.pending_spills.is_empty(), "ICE: Spilled variables must be stored first");
+        for (_, variable) in self.reloaded_variables.drain() {
+            brillig_context.deallocate_register(variable.extract_register());
+        }
+    }
 }
 
 /// Computes the length of an array. This will match with the indexes that SSA will issue
@@ -130,22 +268,29 @@ pub(crate) fn allocate_value<F, Registers: RegisterAllocator>(
     value_id: ValueId,
     brillig_context: &mut BrilligContext<F, Registers>,
     dfg: &DataFlowGraph,
+) -> BrilligVariable {
+    value_in_register(value_id, brillig_context.allocate_register(), dfg)
+}
+
+/// For a given value_id, returns the variable holding it in the given register.
+fn value_in_register(
+    value_id: ValueId,
+    register: MemoryAddress,
+    dfg: &DataFlowGraph,
 ) -> BrilligVariable {
     let typ = dfg.type_of_value(value_id);
 
     match typ {
         Type::Numeric(_) | Type::Reference(_) | Type::Function => {
             BrilligVariable::SingleAddr(SingleAddrVariable {
-                address: brillig_context.allocate_register(),
+                address: register,
                 bit_size: get_bit_size_from_ssa_type(&typ),
             })
         }
         Type::Array(item_typ, elem_count) => BrilligVariable::BrilligArray(BrilligArray {
-            pointer: brillig_context.allocate_register(),
+            pointer: register,
             size: compute_array_length(&item_typ, elem_count),
         }),
-        Type::Slice(_) => BrilligVariable::BrilligVector(BrilligVector {
-            pointer: brillig_context.allocate_register(),
-        }),
+        Type::Slice(_) => BrilligVariable::BrilligVector(BrilligVector { pointer: register }),
     }
 }
@@ -70,6 +70,7 @@ pub(crate) fn directive_invert<F: AcirField>() -> GeneratedBrillig<F> {
         assert_messages: Default::default(),
         locations: Default::default(),
         name: "directive_invert".to_string(),
+        opcodes_optimized_away: 0,
     }
 }
 
@@ -135,5 +136,6 @@ pub(crate) fn directive_quotient<F: AcirField>() -> GeneratedBrillig<F> {
         assert_messages: Default::default(),
         locations: Default::default(),
         name: "directive_integer_quotient".to_string(),
+        opcodes_optimized_away: 0,
     }
 }
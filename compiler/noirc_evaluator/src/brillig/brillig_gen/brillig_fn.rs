@@ -15,7 +15,11 @@ use crate::{
 };
 use fxhash::FxHashMap as HashMap;
 
-use super::{constant_allocation::ConstantAllocation, variable_liveness::VariableLiveness};
+use super::{
+    constant_allocation::ConstantAllocation,
+    register_allocation::{RegisterAllocation, MAX_VARIABLE_REGISTERS},
+    variable_liveness::VariableLiveness,
+};
 
 pub(crate) struct FunctionContext {
     pub(crate) function_id: FunctionId,
@@ -27,11 +31,20 @@ pub(crate) struct FunctionContext {
     pub(crate) liveness: VariableLiveness,
     /// Information on where to allocate constants
     pub(crate) constant_allocation: ConstantAllocation,
+    /// The location of every variable, decided ahead of the codegen from their liveness.
+    pub(crate) register_allocation: RegisterAllocation,
 }
 
 impl FunctionContext {
-    /// Creates a new function context. It will allocate parameters for all blocks and compute the liveness of every variable.
+    /// Creates a new function context. It will allocate parameters for all blocks, compute the liveness of every variable
+    /// and assign them registers.
     pub(crate) fn new(function: &Function) -> Self {
+        Self::with_max_registers(function, MAX_VARIABLE_REGISTERS)
+    }
+
+    /// Creates a new function context which assigns at most `max_registers` registers to variables,
+    /// spilling the others to memory.
+    pub(crate) fn with_max_registers(function: &Function, max_registers: usize) -> Self {
         let id = function.id();
 
         let mut reverse_post_order = Vec::new();
@@ -40,6 +53,13 @@ impl FunctionContext {
 
         let constants = ConstantAllocation::from_function(function);
         let liveness = VariableLiveness::from_function(function, &constants);
+        let register_allocation = RegisterAllocation::from_function(
+            function,
+            &reverse_post_order,
+            &liveness,
+            &constants,
+            max_registers,
+        );
 
         Self {
             function_id: id,
@@ -47,6 +67,7 @@ impl FunctionContext {
             blocks: reverse_post_order,
             liveness,
             constant_allocation: constants,
+            register_allocation,
         }
     }
 
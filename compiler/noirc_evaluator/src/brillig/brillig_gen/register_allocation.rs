@@ -0,0 +1,522 @@
+//! This module assigns a location to every variable of a function before its Brillig code is generated.
+//!
+//! The live interval of a variable spans from the point where it's defined to the last point where it's still
+//! available according to the variable liveness analysis, following the blocks in the order they are generated.
+//! Registers are assigned to these intervals with a linear scan, as described in
+//! https://dl.acm.org/doi/10.1145/330249.330250, reusing the register of each interval as soon as it expires.
+//! When more variables are alive at once than there are registers available, the ones which end the furthest
+//! are spilled to memory and loaded into temporary registers whenever they are used.
+use std::collections::BTreeSet;
+
+use acvm::acir::brillig::MemoryAddress;
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use crate::brillig::brillig_ir::{
+    entry_point::MAX_STACK_SIZE,
+    registers::{RegisterAllocator, Stack},
+};
+use crate::ssa::ir::{
+    basic_block::BasicBlockId,
+    function::Function,
+    instruction::{Instruction, TerminatorInstruction},
+    value::{Value, ValueId},
+};
+
+use super::{
+    constant_allocation::{ConstantAllocation, InstructionLocation},
+    variable_liveness::{collect_variables_of_value, VariableLiveness},
+};
+
+/// The number of stack registers kept free of variables, for the temporaries used while generating each instruction.
+const TEMPORARY_REGISTERS: usize = 1024;
+
+/// The number of stack registers which variables are assigned before they are spilled to memory.
+pub(crate) const MAX_VARIABLE_REGISTERS: usize = MAX_STACK_SIZE - TEMPORARY_REGISTERS;
+
+/// Where a variable is held throughout the function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VariableLocation {
+    /// The variable is held in a register for its whole lifetime.
+    Register(MemoryAddress),
+    /// The variable is held in the given slot of the function's spill area.
+    Spilled(usize),
+}
+
+/// The location assigned to each variable of a function.
+pub(crate) struct RegisterAllocation {
+    locations: HashMap<ValueId, VariableLocation>,
+    /// The number of registers at the start of the stack reserved for variables.
+    variable_registers: usize,
+    /// The register holding a pointer to the spill area, if any variable was spilled.
+    spill_area_pointer: Option<MemoryAddress>,
+    /// The number of slots in the spill area.
+    spill_slots: usize,
+}
+
+impl RegisterAllocation {
+    /// Assigns a location to every variable of the function, given the blocks in the order they are generated.
+    ///
+    /// At most `max_registers` registers are assigned to variables, except for the parameters of the function.
+    pub(crate) fn from_function(
+        func: &Function,
+        blocks: &[BasicBlockId],
+        liveness: &VariableLiveness,
+        constants: &ConstantAllocation,
+        max_registers: usize,
+    ) -> Self {
+        let intervals = compute_live_intervals(func, blocks, liveness, constants);
+        Self::from_intervals(&intervals, max_registers)
+    }
+
+    fn from_intervals(intervals: &[LiveInterval], max_registers: usize) -> Self {
+        let (registers, spilled, registers_used) = linear_scan(intervals, max_registers);
+
+        let mut locations: HashMap<ValueId, VariableLocation> = registers
+            .into_iter()
+            .map(|(value_id, register)| {
+                (
+                    value_id,
+                    VariableLocation::Register(MemoryAddress::from(Stack::start() + register)),
+                )
+            })
+            .collect();
+
+        // Spill slots are assigned with another scan, so that slots are reused by variables which don't overlap.
+        let spilled_intervals: Vec<_> = spilled
+            .into_iter()
+            .map(|index| LiveInterval {
+                fixed_register: None,
+                preferred_register: None,
+                ..intervals[index]
+            })
+            .collect();
+        let (slots, _, spill_slots) = linear_scan(&spilled_intervals, usize::MAX);
+        locations.extend(
+            slots.into_iter().map(|(value_id, slot)| (value_id, VariableLocation::Spilled(slot))),
+        );
+
+        // The pointer to the spill area takes the register following the ones assigned to variables.
+        let (spill_area_pointer, variable_registers) = if spill_slots > 0 {
+            (Some(MemoryAddress::from(Stack::start() + registers_used)), registers_used + 1)
+        } else {
+            (None, registers_used)
+        };
+
+        Self { locations, variable_registers, spill_area_pointer, spill_slots }
+    }
+
+    /// Returns the location assigned to a variable, if it was known before the function was generated.
+    pub(crate) fn location(&self, value_id: ValueId) -> Option<VariableLocation> {
+        self.locations.get(&value_id).copied()
+    }
+
+    pub(crate) fn is_spilled(&self, value_id: ValueId) -> bool {
+        matches!(self.location(value_id), Some(VariableLocation::Spilled(_)))
+    }
+
+    /// Returns the registers reserved for variables, which must never be used as temporaries.
+    pub(crate) fn variable_registers(&self) -> impl Iterator<Item = MemoryAddress> {
+        (Stack::start()..Stack::start() + self.variable_registers).map(MemoryAddress::from)
+    }
+
+    pub(crate) fn spill_area_pointer(&self) -> Option<MemoryAddress> {
+        self.spill_area_pointer
+    }
+
+    pub(crate) fn spill_slots(&self) -> usize {
+        self.spill_slots
+    }
+}
+
+/// The range of program points over which a variable is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LiveInterval {
+    value_id: ValueId,
+    start: usize,
+    end: usize,
+    /// The register this variable must be assigned, relative to the start of the stack.
+    fixed_register: Option<usize>,
+    /// The register which saves moves if assigned to this variable, relative to the start of the stack.
+    preferred_register: Option<usize>,
+}
+
+#[derive(Default)]
+struct IntervalBuilder {
+    ranges: HashMap<ValueId, (usize, usize)>,
+}
+
+impl IntervalBuilder {
+    /// Extends the interval of the variable to cover the given point.
+    fn extend(&mut self, value_id: ValueId, point: usize) {
+        let (start, end) = self.ranges.entry(value_id).or_insert((point, point));
+        *start = (*start).min(point);
+        *end = (*end).max(point);
+    }
+}
+
+/// Computes the live interval of every variable of the function.
+///
+/// Every block is given a point for its start, one for each instruction and one for its terminator. A variable is
+/// available from the point where it's defined, or the start of the block if it's live in, until the point of its
+/// last use, or the terminator if it's still available at the end of the block.
+/// This mirrors the variables the generated code keeps allocated, so any two variables available at the same time
+/// during the codegen have overlapping intervals.
+fn compute_live_intervals(
+    func: &Function,
+    blocks: &[BasicBlockId],
+    liveness: &VariableLiveness,
+    constants: &ConstantAllocation,
+) -> Vec<LiveInterval> {
+    let dfg = &func.dfg;
+    let mut builder = IntervalBuilder::default();
+    let mut point = 0;
+
+    for &block_id in blocks {
+        let block = &dfg[block_id];
+        let last_uses = liveness.get_last_uses(&block_id);
+
+        let mut available: HashSet<ValueId> = liveness.get_live_in(&block_id).clone();
+        available.extend(
+            liveness.defined_block_params(&block_id).into_iter().map(|param| dfg.resolve(param)),
+        );
+        for value_id in &available {
+            builder.extend(*value_id, point);
+        }
+        point += 1;
+
+        // Constants are defined at their allocation point, along with any constant they are built from.
+        let define_constants =
+            |location, point, builder: &mut IntervalBuilder, available: &mut HashSet<ValueId>| {
+                for constant_id in constants.allocated_at_location(block_id, location) {
+                    for value_id in collect_variables_of_value(constant_id, dfg) {
+                        builder.extend(value_id, point);
+                        available.insert(value_id);
+                    }
+                }
+            };
+
+        for &instruction_id in block.instructions() {
+            define_constants(
+                InstructionLocation::Instruction(instruction_id),
+                point,
+                &mut builder,
+                &mut available,
+            );
+
+            for result in dfg.instruction_results(instruction_id) {
+                let result = dfg.resolve(*result);
+                builder.extend(result, point);
+                available.insert(result);
+            }
+
+            let dead_variables = last_uses
+                .get(&instruction_id)
+                .expect("Last uses for instruction should have been computed");
+            for dead_variable in dead_variables {
+                builder.extend(*dead_variable, point);
+                available.remove(dead_variable);
+            }
+            point += 1;
+        }
+
+        define_constants(InstructionLocation::Terminator, point, &mut builder, &mut available);
+        for value_id in &available {
+            builder.extend(*value_id, point);
+        }
+        point += 1;
+    }
+
+    let fixed_registers = fixed_registers(func);
+    let preferred_registers = preferred_registers(func, blocks);
+
+    let mut intervals: Vec<_> = builder
+        .ranges
+        .into_iter()
+        .map(|(value_id, (start, end))| LiveInterval {
+            value_id,
+            start,
+            end,
+            fixed_register: fixed_registers.get(&value_id).copied(),
+            preferred_register: preferred_registers.get(&value_id).copied(),
+        })
+        .collect();
+    intervals.sort_by_key(|interval| interval.value_id);
+    intervals
+}
+
+/// The parameters of a function are passed in the registers at the start of the stack, so they are kept there.
+fn fixed_registers(func: &Function) -> HashMap<ValueId, usize> {
+    func.parameters()
+        .iter()
+        .enumerate()
+        .map(|(index, param)| (func.dfg.resolve(*param), index))
+        .collect()
+}
+
+/// Values passed to or returned from function calls are moved from and to the registers at the start of the stack.
+/// Assigning them these registers when possible avoids the moves.
+fn preferred_registers(func: &Function, blocks: &[BasicBlockId]) -> HashMap<ValueId, usize> {
+    let dfg = &func.dfg;
+    let mut preferred_registers = HashMap::default();
+    let mut prefer = |values: &[ValueId]| {
+        for (index, value_id) in values.iter().enumerate() {
+            preferred_registers.entry(dfg.resolve(*value_id)).or_insert(index);
+        }
+    };
+
+    for &block_id in blocks {
+        let block = &dfg[block_id];
+        for &instruction_id in block.instructions() {
+            if let Instruction::Call { func: callee, arguments } = &dfg[instruction_id] {
+                if let Value::Function(_) = &dfg[*callee] {
+                    prefer(arguments);
+                    prefer(dfg.instruction_results(instruction_id));
+                }
+            }
+        }
+        if let Some(TerminatorInstruction::Return { return_values, .. }) = block.terminator() {
+            prefer(return_values);
+        }
+    }
+    preferred_registers
+}
+
+/// Assigns registers to the intervals in order of their start, freeing the register of each interval once it ends.
+///
+/// When all `max_registers` registers are taken, the interval ending the furthest among the current one and those
+/// holding a register is spilled. Intervals with a fixed register are never spilled.
+/// Returns the register assigned to each variable, the indices of the spilled intervals and the number of registers used.
+fn linear_scan(
+    intervals: &[LiveInterval],
+    max_registers: usize,
+) -> (HashMap<ValueId, usize>, Vec<usize>, usize) {
+    // Intervals with a fixed register go first, so their register is free when they start.
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by_key(|index| {
+        let interval = &intervals[*index];
+        (interval.start, interval.fixed_register.is_none(), interval.end)
+    });
+
+    let mut assigned: Vec<Option<usize>> = vec![None; intervals.len()];
+    let mut spilled = Vec::new();
+    // The intervals currently holding a register, ordered by their end.
+    let mut active: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut free_registers: BTreeSet<usize> = BTreeSet::new();
+    let mut registers_used = 0;
+
+    for index in order {
+        let interval = &intervals[index];
+
+        while let Some(&(end, expired)) = active.first() {
+            if end >= interval.start {
+                break;
+            }
+            active.remove(&(end, expired));
+            free_registers.insert(assigned[expired].expect("Active intervals hold a register"));
+        }
+
+        let register = if let Some(register) = interval.fixed_register {
+            assert!(
+                take_register(&mut free_registers, &mut registers_used, register),
+                "ICE: Fixed register {register} is already in use"
+            );
+            Some(register)
+        } else if let Some(register) = interval.preferred_register.filter(|register| {
+            *register < max_registers
+                && take_register(&mut free_registers, &mut registers_used, *register)
+        }) {
+            Some(register)
+        } else if let Some(register) = free_registers.pop_first() {
+            Some(register)
+        } else if registers_used < max_registers {
+            registers_used += 1;
+            Some(registers_used - 1)
+        } else {
+            let furthest = active
+                .iter()
+                .rev()
+                .find(|(_, active_index)| intervals[*active_index].fixed_register.is_none())
+                .copied();
+            match furthest {
+                Some((end, furthest_index)) if end > interval.end => {
+                    active.remove(&(end, furthest_index));
+                    spilled.push(furthest_index);
+                    assigned[furthest_index].take()
+                }
+                _ => None,
+            }
+        };
+
+        match register {
+            Some(register) => {
+                assigned[index] = Some(register);
+                active.insert((interval.end, index));
+            }
+            None => spilled.push(index),
+        }
+    }
+
+    let registers = assigned
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, register)| Some((intervals[index].value_id, register?)))
+        .collect();
+    (registers, spilled, registers_used)
+}
+
+/// Takes a specific register out of the free ones, growing the used registers if needed.
+/// Returns false if the register is already taken.
+fn take_register(
+    free_registers: &mut BTreeSet<usize>,
+    registers_used: &mut usize,
+    register: usize,
+) -> bool {
+    if register < *registers_used {
+        return free_registers.remove(&register);
+    }
+    free_registers.extend(*registers_used..register);
+    *registers_used = register + 1;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::brillig::MemoryAddress;
+
+    use crate::brillig::brillig_gen::brillig_fn::FunctionContext;
+    use crate::brillig::brillig_ir::registers::{RegisterAllocator, Stack};
+    use crate::ssa::function_builder::FunctionBuilder;
+    use crate::ssa::ir::function::RuntimeType;
+    use crate::ssa::ir::instruction::BinaryOp;
+    use crate::ssa::ir::map::Id;
+    use crate::ssa::ir::types::Type;
+
+    use super::{LiveInterval, RegisterAllocation, VariableLocation};
+
+    fn interval(value: usize, start: usize, end: usize) -> LiveInterval {
+        LiveInterval {
+            value_id: Id::test_new(value),
+            start,
+            end,
+            fixed_register: None,
+            preferred_register: None,
+        }
+    }
+
+    fn register(index: usize) -> Option<VariableLocation> {
+        Some(VariableLocation::Register(MemoryAddress::from(Stack::start() + index)))
+    }
+
+    #[test]
+    fn reuses_registers_of_expired_intervals() {
+        let intervals =
+            [interval(0, 0, 2), interval(1, 1, 3), interval(2, 3, 4), interval(3, 4, 5)];
+        let allocation = RegisterAllocation::from_intervals(&intervals, 8);
+
+        assert_eq!(allocation.location(Id::test_new(0)), register(0));
+        assert_eq!(allocation.location(Id::test_new(1)), register(1));
+        // v2 starts when v1 ends, so it can only take the register of v0
+        assert_eq!(allocation.location(Id::test_new(2)), register(0));
+        assert_eq!(allocation.location(Id::test_new(3)), register(1));
+        assert_eq!(allocation.variable_registers().count(), 2);
+        assert_eq!(allocation.spill_area_pointer(), None);
+    }
+
+    #[test]
+    fn honours_fixed_and_preferred_registers() {
+        let intervals = [
+            LiveInterval { fixed_register: Some(1), ..interval(0, 0, 4) },
+            LiveInterval { fixed_register: Some(0), ..interval(1, 0, 1) },
+            interval(2, 2, 3),
+            LiveInterval { preferred_register: Some(3), ..interval(3, 2, 4) },
+        ];
+        let allocation = RegisterAllocation::from_intervals(&intervals, 8);
+
+        assert_eq!(allocation.location(Id::test_new(0)), register(1));
+        assert_eq!(allocation.location(Id::test_new(1)), register(0));
+        assert_eq!(allocation.location(Id::test_new(2)), register(0));
+        assert_eq!(allocation.location(Id::test_new(3)), register(3));
+        assert_eq!(allocation.variable_registers().count(), 4);
+    }
+
+    #[test]
+    fn spills_the_intervals_ending_the_furthest() {
+        let intervals = [
+            interval(0, 0, 10),
+            interval(1, 1, 3),
+            interval(2, 2, 4),
+            interval(3, 5, 6),
+            interval(4, 7, 8),
+        ];
+        let allocation = RegisterAllocation::from_intervals(&intervals, 2);
+
+        // v0 ends after v2, so it's spilled to make room for it
+        assert_eq!(allocation.location(Id::test_new(0)), Some(VariableLocation::Spilled(0)));
+        assert_eq!(allocation.location(Id::test_new(1)), register(1));
+        assert_eq!(allocation.location(Id::test_new(2)), register(0));
+        assert_eq!(allocation.location(Id::test_new(3)), register(0));
+        assert_eq!(allocation.location(Id::test_new(4)), register(0));
+        assert_eq!(allocation.spill_slots(), 1);
+        // The spill area pointer is held in the register following those of the variables
+        assert_eq!(allocation.spill_area_pointer(), Some(MemoryAddress::from(Stack::start() + 2)));
+        assert_eq!(allocation.variable_registers().count(), 3);
+    }
+
+    #[test]
+    fn reuses_spill_slots_of_expired_intervals() {
+        let intervals = [
+            interval(0, 0, 3),
+            interval(1, 0, 9),
+            interval(2, 1, 2),
+            interval(3, 4, 9),
+            interval(4, 5, 6),
+        ];
+        let allocation = RegisterAllocation::from_intervals(&intervals, 1);
+
+        assert_eq!(allocation.location(Id::test_new(2)), register(0));
+        assert_eq!(allocation.location(Id::test_new(4)), register(0));
+        assert_eq!(allocation.location(Id::test_new(0)), Some(VariableLocation::Spilled(0)));
+        assert_eq!(allocation.location(Id::test_new(1)), Some(VariableLocation::Spilled(1)));
+        assert_eq!(allocation.location(Id::test_new(3)), Some(VariableLocation::Spilled(0)));
+        assert_eq!(allocation.spill_slots(), 2);
+    }
+
+    #[test]
+    fn keeps_parameters_in_place_and_reuses_registers_across_blocks() {
+        // brillig fn main f0 {
+        //     b0(v0: Field, v1: Field):
+        //       v2 = add v0, v1
+        //       jmp b1()
+        //     b1():
+        //       v3 = mul v2, v2
+        //       return v3
+        //   }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+
+        let b1 = builder.insert_block();
+
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.insert_binary(v0, BinaryOp::Add, v1);
+        builder.terminate_with_jmp(b1, vec![]);
+
+        builder.switch_to_block(b1);
+        let v3 = builder.insert_binary(v2, BinaryOp::Mul, v2);
+        builder.terminate_with_return(vec![v3]);
+
+        let ssa = builder.finish();
+        let function_context = FunctionContext::new(ssa.main());
+        let allocation = &function_context.register_allocation;
+
+        // Parameters stay where the caller passes them
+        assert_eq!(allocation.location(v0), register(0));
+        assert_eq!(allocation.location(v1), register(1));
+        // v2 is defined while both parameters are still in use
+        assert_eq!(allocation.location(v2), register(2));
+        // The returned value takes the register it's returned in, which is free by then
+        assert_eq!(allocation.location(v3), register(0));
+        assert_eq!(allocation.spill_area_pointer(), None);
+    }
+}
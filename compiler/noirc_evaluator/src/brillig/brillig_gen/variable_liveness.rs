@@ -68,7 +68,10 @@ pub(crate) fn collect_variables_of_value(value_id: ValueId, dfg: &DataFlowGraph)
             vec![value_id]
         }
         // Functions are not variables in a defunctionalized SSA. Only constant function values should appear.
-        Value::ForeignFunction(_) | Value::Function(_) | Value::Intrinsic(..) => {
+        Value::ForeignFunction(_)
+        | Value::Function(_)
+        | Value::Intrinsic(..)
+        | Value::UserBlackBox(_) => {
             vec![]
         }
     }
@@ -23,6 +23,7 @@ mod codegen_memory;
 mod codegen_stack;
 mod entry_point;
 mod instructions;
+mod peephole;
 
 use artifact::Label;
 pub(crate) use instructions::BrilligBinaryOp;
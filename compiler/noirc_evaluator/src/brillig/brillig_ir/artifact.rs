@@ -1,7 +1,7 @@
 use acvm::acir::brillig::Opcode as BrilligOpcode;
 use std::collections::{BTreeMap, HashMap};
 
-use crate::brillig::brillig_ir::procedures::ProcedureId;
+use crate::brillig::brillig_ir::{peephole, procedures::ProcedureId};
 use crate::ssa::ir::{basic_block::BasicBlockId, dfg::CallStack, function::FunctionId};
 
 /// Represents a parameter or a return value of an entry point function.
@@ -24,6 +24,8 @@ pub(crate) struct GeneratedBrillig<F> {
     pub(crate) locations: BTreeMap<OpcodeLocation, CallStack>,
     pub(crate) assert_messages: BTreeMap<OpcodeLocation, String>,
     pub(crate) name: String,
+    /// Number of opcodes removed from the bytecode by the peephole optimizer.
+    pub(crate) opcodes_optimized_away: usize,
 }
 
 #[derive(Default, Debug, Clone)]
@@ -140,16 +142,19 @@ pub(crate) type JumpInstructionPosition = OpcodeLocation;
 /// to their position in the bytecode.
 pub(crate) type UnresolvedJumpLocation = Label;
 
-impl<F: Clone + std::fmt::Debug> BrilligArtifact<F> {
-    /// Resolves all jumps and generates the final bytecode
+impl<F: Clone + PartialEq + std::fmt::Debug> BrilligArtifact<F> {
+    /// Resolves all jumps and generates the final, optimized bytecode
     pub(crate) fn finish(mut self) -> GeneratedBrillig<F> {
         self.resolve_jumps();
-        GeneratedBrillig {
+        let mut generated = GeneratedBrillig {
             byte_code: self.byte_code,
             locations: self.locations,
             assert_messages: self.assert_messages,
             name: self.name,
-        }
+            opcodes_optimized_away: 0,
+        };
+        peephole::optimize(&mut generated);
+        generated
     }
 
     /// Gets the first unresolved function call of this artifact.
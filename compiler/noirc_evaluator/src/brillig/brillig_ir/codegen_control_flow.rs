@@ -253,7 +253,7 @@ impl<F: AcirField + DebugToString, Registers: RegisterAllocator> BrilligContext<
     }
 
     // Flattens an array by recursively copying nested arrays and regular items.
-    pub(super) fn flatten_array(
+    pub(crate) fn flatten_array(
         &mut self,
         item_type: &[BrilligParameter],
         item_count: usize,
@@ -75,6 +75,42 @@ impl<F: AcirField + DebugToString, Registers: RegisterAllocator> BrilligContext<
         self.deallocate_register(final_index);
     }
 
+    /// Gets the value stored at base_ptr + offset, for an offset known at compile time, and stores it in result
+    pub(crate) fn codegen_load_with_immediate_offset(
+        &mut self,
+        base_ptr: MemoryAddress,
+        offset: usize,
+        result: MemoryAddress,
+    ) {
+        if offset == 0 {
+            self.load_instruction(result, base_ptr);
+            return;
+        }
+        let final_index = self.allocate_register();
+        self.codegen_usize_op(base_ptr, final_index, BrilligBinaryOp::Add, offset);
+        self.load_instruction(result, final_index);
+        // Free up temporary register
+        self.deallocate_register(final_index);
+    }
+
+    /// Stores value at base_ptr + offset, for an offset known at compile time
+    pub(crate) fn codegen_store_with_immediate_offset(
+        &mut self,
+        base_ptr: MemoryAddress,
+        offset: usize,
+        value: MemoryAddress,
+    ) {
+        if offset == 0 {
+            self.store_instruction(base_ptr, value);
+            return;
+        }
+        let final_index = self.allocate_register();
+        self.codegen_usize_op(base_ptr, final_index, BrilligBinaryOp::Add, offset);
+        self.store_instruction(final_index, value);
+        // Free up temporary register
+        self.deallocate_register(final_index);
+    }
+
     /// Copies the values of memory pointed by source with length stored in `num_elements_register`
     /// After the address pointed by destination
     pub(crate) fn codegen_mem_copy(
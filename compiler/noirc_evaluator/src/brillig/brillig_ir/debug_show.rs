@@ -468,6 +468,15 @@ impl DebugShow {
                     output
                 );
             }
+            BlackBoxOp::UserDefined { name, inputs, outputs } => {
+                debug_println!(
+                    self.enable_debug_trace,
+                    "  USER_DEFINED {} ({}) -> {}",
+                    name,
+                    inputs,
+                    outputs
+                );
+            }
         }
     }
 
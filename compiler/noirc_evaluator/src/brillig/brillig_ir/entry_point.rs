@@ -173,7 +173,7 @@ impl<F: AcirField + DebugToString> BrilligContext<F, Stack> {
 
     /// Deflatten an array by recursively allocating nested arrays and copying the plain values.
     /// Returns the pointer to the deflattened items.
-    fn deflatten_array(
+    pub(crate) fn deflatten_array(
         &mut self,
         item_type: &[BrilligParameter],
         item_count: usize,
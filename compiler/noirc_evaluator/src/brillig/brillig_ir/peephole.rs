@@ -0,0 +1,489 @@
+//! A peephole optimizer which runs over the final, linked bytecode of a Brillig entry point.
+//!
+//! Code generation emits a number of opcodes which turn out to be unnecessary once all the
+//! functions and procedures have been linked together. This pass removes them by:
+//! - threading jumps: jumps and calls to an unconditional jump are retargeted to its destination,
+//!   jumps to the next opcode are removed, as are any opcodes which are no longer reachable.
+//! - eliminating dead stores: a `Const`, `Mov` or `Cast` whose destination is overwritten within
+//!   the same block, without being read in between, is removed.
+//! - coalescing moves: a `Const` or `Mov` which writes a value that its destination is already
+//!   known to hold is removed.
+//!
+//! Removing opcodes shifts the position of every opcode after them, so jump destinations as well as
+//! the `locations` and `assert_messages` of the bytecode are remapped onto the new positions.
+use acvm::acir::brillig::{BitSize, MemoryAddress, Opcode as BrilligOpcode};
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
+
+use super::artifact::{GeneratedBrillig, OpcodeLocation};
+
+/// Optimizes the bytecode of `brillig` in place, adding the number of removed opcodes to
+/// `opcodes_optimized_away`.
+pub(crate) fn optimize<F: Clone + PartialEq>(brillig: &mut GeneratedBrillig<F>) {
+    loop {
+        thread_jumps(&mut brillig.byte_code);
+
+        let removed = find_removable_opcodes(brillig);
+        let removed_count = removed.iter().filter(|removed| **removed).count();
+        if removed_count == 0 {
+            break;
+        }
+
+        remove_opcodes(brillig, &removed);
+        brillig.opcodes_optimized_away += removed_count;
+    }
+}
+
+/// Retargets every jump or call whose destination is an unconditional jump to the final
+/// destination of the chain of jumps.
+fn thread_jumps<F>(byte_code: &mut [BrilligOpcode<F>]) {
+    for index in 0..byte_code.len() {
+        let Some(mut target) = jump_target(&byte_code[index]) else {
+            continue;
+        };
+
+        // Bound the number of steps so that a cycle of jumps cannot loop forever.
+        for _ in 0..byte_code.len() {
+            match byte_code.get(target) {
+                Some(BrilligOpcode::Jump { location }) if *location != target => {
+                    target = *location;
+                }
+                _ => break,
+            }
+        }
+
+        set_jump_target(&mut byte_code[index], target);
+    }
+}
+
+/// Returns which opcodes of the bytecode can be removed without changing its behavior.
+fn find_removable_opcodes<F: Clone + PartialEq>(brillig: &GeneratedBrillig<F>) -> Vec<bool> {
+    let byte_code = &brillig.byte_code;
+    let has_message = |index: OpcodeLocation| brillig.assert_messages.contains_key(&index);
+
+    let reachable = reachable_opcodes(byte_code);
+    let mut removed: Vec<bool> = reachable.iter().map(|reachable| !reachable).collect();
+
+    let leaders = block_leaders(byte_code);
+
+    // Direct writes which have not been read yet, mapped to the opcode which performed them.
+    let mut pending_writes: HashMap<MemoryAddress, usize> = HashMap::default();
+    // What is known about the contents of memory at the current point of the block.
+    let mut known_values: HashMap<MemoryAddress, KnownValue<F>> = HashMap::default();
+
+    for (index, opcode) in byte_code.iter().enumerate() {
+        if leaders.contains(&index) {
+            pending_writes.clear();
+            known_values.clear();
+        }
+        if removed[index] {
+            continue;
+        }
+
+        let is_noop_jump =
+            jump_target(opcode) == Some(index + 1) && !matches!(opcode, BrilligOpcode::Call { .. });
+        if !has_message(index) && (is_noop_jump || writes_known_value(opcode, &known_values)) {
+            removed[index] = true;
+            continue;
+        }
+
+        let effects = MemoryEffects::of(opcode);
+        if effects.reads_any {
+            pending_writes.clear();
+        } else {
+            for address in &effects.reads {
+                pending_writes.remove(address);
+            }
+        }
+
+        if effects.writes_any {
+            known_values.clear();
+        }
+
+        if let Some(destination) = effects.write {
+            if let Some(dead_store) = pending_writes.remove(&destination) {
+                removed[dead_store] = true;
+            }
+
+            let new_value = match opcode {
+                BrilligOpcode::Const { value, bit_size, .. } => {
+                    Some(KnownValue::Const(value.clone(), *bit_size))
+                }
+                BrilligOpcode::Mov { source, .. } => match known_values.get(source) {
+                    Some(KnownValue::Const(value, bit_size)) => {
+                        Some(KnownValue::Const(value.clone(), *bit_size))
+                    }
+                    _ => Some(KnownValue::CopyOf(*source)),
+                },
+                _ => None,
+            };
+            known_values.remove(&destination);
+            known_values.retain(|_, known| *known != KnownValue::CopyOf(destination));
+            if let Some(new_value) = new_value {
+                known_values.insert(destination, new_value);
+            }
+
+            let is_pure_write = matches!(
+                opcode,
+                BrilligOpcode::Const { .. }
+                    | BrilligOpcode::Mov { .. }
+                    | BrilligOpcode::Cast { .. }
+            );
+            if is_pure_write && !has_message(index) {
+                pending_writes.insert(destination, index);
+            }
+        }
+
+        if is_control_flow(opcode) {
+            pending_writes.clear();
+            known_values.clear();
+        }
+    }
+
+    removed
+}
+
+/// Removes the opcodes marked in `removed`, remapping every position which refers to the bytecode.
+fn remove_opcodes<F>(brillig: &mut GeneratedBrillig<F>, removed: &[bool]) {
+    // The new position of each opcode. A removed opcode has no effect, so jumping to it
+    // is the same as jumping to the next opcode which is kept.
+    let mut new_positions = Vec::with_capacity(removed.len() + 1);
+    let mut kept = 0;
+    for is_removed in removed {
+        new_positions.push(kept);
+        if !is_removed {
+            kept += 1;
+        }
+    }
+    new_positions.push(kept);
+
+    let byte_code = std::mem::take(&mut brillig.byte_code);
+    brillig.byte_code = byte_code
+        .into_iter()
+        .zip(removed)
+        .filter(|(_, is_removed)| !**is_removed)
+        .map(|(mut opcode, _)| {
+            if let Some(target) = jump_target(&opcode) {
+                set_jump_target(&mut opcode, new_positions.get(target).copied().unwrap_or(target));
+            }
+            opcode
+        })
+        .collect();
+
+    let remap = |position: OpcodeLocation| (!removed[position]).then(|| new_positions[position]);
+    brillig.locations = std::mem::take(&mut brillig.locations)
+        .into_iter()
+        .filter_map(|(position, call_stack)| Some((remap(position)?, call_stack)))
+        .collect();
+    brillig.assert_messages = std::mem::take(&mut brillig.assert_messages)
+        .into_iter()
+        .filter_map(|(position, message)| Some((remap(position)?, message)))
+        .collect();
+}
+
+/// Returns which opcodes can be reached when executing the bytecode from its first opcode.
+fn reachable_opcodes<F>(byte_code: &[BrilligOpcode<F>]) -> Vec<bool> {
+    let mut reachable = vec![false; byte_code.len()];
+    let mut stack = vec![0];
+    while let Some(index) = stack.pop() {
+        if index >= byte_code.len() || reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+
+        let opcode = &byte_code[index];
+        if let Some(target) = jump_target(opcode) {
+            stack.push(target);
+        }
+        if !matches!(
+            opcode,
+            BrilligOpcode::Jump { .. }
+                | BrilligOpcode::Return
+                | BrilligOpcode::Stop { .. }
+                | BrilligOpcode::Trap { .. }
+        ) {
+            stack.push(index + 1);
+        }
+    }
+    reachable
+}
+
+/// Returns the positions at which a block starts: the first opcode, the destinations of
+/// jumps and calls, and the opcodes following any change in control flow.
+fn block_leaders<F>(byte_code: &[BrilligOpcode<F>]) -> HashSet<OpcodeLocation> {
+    let mut leaders = HashSet::default();
+    leaders.insert(0);
+    for (index, opcode) in byte_code.iter().enumerate() {
+        if let Some(target) = jump_target(opcode) {
+            leaders.insert(target);
+        }
+        if is_control_flow(opcode) {
+            leaders.insert(index + 1);
+        }
+    }
+    leaders
+}
+
+/// A value which a memory address is known to hold.
+#[derive(Debug, Clone, PartialEq)]
+enum KnownValue<F> {
+    Const(F, BitSize),
+    CopyOf(MemoryAddress),
+}
+
+/// Returns whether `opcode` writes a value which its destination is already known to hold.
+fn writes_known_value<F: PartialEq>(
+    opcode: &BrilligOpcode<F>,
+    known_values: &HashMap<MemoryAddress, KnownValue<F>>,
+) -> bool {
+    match opcode {
+        BrilligOpcode::Const { destination, value, bit_size } => {
+            matches!(
+                known_values.get(destination),
+                Some(KnownValue::Const(known, known_bit_size))
+                    if known == value && known_bit_size == bit_size
+            )
+        }
+        BrilligOpcode::Mov { destination, source } => {
+            destination == source
+                || known_values.get(destination) == Some(&KnownValue::CopyOf(*source))
+                || known_values.get(source) == Some(&KnownValue::CopyOf(*destination))
+                || matches!(
+                    (known_values.get(destination), known_values.get(source)),
+                    (Some(destination @ KnownValue::Const(..)), Some(source)) if destination == source
+                )
+        }
+        _ => false,
+    }
+}
+
+/// The memory accesses performed by an opcode.
+struct MemoryEffects {
+    /// Addresses which are read directly.
+    reads: Vec<MemoryAddress>,
+    /// The address which is written directly, if any.
+    write: Option<MemoryAddress>,
+    /// Whether the opcode may read from addresses which are not known statically.
+    reads_any: bool,
+    /// Whether the opcode may write to addresses which are not known statically.
+    writes_any: bool,
+}
+
+impl MemoryEffects {
+    fn of<F>(opcode: &BrilligOpcode<F>) -> Self {
+        let direct = |reads: Vec<MemoryAddress>, write: Option<MemoryAddress>| MemoryEffects {
+            reads,
+            write,
+            reads_any: false,
+            writes_any: false,
+        };
+        let unknown =
+            MemoryEffects { reads: Vec::new(), write: None, reads_any: true, writes_any: true };
+
+        match opcode {
+            BrilligOpcode::BinaryFieldOp { destination, lhs, rhs, .. }
+            | BrilligOpcode::BinaryIntOp { destination, lhs, rhs, .. } => {
+                direct(vec![*lhs, *rhs], Some(*destination))
+            }
+            BrilligOpcode::Not { destination, source, .. }
+            | BrilligOpcode::Cast { destination, source, .. }
+            | BrilligOpcode::Mov { destination, source } => {
+                direct(vec![*source], Some(*destination))
+            }
+            BrilligOpcode::ConditionalMov { destination, source_a, source_b, condition } => {
+                direct(vec![*source_a, *source_b, *condition], Some(*destination))
+            }
+            BrilligOpcode::Const { destination, .. } => direct(Vec::new(), Some(*destination)),
+            BrilligOpcode::JumpIfNot { condition, .. }
+            | BrilligOpcode::JumpIf { condition, .. } => direct(vec![*condition], None),
+            BrilligOpcode::Jump { .. } => direct(Vec::new(), None),
+            BrilligOpcode::CalldataCopy { size_address, offset_address, .. } => MemoryEffects {
+                writes_any: true,
+                ..direct(vec![*size_address, *offset_address], None)
+            },
+            BrilligOpcode::IndirectConst { destination_pointer, .. } => {
+                MemoryEffects { writes_any: true, ..direct(vec![*destination_pointer], None) }
+            }
+            BrilligOpcode::Load { destination, .. } => {
+                MemoryEffects { reads_any: true, ..direct(Vec::new(), Some(*destination)) }
+            }
+            BrilligOpcode::Store { destination_pointer, source } => MemoryEffects {
+                writes_any: true,
+                ..direct(vec![*destination_pointer, *source], None)
+            },
+            BrilligOpcode::Return | BrilligOpcode::Stop { .. } | BrilligOpcode::Trap { .. } => {
+                MemoryEffects { writes_any: false, ..unknown }
+            }
+            BrilligOpcode::Call { .. }
+            | BrilligOpcode::ForeignCall { .. }
+            | BrilligOpcode::BlackBox(_) => unknown,
+        }
+    }
+}
+
+fn is_control_flow<F>(opcode: &BrilligOpcode<F>) -> bool {
+    matches!(
+        opcode,
+        BrilligOpcode::Jump { .. }
+            | BrilligOpcode::JumpIf { .. }
+            | BrilligOpcode::JumpIfNot { .. }
+            | BrilligOpcode::Call { .. }
+            | BrilligOpcode::Return
+            | BrilligOpcode::Stop { .. }
+            | BrilligOpcode::Trap { .. }
+    )
+}
+
+fn jump_target<F>(opcode: &BrilligOpcode<F>) -> Option<OpcodeLocation> {
+    match opcode {
+        BrilligOpcode::Jump { location }
+        | BrilligOpcode::JumpIf { location, .. }
+        | BrilligOpcode::JumpIfNot { location, .. }
+        | BrilligOpcode::Call { location } => Some(*location),
+        _ => None,
+    }
+}
+
+fn set_jump_target<F>(opcode: &mut BrilligOpcode<F>, target: OpcodeLocation) {
+    match opcode {
+        BrilligOpcode::Jump { location }
+        | BrilligOpcode::JumpIf { location, .. }
+        | BrilligOpcode::JumpIfNot { location, .. }
+        | BrilligOpcode::Call { location } => *location = target,
+        _ => unreachable!("ICE: expected a jump or call opcode"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::acir::brillig::{
+        BinaryFieldOp, BitSize, HeapArray, MemoryAddress, Opcode as BrilligOpcode,
+    };
+    use acvm::FieldElement;
+    use noirc_errors::Location;
+
+    use crate::brillig::brillig_ir::artifact::GeneratedBrillig;
+    use crate::ssa::ir::dfg::CallStack;
+
+    use super::optimize;
+
+    fn constant(destination: usize, value: u128) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Const {
+            destination: MemoryAddress(destination),
+            bit_size: BitSize::Field,
+            value: FieldElement::from(value),
+        }
+    }
+
+    fn mov(destination: usize, source: usize) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Mov {
+            destination: MemoryAddress(destination),
+            source: MemoryAddress(source),
+        }
+    }
+
+    fn add(destination: usize, lhs: usize, rhs: usize) -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::BinaryFieldOp {
+            destination: MemoryAddress(destination),
+            op: BinaryFieldOp::Add,
+            lhs: MemoryAddress(lhs),
+            rhs: MemoryAddress(rhs),
+        }
+    }
+
+    fn stop() -> BrilligOpcode<FieldElement> {
+        BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 1 }
+    }
+
+    fn optimized(byte_code: Vec<BrilligOpcode<FieldElement>>) -> GeneratedBrillig<FieldElement> {
+        let mut brillig = GeneratedBrillig { byte_code, ..Default::default() };
+        optimize(&mut brillig);
+        brillig
+    }
+
+    #[test]
+    fn threads_jumps_and_removes_unreachable_opcodes() {
+        let brillig = optimized(vec![
+            BrilligOpcode::JumpIf { condition: MemoryAddress(0), location: 3 },
+            BrilligOpcode::Jump { location: 4 },
+            constant(1, 1),
+            BrilligOpcode::Jump { location: 5 },
+            BrilligOpcode::Trap { revert_data: HeapArray::default() },
+            stop(),
+        ]);
+
+        assert_eq!(
+            brillig.byte_code,
+            vec![
+                BrilligOpcode::JumpIf { condition: MemoryAddress(0), location: 3 },
+                BrilligOpcode::Jump { location: 2 },
+                BrilligOpcode::Trap { revert_data: HeapArray::default() },
+                stop(),
+            ]
+        );
+        assert_eq!(brillig.opcodes_optimized_away, 2);
+    }
+
+    #[test]
+    fn removes_dead_stores_and_known_values() {
+        let brillig = optimized(vec![
+            // Overwritten before being read.
+            constant(0, 1),
+            constant(0, 2),
+            constant(1, 3),
+            mov(2, 1),
+            // Both of these write a value which the destination already holds.
+            mov(1, 2),
+            constant(2, 3),
+            add(0, 0, 2),
+            stop(),
+        ]);
+
+        assert_eq!(
+            brillig.byte_code,
+            vec![constant(0, 2), constant(1, 3), mov(2, 1), add(0, 0, 2), stop()]
+        );
+        assert_eq!(brillig.opcodes_optimized_away, 3);
+    }
+
+    #[test]
+    fn does_not_remove_stores_read_by_another_block() {
+        let byte_code = vec![
+            constant(0, 1),
+            BrilligOpcode::JumpIf { condition: MemoryAddress(1), location: 3 },
+            constant(0, 2),
+            add(2, 0, 0),
+            stop(),
+        ];
+        let brillig = optimized(byte_code.clone());
+        assert_eq!(brillig.byte_code, byte_code);
+        assert_eq!(brillig.opcodes_optimized_away, 0);
+    }
+
+    #[test]
+    fn remaps_locations_and_assert_messages() {
+        let call_stack = CallStack::unit(Location::dummy());
+        let mut brillig = GeneratedBrillig {
+            byte_code: vec![
+                constant(0, 1),
+                constant(0, 1),
+                BrilligOpcode::JumpIf { condition: MemoryAddress(0), location: 4 },
+                BrilligOpcode::Trap { revert_data: HeapArray::default() },
+                stop(),
+            ],
+            locations: BTreeMap::from([(1, call_stack.clone()), (3, call_stack.clone())]),
+            assert_messages: BTreeMap::from([(3, "assertion failed".to_string())]),
+            ..Default::default()
+        };
+        optimize(&mut brillig);
+
+        assert_eq!(brillig.byte_code.len(), 4);
+        assert_eq!(
+            brillig.byte_code[1],
+            BrilligOpcode::JumpIf { condition: MemoryAddress(0), location: 3 }
+        );
+        assert_eq!(brillig.locations, BTreeMap::from([(2, call_stack)]));
+        assert_eq!(brillig.assert_messages, BTreeMap::from([(2, "assertion failed".to_string())]));
+    }
+}
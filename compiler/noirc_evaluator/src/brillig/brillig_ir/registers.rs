@@ -1,4 +1,5 @@
 use acvm::acir::brillig::{HeapArray, HeapVector, MemoryAddress};
+use fxhash::FxHashSet as HashSet;
 
 use crate::brillig::brillig_ir::entry_point::MAX_STACK_SIZE;
 
@@ -187,6 +188,7 @@ impl DeallocationListAllocator {
                 }
             },
         );
+        let preallocated_registers: HashSet<_> = preallocated_registers.into_iter().collect();
         let mut deallocated_registers = Vec::new();
         for i in start..next_free_register_index {
             if !preallocated_registers.contains(&MemoryAddress::from(i)) {
@@ -5,7 +5,7 @@ use acvm::FieldElement;
 use brillig_ir::artifact::LabelType;
 
 use self::{
-    brillig_gen::convert_ssa_function,
+    brillig_gen::{convert_ssa_function, MAX_VARIABLE_REGISTERS},
     brillig_ir::{
         artifact::{BrilligArtifact, Label},
         procedures::compile_procedure,
@@ -28,8 +28,13 @@ pub struct Brillig {
 
 impl Brillig {
     /// Compiles a function into brillig and store the compilation artifacts
-    pub(crate) fn compile(&mut self, func: &Function, enable_debug_trace: bool) {
-        let obj = convert_ssa_function(func, enable_debug_trace);
+    pub(crate) fn compile(
+        &mut self,
+        func: &Function,
+        enable_debug_trace: bool,
+        max_registers: usize,
+    ) {
+        let obj = convert_ssa_function(func, enable_debug_trace, max_registers);
         self.ssa_function_to_brillig.insert(func.id(), obj);
     }
 
@@ -58,8 +63,18 @@ impl std::ops::Index<FunctionId> for Brillig {
 
 impl Ssa {
     /// Compile to brillig brillig functions and ACIR functions reachable from them
-    #[tracing::instrument(level = "trace", skip_all)]
     pub(crate) fn to_brillig(&self, enable_debug_trace: bool) -> Brillig {
+        self.to_brillig_with_max_registers(enable_debug_trace, MAX_VARIABLE_REGISTERS)
+    }
+
+    /// Compile to brillig, assigning at most `max_registers` registers to the variables of each function
+    /// before spilling them to memory.
+    #[tracing::instrument(level = "trace", skip_all)]
+    pub(crate) fn to_brillig_with_max_registers(
+        &self,
+        enable_debug_trace: bool,
+        max_registers: usize,
+    ) -> Brillig {
         // Collect all the function ids that are reachable from brillig
         // That means all the functions marked as brillig and ACIR functions called by them
         let brillig_reachable_function_ids = self
@@ -71,9 +86,260 @@ impl Ssa {
         let mut brillig = Brillig::default();
         for brillig_function_id in brillig_reachable_function_ids {
             let func = &self.functions[&brillig_function_id];
-            brillig.compile(func, enable_debug_trace);
+            brillig.compile(func, enable_debug_trace, max_registers);
         }
 
         brillig
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use acvm::{
+        blackbox_solver::{StubbedBlackBoxSolver, UserBlackBoxRegistry, UserBlackBoxSolver},
+        brillig_vm::{VMStatus, VM},
+        AcirField, BlackBoxFunctionSolver, FieldElement,
+    };
+    use iter_extended::vecmap;
+
+    use super::brillig_gen::{brillig_fn::FunctionContext, MAX_VARIABLE_REGISTERS};
+    use super::brillig_ir::BrilligContext;
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{function::RuntimeType, instruction::BinaryOp, map::Id, types::Type},
+        ssa_gen::Ssa,
+    };
+
+    /// Compiles `ssa` to Brillig and executes its `main` function with the given (flattened) calldata,
+    /// returning the (flattened) values it returns.
+    pub(crate) fn execute_brillig_main<B: BlackBoxFunctionSolver<FieldElement>>(
+        ssa: &Ssa,
+        calldata: Vec<FieldElement>,
+        solver: &B,
+    ) -> Vec<FieldElement> {
+        execute_brillig_main_with_max_registers(ssa, calldata, solver, MAX_VARIABLE_REGISTERS)
+    }
+
+    /// Like [`execute_brillig_main`], but assigns at most `max_registers` registers to the variables
+    /// of each function so that the others are spilled to memory.
+    fn execute_brillig_main_with_max_registers<B: BlackBoxFunctionSolver<FieldElement>>(
+        ssa: &Ssa,
+        calldata: Vec<FieldElement>,
+        solver: &B,
+        max_registers: usize,
+    ) -> Vec<FieldElement> {
+        let brillig = ssa.to_brillig_with_max_registers(false, max_registers);
+
+        let main = ssa.main();
+        let arguments = vecmap(main.parameters(), |param| {
+            FunctionContext::ssa_type_to_parameter(&main.dfg.type_of_value(*param))
+        });
+        let mut entry_point = BrilligContext::new_entry_point_artifact(
+            arguments,
+            FunctionContext::return_values(main),
+            main.id(),
+        );
+        while let Some(unresolved_fn_label) = entry_point.first_unresolved_function_call() {
+            let artifact = brillig.find_by_label(unresolved_fn_label).expect("Missing function");
+            entry_point.link_with(&artifact);
+        }
+        let bytecode = entry_point.finish().byte_code;
+
+        let mut vm = VM::new(calldata, &bytecode, vec![], solver);
+        match vm.process_opcodes() {
+            VMStatus::Finished { return_data_offset, return_data_size } => vm.get_memory()
+                [return_data_offset..(return_data_offset + return_data_size)]
+                .iter()
+                .map(|value| value.to_field())
+                .collect(),
+            status => panic!("Brillig execution did not finish: {status:?}"),
+        }
+    }
+
+    #[test]
+    fn flattens_nested_arrays_passed_to_user_black_box_functions() {
+        // unconstrained fn main(x: [[Field; 2]; 2]) -> [[Field; 1]; 2] {
+        //     sum_and_product(x)
+        // }
+        let field_pair = Type::Array(Arc::new(vec![Type::field()]), 2);
+        let single_field = Type::Array(Arc::new(vec![Type::field()]), 1);
+
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        builder.set_runtime(RuntimeType::Brillig);
+        let x = builder.add_parameter(Type::Array(Arc::new(vec![field_pair]), 2));
+        let sum_and_product = builder.import_user_black_box("sum_and_product");
+        let results = builder
+            .insert_call(
+                sum_and_product,
+                vec![x],
+                vec![Type::Array(Arc::new(vec![single_field]), 2)],
+            )
+            .to_vec();
+        builder.terminate_with_return(results);
+        let ssa = builder.finish();
+
+        let mut registry = UserBlackBoxRegistry::new();
+        registry.register("sum_and_product", 4, 2, |inputs: &[FieldElement]| {
+            let sum = inputs.iter().fold(FieldElement::zero(), |sum, input| sum + *input);
+            let product =
+                inputs.iter().fold(FieldElement::one(), |product, input| product * *input);
+            Ok(vec![sum, product])
+        });
+        let solver = UserBlackBoxSolver::new(StubbedBlackBoxSolver, registry);
+
+        let calldata = vecmap([1_u128, 2, 3, 4], FieldElement::from);
+        let returns = execute_brillig_main(&ssa, calldata, &solver);
+        assert_eq!(returns, vecmap([10_u128, 24], FieldElement::from));
+    }
+
+    /// Executes `ssa` with the default register limit and with at most `max_registers` registers,
+    /// checking that `main` does spill variables under the limit and that both builds return the same values.
+    fn assert_spilling_preserves_results(ssa: &Ssa, calldata: &[u128], max_registers: usize) {
+        let spilled_main = FunctionContext::with_max_registers(ssa.main(), max_registers);
+        assert!(spilled_main.register_allocation.spill_slots() > 0, "Expected main to spill");
+
+        let calldata = vecmap(calldata, |value| FieldElement::from(*value));
+        let expected = execute_brillig_main(ssa, calldata.clone(), &StubbedBlackBoxSolver);
+        let returns = execute_brillig_main_with_max_registers(
+            ssa,
+            calldata,
+            &StubbedBlackBoxSolver,
+            max_registers,
+        );
+        assert_eq!(returns, expected);
+    }
+
+    #[test]
+    fn spilling_preserves_loop_block_parameters() {
+        // brillig fn main f0 {
+        //   b0(v0: u32):
+        //     jmp b1(u32 0, Field 0, Field 1, Field 2)
+        //   b1(v1: u32, v2: Field, v3: Field, v4: Field):
+        //     v5 = lt v1, v0
+        //     jmpif v5 then: b2, else: b3
+        //   b2():
+        //     v6 = add v2, v3
+        //     v7 = mul v3, v4
+        //     v8 = add v4, Field 1
+        //     v9 = add v1, u32 1
+        //     jmp b1(v9, v6, v7, v8)
+        //   b3():
+        //     v10 = add v2, v3
+        //     v11 = add v10, v4
+        //     return v11
+        // }
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        builder.set_runtime(RuntimeType::Brillig);
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let zero = builder.numeric_constant(0_u128, Type::unsigned(32));
+        let initial_values = vec![
+            zero,
+            builder.field_constant(0_u128),
+            builder.field_constant(1_u128),
+            builder.field_constant(2_u128),
+        ];
+        builder.terminate_with_jmp(b1, initial_values);
+
+        builder.switch_to_block(b1);
+        let v1 = builder.add_block_parameter(b1, Type::unsigned(32));
+        let v2 = builder.add_block_parameter(b1, Type::field());
+        let v3 = builder.add_block_parameter(b1, Type::field());
+        let v4 = builder.add_block_parameter(b1, Type::field());
+        let v5 = builder.insert_binary(v1, BinaryOp::Lt, v0);
+        builder.terminate_with_jmpif(v5, b2, b3);
+
+        builder.switch_to_block(b2);
+        let v6 = builder.insert_binary(v2, BinaryOp::Add, v3);
+        let v7 = builder.insert_binary(v3, BinaryOp::Mul, v4);
+        let one = builder.field_constant(1_u128);
+        let v8 = builder.insert_binary(v4, BinaryOp::Add, one);
+        let one = builder.numeric_constant(1_u128, Type::unsigned(32));
+        let v9 = builder.insert_binary(v1, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v9, v6, v7, v8]);
+
+        builder.switch_to_block(b3);
+        let v10 = builder.insert_binary(v2, BinaryOp::Add, v3);
+        let v11 = builder.insert_binary(v10, BinaryOp::Add, v4);
+        builder.terminate_with_return(vec![v11]);
+
+        assert_spilling_preserves_results(&builder.finish(), &[5], 1);
+    }
+
+    #[test]
+    fn spilling_preserves_values_live_across_calls() {
+        // brillig fn main f0 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = add v0, v1
+        //     v3 = mul v0, v1
+        //     v4 = call f1(v2, v3)
+        //     v5 = add v4, v2
+        //     v6 = add v5, v3
+        //     return v6
+        // }
+        // brillig fn foo f1 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = mul v0, v1
+        //     v3 = add v2, v0
+        //     return v3
+        // }
+        let foo_id = Id::test_new(1);
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        builder.set_runtime(RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.insert_binary(v0, BinaryOp::Add, v1);
+        let v3 = builder.insert_binary(v0, BinaryOp::Mul, v1);
+        let foo = builder.import_function(foo_id);
+        let v4 = builder.insert_call(foo, vec![v2, v3], vec![Type::field()])[0];
+        let v5 = builder.insert_binary(v4, BinaryOp::Add, v2);
+        let v6 = builder.insert_binary(v5, BinaryOp::Add, v3);
+        builder.terminate_with_return(vec![v6]);
+
+        builder.new_brillig_function("foo".to_string(), foo_id);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.insert_binary(v0, BinaryOp::Mul, v1);
+        let v3 = builder.insert_binary(v2, BinaryOp::Add, v0);
+        builder.terminate_with_return(vec![v3]);
+
+        assert_spilling_preserves_results(&builder.finish(), &[3, 4], 2);
+    }
+
+    #[test]
+    fn spilling_preserves_arrays() {
+        // brillig fn main f0 {
+        //   b0(v0: Field, v1: Field):
+        //     v2 = make_array [v0, v1, v0] : [Field; 3]
+        //     v3 = array_set v2, index u32 1, value v0
+        //     v4 = array_get v3, index u32 1
+        //     v5 = array_get v2, index u32 1
+        //     v6 = add v4, v5
+        //     v7 = array_get v3, index u32 2
+        //     v8 = mul v6, v7
+        //     return v8
+        // }
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 3);
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        builder.set_runtime(RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.array_constant(im::vector![v0, v1, v0], array_type);
+        let one = builder.numeric_constant(1_u128, Type::length_type());
+        let two = builder.numeric_constant(2_u128, Type::length_type());
+        let v3 = builder.insert_array_set(v2, one, v0);
+        let v4 = builder.insert_array_get(v3, one, Type::field());
+        let v5 = builder.insert_array_get(v2, one, Type::field());
+        let v6 = builder.insert_binary(v4, BinaryOp::Add, v5);
+        let v7 = builder.insert_array_get(v3, two, Type::field());
+        let v8 = builder.insert_binary(v6, BinaryOp::Mul, v7);
+        builder.terminate_with_return(vec![v8]);
+
+        assert_spilling_preserves_results(&builder.finish(), &[3, 4], 2);
+    }
+}
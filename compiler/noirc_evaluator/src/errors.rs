@@ -42,7 +42,11 @@ pub enum RuntimeError {
         call_stack: CallStack,
     },
     #[error("Could not determine loop bound at compile-time")]
-    UnknownLoopBound { call_stack: CallStack },
+    UnknownLoopBound {
+        call_stack: CallStack,
+        /// The location from which the non-constant value preventing the loop from being unrolled originates.
+        value_call_stack: CallStack,
+    },
     #[error("Argument is not constant")]
     AssertConstantFailed { call_stack: CallStack },
     #[error("The static_assert message is not constant")]
@@ -53,6 +57,8 @@ pub enum RuntimeError {
     StaticAssertFailed { call_stack: CallStack },
     #[error("Nested slices, i.e. slices within an array or slice, are not supported")]
     NestedSlice { call_stack: CallStack },
+    #[error("Function `{name}` is not available on the {field} field")]
+    UnavailableBlackBoxFunction { name: &'static str, field: &'static str, call_stack: CallStack },
     #[error("Big Integer modulus do no match")]
     BigIntModulus { call_stack: CallStack },
     #[error("Slices cannot be returned from an unconstrained runtime to a constrained runtime")]
@@ -61,6 +67,8 @@ pub enum RuntimeError {
     UnconstrainedOracleReturnToConstrained { call_stack: CallStack },
     #[error("Could not resolve some references to the array. All references must be resolved at compile time")]
     UnknownReference { call_stack: CallStack },
+    #[error("User-defined blackbox function `{name}` can only be called, not used as a value")]
+    UserBlackBoxAsValue { name: String, call_stack: CallStack },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,16 +99,47 @@ impl From<SsaReport> for FileDiagnostic {
             }
             SsaReport::Bug(bug) => {
                 let message = bug.to_string();
+                let mut related_location = None;
+                let mut suggestion = None;
                 let (secondary_message, call_stack) = match bug {
                     InternalBug::IndependentSubgraph { call_stack } => {
                         ("There is no path from the output of this brillig call to either return values or inputs of the circuit, which creates an independent subgraph. This is quite likely a soundness vulnerability".to_string(),call_stack)
                     }
-                    InternalBug::AssertFailed { call_stack } => ("As a result, the compiled circuit is ensured to fail. Other assertions may also fail during execution".to_string(), call_stack)
+                    InternalBug::AssertFailed { call_stack } => ("As a result, the compiled circuit is ensured to fail. Other assertions may also fail during execution".to_string(), call_stack),
+                    InternalBug::UnconstrainedBrilligResult { call_stack, function_name, arguments } => {
+                        let call = format!("{function_name}({})", arguments.join(", "));
+                        let check_arguments = std::iter::once("result".to_string()).chain(arguments).collect::<Vec<_>>().join(", ");
+                        suggestion = Some(format!("Consider adding an `assert` which checks the returned value against the arguments of this call, e.g. `let result = {call}; assert(is_valid_result({check_arguments}));`"));
+                        ("No constraint relates the value returned by this brillig call to the inputs of the circuit, so a malicious prover may return any value".to_string(), call_stack)
+                    }
+                    InternalBug::OnlyRangeCheckedBrilligResult { call_stack, range_check_call_stack } => {
+                        related_location = Some(("The value is only range checked here".to_string(), range_check_call_stack));
+                        suggestion = Some("A range check only bounds the value, consider also asserting that it is the expected result of this call".to_string());
+                        ("The value returned by this brillig call is only range checked, so a malicious prover may return any value within that range".to_string(), call_stack)
+                    }
+                    InternalBug::WeaklyConstrainedBrilligResult { call_stack, constraint_call_stack } => {
+                        related_location = Some(("This constraint has more than one solution".to_string(), constraint_call_stack));
+                        suggestion = Some("Consider range checking the returned values, e.g. `remainder.assert_max_bit_size::<32>()`, so that they are uniquely determined".to_string());
+                        ("The values returned by this brillig call are not range checked and are constrained by fewer equations than there are returned values".to_string(), call_stack)
+                    }
                 };
                 let call_stack = vecmap(call_stack, |location| location);
                 let file_id = call_stack.last().map(|location| location.file).unwrap_or_default();
                 let location = call_stack.last().expect("Expected RuntimeError to have a location");
-                let diagnostic = Diagnostic::simple_bug(message, secondary_message, location.span);
+                let mut diagnostic =
+                    Diagnostic::simple_bug(message, secondary_message, location.span);
+                if let Some((message, related_call_stack)) = related_location {
+                    if let Some(related_location) = related_call_stack.last() {
+                        diagnostic.add_secondary_with_file(
+                            message,
+                            related_location.span,
+                            related_location.file,
+                        );
+                    }
+                }
+                if let Some(suggestion) = suggestion {
+                    diagnostic.add_note(suggestion);
+                }
                 diagnostic.with_call_stack(call_stack).in_file(file_id)
             }
         }
@@ -121,6 +160,16 @@ pub enum InternalBug {
     IndependentSubgraph { call_stack: CallStack },
     #[error("Assertion is always false")]
     AssertFailed { call_stack: CallStack },
+    #[error("Value returned from an unconstrained function is never constrained")]
+    UnconstrainedBrilligResult {
+        call_stack: CallStack,
+        function_name: String,
+        arguments: Vec<String>,
+    },
+    #[error("Value returned from an unconstrained function is only range checked")]
+    OnlyRangeCheckedBrilligResult { call_stack: CallStack, range_check_call_stack: CallStack },
+    #[error("Values returned from an unconstrained function are not uniquely determined by their constraints")]
+    WeaklyConstrainedBrilligResult { call_stack: CallStack, constraint_call_stack: CallStack },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Error)]
@@ -156,7 +205,7 @@ impl RuntimeError {
             | RuntimeError::InvalidRangeConstraint { call_stack, .. }
             | RuntimeError::TypeConversion { call_stack, .. }
             | RuntimeError::UnInitialized { call_stack, .. }
-            | RuntimeError::UnknownLoopBound { call_stack }
+            | RuntimeError::UnknownLoopBound { call_stack, .. }
             | RuntimeError::AssertConstantFailed { call_stack }
             | RuntimeError::StaticAssertDynamicMessage { call_stack }
             | RuntimeError::StaticAssertDynamicPredicate { call_stack }
@@ -164,11 +213,13 @@ impl RuntimeError {
             | RuntimeError::IntegerOutOfBounds { call_stack, .. }
             | RuntimeError::UnsupportedIntegerSize { call_stack, .. }
             | RuntimeError::InvalidBlackBoxInputBitSize { call_stack, .. }
+            | RuntimeError::UnavailableBlackBoxFunction { call_stack, .. }
             | RuntimeError::NestedSlice { call_stack, .. }
             | RuntimeError::BigIntModulus { call_stack, .. }
             | RuntimeError::UnconstrainedSliceReturnToConstrained { call_stack }
             | RuntimeError::UnconstrainedOracleReturnToConstrained { call_stack }
-            | RuntimeError::UnknownReference { call_stack } => call_stack,
+            | RuntimeError::UnknownReference { call_stack }
+            | RuntimeError::UserBlackBoxAsValue { call_stack, .. } => call_stack,
         }
     }
 }
@@ -193,16 +244,27 @@ impl RuntimeError {
                     noirc_errors::Span::inclusive(0, 0)
                 )
             }
-            RuntimeError::UnknownLoopBound { .. } => {
+            RuntimeError::UnknownLoopBound { ref value_call_stack, .. } => {
                 let primary_message = self.to_string();
                 let location =
                     self.call_stack().back().expect("Expected RuntimeError to have a location");
 
-                Diagnostic::simple_error(
+                let mut diagnostic = Diagnostic::simple_error(
                     primary_message,
                     "If attempting to fetch the length of a slice, try converting to an array. Slices only use dynamic lengths.".to_string(),
                     location.span,
-                )
+                );
+
+                if let Some(value_location) = value_call_stack.back() {
+                    if value_location != location {
+                        diagnostic.add_secondary_with_file(
+                            "The loop bound depends on this value, which is not known at compile-time".to_string(),
+                            value_location.span,
+                            value_location.file,
+                        );
+                    }
+                }
+                diagnostic
             }
             _ => {
                 let message = self.to_string();
@@ -30,6 +30,7 @@ use noirc_errors::debug_info::{DebugFunctions, DebugInfo, DebugTypes, DebugVaria
 
 use noirc_frontend::ast::Visibility;
 use noirc_frontend::{
+    hir::def_collector::dc_mod::CHOSEN_FIELD,
     hir_def::{function::FunctionSignature, types::Type as HirType},
     monomorphization::ast::Program,
 };
@@ -95,6 +96,7 @@ pub(crate) fn optimize_into_acir(
     .run_pass(Ssa::separate_runtime, "After Runtime Separation:")
     .run_pass(Ssa::resolve_is_unconstrained, "After Resolving IsUnconstrained:")
     .run_pass(Ssa::inline_functions, "After Inlining:")
+    .run_pass(Ssa::remove_unreachable_functions, "After Removing Unreachable Functions:")
     // Run mem2reg with the CFG separated into blocks
     .run_pass(Ssa::mem2reg, "After Mem2Reg:")
     .run_pass(Ssa::as_slice_optimization, "After `as_slice` optimization")
@@ -120,6 +122,11 @@ pub(crate) fn optimize_into_acir(
     .run_pass(Ssa::dead_instruction_elimination, "After Dead Instruction Elimination:")
     .run_pass(Ssa::simplify_cfg, "After Simplifying:")
     .run_pass(Ssa::array_set_optimization, "After Array Set Optimizations:")
+    // Constant folding and flattening may have removed the last calls to some functions.
+    .run_pass(Ssa::remove_unreachable_functions, "After Removing Unreachable Functions:")
+    // This pass must come last as it records information about instructions which would be lost
+    // if they were to be re-inserted by a later pass.
+    .run_pass(Ssa::eliminate_bounds_checks, "After Bounds Check Elimination:")
     .finish();
 
     let ssa_level_warnings = if options.skip_underconstrained_check {
@@ -130,6 +137,9 @@ pub(crate) fn optimize_into_acir(
         })
     };
 
+    ssa.check_for_user_black_box_values()?;
+    ssa.check_for_unavailable_black_box_functions(CHOSEN_FIELD)?;
+
     drop(ssa_gen_span_guard);
 
     let brillig = time("SSA to Brillig", options.print_codegen_timings, || {
@@ -280,6 +290,7 @@ fn convert_generated_acir_into_circuit(
         return_witnesses,
         locations,
         brillig_locations,
+        brillig_opcodes_optimized_away,
         input_witnesses,
         assertion_payloads: assert_messages,
         warnings,
@@ -323,10 +334,15 @@ fn convert_generated_acir_into_circuit(
 
     let mut debug_info =
         DebugInfo::new(locations, brillig_locations, debug_variables, debug_functions, debug_types);
+    debug_info.brillig_opcodes_optimized_away = brillig_opcodes_optimized_away;
 
     // Perform any ACIR-level optimizations
+    let opcodes_before_optimization = circuit.opcodes.len();
     let (optimized_circuit, transformation_map) = acvm::compiler::optimize(circuit);
     debug_info.update_acir(transformation_map);
+    // The optimizer may add opcodes as well as remove them, in which case none are counted as optimized away.
+    debug_info.acir_opcodes_optimized_away =
+        opcodes_before_optimization.saturating_sub(optimized_circuit.opcodes.len());
 
     SsaCircuitArtifact {
         name,
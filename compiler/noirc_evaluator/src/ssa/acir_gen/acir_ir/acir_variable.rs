@@ -1496,6 +1496,21 @@ impl<F: AcirField> AcirContext<F> {
         Ok(results)
     }
 
+    /// Returns the `AcirVar`s holding the results of calling the user-defined blackbox function `name`.
+    ///
+    /// The inputs are flattened into a single list of field elements.
+    pub(crate) fn user_black_box_function(
+        &mut self,
+        name: String,
+        inputs: Vec<AcirValue>,
+        output_count: usize,
+    ) -> Result<Vec<AcirVar>, RuntimeError> {
+        let inputs = self.prepare_inputs_for_black_box_func_call(inputs, false)?;
+        let inputs = inputs.into_iter().flatten().collect();
+        let outputs = self.acir_ir.call_user_black_box(name, inputs, output_count);
+        Ok(vecmap(outputs, |witness_index| self.add_data(AcirVarData::Witness(witness_index))))
+    }
+
     /// Black box function calls expect their inputs to be in a specific data structure (FunctionInput).
     ///
     /// This function will convert `AcirVar` into `FunctionInput` for a blackbox function call.
@@ -55,6 +55,9 @@ pub(crate) struct GeneratedAcir<F: AcirField> {
     /// This map is used to prevent redundant locations being stored for the same Brillig entry point.
     pub(crate) brillig_locations: BTreeMap<BrilligFunctionId, BrilligOpcodeToLocationsMap>,
 
+    /// Brillig function id -> Number of opcodes removed from its bytecode by the peephole optimizer
+    pub(crate) brillig_opcodes_optimized_away: BTreeMap<BrilligFunctionId, usize>,
+
     /// Source code location of the current instruction being processed
     /// None if we do not know the location
     pub(crate) call_stack: CallStack,
@@ -356,6 +359,9 @@ impl<F: AcirField> GeneratedAcir<F> {
                     .expect("Compiler should generate correct size inputs"),
                 outputs: outputs.try_into().expect("Compiler should generate correct size outputs"),
             },
+            BlackBoxFunc::UserDefined => {
+                unreachable!("ICE: user-defined blackbox functions are called by name with `call_user_black_box`")
+            }
         };
 
         self.push_opcode(AcirOpcode::BlackBoxFuncCall(black_box_func_call));
@@ -363,6 +369,26 @@ impl<F: AcirField> GeneratedAcir<F> {
         Ok(outputs_clone)
     }
 
+    /// Calls the user-defined blackbox function `name`, returning `output_count` witnesses
+    /// which hold its results.
+    ///
+    /// The number of inputs and outputs is only checked against the function's registered
+    /// implementation when the circuit is solved.
+    pub(crate) fn call_user_black_box(
+        &mut self,
+        name: String,
+        inputs: Vec<FunctionInput<F>>,
+        output_count: usize,
+    ) -> Vec<Witness> {
+        let outputs = vecmap(0..output_count, |_| self.next_witness_index());
+        self.push_opcode(AcirOpcode::BlackBoxFuncCall(BlackBoxFuncCall::UserDefined {
+            name,
+            inputs,
+            outputs: outputs.clone(),
+        }));
+        outputs
+    }
+
     /// Takes an input expression and returns witnesses that are constrained to be limbs
     /// decomposed from the input for the given radix and limb count.
     ///
@@ -604,6 +630,9 @@ impl<F: AcirField> GeneratedAcir<F> {
             return;
         }
 
+        self.brillig_opcodes_optimized_away
+            .insert(brillig_function_index, generated_brillig.opcodes_optimized_away);
+
         for (brillig_index, call_stack) in generated_brillig.locations.iter() {
             self.brillig_locations
                 .entry(brillig_function_index)
@@ -684,6 +713,9 @@ fn black_box_func_expected_input_size(name: BlackBoxFunc) -> Option<usize> {
 
         // FromLeBytes takes a variable array of bytes as input
         BlackBoxFunc::BigIntFromLeBytes => None,
+
+        // The arity of user-defined functions is only known to their registered implementation
+        BlackBoxFunc::UserDefined => None,
     }
 }
 
@@ -738,6 +770,9 @@ fn black_box_expected_output_size(name: BlackBoxFunc) -> Option<usize> {
 
         // AES encryption returns a variable number of outputs
         BlackBoxFunc::AES128Encrypt => None,
+
+        // The arity of user-defined functions is only known to their registered implementation
+        BlackBoxFunc::UserDefined => None,
     }
 }
 
@@ -902,6 +902,21 @@ impl<'a> Context<'a> {
                         // assert_eq!(result_ids.len(), outputs.len());
                         self.handle_ssa_call_outputs(result_ids, outputs, dfg)?;
                     }
+                    Value::UserBlackBox(name) => {
+                        let inputs = vecmap(arguments, |arg| self.convert_value(*arg, dfg));
+                        // Nested arrays are returned flattened, as they are passed in.
+                        let output_count = result_ids.iter().fold(0usize, |sum, result_id| {
+                            sum + dfg.type_of_value(*result_id).flattened_size()
+                        });
+
+                        let vars = self.acir_context.user_black_box_function(
+                            name.clone(),
+                            inputs,
+                            output_count,
+                        )?;
+                        let outputs = self.convert_vars_to_values(vars, dfg, result_ids);
+                        self.handle_ssa_call_outputs(result_ids, outputs, dfg)?;
+                    }
                     Value::ForeignFunction(_) => {
                         // TODO: Remove this once elaborator is default frontend. This is now caught by a lint inside the frontend.
                         return Err(RuntimeError::UnconstrainedOracleReturnToConstrained {
@@ -1074,6 +1089,7 @@ impl<'a> Context<'a> {
             index,
             store_value,
             offset.unwrap_or_default(),
+            dfg.is_safe_array_access(instruction),
         )?;
 
         if let Some(new_value) = new_value {
@@ -1188,6 +1204,7 @@ impl<'a> Context<'a> {
         index: ValueId,
         store_value: Option<ValueId>,
         offset: usize,
+        is_safe_index: bool,
     ) -> Result<(AcirVar, Option<AcirValue>), RuntimeError> {
         let array_typ = dfg.type_of_value(array_id);
         let block_id = self.ensure_array_is_initialized(array_id, dfg)?;
@@ -1195,7 +1212,7 @@ impl<'a> Context<'a> {
         let index_var = self.convert_numeric_value(index, dfg)?;
         let index_var = self.get_flattened_index(&array_typ, array_id, index_var, dfg)?;
 
-        let predicate_index = if dfg.is_safe_index(index, array_id) {
+        let predicate_index = if is_safe_index {
             index_var
         } else {
             // index*predicate + (1-predicate)*offset
@@ -1889,6 +1906,9 @@ impl<'a> Context<'a> {
                 AcirValue::Array(elements.collect())
             }
             Value::Intrinsic(..) => todo!(),
+            Value::UserBlackBox(name) => unreachable!(
+                "ICE: user-defined blackbox function `{name}` used as a value should have been rejected by `check_for_user_black_box_values`"
+            ),
             Value::Function(function_id) => {
                 // This conversion is for debugging support only, to allow the
                 // debugging instrumentation code to work. Taking the reference
@@ -2900,8 +2920,9 @@ mod test {
     use acvm::{
         acir::{
             circuit::{
-                brillig::BrilligFunctionId, opcodes::AcirFunctionId, ExpressionWidth, Opcode,
-                OpcodeLocation,
+                brillig::BrilligFunctionId,
+                opcodes::{AcirFunctionId, BlackBoxFuncCall},
+                ExpressionWidth, Opcode, OpcodeLocation,
             },
             native_types::Witness,
         },
@@ -3594,6 +3615,47 @@ mod test {
         assert_eq!(foo_acir.brillig_locations.len(), 0);
     }
 
+    #[test]
+    fn user_defined_black_box_call() {
+        // acir(inline) fn main f0 {
+        //     b0(v0: Field, v1: Field):
+        //       v2, v3 = call my_hash(v0, v1)
+        //       constrain v2 == v3
+        //       return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let main_v0 = builder.add_parameter(Type::field());
+        let main_v1 = builder.add_parameter(Type::field());
+
+        let my_hash = builder.import_user_black_box("my_hash");
+        let results = builder
+            .insert_call(my_hash, vec![main_v0, main_v1], vec![Type::field(), Type::field()])
+            .to_vec();
+        builder.insert_constrain(results[0], results[1], None);
+        builder.terminate_with_return(vec![]);
+
+        let ssa = builder.finish();
+
+        let (acir_functions, brillig_functions, _, _) = ssa
+            .into_acir(&Brillig::default(), ExpressionWidth::default())
+            .expect("Should compile manually written SSA into ACIR");
+        assert_eq!(acir_functions.len(), 1);
+        assert!(brillig_functions.is_empty());
+
+        let Opcode::BlackBoxFuncCall(BlackBoxFuncCall::UserDefined { name, inputs, outputs }) =
+            &acir_functions[0].opcodes()[0]
+        else {
+            panic!("Expected a user-defined blackbox call");
+        };
+        assert_eq!(name, "my_hash");
+        assert_eq!(
+            inputs.iter().map(|input| input.to_witness()).collect::<Vec<_>>(),
+            vec![Witness(0), Witness(1)]
+        );
+        assert_eq!(outputs, &vec![Witness(2), Witness(3)]);
+    }
+
     fn check_brillig_calls(
         brillig_stdlib_function_locations: &BTreeMap<OpcodeLocation, BrilligStdlibFunc>,
         opcodes: &[Opcode<FieldElement>],
@@ -0,0 +1,168 @@
+//! This module defines an SSA check which rejects calls to blackbox functions which are not available
+//! on the field which the program is compiled over, e.g. those defined over the Grumpkin curve when
+//! compiling for bls12_381. Both ACIR and Brillig functions are checked, as neither runtime can
+//! execute these functions on another field.
+//!
+//! Calls to `derive_pedersen_generators` are also rejected on fields other than bn254, as the
+//! generators are points on the Grumpkin curve.
+use acvm::acir::BlackBoxFunc;
+
+use crate::errors::RuntimeError;
+use crate::ssa::ir::instruction::{Instruction, Intrinsic};
+use crate::ssa::ir::value::Value;
+use crate::ssa::ssa_gen::Ssa;
+
+impl Ssa {
+    /// Returns an error if any function calls a blackbox function which is unavailable on `field`.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn check_for_unavailable_black_box_functions(
+        &self,
+        field: &'static str,
+    ) -> Result<(), RuntimeError> {
+        for function in self.functions.values() {
+            let dfg = &function.dfg;
+            for block_id in function.reachable_blocks() {
+                for instruction_id in dfg[block_id].instructions() {
+                    let Instruction::Call { func, .. } = &dfg[*instruction_id] else {
+                        continue;
+                    };
+                    let name = match &dfg[*func] {
+                        Value::Intrinsic(Intrinsic::BlackBox(black_box))
+                            if !is_available(*black_box, field) =>
+                        {
+                            black_box.name()
+                        }
+                        Value::Intrinsic(Intrinsic::DerivePedersenGenerators)
+                            if field != "bn254" =>
+                        {
+                            "derive_pedersen_generators"
+                        }
+                        _ => continue,
+                    };
+                    return Err(RuntimeError::UnavailableBlackBoxFunction {
+                        name,
+                        field,
+                        call_stack: dfg.get_call_stack(*instruction_id),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_available(black_box: BlackBoxFunc, field: &str) -> bool {
+    field == "bn254" || !black_box.is_bn254_specific()
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::BlackBoxFunc;
+    use iter_extended::vecmap;
+
+    use crate::errors::RuntimeError;
+    use crate::ssa::function_builder::FunctionBuilder;
+    use crate::ssa::ir::instruction::Intrinsic;
+    use crate::ssa::ir::map::Id;
+    use crate::ssa::ir::types::Type;
+    use crate::ssa::ssa_gen::Ssa;
+
+    /// Builds a program in which `main` calls `derive_pedersen_generators`.
+    ///
+    /// The starting index is a parameter so that the call is not evaluated at compile-time.
+    ///
+    /// fn main(starting_index: u32) -> [EmbeddedCurvePoint; 1] {
+    ///     derive_generators("DEFAULT_DOMAIN_SEPARATOR".as_bytes(), starting_index)
+    /// }
+    fn derive_generators() -> Ssa {
+        let main_id = Id::test_new(0);
+
+        let mut builder = FunctionBuilder::new("main".to_string(), main_id);
+        let starting_index = builder.add_parameter(Type::unsigned(32));
+        let domain_separator = vecmap(b"DEFAULT_DOMAIN_SEPARATOR", |byte| {
+            builder.numeric_constant(u128::from(*byte), Type::unsigned(8))
+        });
+        let domain_separator_type = Type::Array(Type::unsigned(8).into(), domain_separator.len());
+        let domain_separator =
+            builder.array_constant(domain_separator.into(), domain_separator_type);
+        let derive_generators = builder.import_intrinsic_id(Intrinsic::DerivePedersenGenerators);
+        let result_type = Type::Array(vec![Type::field(), Type::field(), Type::bool()].into(), 1);
+        let result = builder
+            .insert_call(
+                derive_generators,
+                vec![domain_separator, starting_index],
+                vec![result_type],
+            )
+            .to_vec();
+        builder.terminate_with_return(result);
+
+        builder.finish()
+    }
+
+    /// Builds a program in which `main` calls `black_box` from an unconstrained function.
+    ///
+    /// unconstrained fn hash(x: Field) -> Field {
+    ///     black_box([x])
+    /// }
+    /// fn main(x: Field) -> Field {
+    ///     hash(x)
+    /// }
+    fn call_from_brillig(black_box: BlackBoxFunc) -> Ssa {
+        let main_id = Id::test_new(0);
+        let hash_id = Id::test_new(1);
+
+        let mut builder = FunctionBuilder::new("main".to_string(), main_id);
+        let x = builder.add_parameter(Type::field());
+        let hash = builder.import_function(hash_id);
+        let result = builder.insert_call(hash, vec![x], vec![Type::field()]).to_vec();
+        builder.terminate_with_return(result);
+
+        builder.new_brillig_function("hash".to_string(), hash_id);
+        let x = builder.add_parameter(Type::field());
+        let inputs = builder.array_constant(im::vector![x], Type::Array(Type::field().into(), 1));
+        let black_box = builder.import_intrinsic_id(Intrinsic::BlackBox(black_box));
+        let result = builder.insert_call(black_box, vec![inputs], vec![Type::field()]).to_vec();
+        builder.terminate_with_return(result);
+
+        builder.finish()
+    }
+
+    #[test]
+    fn allows_bn254_specific_black_box_functions_on_bn254() {
+        let ssa = call_from_brillig(BlackBoxFunc::PedersenHash);
+        assert!(ssa.check_for_unavailable_black_box_functions("bn254").is_ok());
+    }
+
+    #[test]
+    fn rejects_bn254_specific_black_box_functions_in_brillig_on_bls12_381() {
+        let ssa = call_from_brillig(BlackBoxFunc::PedersenHash);
+        let error = ssa.check_for_unavailable_black_box_functions("bls12_381").unwrap_err();
+        assert!(matches!(
+            error,
+            RuntimeError::UnavailableBlackBoxFunction {
+                name: "pedersen_hash",
+                field: "bls12_381",
+                ..
+            }
+        ));
+
+        let ssa = call_from_brillig(BlackBoxFunc::Blake3);
+        assert!(ssa.check_for_unavailable_black_box_functions("bls12_381").is_ok());
+    }
+
+    #[test]
+    fn rejects_derive_pedersen_generators_on_bls12_381() {
+        let ssa = derive_generators();
+        assert!(ssa.check_for_unavailable_black_box_functions("bn254").is_ok());
+
+        let error = ssa.check_for_unavailable_black_box_functions("bls12_381").unwrap_err();
+        assert!(matches!(
+            error,
+            RuntimeError::UnavailableBlackBoxFunction {
+                name: "derive_pedersen_generators",
+                field: "bls12_381",
+                ..
+            }
+        ));
+    }
+}
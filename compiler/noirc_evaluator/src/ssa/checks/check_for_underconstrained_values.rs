@@ -1,14 +1,22 @@
 //! This module defines an SSA pass that detects if the final function has any subgraphs independent from inputs and outputs.
 //! If this is the case, then part of the final circuit can be completely replaced by any other passing circuit, since there are no constraints ensuring connections.
 //! So the compiler informs the developer of this as a bug
+//!
+//! Additionally, each value returned from a brillig call is checked to be bound by at least one constraint which
+//! relates it to the inputs of the function. Values which are only range checked, or which appear in fewer equations
+//! than the number of unknown values in those equations (e.g. a product check on values without range checks),
+//! are also reported as these can still be freely chosen by a malicious prover. Values which are never used within the
+//! circuit, or which are only passed to other brillig calls, are not reported.
 use crate::errors::{InternalBug, SsaReport};
 use crate::ssa::ir::basic_block::BasicBlockId;
 use crate::ssa::ir::function::RuntimeType;
 use crate::ssa::ir::function::{Function, FunctionId};
-use crate::ssa::ir::instruction::{Instruction, InstructionId, Intrinsic};
+use crate::ssa::ir::instruction::{Instruction, InstructionId, Intrinsic, TerminatorInstruction};
+use crate::ssa::ir::post_order::PostOrder;
+use crate::ssa::ir::types::Type;
 use crate::ssa::ir::value::{Value, ValueId};
 use crate::ssa::ssa_gen::Ssa;
-use im::HashMap;
+use im::{HashMap, OrdSet};
 use rayon::prelude::*;
 use std::collections::{BTreeMap, HashSet};
 
@@ -61,6 +69,25 @@ fn check_for_underconstrained_values_within_function(
             function,
         ));
     }
+
+    // Calls which have already been reported as creating an independent subgraph are not reported again.
+    let reported_calls: HashSet<InstructionId> = context
+        .brillig_return_to_instruction_id
+        .iter()
+        .filter(|(result, _)| {
+            let set = context.value_sets.iter().position(|set| set.contains(result));
+            set.map_or(false, |set_index| !connected_sets_indices.contains(&set_index))
+        })
+        .map(|(_, instruction)| *instruction)
+        .collect();
+
+    let mut dependency_context = DependencyContext::default();
+    dependency_context.compute_dependencies(function, all_functions);
+    warnings.extend(dependency_context.check_brillig_results(
+        function,
+        all_functions,
+        &reported_calls,
+    ));
     warnings
 }
 #[derive(Default)]
@@ -243,6 +270,11 @@ impl Context {
                                 self.value_sets.push(instruction_arguments_and_results);
                             }
                         },
+                        // Like the built-in blackbox functions, the backend is responsible
+                        // for constraining the results of a user-defined blackbox function.
+                        Value::UserBlackBox(..) => {
+                            self.value_sets.push(instruction_arguments_and_results);
+                        }
                         Value::ForeignFunction(..) => {
                             panic!("Should not be able to reach foreign function from non-brillig functions, {func_id} in function {}", function.name());
                         }
@@ -349,11 +381,287 @@ impl Context {
         Self::merge_sets(&sets)
     }
 }
+
+/// An equality constraint within a function, along with the values it depends on.
+struct Constraint {
+    instruction: InstructionId,
+    /// The brillig call results which the constrained values are computed from.
+    brillig_results: OrdSet<ValueId>,
+    /// Whether the constrained values are computed from the function's inputs.
+    depends_on_inputs: bool,
+}
+
+/// Tracks which brillig call results and function inputs each value is computed from so that the
+/// constraints placed on each brillig call result can be checked individually.
+#[derive(Default)]
+struct DependencyContext {
+    /// Maps each value to the brillig call results it is computed from.
+    brillig_dependencies: HashMap<ValueId, OrdSet<ValueId>>,
+    /// Values which are computed from the function's inputs.
+    input_dependent_values: HashSet<ValueId>,
+    /// Maps each brillig call result to the call instruction which returned it.
+    brillig_results: BTreeMap<ValueId, InstructionId>,
+    /// The equality constraints found within the function.
+    constraints: Vec<Constraint>,
+    /// Maps brillig call results to the range checks applied directly to them.
+    range_checks: HashMap<ValueId, InstructionId>,
+    /// Brillig call results which are returned from the function or passed to another ACIR function.
+    /// These may be constrained outside of the current function so they are not checked.
+    escaping_results: HashSet<ValueId>,
+    /// Brillig call results which reach a use within the circuit, such as a constraint or a branch.
+    /// Results which are unused, or only passed to other brillig calls, do not need to be constrained.
+    used_results: HashSet<ValueId>,
+}
+
+impl DependencyContext {
+    fn compute_dependencies(
+        &mut self,
+        function: &Function,
+        all_functions: &BTreeMap<FunctionId, Function>,
+    ) {
+        let dfg = &function.dfg;
+        self.input_dependent_values.extend(function.parameters().iter().copied());
+
+        let post_order = PostOrder::with_function(function);
+        for block in post_order.as_slice().iter().rev() {
+            for instruction in dfg[*block].instructions() {
+                let mut brillig_results = OrdSet::new();
+                let mut depends_on_inputs = false;
+                dfg[*instruction].for_each_value(|value| {
+                    let value = dfg.resolve(value);
+                    if let Some(results) = self.brillig_dependencies.get(&value) {
+                        brillig_results = brillig_results.clone().union(results.clone());
+                    }
+                    depends_on_inputs |= self.input_dependent_values.contains(&value);
+                });
+
+                match &dfg[*instruction] {
+                    Instruction::Call { func, arguments } => match &dfg[*func] {
+                        Value::Function(callee)
+                            if all_functions[callee].runtime() == RuntimeType::Brillig =>
+                        {
+                            // Each result of a brillig call is a new unknown value.
+                            for result in dfg.instruction_results(*instruction) {
+                                if dfg.get_numeric_constant(*result).is_none() {
+                                    self.brillig_results.insert(*result, *instruction);
+                                    self.brillig_dependencies
+                                        .insert(*result, OrdSet::unit(*result));
+                                }
+                            }
+                            continue;
+                        }
+                        Value::Function(_) => {
+                            self.escaping_results.extend(brillig_results.iter().copied());
+                        }
+                        Value::Intrinsic(Intrinsic::ApplyRangeConstraint) => {
+                            self.insert_range_check(dfg.resolve(arguments[0]), *instruction);
+                        }
+                        _ => (),
+                    },
+                    Instruction::Constrain(..) => {
+                        self.constraints.push(Constraint {
+                            instruction: *instruction,
+                            brillig_results: brillig_results.clone(),
+                            depends_on_inputs,
+                        });
+                    }
+                    Instruction::RangeCheck { value, .. } => {
+                        self.insert_range_check(dfg.resolve(*value), *instruction);
+                    }
+                    _ => (),
+                }
+
+                match &dfg[*instruction] {
+                    // These only compute new values, which are checked if they are used in turn.
+                    Instruction::ArrayGet { .. }
+                    | Instruction::ArraySet { .. }
+                    | Instruction::Binary(..)
+                    | Instruction::Cast(..)
+                    | Instruction::IfElse { .. }
+                    | Instruction::Load { .. }
+                    | Instruction::Not(..)
+                    | Instruction::Truncate { .. }
+                    | Instruction::Allocate { .. }
+                    | Instruction::IncrementRc { .. }
+                    | Instruction::DecrementRc { .. } => (),
+                    Instruction::Call { .. }
+                    | Instruction::Constrain(..)
+                    | Instruction::EnableSideEffectsIf { .. }
+                    | Instruction::RangeCheck { .. }
+                    | Instruction::Store { .. } => {
+                        self.used_results.extend(brillig_results.iter().copied());
+                    }
+                }
+
+                for result in dfg.instruction_results(*instruction) {
+                    self.insert_dependencies(*result, &brillig_results, depends_on_inputs);
+                }
+            }
+
+            match dfg[*block].unwrap_terminator() {
+                TerminatorInstruction::Jmp { destination, arguments, .. } => {
+                    let parameters = dfg.block_parameters(*destination);
+                    for (parameter, argument) in parameters.iter().zip(arguments) {
+                        let argument = dfg.resolve(*argument);
+                        let brillig_results =
+                            self.brillig_dependencies.get(&argument).cloned().unwrap_or_default();
+                        let depends_on_inputs = self.input_dependent_values.contains(&argument);
+                        self.insert_dependencies(*parameter, &brillig_results, depends_on_inputs);
+                    }
+                }
+                TerminatorInstruction::Return { return_values, .. } => {
+                    for value in return_values {
+                        if let Some(results) = self.brillig_dependencies.get(&dfg.resolve(*value)) {
+                            self.escaping_results.extend(results.iter().copied());
+                        }
+                    }
+                }
+                TerminatorInstruction::JmpIf { condition, .. } => {
+                    if let Some(results) = self.brillig_dependencies.get(&dfg.resolve(*condition)) {
+                        self.used_results.extend(results.iter().copied());
+                    }
+                }
+            }
+        }
+    }
+
+    fn insert_dependencies(
+        &mut self,
+        value: ValueId,
+        brillig_results: &OrdSet<ValueId>,
+        depends_on_inputs: bool,
+    ) {
+        if !brillig_results.is_empty() {
+            let dependencies = self.brillig_dependencies.entry(value).or_default();
+            *dependencies = dependencies.clone().union(brillig_results.clone());
+        }
+        if depends_on_inputs {
+            self.input_dependent_values.insert(value);
+        }
+    }
+
+    fn insert_range_check(&mut self, value: ValueId, instruction: InstructionId) {
+        if self.brillig_results.contains_key(&value) {
+            self.range_checks.entry(value).or_insert(instruction);
+        }
+    }
+
+    /// Returns whether `result` is a `Field` which has not been range checked, and so can take any value.
+    fn is_unbounded(&self, function: &Function, result: ValueId) -> bool {
+        function.dfg.type_of_value(result) == Type::field()
+            && !self.range_checks.contains_key(&result)
+    }
+
+    /// Check that each used brillig call result is sufficiently constrained, returning a bug report for each
+    /// brillig call with an insufficiently constrained result.
+    ///
+    /// A constraint binds a brillig call result if it relates that result to the function's inputs,
+    /// or if the result is the only unknown value within the constraint (e.g. it is compared against a constant).
+    fn check_brillig_results(
+        &self,
+        function: &Function,
+        all_functions: &BTreeMap<FunctionId, Function>,
+        reported_calls: &HashSet<InstructionId>,
+    ) -> Vec<SsaReport> {
+        let mut reported_calls = reported_calls.clone();
+        let mut warnings = Vec::new();
+
+        for (result, call) in &self.brillig_results {
+            if !self.used_results.contains(result)
+                || self.escaping_results.contains(result)
+                || reported_calls.contains(call)
+            {
+                continue;
+            }
+
+            let binding_constraints: Vec<&Constraint> = self
+                .constraints
+                .iter()
+                .filter(|constraint| {
+                    constraint.brillig_results.contains(result)
+                        && (constraint.depends_on_inputs || constraint.brillig_results.len() == 1)
+                })
+                .collect();
+
+            let call_stack = function.dfg.get_call_stack(*call);
+            let warning = if binding_constraints.is_empty() {
+                match self.range_checks.get(result) {
+                    Some(range_check) => InternalBug::OnlyRangeCheckedBrilligResult {
+                        call_stack,
+                        range_check_call_stack: function.dfg.get_call_stack(*range_check),
+                    },
+                    None => {
+                        let (function_name, arguments) =
+                            describe_brillig_call(function, all_functions, *call);
+                        InternalBug::UnconstrainedBrilligResult {
+                            call_stack,
+                            function_name,
+                            arguments,
+                        }
+                    }
+                }
+            } else if self.is_unbounded(function, *result) {
+                // Each constraint is a single equation, so the unknown values within them can only be
+                // uniquely determined if there are at least as many equations as unknown values.
+                let unknowns: HashSet<ValueId> = binding_constraints
+                    .iter()
+                    .flat_map(|constraint| constraint.brillig_results.iter().copied())
+                    .filter(|value| self.is_unbounded(function, *value))
+                    .collect();
+                if binding_constraints.len() >= unknowns.len() {
+                    continue;
+                }
+                InternalBug::WeaklyConstrainedBrilligResult {
+                    call_stack,
+                    constraint_call_stack: function
+                        .dfg
+                        .get_call_stack(binding_constraints[0].instruction),
+                }
+            } else {
+                continue;
+            };
+
+            reported_calls.insert(*call);
+            warnings.push(SsaReport::Bug(warning));
+        }
+        warnings
+    }
+}
+
+/// Returns the name of the function called by the brillig call `call` along with a description of
+/// each of its arguments: constants are shown by their value and other values by their position.
+fn describe_brillig_call(
+    function: &Function,
+    all_functions: &BTreeMap<FunctionId, Function>,
+    call: InstructionId,
+) -> (String, Vec<String>) {
+    let dfg = &function.dfg;
+    let Instruction::Call { func, arguments } = &dfg[call] else {
+        unreachable!("Expected brillig call results to be returned by a call instruction");
+    };
+    let function_name = match &dfg[*func] {
+        Value::Function(callee) => all_functions[callee].name().to_string(),
+        _ => unreachable!("Expected brillig call to call a function"),
+    };
+    let arguments = arguments
+        .iter()
+        .enumerate()
+        .map(|(index, argument)| match dfg.get_numeric_constant(*argument) {
+            Some(constant) => constant.to_string(),
+            None => format!("arg{index}"),
+        })
+        .collect();
+    (function_name, arguments)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::ssa::{
-        function_builder::FunctionBuilder,
-        ir::{instruction::BinaryOp, map::Id, types::Type},
+    use crate::{
+        errors::{InternalBug, SsaReport},
+        ssa::{
+            function_builder::FunctionBuilder,
+            ir::{instruction::BinaryOp, map::Id, types::Type},
+        },
     };
 
     #[test]
@@ -428,4 +736,228 @@ mod test {
         let ssa_level_warnings = ssa.check_for_underconstrained_values();
         assert_eq!(ssa_level_warnings.len(), 1);
     }
+
+    #[test]
+    fn only_range_checked_brillig_result() {
+        //  unconstrained fn br(v0: u32) -> u32 {
+        //      return v0
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: u32):
+        //      v1 = call br(v0)
+        //      range_check v1 to 32 bits
+        //      v2 = add v0, v1
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        let v1 = builder.insert_call(br_function, vec![v0], vec![Type::unsigned(32)])[0];
+        builder.insert_range_check(v1, 32, None);
+        builder.insert_binary(v0, BinaryOp::Add, v1);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("br".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        builder.terminate_with_return(vec![v0]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 1);
+        assert!(matches!(
+            ssa_level_warnings[0],
+            SsaReport::Bug(InternalBug::OnlyRangeCheckedBrilligResult { .. })
+        ));
+    }
+
+    #[test]
+    fn weakly_constrained_brillig_results() {
+        //  unconstrained fn div_mod(v0: Field, v1: Field) -> (Field, Field) {
+        //      v2 = div v0, v1
+        //      return v2, v0
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: Field, v1: Field):
+        //      v2, v3 = call div_mod(v0, v1)
+        //      v4 = mul v2, v1
+        //      v5 = add v4, v3
+        //      constrain v5 == v0
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        let results =
+            builder.insert_call(br_function, vec![v0, v1], vec![Type::field(), Type::field()]);
+        let (v2, v3) = (results[0], results[1]);
+        let v4 = builder.insert_binary(v2, BinaryOp::Mul, v1);
+        let v5 = builder.insert_binary(v4, BinaryOp::Add, v3);
+        builder.insert_constrain(v5, v0, None);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("div_mod".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.insert_binary(v0, BinaryOp::Div, v1);
+        builder.terminate_with_return(vec![v2, v0]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 1);
+        assert!(matches!(
+            ssa_level_warnings[0],
+            SsaReport::Bug(InternalBug::WeaklyConstrainedBrilligResult { .. })
+        ));
+    }
+
+    #[test]
+    fn brillig_result_constrained_against_inputs() {
+        //  unconstrained fn inverse(v0: Field) -> Field {
+        //      v1 = div Field 1, v0
+        //      return v1
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: Field):
+        //      v1 = call inverse(v0)
+        //      v2 = mul v0, v1
+        //      constrain v2 == Field 1
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::field());
+        let one = builder.field_constant(1u128);
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        let v1 = builder.insert_call(br_function, vec![v0], vec![Type::field()])[0];
+        let v2 = builder.insert_binary(v0, BinaryOp::Mul, v1);
+        builder.insert_constrain(v2, one, None);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("inverse".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::field());
+        let one = builder.field_constant(1u128);
+        let v1 = builder.insert_binary(one, BinaryOp::Div, v0);
+        builder.terminate_with_return(vec![v1]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 0);
+    }
+
+    #[test]
+    fn unused_brillig_results() {
+        //  unconstrained fn br(v0: Field) -> Field {
+        //      return v0
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: Field):
+        //      v1 = call br(v0)
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::field());
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        builder.insert_call(br_function, vec![v0], vec![Type::field()]);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("br".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 0);
+    }
+
+    #[test]
+    fn brillig_results_only_passed_to_brillig_calls() {
+        //  unconstrained fn br(v0: Field) -> Field {
+        //      return v0
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: Field):
+        //      v1 = call br(v0)
+        //      v2 = call br(v1)
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::field());
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        let v1 = builder.insert_call(br_function, vec![v0], vec![Type::field()])[0];
+        builder.insert_call(br_function, vec![v1], vec![Type::field()]);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("br".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::field());
+        builder.terminate_with_return(vec![v0]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 0);
+    }
+
+    #[test]
+    fn used_brillig_result_without_constraint() {
+        //  unconstrained fn is_small(v0: Field, v1: Field) -> u1 {
+        //      v2 = lt v0, v1
+        //      return v2
+        //  }
+        //
+        //  fn main {
+        //   b0(v0: Field):
+        //      v1 = call is_small(v0, Field 5)
+        //      enable_side_effects v1
+        //      return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let v0 = builder.add_parameter(Type::field());
+        let five = builder.field_constant(5u128);
+
+        let br_function_id = Id::test_new(1);
+        let br_function = builder.import_function(br_function_id);
+        let v1 = builder.insert_call(br_function, vec![v0, five], vec![Type::bool()])[0];
+        builder.insert_enable_side_effects_if(v1);
+        builder.terminate_with_return(vec![]);
+
+        builder.new_brillig_function("is_small".into(), br_function_id);
+        let v0 = builder.add_parameter(Type::field());
+        let v1 = builder.add_parameter(Type::field());
+        let v2 = builder.insert_binary(v0, BinaryOp::Lt, v1);
+        builder.terminate_with_return(vec![v2]);
+
+        let mut ssa = builder.finish();
+        let ssa_level_warnings = ssa.check_for_underconstrained_values();
+        assert_eq!(ssa_level_warnings.len(), 1);
+        let SsaReport::Bug(InternalBug::UnconstrainedBrilligResult {
+            function_name,
+            arguments,
+            ..
+        }) = &ssa_level_warnings[0]
+        else {
+            panic!("Expected an unconstrained brillig result, got {:?}", ssa_level_warnings[0]);
+        };
+        assert_eq!(function_name, "is_small");
+        assert_eq!(arguments, &["arg0", "5"]);
+    }
 }
@@ -0,0 +1,105 @@
+//! This module defines an SSA check which rejects user-defined blackbox functions being used as values,
+//! e.g. stored in an array or passed to another function, rather than being called directly.
+//! Neither ACIR nor Brillig have a representation for such values, as the function to call is fixed
+//! in the opcode which calls it.
+use crate::errors::RuntimeError;
+use crate::ssa::ir::dfg::{CallStack, DataFlowGraph};
+use crate::ssa::ir::instruction::Instruction;
+use crate::ssa::ir::value::{Value, ValueId};
+use crate::ssa::ssa_gen::Ssa;
+
+impl Ssa {
+    /// Returns an error if a user-defined blackbox function is used other than as the target of a call.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn check_for_user_black_box_values(&self) -> Result<(), RuntimeError> {
+        for function in self.functions.values() {
+            let dfg = &function.dfg;
+            for block_id in function.reachable_blocks() {
+                let block = &dfg[block_id];
+                for instruction_id in block.instructions() {
+                    let mut values = Vec::new();
+                    match &dfg[*instruction_id] {
+                        // The target of a call is the only place a user-defined blackbox function may appear.
+                        Instruction::Call { arguments, .. } => values.extend(arguments),
+                        instruction => instruction.for_each_value(|value| values.push(value)),
+                    }
+                    check_values(dfg, &values, || dfg.get_call_stack(*instruction_id))?;
+                }
+
+                if let Some(terminator) = block.terminator() {
+                    let mut values = Vec::new();
+                    terminator.for_each_value(|value| values.push(value));
+                    check_values(dfg, &values, || terminator.call_stack())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn check_values(
+    dfg: &DataFlowGraph,
+    values: &[ValueId],
+    call_stack: impl Fn() -> CallStack,
+) -> Result<(), RuntimeError> {
+    match values.iter().find_map(|value| find_user_black_box(dfg, *value)) {
+        Some(name) => Err(RuntimeError::UserBlackBoxAsValue {
+            name: name.to_string(),
+            call_stack: call_stack(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the name of the user-defined blackbox function `value` refers to, if any,
+/// looking through the elements of constant arrays.
+fn find_user_black_box(dfg: &DataFlowGraph, value: ValueId) -> Option<&str> {
+    match &dfg[dfg.resolve(value)] {
+        Value::UserBlackBox(name) => Some(name),
+        Value::Array { array, .. } => {
+            array.iter().find_map(|element| find_user_black_box(dfg, *element))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::errors::RuntimeError;
+    use crate::ssa::function_builder::FunctionBuilder;
+    use crate::ssa::ir::map::Id;
+    use crate::ssa::ir::types::Type;
+
+    #[test]
+    fn allows_calls_to_user_black_box_functions() {
+        // fn main(x: Field) -> Field {
+        //     my_hash(x)
+        // }
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        let x = builder.add_parameter(Type::field());
+        let my_hash = builder.import_user_black_box("my_hash");
+        let result = builder.insert_call(my_hash, vec![x], vec![Type::field()]).to_vec();
+        builder.terminate_with_return(result);
+
+        assert!(builder.finish().check_for_user_black_box_values().is_ok());
+    }
+
+    #[test]
+    fn rejects_user_black_box_functions_used_as_values() {
+        // fn main() -> [fn(Field) -> Field; 1] {
+        //     [my_hash]
+        // }
+        let mut builder = FunctionBuilder::new("main".to_string(), Id::test_new(0));
+        let my_hash = builder.import_user_black_box("my_hash");
+        let array = builder
+            .array_constant(im::vector![my_hash], Type::Array(Arc::new(vec![Type::Function]), 1));
+        builder.terminate_with_return(vec![array]);
+
+        let error = builder.finish().check_for_user_black_box_values().unwrap_err();
+        assert!(
+            matches!(error, RuntimeError::UserBlackBoxAsValue { name, .. } if name == "my_hash")
+        );
+    }
+}
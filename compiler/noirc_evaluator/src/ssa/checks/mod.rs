@@ -1 +1,3 @@
+mod check_for_unavailable_black_box_functions;
 mod check_for_underconstrained_values;
+mod check_for_user_black_box_values;
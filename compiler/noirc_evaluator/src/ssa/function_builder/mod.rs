@@ -398,6 +398,12 @@ impl FunctionBuilder {
         self.current_function.dfg.import_foreign_function(function)
     }
 
+    /// Returns a ValueId pointing to the user-defined blackbox function with the given name,
+    /// importing it into the current function if it was not already.
+    pub(crate) fn import_user_black_box(&mut self, name: &str) -> ValueId {
+        self.current_function.dfg.import_user_black_box(name)
+    }
+
     /// Retrieve a value reference to the given intrinsic operation.
     /// Returns None if there is no intrinsic matching the given name.
     pub(crate) fn import_intrinsic(&mut self, name: &str) -> Option<ValueId> {
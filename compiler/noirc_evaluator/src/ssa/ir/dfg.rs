@@ -14,7 +14,7 @@ use super::{
 };
 
 use acvm::{acir::AcirField, FieldElement};
-use fxhash::FxHashMap as HashMap;
+use fxhash::{FxHashMap as HashMap, FxHashSet as HashSet};
 use iter_extended::vecmap;
 use noirc_errors::Location;
 use serde::{Deserialize, Serialize};
@@ -70,6 +70,11 @@ pub(crate) struct DataFlowGraph {
     #[serde(skip)]
     foreign_functions: HashMap<String, ValueId>,
 
+    /// Contains each user-defined blackbox function that has been imported into the current
+    /// function, for the same reason as `foreign_functions`.
+    #[serde(skip)]
+    user_black_boxes: HashMap<String, ValueId>,
+
     /// All blocks in a function
     blocks: DenseMap<BasicBlock>,
 
@@ -92,6 +97,11 @@ pub(crate) struct DataFlowGraph {
     #[serde(skip)]
     locations: HashMap<InstructionId, CallStack>,
 
+    /// `ArrayGet` and `ArraySet` instructions which have been proven to only access indices
+    /// within the bounds of their array by the bounds check elimination pass.
+    #[serde(skip)]
+    safe_array_accesses: HashSet<InstructionId>,
+
     #[serde(skip)]
     pub(crate) data_bus: DataBus,
 }
@@ -287,6 +297,16 @@ impl DataFlowGraph {
         self.values.insert(Value::ForeignFunction(function.to_owned()))
     }
 
+    /// Gets or creates a ValueId for the user-defined blackbox function with the given name.
+    pub(crate) fn import_user_black_box(&mut self, name: &str) -> ValueId {
+        if let Some(existing) = self.user_black_boxes.get(name) {
+            return *existing;
+        }
+        let value_id = self.values.insert(Value::UserBlackBox(name.to_owned()));
+        self.user_black_boxes.insert(name.to_owned(), value_id);
+        value_id
+    }
+
     /// Gets or creates a ValueId for the given Intrinsic.
     pub(crate) fn import_intrinsic(&mut self, intrinsic: Intrinsic) -> ValueId {
         if let Some(existing) = self.get_intrinsic(intrinsic) {
@@ -480,6 +500,26 @@ impl DataFlowGraph {
             _ => false,
         }
     }
+
+    /// Records that the `ArrayGet` or `ArraySet` instruction `instruction` is known to only
+    /// access an index within the bounds of its array.
+    pub(crate) fn mark_safe_array_access(&mut self, instruction: InstructionId) {
+        self.safe_array_accesses.insert(instruction);
+    }
+
+    /// Returns true if the `ArrayGet` or `ArraySet` instruction `instruction` is known to only
+    /// access an index within the bounds of its array, either because the index is a constant
+    /// or because it has been proven by the bounds check elimination pass.
+    pub(crate) fn is_safe_array_access(&self, instruction: InstructionId) -> bool {
+        match &self[instruction] {
+            Instruction::ArrayGet { array, index } | Instruction::ArraySet { array, index, .. } => {
+                self.is_safe_index(*index, *array)
+                    || self.safe_array_accesses.contains(&instruction)
+            }
+            _ => false,
+        }
+    }
+
     /// Sets the terminator instruction for the given basic block
     pub(crate) fn set_block_terminator(
         &mut self,
@@ -169,7 +169,10 @@ impl Intrinsic {
             "as_witness" => Some(Intrinsic::AsWitness),
             "is_unconstrained" => Some(Intrinsic::IsUnconstrained),
             "derive_pedersen_generators" => Some(Intrinsic::DerivePedersenGenerators),
-            other => BlackBoxFunc::lookup(other).map(Intrinsic::BlackBox),
+            // `user_defined` is reserved: user-defined blackbox functions are looked up by their own name.
+            other => BlackBoxFunc::lookup(other)
+                .filter(|func| *func != BlackBoxFunc::UserDefined)
+                .map(Intrinsic::BlackBox),
         }
     }
 }
@@ -336,6 +339,7 @@ impl Instruction {
 
             Call { func, .. } => match dfg[*func] {
                 Value::Intrinsic(intrinsic) => !intrinsic.has_side_effects(),
+                Value::UserBlackBox(_) => true,
                 _ => false,
             },
 
@@ -400,6 +404,9 @@ impl Instruction {
                 // from the ACVM to the external world during execution.
                 Value::ForeignFunction(_) => false,
 
+                // User-defined blackbox functions are pure, like the built-in hash functions.
+                Value::UserBlackBox(_) => true,
+
                 // We must assume that functions contain a side effect as we cannot inspect more deeply.
                 Value::Function(_) => false,
 
@@ -1052,3 +1059,90 @@ impl SimplifyResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::BlackBoxFunc;
+    use noirc_frontend::hir_def::function::INTRINSIC_FUNCTION_NAMES;
+
+    use super::{Endian, Intrinsic};
+
+    #[test]
+    fn frontend_intrinsic_names_are_intrinsics() {
+        for name in INTRINSIC_FUNCTION_NAMES {
+            let intrinsic = Intrinsic::lookup(name);
+            assert!(
+                matches!(intrinsic, Some(intrinsic) if !matches!(intrinsic, Intrinsic::BlackBox(_))),
+                "`{name}` should be a compiler intrinsic"
+            );
+            assert_eq!(intrinsic.unwrap().to_string(), *name);
+        }
+    }
+
+    #[test]
+    fn intrinsics_are_frontend_intrinsic_names() {
+        let intrinsics = [
+            Intrinsic::ArrayLen,
+            Intrinsic::ArrayAsStrUnchecked,
+            Intrinsic::AsSlice,
+            Intrinsic::AssertConstant,
+            Intrinsic::StaticAssert,
+            Intrinsic::SlicePushBack,
+            Intrinsic::SlicePushFront,
+            Intrinsic::SlicePopBack,
+            Intrinsic::SlicePopFront,
+            Intrinsic::SliceInsert,
+            Intrinsic::SliceRemove,
+            Intrinsic::ApplyRangeConstraint,
+            Intrinsic::StrAsBytes,
+            Intrinsic::ToBits(Endian::Big),
+            Intrinsic::ToBits(Endian::Little),
+            Intrinsic::ToRadix(Endian::Big),
+            Intrinsic::ToRadix(Endian::Little),
+            Intrinsic::FromField,
+            Intrinsic::AsField,
+            Intrinsic::AsWitness,
+            Intrinsic::IsUnconstrained,
+            Intrinsic::DerivePedersenGenerators,
+        ];
+        for intrinsic in intrinsics {
+            // There is no wildcard arm so that adding an intrinsic fails to compile until it is
+            // added to the list above, and so checked against `INTRINSIC_FUNCTION_NAMES`.
+            match intrinsic {
+                Intrinsic::ArrayLen
+                | Intrinsic::ArrayAsStrUnchecked
+                | Intrinsic::AsSlice
+                | Intrinsic::AssertConstant
+                | Intrinsic::StaticAssert
+                | Intrinsic::SlicePushBack
+                | Intrinsic::SlicePushFront
+                | Intrinsic::SlicePopBack
+                | Intrinsic::SlicePopFront
+                | Intrinsic::SliceInsert
+                | Intrinsic::SliceRemove
+                | Intrinsic::ApplyRangeConstraint
+                | Intrinsic::StrAsBytes
+                | Intrinsic::ToBits(_)
+                | Intrinsic::ToRadix(_)
+                | Intrinsic::FromField
+                | Intrinsic::AsField
+                | Intrinsic::AsWitness
+                | Intrinsic::IsUnconstrained
+                | Intrinsic::DerivePedersenGenerators => (),
+                Intrinsic::BlackBox(_) => unreachable!("Blackbox functions are not listed"),
+            }
+
+            let name = intrinsic.to_string();
+            assert!(
+                INTRINSIC_FUNCTION_NAMES.contains(&name.as_str()),
+                "`{name}` is missing from `INTRINSIC_FUNCTION_NAMES`"
+            );
+        }
+        assert_eq!(intrinsics.len(), INTRINSIC_FUNCTION_NAMES.len());
+    }
+
+    #[test]
+    fn user_defined_is_not_an_intrinsic() {
+        assert_eq!(Intrinsic::lookup(BlackBoxFunc::UserDefined.name()), None);
+    }
+}
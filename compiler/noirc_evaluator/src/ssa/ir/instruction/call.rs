@@ -7,6 +7,7 @@ use acvm::{
 };
 use bn254_blackbox_solver::derive_generators;
 use iter_extended::vecmap;
+use noirc_frontend::hir::def_collector::dc_mod::CHOSEN_FIELD;
 use num_bigint::BigUint;
 
 use crate::ssa::{
@@ -481,7 +482,9 @@ fn simplify_black_box_func(
     dfg: &mut DataFlowGraph,
 ) -> SimplifyResult {
     cfg_if::cfg_if! {
-        if #[cfg(feature = "bn254")] {
+        if #[cfg(feature = "bls12_381")] {
+            let solver = acvm::blackbox_solver::Bls12381BlackBoxSolver;
+        } else if #[cfg(feature = "bn254")] {
             let solver = bn254_blackbox_solver::Bn254BlackBoxSolver;
         } else {
             let solver = acvm::blackbox_solver::StubbedBlackBoxSolver;
@@ -561,6 +564,11 @@ fn simplify_black_box_func(
         }
         BlackBoxFunc::Sha256Compression => SimplifyResult::None, //TODO(Guillaume)
         BlackBoxFunc::AES128Encrypt => SimplifyResult::None,
+        BlackBoxFunc::UserDefined => {
+            unreachable!(
+                "ICE: user-defined blackbox functions are called through `Value::UserBlackBox`"
+            )
+        }
     }
 }
 
@@ -698,6 +706,10 @@ fn simplify_derive_generators(
     arguments: &[ValueId],
     num_generators: u32,
 ) -> SimplifyResult {
+    // The generators are points on the Grumpkin curve, whose coordinates are elements of the bn254 field.
+    if CHOSEN_FIELD != "bn254" {
+        return SimplifyResult::None;
+    }
     if arguments.len() == 2 {
         let domain_separator_string = dfg.get_array_constant(arguments[0]);
         let starting_index = dfg.get_numeric_constant(arguments[1]);
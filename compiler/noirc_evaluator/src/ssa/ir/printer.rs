@@ -70,6 +70,7 @@ fn value(function: &Function, id: ValueId) -> String {
         }
         Value::Function(id) => id.to_string(),
         Value::Intrinsic(intrinsic) => intrinsic.to_string(),
+        Value::UserBlackBox(name) => name.clone(),
         Value::Array { array, .. } => {
             let elements = vecmap(array, |element| value(function, *element));
             format!("[{}]", elements.join(", "))
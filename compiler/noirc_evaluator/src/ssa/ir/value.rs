@@ -54,6 +54,11 @@ pub(crate) enum Value {
     /// ForeignFunction's always have the type Type::Function and have similar semantics to Function,
     /// other than generating different backend operations and being only accessible through Brillig.
     ForeignFunction(String),
+
+    /// This Value refers to a blackbox function which is not built into ACIR, declared with
+    /// a `#[foreign(name)]` attribute. Calls to it are carried by name through to the solver,
+    /// which looks up the implementation in its registry.
+    UserBlackBox(String),
 }
 
 impl Value {
@@ -67,6 +72,7 @@ impl Value {
             Value::Function { .. } => &Type::Function,
             Value::Intrinsic { .. } => &Type::Function,
             Value::ForeignFunction { .. } => &Type::Function,
+            Value::UserBlackBox { .. } => &Type::Function,
         }
     }
 }
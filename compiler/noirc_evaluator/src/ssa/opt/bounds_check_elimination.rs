@@ -0,0 +1,523 @@
+//! The bounds check elimination pass finds array accesses whose index is provably within the
+//! bounds of the array being accessed so that codegen can skip checking the index at runtime.
+//!
+//! This is done with a simple value range analysis which tracks an upper bound for numeric values.
+//! Upper bounds are derived from:
+//! - numeric constants,
+//! - [`Binary`] operations, [casts][Instruction::Cast] and [truncations][Instruction::Truncate]
+//!   applied to values with a known upper bound,
+//! - [range checks][Instruction::RangeCheck] and `constrain lt(a, b) == 1` assertions, which bound a
+//!   value in every block they dominate,
+//! - loop conditions of the form `jmpif lt(i, end)` which bound the loop induction variable `i`
+//!   within the loop body. This only affects Brillig functions as ACIR functions have been fully
+//!   unrolled by this point.
+//!
+//! In ACIR functions, values computed while side effects are disabled are not constrained, so bounds
+//! which rely on a predicated instruction (e.g. division) are only used when side effects are enabled.
+//!
+//! Array accesses found to be safe are recorded in the [`DataFlowGraph`] and can be queried with
+//! [`DataFlowGraph::is_safe_array_access`]. This pass must therefore be run after any pass which
+//! re-inserts instructions.
+use acvm::acir::AcirField;
+use fxhash::FxHashMap as HashMap;
+
+use crate::ssa::{
+    ir::{
+        basic_block::BasicBlockId,
+        cfg::ControlFlowGraph,
+        dfg::DataFlowGraph,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
+        instruction::{Binary, BinaryOp, Instruction, InstructionId, TerminatorInstruction},
+        post_order::PostOrder,
+        types::{NumericType, Type},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Marks array accesses with provably safe indices so that their index checks can be skipped.
+    ///
+    /// See [`bounds_check_elimination`][self] module for more information.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn eliminate_bounds_checks(mut self) -> Ssa {
+        for function in self.functions.values_mut() {
+            function.eliminate_bounds_checks();
+        }
+        self
+    }
+}
+
+impl Function {
+    pub(crate) fn eliminate_bounds_checks(&mut self) {
+        let safe_accesses = Context::new(self).find_safe_array_accesses(self);
+        for instruction in safe_accesses {
+            self.dfg.mark_safe_array_access(instruction);
+        }
+    }
+}
+
+struct Context {
+    cfg: ControlFlowGraph,
+    post_order: PostOrder,
+    dom: DominatorTree,
+    is_brillig_runtime: bool,
+
+    /// Upper bounds (inclusive) on values which hold wherever the value is defined.
+    bounds: HashMap<ValueId, u128>,
+
+    /// Upper bounds (inclusive) on values which have been established by a constraint or a branch
+    /// condition, along with the block from which they hold.
+    ///
+    /// These only hold in blocks dominated by the block in which they were established.
+    constrained_bounds: HashMap<ValueId, Vec<(BasicBlockId, u128)>>,
+}
+
+impl Context {
+    fn new(function: &Function) -> Self {
+        let cfg = ControlFlowGraph::with_function(function);
+        let post_order = PostOrder::with_function(function);
+        let dom = DominatorTree::with_cfg_and_post_order(&cfg, &post_order);
+
+        Context {
+            cfg,
+            post_order,
+            dom,
+            is_brillig_runtime: matches!(function.runtime(), RuntimeType::Brillig),
+            bounds: HashMap::default(),
+            constrained_bounds: HashMap::default(),
+        }
+    }
+
+    /// Returns all `ArrayGet` and `ArraySet` instructions in `function` which are known to only
+    /// access indices within the bounds of the array.
+    fn find_safe_array_accesses(&mut self, function: &Function) -> Vec<InstructionId> {
+        let dfg = &function.dfg;
+        let mut safe_accesses = Vec::new();
+
+        // Blocks are visited in reverse post-order so that every block is visited after its dominators.
+        let blocks: Vec<_> = self.post_order.as_slice().iter().rev().copied().collect();
+        for block in blocks {
+            self.bound_from_branch_condition(dfg, block);
+
+            let mut side_effects_enabled = true;
+            for instruction_id in dfg[block].instructions() {
+                match &dfg[*instruction_id] {
+                    Instruction::EnableSideEffectsIf { condition } => {
+                        side_effects_enabled =
+                            dfg.get_numeric_constant(*condition).map_or(false, |c| c.is_one());
+                    }
+                    Instruction::ArrayGet { array, index }
+                    | Instruction::ArraySet { array, index, .. } => {
+                        if self.index_is_in_bounds(dfg, block, *array, *index) {
+                            safe_accesses.push(*instruction_id);
+                        }
+                    }
+                    Instruction::RangeCheck { value, max_bit_size, .. } => {
+                        // Range checks are not affected by the side effects predicate.
+                        self.add_constrained_bound(
+                            dfg,
+                            block,
+                            *value,
+                            max_for_bit_size(*max_bit_size),
+                        );
+                    }
+                    Instruction::Constrain(lhs, rhs, _)
+                        if self.is_brillig_runtime || side_effects_enabled =>
+                    {
+                        self.bound_from_constraint(dfg, block, *lhs, *rhs);
+                    }
+                    instruction => {
+                        let predicate_applies = !self.is_brillig_runtime
+                            && !side_effects_enabled
+                            && instruction.requires_acir_gen_predicate(dfg);
+                        if predicate_applies {
+                            continue;
+                        }
+
+                        if let Some(bound) = self.instruction_bound(dfg, block, *instruction_id) {
+                            let result = dfg.instruction_results(*instruction_id)[0];
+                            self.bounds.insert(result, bound);
+                        }
+                    }
+                }
+            }
+        }
+
+        safe_accesses
+    }
+
+    /// Returns whether `index` is known to be less than the length of `array`.
+    fn index_is_in_bounds(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        array: ValueId,
+        index: ValueId,
+    ) -> bool {
+        // The length of a slice is only known at runtime.
+        let Type::Array(_, len) = dfg.type_of_value(array) else {
+            return false;
+        };
+        self.upper_bound(dfg, block, index).map_or(false, |bound| bound < len as u128)
+    }
+
+    /// Returns the tightest known upper bound (inclusive) for `value` within `block`.
+    fn upper_bound(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        value: ValueId,
+    ) -> Option<u128> {
+        let value = dfg.resolve(value);
+
+        if let Value::NumericConstant { constant, .. } = &dfg[value] {
+            return (constant.num_bits() <= 128).then(|| constant.to_u128());
+        }
+
+        let mut bound = self.bounds.get(&value).copied();
+
+        // Brillig wraps all integer operations to the bit size of their type.
+        if self.is_brillig_runtime {
+            if let Type::Numeric(NumericType::Unsigned { bit_size }) = dfg.type_of_value(value) {
+                bound = min_bound(bound, Some(max_for_bit_size(bit_size)));
+            }
+        }
+
+        if let Some(constrained_bounds) = self.constrained_bounds.get(&value) {
+            for (origin_block, constrained_bound) in constrained_bounds {
+                if self.dom.dominates(*origin_block, block) {
+                    bound = min_bound(bound, Some(*constrained_bound));
+                }
+            }
+        }
+
+        bound
+    }
+
+    /// Computes an upper bound for the single result of `instruction` from the bounds of its inputs.
+    fn instruction_bound(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        instruction: InstructionId,
+    ) -> Option<u128> {
+        let results = dfg.instruction_results(instruction);
+        if results.len() != 1 {
+            return None;
+        }
+        let result_type = dfg.type_of_value(results[0]);
+        let type_max = match result_type {
+            Type::Numeric(NumericType::Unsigned { bit_size }) => max_for_bit_size(bit_size),
+            Type::Numeric(NumericType::NativeField) => u128::MAX,
+            _ => return None,
+        };
+
+        match &dfg[instruction] {
+            Instruction::Binary(binary) => self.binary_bound(dfg, block, binary, type_max),
+            Instruction::Cast(value, _) => {
+                // Casting a signed value may change its unsigned representation.
+                if dfg.type_of_value(*value).is_unsigned() {
+                    self.upper_bound(dfg, block, *value).filter(|bound| *bound <= type_max)
+                } else {
+                    None
+                }
+            }
+            Instruction::Truncate { value, bit_size, .. } => {
+                min_bound(self.upper_bound(dfg, block, *value), Some(max_for_bit_size(*bit_size)))
+            }
+            _ => None,
+        }
+    }
+
+    fn binary_bound(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        binary: &Binary,
+        type_max: u128,
+    ) -> Option<u128> {
+        if !matches!(dfg.type_of_value(binary.lhs), Type::Numeric(NumericType::Unsigned { .. }))
+            && !matches!(binary.operator, BinaryOp::Add | BinaryOp::Mul)
+        {
+            return None;
+        }
+
+        let lhs = self.upper_bound(dfg, block, binary.lhs);
+        let rhs = self.upper_bound(dfg, block, binary.rhs);
+
+        match binary.operator {
+            BinaryOp::Add => self.arithmetic_bound(lhs?.checked_add(rhs?), type_max),
+            BinaryOp::Mul => self.arithmetic_bound(lhs?.checked_mul(rhs?), type_max),
+            BinaryOp::Div | BinaryOp::Shr => lhs,
+            BinaryOp::Mod => min_bound(lhs, rhs.map(|rhs| rhs.saturating_sub(1))),
+            BinaryOp::And => min_bound(lhs, rhs),
+            BinaryOp::Or | BinaryOp::Xor => {
+                let max = lhs?.max(rhs?);
+                Some(max_for_bit_size(u128::BITS - max.leading_zeros()))
+            }
+            BinaryOp::Eq | BinaryOp::Lt => Some(1),
+            BinaryOp::Sub | BinaryOp::Shl => None,
+        }
+    }
+
+    /// Returns the bound on the result of an addition or multiplication with a maximum possible
+    /// (non-wrapping) result of `max_result`.
+    fn arithmetic_bound(&self, max_result: Option<u128>, type_max: u128) -> Option<u128> {
+        match max_result {
+            Some(max_result) if max_result <= type_max => Some(max_result),
+            // In ACIR the result of the operation is the non-wrapping result. Any overflow will
+            // result in the circuit failing so this remains a valid bound.
+            Some(max_result) if !self.is_brillig_runtime => Some(max_result),
+            // In Brillig the operation wraps so the result can take any value of its type.
+            _ => None,
+        }
+    }
+
+    /// Handles `constrain lhs == rhs` where one side is `lt(a, b)` and the other is `true`,
+    /// which bounds `a` to be less than the upper bound on `b`.
+    fn bound_from_constraint(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        lhs: ValueId,
+        rhs: ValueId,
+    ) {
+        let is_true = |value| dfg.get_numeric_constant(value).map_or(false, |c| c.is_one());
+        let condition = if is_true(rhs) {
+            lhs
+        } else if is_true(lhs) {
+            rhs
+        } else {
+            return;
+        };
+        self.bound_from_less_than(dfg, block, condition, block);
+    }
+
+    /// Handles blocks which are only entered when a `jmpif lt(a, b)` condition holds,
+    /// such as the body of a loop, which bounds `a` to be less than the upper bound on `b`
+    /// within that block and any blocks it dominates.
+    fn bound_from_branch_condition(&mut self, dfg: &DataFlowGraph, block: BasicBlockId) {
+        let predecessor = {
+            let mut predecessors = self.cfg.predecessors(block);
+            if predecessors.len() != 1 {
+                return;
+            }
+            predecessors.next().expect("Checked that there is one predecessor")
+        };
+
+        if let Some(TerminatorInstruction::JmpIf {
+            condition,
+            then_destination,
+            else_destination,
+            ..
+        }) = dfg[predecessor].terminator()
+        {
+            if *then_destination == block && *else_destination != block {
+                self.bound_from_less_than(dfg, predecessor, *condition, block);
+            }
+        }
+    }
+
+    /// If `condition` is the result of `lt(a, b)`, records that `a` is less than the upper bound
+    /// on `b` (as known in `condition_block`) in all blocks dominated by `origin_block`.
+    fn bound_from_less_than(
+        &mut self,
+        dfg: &DataFlowGraph,
+        condition_block: BasicBlockId,
+        condition: ValueId,
+        origin_block: BasicBlockId,
+    ) {
+        let condition = dfg.resolve(condition);
+        let Value::Instruction { instruction, .. } = &dfg[condition] else {
+            return;
+        };
+        let Instruction::Binary(Binary { lhs, operator: BinaryOp::Lt, rhs }) = &dfg[*instruction]
+        else {
+            return;
+        };
+        if !dfg.type_of_value(*lhs).is_unsigned() {
+            return;
+        }
+
+        if let Some(rhs_bound) = self.upper_bound(dfg, condition_block, *rhs) {
+            // If `rhs` can only be zero then `lhs < rhs` can never hold and the block is unreachable.
+            if let Some(lhs_bound) = rhs_bound.checked_sub(1) {
+                self.add_constrained_bound(dfg, origin_block, *lhs, lhs_bound);
+            }
+        }
+    }
+
+    fn add_constrained_bound(
+        &mut self,
+        dfg: &DataFlowGraph,
+        block: BasicBlockId,
+        value: ValueId,
+        bound: u128,
+    ) {
+        let value = dfg.resolve(value);
+        self.constrained_bounds.entry(value).or_default().push((block, bound));
+    }
+}
+
+/// Returns the maximum value of an unsigned integer with `bit_size` bits.
+fn max_for_bit_size(bit_size: u32) -> u128 {
+    if bit_size >= u128::BITS {
+        u128::MAX
+    } else {
+        (1u128 << bit_size) - 1
+    }
+}
+
+fn min_bound(a: Option<u128>, b: Option<u128>) -> Option<u128> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{function::RuntimeType, instruction::BinaryOp, map::Id, types::Type},
+    };
+
+    #[test]
+    fn loop_induction_variable_is_in_bounds() {
+        // brillig fn main f0 {
+        //   b0(v0: [Field; 4]):
+        //     jmp b1(u32 0)
+        //   b1(v1: u32):
+        //     v2 = lt v1, u32 4
+        //     jmpif v2 then: b2, else: b3
+        //   b2():
+        //     v3 = array_get v0, index v1
+        //     v4 = add v1, u32 1
+        //     jmp b1(v4)
+        //   b3():
+        //     v5 = array_get v0, index v1
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 4);
+        let v0 = builder.add_parameter(array_type);
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let zero = builder.length_constant(0u128);
+        let one = builder.length_constant(1u128);
+        let four = builder.length_constant(4u128);
+        builder.terminate_with_jmp(b1, vec![zero]);
+
+        builder.switch_to_block(b1);
+        let v1 = builder.add_block_parameter(b1, Type::length_type());
+        let v2 = builder.insert_binary(v1, BinaryOp::Lt, four);
+        builder.terminate_with_jmpif(v2, b2, b3);
+
+        builder.switch_to_block(b2);
+        builder.insert_array_get(v0, v1, Type::field());
+        let v4 = builder.insert_binary(v1, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v4]);
+
+        builder.switch_to_block(b3);
+        builder.insert_array_get(v0, v1, Type::field());
+        builder.terminate_with_return(vec![]);
+
+        let ssa = builder.finish().eliminate_bounds_checks();
+        let main = ssa.main();
+
+        let body_access = main.dfg[b2].instructions()[0];
+        assert!(main.dfg.is_safe_array_access(body_access));
+
+        // After the loop `v1` is equal to the array length.
+        let exit_access = main.dfg[b3].instructions()[0];
+        assert!(!main.dfg.is_safe_array_access(exit_access));
+    }
+
+    #[test]
+    fn range_checked_and_truncated_indices_are_in_bounds() {
+        // fn main f0 {
+        //   b0(v0: [Field; 256], v1: u32, v2: u32):
+        //     range_check v1 to 8 bits
+        //     v3 = array_get v0, index v1
+        //     v4 = truncate v2 to 8 bits, max_bit_size: 32
+        //     v5 = array_get v0, index v4
+        //     v6 = add v4, u32 1
+        //     v7 = array_get v0, index v6
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 256);
+        let v0 = builder.add_parameter(array_type);
+        let v1 = builder.add_parameter(Type::length_type());
+        let v2 = builder.add_parameter(Type::length_type());
+
+        builder.insert_range_check(v1, 8, None);
+        builder.insert_array_get(v0, v1, Type::field());
+        let v4 = builder.insert_truncate(v2, 8, 32);
+        builder.insert_array_get(v0, v4, Type::field());
+        let one = builder.length_constant(1u128);
+        let v6 = builder.insert_binary(v4, BinaryOp::Add, one);
+        builder.insert_array_get(v0, v6, Type::field());
+        builder.terminate_with_return(vec![]);
+
+        let ssa = builder.finish().eliminate_bounds_checks();
+        let main = ssa.main();
+
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert!(main.dfg.is_safe_array_access(instructions[1]));
+        assert!(main.dfg.is_safe_array_access(instructions[3]));
+        // `v6` may be equal to 256
+        assert!(!main.dfg.is_safe_array_access(instructions[5]));
+    }
+
+    #[test]
+    fn constant_indices_are_only_safe_within_arrays() {
+        // fn main f0 {
+        //   b0(v0: [Field; 4], v1: [Field], v2: u32):
+        //     v3 = array_get v0, index u32 3
+        //     v4 = array_get v0, index u32 4
+        //     v5 = array_get v1, index u32 0
+        //     v6 = array_get v0, index v2
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+
+        let array_type = Type::Array(Arc::new(vec![Type::field()]), 4);
+        let v0 = builder.add_parameter(array_type);
+        let v1 = builder.add_parameter(Type::Slice(Arc::new(vec![Type::field()])));
+        let v2 = builder.add_parameter(Type::length_type());
+
+        let zero = builder.length_constant(0u128);
+        let three = builder.length_constant(3u128);
+        let four = builder.length_constant(4u128);
+        builder.insert_array_get(v0, three, Type::field());
+        builder.insert_array_get(v0, four, Type::field());
+        builder.insert_array_get(v1, zero, Type::field());
+        builder.insert_array_get(v0, v2, Type::field());
+        builder.terminate_with_return(vec![]);
+
+        let ssa = builder.finish().eliminate_bounds_checks();
+        let main = ssa.main();
+
+        let instructions = main.dfg[main.entry_block()].instructions();
+        assert!(main.dfg.is_safe_array_access(instructions[0]));
+        assert!(!main.dfg.is_safe_array_access(instructions[1]));
+        // The length of a slice is only known at runtime.
+        assert!(!main.dfg.is_safe_array_access(instructions[2]));
+        assert!(!main.dfg.is_safe_array_access(instructions[3]));
+    }
+}
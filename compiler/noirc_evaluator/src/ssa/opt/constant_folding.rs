@@ -8,6 +8,8 @@
 //!   by a [constrain instruction][Instruction::Constrain]. If so, replace the input value with the simpler form.
 //! - Check whether the instruction [can_be_replaced][Instruction::can_be_replaced()]
 //!   by duplicate instruction earlier in the same block.
+//!   For Brillig functions, which keep their control flow graph, a duplicate instruction in any
+//!   block which dominates the current block can also be used.
 //!
 //! These operations are done in parallel so that they can each benefit from each other
 //! without the need for multiple passes.
@@ -19,6 +21,8 @@
 //!
 //! This is the only pass which removes duplicated pure [`Instruction`]s however and so is needed when
 //! different blocks are merged, i.e. after the [`flatten_cfg`][super::flatten_cfg] pass.
+//! As Brillig functions are not flattened, deduplication is instead scoped by the [`DominatorTree`]
+//! so that computations in a dominating block are reused by all blocks which it dominates.
 use std::collections::HashSet;
 
 use acvm::{acir::AcirField, FieldElement};
@@ -28,7 +32,8 @@ use crate::ssa::{
     ir::{
         basic_block::BasicBlockId,
         dfg::{DataFlowGraph, InsertInstructionResult},
-        function::Function,
+        dom::DominatorTree,
+        function::{Function, RuntimeType},
         instruction::{Instruction, InstructionId},
         types::Type,
         value::{Value, ValueId},
@@ -67,7 +72,7 @@ impl Function {
     /// The structure of this pass is simple:
     /// Go through each block and re-insert all instructions.
     pub(crate) fn constant_fold(&mut self, use_constraint_info: bool) {
-        let mut context = Context { use_constraint_info, ..Default::default() };
+        let mut context = Context::new(self, use_constraint_info);
         context.block_queue.push(self.entry_block());
 
         while let Some(block) = context.block_queue.pop() {
@@ -81,25 +86,52 @@ impl Function {
     }
 }
 
-#[derive(Default)]
 struct Context {
     use_constraint_info: bool,
     /// Maps pre-folded ValueIds to the new ValueIds obtained by re-inserting the instruction.
     visited_blocks: HashSet<BasicBlockId>,
     block_queue: Vec<BasicBlockId>,
+
+    /// Cache of instructions without any side-effects along with their outputs.
+    cached_instruction_results: InstructionResultCache,
+
+    /// The dominator tree of the function being folded.
+    ///
+    /// This is only computed for Brillig functions, allowing results to be reused across blocks.
+    /// ACIR functions have been flattened into a single block by the time that deduplication matters,
+    /// so we only reuse results from within the same block for these.
+    dom: Option<DominatorTree>,
 }
 
 /// HashMap from (Instruction, side_effects_enabled_var) to the results of the instruction.
 /// Stored as a two-level map to avoid cloning Instructions during the `.get` call.
-type InstructionResultCache = HashMap<Instruction, HashMap<Option<ValueId>, Vec<ValueId>>>;
+type InstructionResultCache = HashMap<Instruction, HashMap<Option<ValueId>, ResultCache>>;
+
+/// Records the results of all duplicate [`Instruction`]s along with the blocks in which they sit.
+///
+/// For more information see [`InstructionResultCache`].
+#[derive(Default)]
+struct ResultCache {
+    results: Vec<(BasicBlockId, Vec<ValueId>)>,
+}
 
 impl Context {
+    fn new(function: &Function, use_constraint_info: bool) -> Self {
+        let dom = matches!(function.runtime(), RuntimeType::Brillig)
+            .then(|| DominatorTree::with_function(function));
+
+        Context {
+            use_constraint_info,
+            visited_blocks: HashSet::new(),
+            block_queue: Vec::new(),
+            cached_instruction_results: HashMap::default(),
+            dom,
+        }
+    }
+
     fn fold_constants_in_block(&mut self, function: &mut Function, block: BasicBlockId) {
         let instructions = function.dfg[block].take_instructions();
 
-        // Cache of instructions without any side-effects along with their outputs.
-        let mut cached_instruction_results = HashMap::default();
-
         // Contains sets of values which are constrained to be equivalent to each other.
         //
         // The mapping's structure is `side_effects_enabled_var => (constrained_value => simplified_value)`.
@@ -117,7 +149,6 @@ impl Context {
                 &mut function.dfg,
                 block,
                 instruction_id,
-                &mut cached_instruction_results,
                 &mut constraint_simplification_mappings,
                 &mut side_effects_enabled_var,
             );
@@ -126,11 +157,10 @@ impl Context {
     }
 
     fn fold_constants_into_instruction(
-        &self,
+        &mut self,
         dfg: &mut DataFlowGraph,
         block: BasicBlockId,
         id: InstructionId,
-        instruction_result_cache: &mut InstructionResultCache,
         constraint_simplification_mappings: &mut HashMap<ValueId, HashMap<ValueId, ValueId>>,
         side_effects_enabled_var: &mut ValueId,
     ) {
@@ -139,10 +169,16 @@ impl Context {
         let instruction = Self::resolve_instruction(id, dfg, constraint_simplification_mapping);
         let old_results = dfg.instruction_results(id).to_vec();
 
-        // If a copy of this instruction exists earlier in the block, then reuse the previous results.
-        if let Some(cached_results) =
-            Self::get_cached(dfg, instruction_result_cache, &instruction, *side_effects_enabled_var)
-        {
+        // If a copy of this instruction exists earlier in the block (or in a dominating block for
+        // Brillig functions), then reuse the previous results.
+        if let Some(cached_results) = Self::get_cached(
+            dfg,
+            &mut self.cached_instruction_results,
+            self.dom.as_mut(),
+            &instruction,
+            *side_effects_enabled_var,
+            block,
+        ) {
             Self::replace_result_ids(dfg, &old_results, cached_results);
             return;
         }
@@ -156,9 +192,9 @@ impl Context {
             instruction.clone(),
             new_results,
             dfg,
-            instruction_result_cache,
             constraint_simplification_mapping,
             *side_effects_enabled_var,
+            block,
         );
 
         // If we just inserted an `Instruction::EnableSideEffectsIf`, we need to update `side_effects_enabled_var`
@@ -229,13 +265,13 @@ impl Context {
     }
 
     fn cache_instruction(
-        &self,
+        &mut self,
         instruction: Instruction,
         instruction_results: Vec<ValueId>,
         dfg: &DataFlowGraph,
-        instruction_result_cache: &mut InstructionResultCache,
         constraint_simplification_mapping: &mut HashMap<ValueId, ValueId>,
         side_effects_enabled_var: ValueId,
+        block: BasicBlockId,
     ) {
         if self.use_constraint_info {
             // If the instruction was a constraint, then create a link between the two `ValueId`s
@@ -267,16 +303,19 @@ impl Context {
         }
 
         // If the instruction doesn't have side-effects and if it won't interact with enable_side_effects during acir_gen,
-        // we cache the results so we can reuse them if the same instruction appears again later in the block.
+        // we cache the results so we can reuse them if the same instruction appears again later in the block
+        // (or in a block dominated by this one for Brillig functions).
         if instruction.can_be_deduplicated(dfg, self.use_constraint_info) {
             let use_predicate =
                 self.use_constraint_info && instruction.requires_acir_gen_predicate(dfg);
             let predicate = use_predicate.then_some(side_effects_enabled_var);
 
-            instruction_result_cache
+            self.cached_instruction_results
                 .entry(instruction)
                 .or_default()
-                .insert(predicate, instruction_results);
+                .entry(predicate)
+                .or_default()
+                .cache(block, instruction_results);
         }
     }
 
@@ -294,20 +333,44 @@ impl Context {
     fn get_cached<'a>(
         dfg: &DataFlowGraph,
         instruction_result_cache: &'a mut InstructionResultCache,
+        mut dom: Option<&mut DominatorTree>,
         instruction: &Instruction,
         side_effects_enabled_var: ValueId,
-    ) -> Option<&'a Vec<ValueId>> {
-        let results_for_instruction = instruction_result_cache.get(instruction);
+        block: BasicBlockId,
+    ) -> Option<&'a [ValueId]> {
+        let results_for_instruction = instruction_result_cache.get(instruction)?;
 
         // See if there's a cached version with no predicate first
-        if let Some(results) = results_for_instruction.and_then(|map| map.get(&None)) {
+        if let Some(results) = results_for_instruction
+            .get(&None)
+            .and_then(|cache| cache.get(block, dom.as_deref_mut()))
+        {
             return Some(results);
         }
 
         let predicate =
             instruction.requires_acir_gen_predicate(dfg).then_some(side_effects_enabled_var);
 
-        results_for_instruction.and_then(|map| map.get(&predicate))
+        results_for_instruction.get(&predicate)?.get(block, dom)
+    }
+}
+
+impl ResultCache {
+    /// Records that an `Instruction` in block `block` produced the result values `results`.
+    fn cache(&mut self, block: BasicBlockId, results: Vec<ValueId>) {
+        self.results.push((block, results));
+    }
+
+    /// Returns a set of [`ValueId`]s produced from a copy of this [`Instruction`] which sits
+    /// within a block which dominates `block`.
+    ///
+    /// If no dominator tree is provided then only results from `block` itself are returned.
+    fn get(&self, block: BasicBlockId, mut dom: Option<&mut DominatorTree>) -> Option<&[ValueId]> {
+        self.results.iter().find_map(|(origin_block, results)| {
+            let dominates = *origin_block == block
+                || dom.as_deref_mut().map_or(false, |dom| dom.dominates(*origin_block, block));
+            dominates.then_some(results.as_slice())
+        })
     }
 }
 
@@ -318,6 +381,7 @@ mod test {
     use crate::ssa::{
         function_builder::FunctionBuilder,
         ir::{
+            function::RuntimeType,
             instruction::{Binary, BinaryOp, Instruction, TerminatorInstruction},
             map::Id,
             types::Type,
@@ -843,4 +907,87 @@ mod test {
         let instructions = main.dfg[main.entry_block()].instructions();
         assert_eq!(instructions.len(), 10);
     }
+
+    #[test]
+    fn deduplicate_across_dominating_blocks_in_brillig() {
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: u1):
+        //     v2 = mul v0, u32 3
+        //     jmpif v1 then: b1, else: b2
+        //   b1():
+        //     v3 = mul v0, u32 3
+        //     v4 = add v0, u32 1
+        //     jmp b3()
+        //   b2():
+        //     v5 = add v0, u32 1
+        //     jmp b3()
+        //   b3():
+        //     v6 = mul v0, u32 3
+        //     v7 = add v0, u32 1
+        //     return v6, v7
+        // }
+        //
+        // `b0` dominates every other block so the multiplications in `b1` and `b3` can reuse `v2`.
+        // Neither `b1` nor `b2` dominates `b3` however, so the addition in `b3` must be kept.
+        let main_id = Id::test_new(0);
+
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_parameter(Type::bool());
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let one = builder.numeric_constant(1u128, Type::unsigned(32));
+        let three = builder.numeric_constant(3u128, Type::unsigned(32));
+
+        let v2 = builder.insert_binary(v0, BinaryOp::Mul, three);
+        builder.terminate_with_jmpif(v1, b1, b2);
+
+        builder.switch_to_block(b1);
+        let v3 = builder.insert_binary(v0, BinaryOp::Mul, three);
+        builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b3, vec![]);
+
+        builder.switch_to_block(b2);
+        builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b3, vec![]);
+
+        builder.switch_to_block(b3);
+        let v6 = builder.insert_binary(v0, BinaryOp::Mul, three);
+        let v7 = builder.insert_binary(v0, BinaryOp::Add, one);
+        builder.terminate_with_return(vec![v6, v7]);
+
+        let ssa = builder.finish();
+
+        // Expected output:
+        //
+        // brillig fn main f0 {
+        //   b0(v0: u32, v1: u1):
+        //     v2 = mul v0, u32 3
+        //     jmpif v1 then: b1, else: b2
+        //   b1():
+        //     v4 = add v0, u32 1
+        //     jmp b3()
+        //   b2():
+        //     v5 = add v0, u32 1
+        //     jmp b3()
+        //   b3():
+        //     v7 = add v0, u32 1
+        //     return v2, v7
+        // }
+        let ssa = ssa.fold_constants();
+        let main = ssa.main();
+
+        assert_eq!(main.dfg[main.entry_block()].instructions().len(), 1);
+        assert_eq!(main.dfg[b1].instructions().len(), 1);
+        assert_eq!(main.dfg[b2].instructions().len(), 1);
+        assert_eq!(main.dfg[b3].instructions().len(), 1);
+
+        assert_eq!(main.dfg.resolve(v3), main.dfg.resolve(v2));
+        assert_eq!(main.dfg.resolve(v6), main.dfg.resolve(v2));
+        assert_ne!(main.dfg.resolve(v7), main.dfg.resolve(v2));
+    }
 }
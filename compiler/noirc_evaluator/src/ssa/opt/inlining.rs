@@ -353,6 +353,7 @@ impl<'function> PerFunctionContext<'function> {
             Value::ForeignFunction(function) => {
                 self.context.builder.import_foreign_function(function)
             }
+            Value::UserBlackBox(name) => self.context.builder.import_user_black_box(name),
             Value::Array { array, typ } => {
                 let elements = array.iter().map(|value| self.translate_value(*value)).collect();
                 self.context.builder.array_constant(elements, typ.clone())
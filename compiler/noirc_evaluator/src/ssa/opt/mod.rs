@@ -6,6 +6,7 @@
 mod array_set;
 mod as_slice_length;
 mod assert_constant;
+mod bounds_check_elimination;
 mod constant_folding;
 mod defunctionalize;
 mod die;
@@ -17,6 +18,7 @@ mod rc;
 mod remove_bit_shifts;
 mod remove_enable_side_effects;
 mod remove_if_else;
+mod remove_unreachable;
 mod resolve_is_unconstrained;
 mod runtime_separation;
 mod simplify_cfg;
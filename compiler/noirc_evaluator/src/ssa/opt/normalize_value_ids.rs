@@ -189,6 +189,7 @@ impl IdMaps {
             }
             Value::Intrinsic(intrinsic) => new_function.dfg.import_intrinsic(*intrinsic),
             Value::ForeignFunction(name) => new_function.dfg.import_foreign_function(name),
+            Value::UserBlackBox(name) => new_function.dfg.import_user_black_box(name),
         }
     }
 }
@@ -0,0 +1,143 @@
+//! This module defines an SSA pass to remove any functions which are unreachable from the program's entry points.
+//!
+//! Passes such as inlining, runtime separation and constant folding can leave behind functions
+//! which are no longer called from anywhere. Removing these avoids generating ACIR or Brillig
+//! bytecode for them which would never be executed.
+use std::collections::BTreeSet;
+
+use crate::ssa::{
+    ir::{
+        function::{Function, FunctionId},
+        value::{Value, ValueId},
+    },
+    ssa_gen::Ssa,
+};
+
+impl Ssa {
+    /// Removes any functions which cannot be reached from `main` or another ACIR entry point.
+    ///
+    /// A function is considered reachable if it is referenced from within a reachable function,
+    /// whether that is as the target of a call or as a function value.
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(crate) fn remove_unreachable_functions(mut self) -> Ssa {
+        let mut reachable_functions = BTreeSet::new();
+
+        let mut functions_to_visit: Vec<FunctionId> = std::iter::once(self.main_id)
+            .chain(self.entry_point_to_generated_index.keys().copied())
+            .collect();
+
+        while let Some(function_id) = functions_to_visit.pop() {
+            if !reachable_functions.insert(function_id) {
+                continue;
+            }
+
+            let function = &self.functions[&function_id];
+            functions_to_visit.extend(
+                used_functions(function)
+                    .into_iter()
+                    .filter(|function_id| !reachable_functions.contains(function_id)),
+            );
+        }
+
+        self.functions.retain(|function_id, _| reachable_functions.contains(function_id));
+        self
+    }
+}
+
+/// Returns the ids of every function referenced from within the reachable blocks of `func`.
+fn used_functions(func: &Function) -> BTreeSet<FunctionId> {
+    let mut used_function_ids = BTreeSet::default();
+
+    let mut find_functions = |value: ValueId| {
+        if let Value::Function(function_id) = func.dfg[func.dfg.resolve(value)] {
+            used_function_ids.insert(function_id);
+        }
+    };
+
+    for block_id in func.reachable_blocks() {
+        let block = &func.dfg[block_id];
+        for instruction_id in block.instructions() {
+            func.dfg[*instruction_id].for_each_value(&mut find_functions);
+        }
+        block.unwrap_terminator().for_each_value(&mut find_functions);
+    }
+
+    used_function_ids
+}
+
+#[cfg(test)]
+mod test {
+    use noirc_frontend::monomorphization::ast::InlineType;
+
+    use crate::ssa::{
+        function_builder::FunctionBuilder,
+        ir::{map::Id, types::Type},
+    };
+
+    #[test]
+    fn remove_unused_brillig_functions() {
+        // acir(inline) fn main f0 {
+        //   b0():
+        //     v0 = call f1()
+        //     return v0
+        // }
+        // brillig fn used f1 {
+        //   b0():
+        //     return Field 1
+        // }
+        // brillig fn unused f2 {
+        //   b0():
+        //     v0 = call f1()
+        //     return v0
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+
+        let used_id = Id::test_new(1);
+        let used = builder.import_function(used_id);
+        let results = builder.insert_call(used, Vec::new(), vec![Type::field()]).to_vec();
+        builder.terminate_with_return(results);
+
+        builder.new_brillig_function("used".into(), used_id);
+        let one = builder.field_constant(1u128);
+        builder.terminate_with_return(vec![one]);
+
+        let unused_id = Id::test_new(2);
+        builder.new_brillig_function("unused".into(), unused_id);
+        let used = builder.import_function(used_id);
+        let results = builder.insert_call(used, Vec::new(), vec![Type::field()]).to_vec();
+        builder.terminate_with_return(results);
+
+        let ssa = builder.finish();
+        assert_eq!(ssa.functions.len(), 3);
+
+        let ssa = ssa.remove_unreachable_functions();
+        assert_eq!(ssa.functions.len(), 2);
+        assert!(ssa.functions.contains_key(&main_id));
+        assert!(ssa.functions.contains_key(&used_id));
+    }
+
+    #[test]
+    fn keep_unused_acir_entry_points() {
+        // acir(inline) fn main f0 {
+        //   b0():
+        //     return Field 1
+        // }
+        // acir(fold) fn entry_point f1 {
+        //   b0():
+        //     return Field 1
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        let one = builder.field_constant(1u128);
+        builder.terminate_with_return(vec![one]);
+
+        let entry_point_id = Id::test_new(1);
+        builder.new_function("entry_point".into(), entry_point_id, InlineType::Fold);
+        let one = builder.field_constant(1u128);
+        builder.terminate_with_return(vec![one]);
+
+        let ssa = builder.finish().remove_unreachable_functions();
+        assert_eq!(ssa.functions.len(), 2);
+    }
+}
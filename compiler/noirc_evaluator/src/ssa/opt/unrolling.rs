@@ -12,8 +12,12 @@
 //!
 //! Note that this pass also often creates superfluous jmp instructions in the
 //! program that will need to be removed by a later simplify cfg pass.
-//! Note also that unrolling is skipped for Brillig runtime and as a result
-//! we remove reference count instructions because they are only used by Brillig bytecode
+//!
+//! Brillig functions do not need to be fully unrolled and generally prefer smaller code over faster code,
+//! so only small innermost loops with constant bounds are fully unrolled. Larger loops are either
+//! partially unrolled by a factor which evenly divides their iteration count, or left intact.
+//! As ACIR functions are always fully unrolled, we remove reference count instructions from them
+//! because they are only used by Brillig bytecode.
 use std::collections::HashSet;
 
 use acvm::acir::AcirField;
@@ -28,15 +32,24 @@ use crate::{
             dom::DominatorTree,
             function::{Function, RuntimeType},
             function_inserter::FunctionInserter,
-            instruction::{Instruction, TerminatorInstruction},
+            instruction::{Binary, BinaryOp, Instruction, TerminatorInstruction},
             post_order::PostOrder,
-            value::ValueId,
+            value::{Value, ValueId},
         },
         ssa_gen::Ssa,
     },
 };
 use fxhash::FxHashMap as HashMap;
 
+/// The maximum number of instructions which unrolling a Brillig loop may produce for the loop body.
+///
+/// Loops whose fully unrolled body would be larger than this are instead partially unrolled
+/// using the largest factor from [`BRILLIG_PARTIAL_UNROLL_FACTORS`] that keeps within this limit.
+const BRILLIG_MAX_UNROLLED_INSTRUCTIONS: usize = 32;
+
+/// The factors by which a Brillig loop may be partially unrolled, in order of preference.
+const BRILLIG_PARTIAL_UNROLL_FACTORS: [u128; 3] = [8, 4, 2];
+
 impl Ssa {
     /// Loop unrolling can return errors, since ACIR functions need to be fully unrolled.
     /// This meta-pass will keep trying to unroll loops and simplifying the SSA until no more errors are found.
@@ -111,10 +124,13 @@ impl Function {
     }
 
     pub(crate) fn try_to_unroll_loops(&mut self, errors: &mut Vec<RuntimeError>) {
-        // Loop unrolling in brillig can lead to a code explosion currently. This can
+        // Loop unrolling in brillig can lead to a code explosion. This can
         // also be true for ACIR, but we have no alternative to unrolling in ACIR.
-        // Brillig also generally prefers smaller code rather than faster code.
-        if self.runtime() != RuntimeType::Brillig {
+        // Brillig also generally prefers smaller code rather than faster code so we only
+        // unroll loops which are small enough, and never fail if a loop can't be unrolled.
+        if self.runtime() == RuntimeType::Brillig {
+            find_all_loops(self).unroll_brillig_loops(self);
+        } else {
             errors.extend(find_all_loops(self).unroll_each_loop(self));
         }
     }
@@ -135,6 +151,7 @@ struct Loop {
 
 struct Loops {
     /// The loops that failed to be unrolled so that we do not try to unroll them again.
+    /// This also includes Brillig loops which have been partially unrolled or deliberately left intact.
     /// Each loop is identified by its header block id.
     failed_to_unroll: HashSet<BasicBlockId>,
 
@@ -198,15 +215,85 @@ impl Loops {
             if !self.failed_to_unroll.contains(&next_loop.header) {
                 match unroll_loop(function, &self.cfg, &next_loop) {
                     Ok(_) => self.modified_blocks.extend(next_loop.blocks),
-                    Err(call_stack) => {
+                    Err(error) => {
                         self.failed_to_unroll.insert(next_loop.header);
-                        unroll_errors.push(RuntimeError::UnknownLoopBound { call_stack });
+                        unroll_errors.push(error);
                     }
                 }
             }
         }
         unroll_errors
     }
+
+    /// Unroll the loops within a Brillig function which are small enough that unrolling them
+    /// does not cause a large increase in code size.
+    ///
+    /// Only innermost loops with constant bounds are considered. These are fully unrolled if the
+    /// unrolled body is within [`BRILLIG_MAX_UNROLLED_INSTRUCTIONS`], partially unrolled if a suitable
+    /// factor from [`BRILLIG_PARTIAL_UNROLL_FACTORS`] exists, and left intact otherwise.
+    fn unroll_brillig_loops(mut self, function: &mut Function) {
+        let loop_headers: HashSet<_> =
+            self.yet_to_unroll.iter().map(|loop_| loop_.header).collect();
+
+        // Unlike in ACIR, inner loops are handled first here as outer loops are only unrolled
+        // once they no longer contain any other loops.
+        self.yet_to_unroll.reverse();
+
+        while let Some(next_loop) = self.yet_to_unroll.pop() {
+            // If we've previously modified a block in this loop we need to refresh the context.
+            if next_loop.blocks.iter().any(|block| self.modified_blocks.contains(block)) {
+                let mut new_context = find_all_loops(function);
+                new_context.failed_to_unroll = self.failed_to_unroll;
+                return new_context.unroll_brillig_loops(function);
+            }
+
+            if self.failed_to_unroll.contains(&next_loop.header) {
+                continue;
+            }
+
+            let is_innermost = next_loop
+                .blocks
+                .iter()
+                .all(|block| *block == next_loop.header || !loop_headers.contains(block));
+            let iteration_count = get_constant_iteration_count(function, &self.cfg, &next_loop);
+
+            let (Some(iteration_count), true) = (iteration_count, is_innermost) else {
+                self.failed_to_unroll.insert(next_loop.header);
+                continue;
+            };
+
+            let loop_size: usize = next_loop
+                .blocks
+                .iter()
+                .map(|block| function.dfg[*block].instructions().len())
+                .sum();
+            let unrolled_size = |factor: u128| {
+                usize::try_from(factor).ok().and_then(|factor| factor.checked_mul(loop_size))
+            };
+            let fits = |factor| {
+                unrolled_size(factor)
+                    .map_or(false, |size| size <= BRILLIG_MAX_UNROLLED_INSTRUCTIONS)
+            };
+
+            if fits(iteration_count) {
+                match unroll_loop(function, &self.cfg, &next_loop) {
+                    Ok(_) => self.modified_blocks.extend(next_loop.blocks),
+                    Err(_) => {
+                        self.failed_to_unroll.insert(next_loop.header);
+                    }
+                }
+            } else {
+                let factor = BRILLIG_PARTIAL_UNROLL_FACTORS.into_iter().find(|factor| {
+                    iteration_count > *factor && iteration_count % factor == 0 && fits(*factor)
+                });
+                if let Some(factor) = factor {
+                    partially_unroll_loop(function, &next_loop, factor);
+                    self.modified_blocks.extend(next_loop.blocks.iter().copied());
+                }
+                self.failed_to_unroll.insert(next_loop.header);
+            }
+        }
+    }
 }
 
 /// Return each block that is in a loop starting in the given header block.
@@ -246,7 +333,7 @@ fn unroll_loop(
     function: &mut Function,
     cfg: &ControlFlowGraph,
     loop_: &Loop,
-) -> Result<(), CallStack> {
+) -> Result<(), RuntimeError> {
     let mut unroll_into = get_pre_header(cfg, loop_);
     let mut jump_value = get_induction_variable(function, unroll_into)?;
 
@@ -259,6 +346,46 @@ fn unroll_loop(
     Ok(())
 }
 
+/// Partially unroll a single loop in the function so that each iteration of the resulting loop
+/// performs `factor` iterations of the original loop.
+///
+/// This expects the loop's iteration count to be known and to be a multiple of `factor` so that
+/// the loop condition only needs to be checked once every `factor` iterations.
+fn partially_unroll_loop(function: &mut Function, loop_: &Loop, factor: u128) {
+    let (mut jump_value, call_stack) = match function.dfg[loop_.back_edge_start].unwrap_terminator()
+    {
+        TerminatorInstruction::Jmp { arguments, call_stack, .. } => {
+            (arguments[0], call_stack.clone())
+        }
+        other => {
+            unreachable!("Expected loop back-edge to be a jmp, but found {other:?} instead")
+        }
+    };
+
+    // The additional iterations are unrolled into a fresh block rather than the back-edge block
+    // itself, as the original loop blocks must stay intact while they are being copied.
+    let first_block = function.dfg.make_block();
+    let mut unroll_into = first_block;
+    for _ in 1..factor {
+        let context = unroll_entered_loop_header(function, loop_, unroll_into, jump_value);
+        let (last_block, last_value) = context.unroll_loop_iteration();
+        unroll_into = last_block;
+        jump_value = last_value;
+    }
+
+    // The final copy of the loop body jumps back to the original loop header.
+    let jmp = TerminatorInstruction::Jmp {
+        destination: loop_.header,
+        arguments: vec![jump_value],
+        call_stack: call_stack.clone(),
+    };
+    function.dfg.set_block_terminator(unroll_into, jmp);
+
+    let jmp =
+        TerminatorInstruction::Jmp { destination: first_block, arguments: Vec::new(), call_stack };
+    function.dfg.set_block_terminator(loop_.back_edge_start, jmp);
+}
+
 /// The loop pre-header is the block that comes before the loop begins. Generally a header block
 /// is expected to have 2 predecessors: the pre-header and the final block of the loop which jumps
 /// back to the beginning.
@@ -276,7 +403,10 @@ fn get_pre_header(cfg: &ControlFlowGraph, loop_: &Loop) -> BasicBlockId {
 ///
 /// Expects the current block to terminate in `jmp h(N)` where h is the loop header and N is
 /// a Field value.
-fn get_induction_variable(function: &Function, block: BasicBlockId) -> Result<ValueId, CallStack> {
+fn get_induction_variable(
+    function: &Function,
+    block: BasicBlockId,
+) -> Result<ValueId, RuntimeError> {
     match function.dfg[block].terminator() {
         Some(TerminatorInstruction::Jmp { arguments, call_stack: location, .. }) => {
             // This assumption will no longer be valid if e.g. mutable variables are represented as
@@ -288,11 +418,135 @@ fn get_induction_variable(function: &Function, block: BasicBlockId) -> Result<Va
             if function.dfg.get_numeric_constant(value).is_some() {
                 Ok(value)
             } else {
-                Err(location.clone())
+                Err(RuntimeError::UnknownLoopBound {
+                    call_stack: location.clone(),
+                    value_call_stack: find_dynamic_value_origin(&function.dfg, value),
+                })
+            }
+        }
+        Some(terminator) => Err(RuntimeError::UnknownLoopBound {
+            call_stack: terminator.call_stack(),
+            value_call_stack: CallStack::new(),
+        }),
+        None => Err(RuntimeError::UnknownLoopBound {
+            call_stack: CallStack::new(),
+            value_call_stack: CallStack::new(),
+        }),
+    }
+}
+
+/// Returns the number of iterations of a Brillig loop if it has the shape of a `for` loop with
+/// constant bounds: `jmp h(start)`, `h(i): jmpif lt(i, end)` and a single back-edge `jmp h(add(i, 1))`.
+///
+/// Loops with any other shape, such as those containing a `break` or `continue`, return `None`.
+fn get_constant_iteration_count(
+    function: &Function,
+    cfg: &ControlFlowGraph,
+    loop_: &Loop,
+) -> Option<u128> {
+    let dfg = &function.dfg;
+
+    // The only way into the loop header should be from the pre-header and the single back-edge,
+    // and the only way out of the loop should be through the header.
+    if cfg.predecessors(loop_.header).len() != 2 {
+        return None;
+    }
+    let exits_loop = |block: &BasicBlockId| {
+        *block != loop_.header
+            && cfg.successors(*block).any(|successor| !loop_.blocks.contains(&successor))
+    };
+    if loop_.blocks.iter().any(exits_loop) {
+        return None;
+    }
+
+    let [induction_variable] = dfg[loop_.header].parameters() else {
+        return None;
+    };
+    let induction_variable = *induction_variable;
+
+    let pre_header = get_pre_header(cfg, loop_);
+    let start = match dfg[pre_header].terminator() {
+        Some(TerminatorInstruction::Jmp { arguments, .. }) if arguments.len() == 1 => {
+            dfg.get_numeric_constant(arguments[0])?
+        }
+        _ => return None,
+    };
+
+    let end = match dfg[loop_.header].terminator() {
+        Some(TerminatorInstruction::JmpIf { condition, then_destination, .. })
+            if loop_.blocks.contains(then_destination) =>
+        {
+            let (lhs, end) = get_binary_operands(dfg, *condition, BinaryOp::Lt)?;
+            if lhs != induction_variable {
+                return None;
+            }
+            dfg.get_numeric_constant(end)?
+        }
+        _ => return None,
+    };
+
+    match dfg[loop_.back_edge_start].terminator() {
+        Some(TerminatorInstruction::Jmp { arguments, .. }) if arguments.len() == 1 => {
+            let (lhs, step) = get_binary_operands(dfg, arguments[0], BinaryOp::Add)?;
+            if lhs != induction_variable || !dfg.get_numeric_constant(step)?.is_one() {
+                return None;
+            }
+        }
+        _ => return None,
+    }
+
+    if start.num_bits() > 128 || end.num_bits() > 128 {
+        return None;
+    }
+    Some(end.to_u128().saturating_sub(start.to_u128()))
+}
+
+/// If `value` is the result of a binary instruction with the operator `operator`,
+/// returns the resolved operands of that instruction.
+fn get_binary_operands(
+    dfg: &DataFlowGraph,
+    value: ValueId,
+    operator: BinaryOp,
+) -> Option<(ValueId, ValueId)> {
+    let Value::Instruction { instruction, .. } = &dfg[dfg.resolve(value)] else {
+        return None;
+    };
+    match &dfg[*instruction] {
+        Instruction::Binary(Binary { lhs, operator: op, rhs }) if *op == operator => {
+            Some((dfg.resolve(*lhs), dfg.resolve(*rhs)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the call stack of the location where the non-constant `value` originates.
+///
+/// Arithmetic on non-constant values is followed back to its inputs so that the user is pointed
+/// towards the source of the dynamic value (e.g. a function parameter, a mutable variable or a call)
+/// rather than an intermediate computation.
+fn find_dynamic_value_origin(dfg: &DataFlowGraph, value: ValueId) -> CallStack {
+    let mut value = dfg.resolve(value);
+    loop {
+        let Value::Instruction { instruction, .. } = &dfg[value] else {
+            return dfg.get_value_call_stack(value);
+        };
+        let input = match &dfg[*instruction] {
+            Instruction::Binary(Binary { lhs, rhs, .. }) => {
+                [*lhs, *rhs].into_iter().find(|input| !dfg.is_constant(*input))
+            }
+            Instruction::Cast(input, _)
+            | Instruction::Not(input)
+            | Instruction::Truncate { value: input, .. } => Some(*input),
+            _ => None,
+        };
+
+        // Stop once the next input has no location to point at.
+        match input {
+            Some(input) if !dfg.get_value_call_stack(input).is_empty() => {
+                value = dfg.resolve(input);
             }
+            _ => return dfg.get_call_stack(*instruction),
         }
-        Some(terminator) => Err(terminator.call_stack()),
-        None => Err(CallStack::new()),
     }
 }
 
@@ -304,7 +558,7 @@ fn unroll_loop_header<'a>(
     loop_: &'a Loop,
     unroll_into: BasicBlockId,
     induction_value: ValueId,
-) -> Result<Option<LoopIteration<'a>>, CallStack> {
+) -> Result<Option<LoopIteration<'a>>, RuntimeError> {
     // We insert into a fresh block first and move instructions into the unroll_into block later
     // only once we verify the jmpif instruction has a constant condition. If it does not, we can
     // just discard this fresh block and leave the loop unmodified.
@@ -337,13 +591,51 @@ fn unroll_loop_header<'a>(
             } else {
                 // If this case is reached the loop either uses non-constant indices or we need
                 // another pass, such as mem2reg to resolve them to constants.
-                Err(context.inserter.function.dfg.get_value_call_stack(condition))
+                let dfg = context.dfg();
+                Err(RuntimeError::UnknownLoopBound {
+                    call_stack: dfg.get_value_call_stack(condition),
+                    value_call_stack: find_dynamic_value_origin(dfg, condition),
+                })
             }
         }
         other => unreachable!("Expected loop header to terminate in a JmpIf to the loop body, but found {other:?} instead"),
     }
 }
 
+/// Unrolls the header block of the loop for an iteration which is known to enter the loop body,
+/// regardless of whether the loop condition can be evaluated at compile-time.
+///
+/// This is used when partially unrolling a loop, where the loop condition is known to hold for
+/// all iterations but the first of each group of unrolled iterations.
+fn unroll_entered_loop_header<'a>(
+    function: &'a mut Function,
+    loop_: &'a Loop,
+    unroll_into: BasicBlockId,
+    induction_value: ValueId,
+) -> LoopIteration<'a> {
+    let fresh_block = function.dfg.make_block();
+
+    let mut context = LoopIteration::new(function, loop_, fresh_block, loop_.header);
+    let source_block = &context.dfg()[context.source_block];
+    assert_eq!(source_block.parameters().len(), 1, "Expected only 1 argument in loop header");
+
+    let first_param = source_block.parameters()[0];
+    context.inserter.try_map_value(first_param, induction_value);
+    context.inline_instructions_from_block();
+
+    match context.dfg()[fresh_block].unwrap_terminator() {
+        TerminatorInstruction::JmpIf { then_destination, call_stack, .. } => {
+            let (then_destination, call_stack) = (*then_destination, call_stack.clone());
+            context.jump_to(then_destination, call_stack);
+        }
+        other => unreachable!("Expected loop header to terminate in a JmpIf to the loop body, but found {other:?} instead"),
+    }
+
+    context.dfg_mut().inline_block(fresh_block, unroll_into);
+    context.insert_block = unroll_into;
+    context
+}
+
 /// The context object for each loop iteration.
 /// Notably each loop iteration maps each loop block to a fresh, unrolled block.
 struct LoopIteration<'f> {
@@ -465,17 +757,22 @@ impl<'f> LoopIteration<'f> {
                 let destination =
                     if constant.is_zero() { else_destination } else { then_destination };
 
-                self.source_block = self.get_original_block(destination);
-
-                let arguments = Vec::new();
-                let jmp = TerminatorInstruction::Jmp { destination, arguments, call_stack };
-                self.inserter.function.dfg.set_block_terminator(self.insert_block, jmp);
+                self.jump_to(destination, call_stack);
                 vec![destination]
             }
             None => vec![then_destination, else_destination],
         }
     }
 
+    /// Replaces the terminator of the current block with an unconditional jump to `destination`.
+    fn jump_to(&mut self, destination: BasicBlockId, call_stack: CallStack) {
+        self.source_block = self.get_original_block(destination);
+
+        let arguments = Vec::new();
+        let jmp = TerminatorInstruction::Jmp { destination, arguments, call_stack };
+        self.inserter.function.dfg.set_block_terminator(self.insert_block, jmp);
+    }
+
     /// Translate a block id to a block id in the unrolled loop. If the given
     /// block id is not within the loop, it is returned as-is.
     fn get_or_insert_block(&mut self, block: BasicBlockId) -> BasicBlockId {
@@ -504,15 +801,18 @@ impl<'f> LoopIteration<'f> {
         let source_block = &self.dfg()[self.source_block];
         let instructions = source_block.instructions().to_vec();
 
+        let is_brillig_runtime = self.inserter.function.runtime() == RuntimeType::Brillig;
+
         // We cannot directly append each instruction since we need to substitute any
         // instances of the induction variable or any values that were changed as a result
         // of the new induction variable value.
         for instruction in instructions {
-            // Skip reference count instructions since they are only used for brillig, and brillig code is not unrolled
-            if !matches!(
+            // Skip reference count instructions in ACIR since they are only used for brillig
+            let is_rc_instruction = matches!(
                 self.dfg()[instruction],
                 Instruction::IncrementRc { .. } | Instruction::DecrementRc { .. }
-            ) {
+            );
+            if is_brillig_runtime || !is_rc_instruction {
                 self.inserter.push_instruction(instruction, self.insert_block);
             }
         }
@@ -536,9 +836,18 @@ impl<'f> LoopIteration<'f> {
 
 #[cfg(test)]
 mod tests {
+    use noirc_errors::{FileDiagnostic, Location, Span};
+
+    use crate::errors::RuntimeError;
     use crate::ssa::{
         function_builder::FunctionBuilder,
-        ir::{instruction::BinaryOp, map::Id, types::Type},
+        ir::{
+            function::{Function, RuntimeType},
+            instruction::{BinaryOp, Instruction, TerminatorInstruction},
+            map::Id,
+            types::Type,
+        },
+        ssa_gen::Ssa,
     };
 
     #[test]
@@ -712,4 +1021,179 @@ mod tests {
         let (_, errors) = ssa.try_to_unroll_loops();
         assert_eq!(errors.len(), 1, "Expected to fail to unroll loop");
     }
+
+    #[test]
+    fn unknown_loop_bound_points_at_dynamic_value() {
+        // fn main f0 {
+        //   b0(v0: Field):
+        //     v1 = add v0, Field 5      // bound_location
+        //     jmp b1(Field 0)           // loop_location
+        //   b1(v2: Field):
+        //     v3 = lt v2, v1
+        //     jmpif v3, then: b2, else: b3
+        //   b2():
+        //     v4 = add v2, Field 1
+        //     jmp b1(v4)
+        //   b3():
+        //     return Field 0
+        // }
+        let bound_location = Location { span: Span::inclusive(10, 20), ..Location::dummy() };
+        let loop_location = Location { span: Span::inclusive(30, 40), ..Location::dummy() };
+
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let v0 = builder.add_parameter(Type::field());
+        let v2 = builder.add_block_parameter(b1, Type::field());
+
+        let zero = builder.field_constant(0u128);
+        let one = builder.field_constant(1u128);
+        let five = builder.field_constant(5u128);
+
+        builder.set_location(bound_location);
+        let v1 = builder.insert_binary(v0, BinaryOp::Add, five);
+
+        builder.set_location(loop_location);
+        builder.terminate_with_jmp(b1, vec![zero]);
+
+        builder.switch_to_block(b1);
+        let v3 = builder.insert_binary(v2, BinaryOp::Lt, v1);
+        builder.terminate_with_jmpif(v3, b2, b3);
+
+        builder.switch_to_block(b2);
+        let v4 = builder.insert_binary(v2, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v4]);
+
+        builder.switch_to_block(b3);
+        builder.terminate_with_return(vec![zero]);
+
+        let (_, mut errors) = builder.finish().try_to_unroll_loops();
+        assert_eq!(errors.len(), 1, "Expected to fail to unroll loop");
+
+        let error = errors.remove(0);
+        assert!(matches!(error, RuntimeError::UnknownLoopBound { .. }));
+
+        let diagnostic = FileDiagnostic::from(error).diagnostic;
+        let primary = &diagnostic.secondaries[0];
+        assert_eq!(primary.span, loop_location.span);
+
+        let secondary = &diagnostic.secondaries[1];
+        assert_eq!(secondary.span, bound_location.span);
+        assert_eq!(
+            secondary.message,
+            "The loop bound depends on this value, which is not known at compile-time"
+        );
+    }
+
+    /// Builds a Brillig function containing the loop `for i in 2..2 + iterations { v0 * i; }`.
+    ///
+    /// The loop starts from 2 so that none of the multiplications are simplified away once unrolled.
+    fn brillig_loop(iterations: u128) -> Ssa {
+        // brillig fn main f0 {
+        //   b0(v0: u32):
+        //     jmp b1(u32 2)
+        //   b1(v1: u32):
+        //     v2 = lt v1, u32 {2 + iterations}
+        //     jmpif v2, then: b2, else: b3
+        //   b2():
+        //     v3 = mul v0, v1
+        //     v4 = add v1, u32 1
+        //     jmp b1(v4)
+        //   b3():
+        //     return
+        // }
+        let main_id = Id::test_new(0);
+        let mut builder = FunctionBuilder::new("main".into(), main_id);
+        builder.set_runtime(RuntimeType::Brillig);
+
+        let b1 = builder.insert_block();
+        let b2 = builder.insert_block();
+        let b3 = builder.insert_block();
+
+        let v0 = builder.add_parameter(Type::unsigned(32));
+        let v1 = builder.add_block_parameter(b1, Type::unsigned(32));
+
+        let start = builder.numeric_constant(2u128, Type::unsigned(32));
+        builder.terminate_with_jmp(b1, vec![start]);
+
+        builder.switch_to_block(b1);
+        let end = builder.numeric_constant(2 + iterations, Type::unsigned(32));
+        let v2 = builder.insert_binary(v1, BinaryOp::Lt, end);
+        builder.terminate_with_jmpif(v2, b2, b3);
+
+        builder.switch_to_block(b2);
+        builder.insert_binary(v0, BinaryOp::Mul, v1);
+        let one = builder.numeric_constant(1u128, Type::unsigned(32));
+        let v4 = builder.insert_binary(v1, BinaryOp::Add, one);
+        builder.terminate_with_jmp(b1, vec![v4]);
+
+        builder.switch_to_block(b3);
+        builder.terminate_with_return(vec![]);
+
+        builder.finish()
+    }
+
+    fn count_multiplications(function: &Function) -> usize {
+        function
+            .reachable_blocks()
+            .into_iter()
+            .flat_map(|block| function.dfg[block].instructions())
+            .filter(|instruction| {
+                matches!(
+                    &function.dfg[**instruction],
+                    Instruction::Binary(binary) if binary.operator == BinaryOp::Mul
+                )
+            })
+            .count()
+    }
+
+    fn contains_loop(function: &Function) -> bool {
+        function.reachable_blocks().into_iter().any(|block| {
+            matches!(function.dfg[block].terminator(), Some(TerminatorInstruction::JmpIf { .. }))
+        })
+    }
+
+    #[test]
+    fn fully_unroll_small_brillig_loop() {
+        // The loop has 3 instructions and 4 iterations, which is small enough to fully unroll.
+        let ssa = brillig_loop(4);
+
+        let (ssa, errors) = ssa.try_to_unroll_loops();
+        assert_eq!(errors.len(), 0);
+
+        let main = ssa.main();
+        assert!(!contains_loop(main), "Expected the loop to be fully unrolled");
+        assert_eq!(count_multiplications(main), 4);
+    }
+
+    #[test]
+    fn partially_unroll_brillig_loop() {
+        // Fully unrolling the 16 iterations would produce 48 instructions, so the loop
+        // should instead be unrolled by a factor of 8, leaving 2 iterations of the new loop.
+        let ssa = brillig_loop(16);
+
+        let (ssa, errors) = ssa.try_to_unroll_loops();
+        assert_eq!(errors.len(), 0);
+
+        let main = ssa.main();
+        assert!(contains_loop(main), "Expected the loop to remain");
+        assert_eq!(count_multiplications(main), 8);
+    }
+
+    #[test]
+    fn leave_large_brillig_loop_intact() {
+        // An iteration count of 17 has no factor which can be used to partially unroll the loop.
+        let ssa = brillig_loop(17);
+
+        let (ssa, errors) = ssa.try_to_unroll_loops();
+        assert_eq!(errors.len(), 0);
+
+        let main = ssa.main();
+        assert!(contains_loop(main), "Expected the loop to remain");
+        assert_eq!(count_multiplications(main), 1);
+    }
 }
@@ -178,12 +178,15 @@ impl<'a> FunctionContext<'a> {
             ast::Definition::Local(id) => self.lookup(*id),
             ast::Definition::Function(id) => self.get_or_queue_function(*id),
             ast::Definition::Oracle(name) => self.builder.import_foreign_function(name).into(),
-            ast::Definition::Builtin(name) | ast::Definition::LowLevel(name) => {
-                match self.builder.import_intrinsic(name) {
-                    Some(builtin) => builtin.into(),
-                    None => panic!("No builtin function named '{name}' found"),
-                }
-            }
+            ast::Definition::Builtin(name) => match self.builder.import_intrinsic(name) {
+                Some(builtin) => builtin.into(),
+                None => panic!("No builtin function named '{name}' found"),
+            },
+            // Any `#[foreign(name)]` function which isn't a known blackbox function is user-defined
+            ast::Definition::LowLevel(name) => match self.builder.import_intrinsic(name) {
+                Some(builtin) => builtin.into(),
+                None => self.builder.import_user_black_box(name).into(),
+            },
         }
     }
 
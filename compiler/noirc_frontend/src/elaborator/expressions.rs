@@ -464,6 +464,10 @@ impl<'context> Elaborator<'context> {
 
                 self.interner.push_expr_type(function_id, func_type.clone());
 
+                if let Some(current_item) = self.current_item {
+                    self.interner.add_function_dependency(current_item, func_id);
+                }
+
                 self.interner
                     .add_function_reference(func_id, Location::new(method_name_span, self.file));
 
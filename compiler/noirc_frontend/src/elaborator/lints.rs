@@ -7,13 +7,14 @@ use crate::{
     },
     hir_def::{
         expr::{HirExpression, HirIdent, HirLiteral},
-        function::FuncMeta,
+        function::{FuncMeta, INTRINSIC_FUNCTION_NAMES},
     },
     node_interner::NodeInterner,
     node_interner::{DefinitionKind, ExprId, FuncId, FunctionModifiers},
     Type,
 };
 
+use acvm::acir::BlackBoxFunc;
 use noirc_errors::{Span, Spanned};
 
 pub(super) fn deprecated_function(interner: &NodeInterner, expr: ExprId) -> Option<TypeCheckError> {
@@ -59,13 +60,20 @@ pub(super) fn inlining_attributes(
 }
 
 /// Attempting to define new low level (`#[builtin]` or `#[foreign]`) functions outside of the stdlib is disallowed.
+///
+/// The exception are `#[foreign]` functions which don't name one of ACIR's blackbox functions
+/// nor one of the compiler's intrinsics: these are user-defined blackbox functions which are
+/// solved by the backend's registry.
 pub(super) fn low_level_function_outside_stdlib(
     func: &FuncMeta,
     modifiers: &FunctionModifiers,
     crate_id: CrateId,
 ) -> Option<ResolverError> {
-    let is_low_level_function =
-        modifiers.attributes.function.as_ref().map_or(false, |func| func.is_low_level());
+    let is_low_level_function = modifiers.attributes.function.as_ref().map_or(false, |func| {
+        let is_user_black_box =
+            matches!(func.foreign(), Some(name) if is_user_black_box_name(name));
+        func.is_low_level() && !is_user_black_box
+    });
     if !crate_id.is_stdlib() && is_low_level_function {
         let ident = func_meta_name_ident(func, modifiers);
         Some(ResolverError::LowLevelFunctionOutsideOfStdlib { ident })
@@ -74,6 +82,14 @@ pub(super) fn low_level_function_outside_stdlib(
     }
 }
 
+/// Whether `name` is free to be used by a user-defined blackbox function.
+///
+/// Names of ACIR's blackbox functions (including the reserved `user_defined`) and of the
+/// compiler's intrinsics are resolved before user-defined blackbox functions during SSA generation.
+fn is_user_black_box_name(name: &str) -> bool {
+    BlackBoxFunc::lookup(name).is_none() && !INTRINSIC_FUNCTION_NAMES.contains(&name)
+}
+
 /// Oracle definitions (functions with the `#[oracle]` attribute) must be marked as unconstrained.
 pub(super) fn oracle_not_marked_unconstrained(
     func: &FuncMeta,
@@ -1,5 +1,4 @@
 use acvm::blackbox_solver::BlackBoxFunctionSolver;
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use im::Vector;
 use iter_extended::try_vecmap;
 use noirc_errors::Location;
@@ -43,8 +42,14 @@ fn poseidon2_permutation(
 
     let input = try_vecmap(input, |integer| get_field((integer, input_location)))?;
 
-    // Currently locked to only bn254!
-    let fields = Bn254BlackBoxSolver
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "bls12_381")] {
+            let solver = acvm::blackbox_solver::Bls12381BlackBoxSolver;
+        } else {
+            let solver = bn254_blackbox_solver::Bn254BlackBoxSolver;
+        }
+    };
+    let fields = solver
         .poseidon2_permutation(&input, state_length)
         .map_err(|error| InterpreterError::BlackBoxError(error, location))?;
 
@@ -28,6 +28,8 @@ pub enum ResolverError {
     UnusedVariable { ident: Ident },
     #[error("Unused {}", item.item_type())]
     UnusedItem { ident: Ident, item: UnusedItem },
+    #[error("Unreachable {}", item.item_type())]
+    UnreachableItem { ident: Ident, item: UnusedItem },
     #[error("Could not find variable in this scope")]
     VariableNotDeclared { name: String, span: Span },
     #[error("path is not an identifier")]
@@ -84,7 +86,7 @@ pub enum ResolverError {
     MisplacedRecursiveAttribute { ident: Ident },
     #[error("#[abi(tag)] attribute is only allowed in contracts")]
     AbiAttributeOutsideContract { span: Span },
-    #[error("Usage of the `#[foreign]` or `#[builtin]` function attributes are not allowed outside of the Noir standard library")]
+    #[error("Usage of the `#[builtin]` function attribute, or of `#[foreign]` with the name of a built-in blackbox function, is not allowed outside of the Noir standard library")]
     LowLevelFunctionOutsideOfStdlib { ident: Ident },
     #[error(
         "Usage of the `#[oracle]` function attribute is only valid on unconstrained functions"
@@ -193,6 +195,18 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
                 diagnostic.unnecessary = true;
                 diagnostic
             }
+            ResolverError::UnreachableItem { ident, item } => {
+                let name = &ident.0.contents;
+                let item_type = item.item_type();
+
+                let mut diagnostic = Diagnostic::simple_warning(
+                    format!("{item_type} `{name}` is never reachable from an entry point"),
+                    format!("{item_type} is not used by any entry point or test"),
+                    ident.span(),
+                );
+                diagnostic.unnecessary = true;
+                diagnostic
+            }
             ResolverError::VariableNotDeclared { name, span } => Diagnostic::simple_error(
                 format!("cannot find `{name}` in this scope "),
                 "not found in this scope".to_string(),
@@ -372,7 +386,7 @@ impl<'a> From<&'a ResolverError> for Diagnostic {
             },
             ResolverError::LowLevelFunctionOutsideOfStdlib { ident } => Diagnostic::simple_error(
                 "Definition of low-level function outside of standard library".into(),
-                "Usage of the `#[foreign]` or `#[builtin]` function attributes are not allowed outside of the Noir standard library".into(),
+                "Usage of the `#[builtin]` function attribute, or of `#[foreign]` with the name of a built-in blackbox function, is not allowed outside of the Noir standard library".into(),
                 ident.span(),
             ),
             ResolverError::OracleMarkedAsConstrained { ident } => Diagnostic::simple_warning(
@@ -12,6 +12,36 @@ use crate::node_interner::{ExprId, NodeInterner, StructId, TraitId, TraitImplId}
 use crate::token::CustomAttribute;
 use crate::{ResolvedGeneric, Type};
 
+/// Names of the `#[builtin]` and `#[foreign]` functions which are implemented by the compiler itself
+/// rather than by one of ACIR's blackbox functions.
+///
+/// This must be kept in sync with `Intrinsic::lookup` in `noirc_evaluator`, whose tests check both lists
+/// contain the same names.
+pub const INTRINSIC_FUNCTION_NAMES: &[&str] = &[
+    "array_len",
+    "array_as_str_unchecked",
+    "as_slice",
+    "assert_constant",
+    "static_assert",
+    "apply_range_constraint",
+    "slice_push_back",
+    "slice_push_front",
+    "slice_pop_back",
+    "slice_pop_front",
+    "slice_insert",
+    "slice_remove",
+    "str_as_bytes",
+    "to_le_radix",
+    "to_be_radix",
+    "to_le_bits",
+    "to_be_bits",
+    "from_field",
+    "as_field",
+    "as_witness",
+    "is_unconstrained",
+    "derive_pedersen_generators",
+];
+
 /// A Hir function is a block expression with a list of statements.
 /// If the function has yet to be resolved, the body starts off empty (None).
 #[derive(Debug, Clone)]
@@ -12,6 +12,7 @@ use petgraph::algo::tarjan_scc;
 use petgraph::prelude::DiGraph;
 use petgraph::prelude::NodeIndex as PetGraphIndex;
 use rustc_hash::FxHashMap as HashMap;
+use rustc_hash::FxHashSet as HashSet;
 
 use crate::ast::{
     ExpressionKind, Ident, LValue, Pattern, StatementKind, UnaryOp, UnresolvedTypeData,
@@ -2032,6 +2033,48 @@ impl NodeInterner {
         index
     }
 
+    /// Returns every item which can be reached from one of the given entry points
+    /// by following the dependency graph.
+    ///
+    /// Functions which may be called without being referenced by name are also treated as entry points.
+    /// These are trait methods and their implementations, which are only resolved during monomorphization,
+    /// and comptime functions which may be called from attributes. Similarly, as types and type aliases
+    /// may be used from within any type annotation, their dependencies are always considered reachable.
+    pub(crate) fn reachable_items(
+        &self,
+        entry_points: impl IntoIterator<Item = DependencyId>,
+    ) -> HashSet<DependencyId> {
+        let implicit_entry_points = self.func_meta.iter().filter_map(|(func_id, meta)| {
+            let is_trait_method = meta.trait_id.is_some() || meta.trait_impl.is_some();
+            let is_comptime = self
+                .function_modifiers
+                .get(func_id)
+                .map_or(false, |modifiers| modifiers.is_comptime);
+            (is_trait_method || is_comptime).then_some(DependencyId::Function(*func_id))
+        });
+        let types = self
+            .dependency_graph_indices
+            .keys()
+            .filter(|item| matches!(item, DependencyId::Struct(_) | DependencyId::Alias(_)));
+
+        let mut to_visit: Vec<_> =
+            entry_points.into_iter().chain(implicit_entry_points).chain(types.copied()).collect();
+
+        let mut reachable = HashSet::default();
+        while let Some(item) = to_visit.pop() {
+            if !reachable.insert(item) {
+                continue;
+            }
+
+            if let Some(index) = self.dependency_graph_indices.get(&item) {
+                let dependencies = self.dependency_graph.neighbors(*index);
+                to_visit.extend(dependencies.map(|index| self.dependency_graph[index]));
+            }
+        }
+
+        reachable
+    }
+
     pub(crate) fn check_for_dependency_cycles(&self) -> Vec<(CompilationError, FileId)> {
         let strongly_connected_components = tarjan_scc(&self.dependency_graph);
         let mut errors = Vec::new();
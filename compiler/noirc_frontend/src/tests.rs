@@ -3193,3 +3193,45 @@ fn trait_unconstrained_methods_typechecked_correctly() {
     println!("{errors:?}");
     assert_eq!(errors.len(), 0);
 }
+
+#[test]
+fn allows_user_defined_black_box_function_outside_stdlib() {
+    let src = r#"
+        #[foreign(my_hash)]
+        fn my_hash(_input: [Field; 2]) -> Field {}
+
+        fn main(x: Field) {
+            assert(my_hash([x, x]) != 0);
+        }
+    "#;
+    let errors = get_program_errors(src);
+    assert_eq!(errors.len(), 0, "Expected no errors, got {errors:?}");
+}
+
+#[test]
+fn rejects_foreign_function_with_reserved_name_outside_stdlib() {
+    for name in ["pedersen_hash", "user_defined", "array_len", "to_le_bits", "as_witness"] {
+        let src = format!(
+            r#"
+            #[foreign({name})]
+            fn foo(_input: Field) -> Field {{}}
+
+            fn main(x: Field) {{
+                assert(foo(x) != 0);
+            }}
+        "#
+        );
+        let errors = get_program_errors(&src);
+        assert_eq!(errors.len(), 1, "Expected one error for `{name}`, got {errors:?}");
+        assert!(
+            matches!(
+                errors[0].0,
+                CompilationError::ResolverError(
+                    ResolverError::LowLevelFunctionOutsideOfStdlib { .. }
+                )
+            ),
+            "Expected LowLevelFunctionOutsideOfStdlib for `{name}`, got {:?}",
+            errors[0].0
+        );
+    }
+}
@@ -1,9 +1,10 @@
 use crate::{
     hir::{def_collector::dc_crate::CompilationError, resolution::errors::ResolverError},
     tests::assert_no_errors,
+    usage_tracker::unreachable_items,
 };
 
-use super::get_program_errors;
+use super::{get_program, get_program_errors};
 
 #[test]
 fn errors_on_unused_private_import() {
@@ -262,3 +263,80 @@ fn warns_on_unused_global() {
     assert_eq!(ident.to_string(), "foo");
     assert_eq!(item.item_type(), "global");
 }
+
+/// Returns the names of the items in the program which are unreachable from `main`.
+fn get_unreachable_item_names(src: &str) -> Vec<String> {
+    let (_, context, errors) = get_program(src);
+    assert!(errors.is_empty(), "Expected no errors, got: {errors:?}");
+
+    let crate_id = *context.root_crate_id();
+    let def_map = context.def_map(&crate_id).unwrap();
+    let main = context.get_main_function(&crate_id);
+
+    let unreachable = unreachable_items(&context.def_interner, def_map, main, false);
+    let mut names: Vec<_> =
+        unreachable.into_values().flatten().map(|(ident, _)| ident.to_string()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn reports_items_only_referenced_from_unreachable_items() {
+    let src = r#"
+    global used_global = 1;
+    global unreachable_global = 2;
+
+    struct Foo {}
+
+    impl Foo {
+        fn method(self) -> Field {
+            used_function()
+        }
+    }
+
+    fn used_function() -> Field {
+        used_global
+    }
+
+    fn unreachable_function() -> Field {
+        unreachable_helper() + unreachable_global
+    }
+
+    fn unreachable_helper() -> Field {
+        unreachable_function()
+    }
+
+    fn main() -> pub Field {
+        let foo = Foo {};
+        foo.method()
+    }
+    "#;
+
+    let names = get_unreachable_item_names(src);
+    assert_eq!(names, vec!["unreachable_function", "unreachable_global", "unreachable_helper"]);
+}
+
+#[test]
+fn does_not_report_items_used_through_traits() {
+    let src = r#"
+    trait Double {
+        fn double(self) -> Self;
+    }
+
+    impl Double for Field {
+        fn double(self) -> Field {
+            helper(self)
+        }
+    }
+
+    fn helper(x: Field) -> Field {
+        x * 2
+    }
+
+    fn main(x: Field) -> pub Field {
+        x.double()
+    }
+    "#;
+
+    assert!(get_unreachable_item_names(src).is_empty());
+}
@@ -2,8 +2,8 @@ use std::collections::HashMap;
 
 use crate::{
     ast::{Ident, ItemVisibility},
-    hir::def_map::ModuleId,
-    node_interner::{FuncId, GlobalId, StructId, TraitId, TypeAliasId},
+    hir::def_map::{CrateDefMap, LocalModuleId, ModuleDefId, ModuleId},
+    node_interner::{DependencyId, FuncId, GlobalId, NodeInterner, StructId, TraitId, TypeAliasId},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -17,6 +17,16 @@ pub enum UnusedItem {
 }
 
 impl UnusedItem {
+    fn dependency_id(&self) -> Option<DependencyId> {
+        match self {
+            UnusedItem::Function(func_id) => Some(DependencyId::Function(*func_id)),
+            UnusedItem::Struct(struct_id) => Some(DependencyId::Struct(*struct_id)),
+            UnusedItem::TypeAlias(alias_id) => Some(DependencyId::Alias(*alias_id)),
+            UnusedItem::Global(global_id) => Some(DependencyId::Global(*global_id)),
+            UnusedItem::Import | UnusedItem::Trait(_) => None,
+        }
+    }
+
     pub fn item_type(&self) -> &'static str {
         match self {
             UnusedItem::Import => "import",
@@ -77,3 +87,50 @@ impl UsageTracker {
         &self.unused_items
     }
 }
+
+/// Returns the functions and globals defined in the crate described by `def_map` which are not
+/// reachable from any of the given entry points, grouped by the module they are defined in.
+///
+/// Unlike the items tracked by [UsageTracker], this includes items which are referenced by name
+/// but only from other unreachable items. If `public_items_are_entry_points` is set, as is the case
+/// for libraries, each public item is also treated as an entry point.
+pub fn unreachable_items(
+    interner: &NodeInterner,
+    def_map: &CrateDefMap,
+    entry_points: impl IntoIterator<Item = FuncId>,
+    public_items_are_entry_points: bool,
+) -> HashMap<ModuleId, Vec<(Ident, UnusedItem)>> {
+    let mut items = Vec::new();
+    for (local_id, module) in def_map.modules().iter() {
+        let module_id = ModuleId { krate: def_map.krate(), local_id: LocalModuleId(local_id) };
+        for (name, scope) in module.definitions().values() {
+            let Some((definition, visibility, _)) = scope.get(&None) else {
+                continue;
+            };
+            let item = match definition {
+                ModuleDefId::FunctionId(func_id) => UnusedItem::Function(*func_id),
+                ModuleDefId::GlobalId(global_id) => UnusedItem::Global(*global_id),
+                _ => continue,
+            };
+            items.push((module_id, name, item, *visibility));
+        }
+    }
+
+    let public_items = items
+        .iter()
+        .filter(|(_, _, _, visibility)| {
+            public_items_are_entry_points && *visibility == ItemVisibility::Public
+        })
+        .filter_map(|(_, _, item, _)| item.dependency_id());
+    let entry_points = entry_points.into_iter().map(DependencyId::Function).chain(public_items);
+    let reachable = interner.reachable_items(entry_points);
+
+    let mut unreachable: HashMap<ModuleId, Vec<(Ident, UnusedItem)>> = HashMap::new();
+    for (module_id, name, item, _) in items {
+        let is_reachable = item.dependency_id().map_or(true, |id| reachable.contains(&id));
+        if !is_reachable {
+            unreachable.entry(module_id).or_default().push((name.clone(), item));
+        }
+    }
+    unreachable
+}
@@ -80,6 +80,15 @@ pub enum ForeignCallError {
 
     #[error("Assert message resolved after an unsatisified constrain. {0}")]
     ResolvedAssertMessage(String),
+
+    #[error("Foreign call diverged from the recorded transcript. {0}")]
+    TranscriptDivergence(String),
+
+    #[error("Oracle mocks were not called as many times as expected: {0}")]
+    UnsatisfiedMocks(String),
+
+    #[error("No handler could be found for foreign call `{0}`")]
+    NoHandler(String),
 }
 
 impl<F: AcirField> TryFrom<&[ForeignCallParam<F>]> for PrintableValueDisplay<F> {
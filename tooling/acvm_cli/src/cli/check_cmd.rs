@@ -0,0 +1,60 @@
+use acir::circuit::ResolvedAssertionPayload;
+use acir::FieldElement;
+use acvm::pwg::OpcodeResolutionError;
+use acvm::{check_witness, WitnessCheckError};
+use clap::Args;
+
+use super::FieldBlackBoxSolver;
+use crate::cli::fs::inputs::{read_bytecode_from_file, read_program_from_bytecode};
+use crate::cli::fs::witness::read_witness_stack_from_file;
+use crate::errors::CliError;
+
+/// Checks that a witness satisfies every constraint of a circuit
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CheckCommand {
+    /// The name of the file containing the witness stack to check, e.g. as written by `acvm execute`
+    #[clap(long)]
+    witness: String,
+
+    /// The name of the binary file containing circuit bytecode, or of a file containing
+    /// the circuit in the textual ACIR assembly format
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+}
+
+pub(crate) fn run(args: CheckCommand) -> Result<(), CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program = read_program_from_bytecode(&bytecode)?;
+    let witness_stack = read_witness_stack_from_file(&args.working_directory, &args.witness)?;
+
+    match check_witness(&program, &witness_stack, &FieldBlackBoxSolver::default()) {
+        Ok(()) => {
+            println!("Witness satisfies all constraints of the circuit");
+            Ok(())
+        }
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", format_error(error));
+            }
+            Err(CliError::UnsatisfiedWitness(errors.len()))
+        }
+    }
+}
+
+fn format_error(error: &WitnessCheckError<FieldElement>) -> String {
+    match error {
+        WitnessCheckError::UnsatisfiedOpcode {
+            error:
+                OpcodeResolutionError::UnsatisfiedConstrain {
+                    payload: Some(ResolvedAssertionPayload::String(message)),
+                    ..
+                },
+            ..
+        } => format!("{error}: {message}"),
+        _ => error.to_string(),
+    }
+}
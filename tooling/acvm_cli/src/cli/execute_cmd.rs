@@ -1,16 +1,31 @@
 use std::io::{self, Write};
 
-use acir::circuit::Program;
 use acir::native_types::{WitnessMap, WitnessStack};
 use acir::FieldElement;
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
-use clap::Args;
+use clap::{Args, ValueEnum};
 
-use crate::cli::fs::inputs::{read_bytecode_from_file, read_inputs_from_file};
+use super::FieldBlackBoxSolver;
+use crate::cli::fs::inputs::{
+    read_bytecode_from_file, read_inputs_from_file, read_program_from_bytecode,
+};
 use crate::errors::CliError;
 use nargo::ops::{execute_program, DefaultForeignCallExecutor};
+use nargo::NargoError;
 
-use super::fs::witness::{create_output_witness_string, save_witness_to_dir};
+use super::fs::witness::{
+    create_output_witness_stack_json, create_output_witness_string, save_witness_json_to_dir,
+    save_witness_to_dir,
+};
+
+/// The format in which the execution witness is written and printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub(crate) enum WitnessFormat {
+    /// A compressed binary witness stack is written, and the entry-point witness is printed as toml
+    #[default]
+    Binary,
+    /// The whole witness stack is written and printed as json
+    Json,
+}
 
 /// Executes a circuit to calculate its return value
 #[derive(Debug, Clone, Args)]
@@ -19,11 +34,16 @@ pub(crate) struct ExecuteCommand {
     #[clap(long, short)]
     output_witness: Option<String>,
 
-    /// The name of the toml file which contains the input witness map
+    /// The format of the output witness file and of the witness printed to stdout
+    #[clap(long, value_enum, default_value_t)]
+    output_format: WitnessFormat,
+
+    /// The name of the toml or json file which contains the input witness map
     #[clap(long, short)]
     input_witness: String,
 
-    /// The name of the binary file containing circuit bytecode
+    /// The name of the binary file containing circuit bytecode, or of a file containing
+    /// the circuit in the textual ACIR assembly format
     #[clap(long, short)]
     bytecode: String,
 
@@ -34,22 +54,36 @@ pub(crate) struct ExecuteCommand {
     /// Set to print output witness to stdout
     #[clap(long, short, action)]
     print: bool,
+
+    /// JSON RPC url to solve oracle calls
+    #[clap(long)]
+    oracle_resolver: Option<String>,
 }
 
 fn run_command(args: ExecuteCommand) -> Result<String, CliError> {
     let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
     let circuit_inputs = read_inputs_from_file(&args.working_directory, &args.input_witness)?;
-    let output_witness = execute_program_from_witness(circuit_inputs, &bytecode)?;
-    assert_eq!(output_witness.length(), 1, "ACVM CLI only supports a witness stack of size 1");
-    let output_witness_string = create_output_witness_string(
-        &output_witness.peek().expect("Should have a witness stack item").witness,
-    )?;
-    if args.output_witness.is_some() {
-        save_witness_to_dir(
-            output_witness,
-            &args.output_witness.unwrap(),
-            &args.working_directory,
-        )?;
+    let output_witness =
+        execute_program_from_witness(circuit_inputs, &bytecode, args.oracle_resolver.as_deref())?;
+    let output_witness_string = match args.output_format {
+        WitnessFormat::Binary => create_output_witness_string(
+            &output_witness.peek().expect("Should have a witness stack item").witness,
+        )?,
+        WitnessFormat::Json => create_output_witness_stack_json(&output_witness)?,
+    };
+    if let Some(output_witness_name) = &args.output_witness {
+        match args.output_format {
+            WitnessFormat::Binary => {
+                save_witness_to_dir(output_witness, output_witness_name, &args.working_directory)?;
+            }
+            WitnessFormat::Json => {
+                save_witness_json_to_dir(
+                    &output_witness,
+                    output_witness_name,
+                    &args.working_directory,
+                )?;
+            }
+        }
     }
     Ok(output_witness_string)
 }
@@ -66,14 +100,17 @@ pub(crate) fn run(args: ExecuteCommand) -> Result<String, CliError> {
 pub(crate) fn execute_program_from_witness(
     inputs_map: WitnessMap<FieldElement>,
     bytecode: &[u8],
+    oracle_resolver: Option<&str>,
 ) -> Result<WitnessStack<FieldElement>, CliError> {
-    let program: Program<FieldElement> = Program::deserialize_program(bytecode)
-        .map_err(|_| CliError::CircuitDeserializationError())?;
+    let program = read_program_from_bytecode(bytecode)?;
+    let mut foreign_call_executor =
+        DefaultForeignCallExecutor::new(true, oracle_resolver, None, None)
+            .map_err(NargoError::<FieldElement>::from)?;
     execute_program(
         &program,
         inputs_map,
-        &Bn254BlackBoxSolver,
-        &mut DefaultForeignCallExecutor::new(true, None, None, None),
+        &FieldBlackBoxSolver::default(),
+        &mut foreign_call_executor,
     )
     .map_err(CliError::CircuitExecutionError)
 }
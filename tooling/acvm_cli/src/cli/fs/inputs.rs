@@ -1,4 +1,5 @@
 use acir::{
+    circuit::{Program, PROGRAM_MAGIC},
     native_types::{Witness, WitnessMap},
     AcirField, FieldElement,
 };
@@ -7,32 +8,74 @@ use toml::Table;
 use crate::errors::{CliError, FilesystemError};
 use std::{fs::read, path::Path};
 
-/// Returns the circuit's parameters parsed from a toml file at the given location
+/// Returns the circuit's parameters parsed from the file at the given location.
+///
+/// Files with a `.json` extension are read as a JSON object, all others as a toml table.
+/// Either way, each key is a witness index and each value the witness' value as a hex string.
 pub(crate) fn read_inputs_from_file<P: AsRef<Path>>(
     working_directory: P,
     file_name: &String,
 ) -> Result<WitnessMap<FieldElement>, CliError> {
     let file_path = working_directory.as_ref().join(file_name);
     if !file_path.exists() {
-        return Err(CliError::FilesystemError(FilesystemError::MissingTomlFile(
+        return Err(CliError::FilesystemError(FilesystemError::MissingInputFile(
             file_name.to_owned(),
             file_path,
         )));
     }
 
-    let input_string = std::fs::read_to_string(file_path)
-        .map_err(|_| FilesystemError::InvalidTomlFile(file_name.clone()))?;
-    let input_map = input_string
-        .parse::<Table>()
-        .map_err(|_| FilesystemError::InvalidTomlFile(file_name.clone()))?;
+    let is_json = file_path.extension().is_some_and(|extension| extension == "json");
+    let input_string = std::fs::read_to_string(file_path).map_err(|_| {
+        if is_json {
+            FilesystemError::InvalidJsonFile(file_name.clone())
+        } else {
+            FilesystemError::InvalidTomlFile(file_name.clone())
+        }
+    })?;
+
+    parse_inputs(&input_string, is_json, file_name)
+}
+
+/// Parses the circuit's parameters from the contents of the input file `file_name`,
+/// as a JSON object if `is_json` is set and as a toml table otherwise.
+fn parse_inputs(
+    input_string: &str,
+    is_json: bool,
+    file_name: &str,
+) -> Result<WitnessMap<FieldElement>, CliError> {
+    if is_json {
+        let input_map =
+            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(input_string)
+                .map_err(|_| FilesystemError::InvalidJsonFile(file_name.to_owned()))?;
+        parse_witness_map(input_map.into_iter().map(|(key, value)| {
+            let value = value.as_str().map(str::to_owned);
+            (key, value)
+        }))
+    } else {
+        let input_map = input_string
+            .parse::<Table>()
+            .map_err(|_| FilesystemError::InvalidTomlFile(file_name.to_owned()))?;
+        parse_witness_map(input_map.into_iter().map(|(key, value)| {
+            let value = value.as_str().map(str::to_owned);
+            (key, value)
+        }))
+    }
+}
+
+/// Parses a witness map from pairs of witness indices and hex encoded values.
+///
+/// A value of `None` signals that the input file contained something other than a string.
+pub(crate) fn parse_witness_map(
+    entries: impl IntoIterator<Item = (String, Option<String>)>,
+) -> Result<WitnessMap<FieldElement>, CliError> {
     let mut witnesses: WitnessMap<FieldElement> = WitnessMap::new();
-    for (key, value) in input_map.into_iter() {
+    for (key, value) in entries {
         let index =
             Witness(key.trim().parse().map_err(|_| CliError::WitnessIndexError(key.clone()))?);
-        if !value.is_str() {
-            return Err(CliError::WitnessValueError(key.clone()));
-        }
-        let field = FieldElement::from_hex(value.as_str().unwrap()).unwrap();
+        let field = value
+            .as_deref()
+            .and_then(FieldElement::from_hex)
+            .ok_or_else(|| CliError::WitnessValueError(key.clone()))?;
         witnesses.insert(index, field);
     }
 
@@ -52,3 +95,92 @@ pub(crate) fn read_bytecode_from_file<P: AsRef<Path>>(
         read(file_path).map_err(|_| FilesystemError::InvalidBytecodeFile(file_name.clone()))?;
     Ok(bytecode)
 }
+
+/// Returns the program contained in `bytecode`, which is either a serialized program
+/// or a program written in the textual ACIR assembly format.
+///
+/// Serialized programs are recognised by their format header, or by not being valid UTF-8 in the case of
+/// programs serialized before the header was introduced. They are migrated from older versions of the format.
+pub(crate) fn read_program_from_bytecode(
+    bytecode: &[u8],
+) -> Result<Program<FieldElement>, CliError> {
+    match std::str::from_utf8(bytecode) {
+        Ok(source) if !bytecode.starts_with(&PROGRAM_MAGIC) => Ok(source.parse()?),
+        _ => Ok(Program::migrate(bytecode)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{Program, ProgramDeserializationError},
+        native_types::{Witness, WitnessMap},
+        FieldElement,
+    };
+
+    use super::{parse_inputs, read_program_from_bytecode};
+    use crate::errors::{CliError, FilesystemError};
+
+    const PROGRAM: &str = "func 0 {
+        current_witness_index: 1
+        private_parameters: [_0, _1]
+        ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+    }";
+
+    #[test]
+    fn parses_json_and_toml_inputs() {
+        let expected = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(0), FieldElement::from(1u128)),
+            (Witness(2), FieldElement::from(255u128)),
+        ]));
+
+        let json = r#"{ "0": "0x01", "2": "0xff" }"#;
+        assert_eq!(parse_inputs(json, true, "inputs.json").unwrap(), expected);
+
+        let toml = "0 = \"0x01\"\n2 = \"0xff\"";
+        assert_eq!(parse_inputs(toml, false, "inputs.toml").unwrap(), expected);
+    }
+
+    #[test]
+    fn reports_invalid_json_inputs() {
+        let error = parse_inputs(r#"{ "0": "0x01""#, true, "inputs.json").unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::FilesystemError(FilesystemError::InvalidJsonFile(file_name))
+                if file_name == "inputs.json"
+        ));
+
+        // JSON which isn't an object of witness indices to hex strings is rejected as well.
+        let error = parse_inputs(r#"["0x01"]"#, true, "inputs.json").unwrap_err();
+        assert!(matches!(error, CliError::FilesystemError(FilesystemError::InvalidJsonFile(_))));
+
+        let error = parse_inputs(r#"{ "0": 1 }"#, true, "inputs.json").unwrap_err();
+        assert!(matches!(error, CliError::WitnessValueError(key) if key == "0"));
+
+        let error = parse_inputs(r#"{ "x": "0x01" }"#, true, "inputs.json").unwrap_err();
+        assert!(matches!(error, CliError::WitnessIndexError(key) if key == "x"));
+    }
+
+    #[test]
+    fn reads_programs_in_either_format() {
+        let program: Program<FieldElement> = PROGRAM.parse().unwrap();
+        assert_eq!(read_program_from_bytecode(PROGRAM.as_bytes()).unwrap(), program);
+
+        let serialized = Program::serialize_program(&program);
+        assert_eq!(read_program_from_bytecode(&serialized).unwrap(), program);
+    }
+
+    #[test]
+    fn reports_errors_for_the_detected_format() {
+        let error = read_program_from_bytecode(b"func 0 {").unwrap_err();
+        assert!(matches!(error, CliError::CircuitParserError(_)));
+
+        let mut serialized = Program::serialize_program(&PROGRAM.parse().unwrap());
+        serialized.truncate(serialized.len() / 2);
+        let error = read_program_from_bytecode(&serialized).unwrap_err();
+        assert!(matches!(
+            error,
+            CliError::CircuitDeserializationError(ProgramDeserializationError::Decompression(_))
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     collections::BTreeMap,
-    fs::File,
+    fs::{read, File},
     io::Write,
     path::{Path, PathBuf},
 };
@@ -13,6 +13,8 @@ use acvm::acir::{
 
 use crate::errors::{CliError, FilesystemError};
 
+use super::inputs::parse_witness_map;
+
 fn create_named_dir(named_dir: &Path, name: &str) -> PathBuf {
     std::fs::create_dir_all(named_dir)
         .unwrap_or_else(|_| panic!("could not create the `{name}` directory"));
@@ -46,6 +48,71 @@ pub(crate) fn create_output_witness_string(
     toml::to_string(&witness_map).map_err(|_| CliError::OutputWitnessSerializationFailed())
 }
 
+/// Creates a json representation of the provided witness stack.
+///
+/// The stack is written as a list of `{ "index": .., "witness": { .. } }` objects, ordered as in the stack
+/// so that the witness of the entry-point circuit comes last.
+pub(crate) fn create_output_witness_stack_json(
+    witness_stack: &WitnessStack<FieldElement>,
+) -> Result<String, CliError> {
+    let mut witness_stack = witness_stack.clone();
+    let mut stack_items = Vec::with_capacity(witness_stack.length());
+    while let Some(stack_item) = witness_stack.pop() {
+        let witness: serde_json::Map<String, serde_json::Value> = stack_item
+            .witness
+            .into_iter()
+            .map(|(key, value)| (key.0.to_string(), format!("0x{}", value.to_hex()).into()))
+            .collect();
+        stack_items.push(serde_json::json!({ "index": stack_item.index, "witness": witness }));
+    }
+    stack_items.reverse();
+
+    serde_json::to_string_pretty(&stack_items)
+        .map_err(|_| CliError::OutputWitnessSerializationFailed())
+}
+
+/// Parses a witness stack from its json representation, as created by [create_output_witness_stack_json].
+fn parse_witness_stack_json(
+    json: &str,
+    file_name: &str,
+) -> Result<WitnessStack<FieldElement>, CliError> {
+    let invalid_file = || FilesystemError::InvalidJsonFile(file_name.to_owned());
+
+    let stack_items: Vec<serde_json::Value> =
+        serde_json::from_str(json).map_err(|_| invalid_file())?;
+    let mut witness_stack = WitnessStack::default();
+    for stack_item in stack_items {
+        let index = stack_item
+            .get("index")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|index| u32::try_from(index).ok())
+            .ok_or_else(invalid_file)?;
+        let witness = stack_item
+            .get("witness")
+            .and_then(serde_json::Value::as_object)
+            .ok_or_else(invalid_file)?;
+        let witness = parse_witness_map(
+            witness.iter().map(|(key, value)| (key.clone(), value.as_str().map(str::to_owned))),
+        )?;
+        witness_stack.push(index, witness);
+    }
+    Ok(witness_stack)
+}
+
+pub(crate) fn save_witness_json_to_dir<P: AsRef<Path>>(
+    witnesses: &WitnessStack<FieldElement>,
+    witness_name: &str,
+    witness_dir: P,
+) -> Result<PathBuf, CliError> {
+    create_named_dir(witness_dir.as_ref(), "witness");
+    let witness_path = witness_dir.as_ref().join(witness_name).with_extension("json");
+
+    let json = create_output_witness_stack_json(witnesses)?;
+    write_to_file(json.as_bytes(), &witness_path);
+
+    Ok(witness_path)
+}
+
 pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
     witnesses: WitnessStack<FieldElement>,
     witness_name: &str,
@@ -61,3 +128,61 @@ pub(crate) fn save_witness_to_dir<P: AsRef<Path>>(
 
     Ok(witness_path)
 }
+
+/// Returns the witness stack read from the file at the given location.
+///
+/// Files with a `.json` extension are read as created by [create_output_witness_stack_json],
+/// all others as a compressed binary witness stack.
+pub(crate) fn read_witness_stack_from_file<P: AsRef<Path>>(
+    working_directory: P,
+    file_name: &String,
+) -> Result<WitnessStack<FieldElement>, CliError> {
+    let file_path = working_directory.as_ref().join(file_name);
+    if !file_path.exists() {
+        return Err(FilesystemError::MissingWitnessFile(file_name.to_owned(), file_path).into());
+    }
+    if file_path.extension().is_some_and(|extension| extension == "json") {
+        let json = std::fs::read_to_string(file_path)
+            .map_err(|_| FilesystemError::InvalidJsonFile(file_name.clone()))?;
+        return parse_witness_stack_json(&json, file_name);
+    }
+
+    let witness_bytes =
+        read(file_path).map_err(|_| FilesystemError::InvalidWitnessFile(file_name.clone()))?;
+    let witness_stack = WitnessStack::try_from(witness_bytes.as_slice())
+        .map_err(|_| FilesystemError::InvalidWitnessFile(file_name.clone()))?;
+    Ok(witness_stack)
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        native_types::{Witness, WitnessMap, WitnessStack},
+        FieldElement,
+    };
+
+    use super::{create_output_witness_stack_json, parse_witness_stack_json};
+
+    #[test]
+    fn witness_stack_json_round_trip() {
+        let mut witness_stack = WitnessStack::default();
+        witness_stack.push(
+            1,
+            WitnessMap::from(std::collections::BTreeMap::from([(
+                Witness(0),
+                FieldElement::from(3u128),
+            )])),
+        );
+        witness_stack.push(
+            0,
+            WitnessMap::from(std::collections::BTreeMap::from([
+                (Witness(0), FieldElement::from(1u128)),
+                (Witness(2), -FieldElement::from(2u128)),
+            ])),
+        );
+
+        let json = create_output_witness_stack_json(&witness_stack).unwrap();
+        let parsed = parse_witness_stack_json(&json, "witness.json").unwrap();
+        assert_eq!(parsed, witness_stack);
+    }
+}
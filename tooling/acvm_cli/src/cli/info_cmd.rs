@@ -0,0 +1,77 @@
+use acir::{circuit::Program, FieldElement};
+use clap::Args;
+
+use crate::cli::fs::inputs::{read_bytecode_from_file, read_program_from_bytecode};
+use crate::errors::CliError;
+
+/// Prints the size of each function of a program
+#[derive(Debug, Clone, Args)]
+pub(crate) struct InfoCommand {
+    /// The name of the binary file containing circuit bytecode, or of a file containing
+    /// the circuit in the textual ACIR assembly format
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+}
+
+pub(crate) fn run(args: InfoCommand) -> Result<(), CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program = read_program_from_bytecode(&bytecode)?;
+
+    print!("{}", program_info(&program));
+    Ok(())
+}
+
+/// Describes the size of each function of `program`, one function per line.
+fn program_info(program: &Program<FieldElement>) -> String {
+    let mut info = String::new();
+    for (index, circuit) in program.functions.iter().enumerate() {
+        info += &format!(
+            "func {index}: {} opcodes, {} witnesses, {} private parameters, {} public parameters, {} return values\n",
+            circuit.opcodes.len(),
+            circuit.current_witness_index + 1,
+            circuit.private_parameters.len(),
+            circuit.public_parameters.0.len(),
+            circuit.return_values.0.len(),
+        );
+    }
+    for (index, function) in program.unconstrained_functions.iter().enumerate() {
+        info += &format!("brillig func {index}: {} opcodes\n", function.bytecode.len());
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{circuit::Program, FieldElement};
+
+    use super::program_info;
+
+    #[test]
+    fn describes_each_function() {
+        let program: Program<FieldElement> = "func 0 {
+                current_witness_index: 2
+                private_parameters: [_0]
+                public_parameters: [_1]
+                return_values: [_2]
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_2)] }
+                ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+            }
+
+            brillig func 0 {
+                const { destination: @0, bit_size: field, value: 1 }
+                stop { return_data_offset: 0, return_data_size: 1 }
+            }"
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            program_info(&program),
+            "func 0: 2 opcodes, 3 witnesses, 1 private parameters, 1 public parameters, 1 return values\n\
+             brillig func 0: 2 opcodes\n"
+        );
+    }
+}
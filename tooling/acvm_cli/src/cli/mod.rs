@@ -2,8 +2,17 @@ use clap::{Parser, Subcommand};
 use color_eyre::eyre;
 use const_format::formatcp;
 
+mod check_cmd;
 mod execute_cmd;
 mod fs;
+mod info_cmd;
+mod print_cmd;
+
+/// Solver for the blackbox functions of the field which the ACVM was built for.
+#[cfg(not(feature = "bls12_381"))]
+type FieldBlackBoxSolver = bn254_blackbox_solver::Bn254BlackBoxSolver;
+#[cfg(feature = "bls12_381")]
+type FieldBlackBoxSolver = acvm::blackbox_solver::Bls12381BlackBoxSolver;
 
 const ACVM_VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -20,6 +29,9 @@ struct ACVMCli {
 #[derive(Subcommand, Clone, Debug)]
 enum ACVMCommand {
     Execute(execute_cmd::ExecuteCommand),
+    Check(check_cmd::CheckCommand),
+    Info(info_cmd::InfoCommand),
+    Print(print_cmd::PrintCommand),
 }
 
 #[cfg(not(feature = "codegen-docs"))]
@@ -27,7 +39,10 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     let ACVMCli { command } = ACVMCli::parse();
 
     match command {
-        ACVMCommand::Execute(args) => execute_cmd::run(args),
+        ACVMCommand::Execute(args) => execute_cmd::run(args).map(|_| ()),
+        ACVMCommand::Check(args) => check_cmd::run(args),
+        ACVMCommand::Info(args) => info_cmd::run(args),
+        ACVMCommand::Print(args) => print_cmd::run(args),
     }?;
 
     Ok(())
@@ -0,0 +1,49 @@
+use acir::asm::print_program;
+use clap::Args;
+
+use crate::cli::fs::inputs::{read_bytecode_from_file, read_program_from_bytecode};
+use crate::errors::CliError;
+
+/// Prints a program in the textual ACIR assembly format
+#[derive(Debug, Clone, Args)]
+pub(crate) struct PrintCommand {
+    /// The name of the binary file containing circuit bytecode, or of a file containing
+    /// the circuit in the textual ACIR assembly format
+    #[clap(long, short)]
+    bytecode: String,
+
+    /// The working directory
+    #[clap(long, short)]
+    working_directory: String,
+}
+
+pub(crate) fn run(args: PrintCommand) -> Result<(), CliError> {
+    let bytecode = read_bytecode_from_file(&args.working_directory, &args.bytecode)?;
+    let program = read_program_from_bytecode(&bytecode)?;
+
+    print!("{}", print_program(&program));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{asm::print_program, circuit::Program, FieldElement};
+
+    use crate::cli::fs::inputs::read_program_from_bytecode;
+
+    #[test]
+    fn prints_serialized_programs_as_assembly() {
+        let program: Program<FieldElement> = "func 0 {
+                current_witness_index: 1
+                private_parameters: [_0, _1]
+                ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+            }"
+        .parse()
+        .unwrap();
+        let bytecode = Program::serialize_program(&program);
+
+        let printed = print_program(&read_program_from_bytecode(&bytecode).unwrap());
+        assert!(printed.contains("ASSERT_ZERO"));
+        assert_eq!(read_program_from_bytecode(printed.as_bytes()).unwrap(), program);
+    }
+}
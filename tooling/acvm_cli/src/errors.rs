@@ -1,4 +1,4 @@
-use acir::FieldElement;
+use acir::{asm::ParserError, circuit::ProgramDeserializationError, FieldElement};
 use nargo::NargoError;
 use std::path::PathBuf;
 use thiserror::Error;
@@ -8,9 +8,11 @@ pub(crate) enum FilesystemError {
     #[error(
         " Error: cannot find {0} in expected location {1:?}.\n Please generate this file at the expected location."
     )]
-    MissingTomlFile(String, PathBuf),
+    MissingInputFile(String, PathBuf),
     #[error(" Error: failed to parse toml file {0}.")]
     InvalidTomlFile(String),
+    #[error(" Error: failed to parse json file {0}.")]
+    InvalidJsonFile(String),
     #[error(
       " Error: cannot find {0} in expected location {1:?}.\n Please generate this file at the expected location."
     )]
@@ -21,6 +23,14 @@ pub(crate) enum FilesystemError {
 
     #[error(" Error: failed to create output witness file {0}.")]
     OutputWitnessCreationFailed(String),
+
+    #[error(
+        " Error: cannot find {0} in expected location {1:?}.\n Please generate this file at the expected location."
+    )]
+    MissingWitnessFile(String, PathBuf),
+
+    #[error(" Error: failed to read witness file {0}.")]
+    InvalidWitnessFile(String),
 }
 
 #[derive(Debug, Error)]
@@ -30,8 +40,12 @@ pub(crate) enum CliError {
     FilesystemError(#[from] FilesystemError),
 
     /// Error related to circuit deserialization
-    #[error("Error: failed to deserialize circuit in ACVM CLI")]
-    CircuitDeserializationError(),
+    #[error("Error: failed to deserialize circuit in ACVM CLI: {0}")]
+    CircuitDeserializationError(#[from] ProgramDeserializationError),
+
+    /// Error related to parsing a circuit written in the textual ACIR assembly format
+    #[error("Error: failed to parse circuit in ACVM CLI: {0}")]
+    CircuitParserError(#[from] ParserError),
 
     /// Error related to circuit execution
     #[error(transparent)]
@@ -47,4 +61,8 @@ pub(crate) enum CliError {
 
     #[error(" Error: failed to serialize output witness.")]
     OutputWitnessSerializationFailed(),
+
+    /// Witness check failure
+    #[error("Error: witness does not satisfy the circuit, found {0} errors")]
+    UnsatisfiedWitness(usize),
 }
@@ -1026,8 +1026,9 @@ mod tests {
 
         let initial_witness = BTreeMap::from([(Witness(1), fe_1)]).into();
 
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let foreign_call_executor = Box::new(
+            DefaultDebugForeignCallExecutor::from_artifact(true, None, debug_artifact).unwrap(),
+        );
         let mut context = DebugContext::new(
             &StubbedBlackBoxSolver,
             circuits,
@@ -1193,8 +1194,9 @@ mod tests {
 
         let initial_witness = BTreeMap::from([(Witness(1), fe_1), (Witness(2), fe_1)]).into();
 
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        let foreign_call_executor = Box::new(
+            DefaultDebugForeignCallExecutor::from_artifact(true, None, debug_artifact).unwrap(),
+        );
         let brillig_funcs = &vec![brillig_bytecode];
         let mut context = DebugContext::new(
             &StubbedBlackBoxSolver,
@@ -1296,7 +1298,7 @@ mod tests {
             &circuits,
             &debug_artifact,
             WitnessMap::new(),
-            Box::new(DefaultDebugForeignCallExecutor::new(true)),
+            Box::new(DefaultDebugForeignCallExecutor::new(true, None).unwrap()),
             brillig_funcs,
         );
 
@@ -8,6 +8,7 @@ use acvm::{BlackBoxFunctionSolver, FieldElement};
 
 use crate::context::DebugContext;
 use crate::context::{DebugCommandResult, DebugLocation};
+use crate::errors::DapError;
 use crate::foreign_calls::DefaultDebugForeignCallExecutor;
 
 use dap::errors::ServerError;
@@ -27,6 +28,7 @@ use dap::types::{
 use noirc_artifacts::debug::DebugArtifact;
 
 use fm::FileId;
+use nargo::errors::OracleResolverError;
 use noirc_driver::CompiledProgram;
 
 type BreakpointId = i64;
@@ -65,16 +67,21 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap<FieldElement>,
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
-    ) -> Self {
+        foreign_call_resolver_url: Option<&str>,
+    ) -> Result<Self, OracleResolverError> {
         let context = DebugContext::new(
             solver,
             circuits,
             debug_artifact,
             initial_witness,
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact)),
+            Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+                true,
+                foreign_call_resolver_url,
+                debug_artifact,
+            )?),
             unconstrained_functions,
         );
-        Self {
+        Ok(Self {
             server,
             context,
             debug_artifact,
@@ -82,7 +89,7 @@ impl<'a, R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>> DapSession<
             next_breakpoint_id: 1,
             instruction_breakpoints: vec![],
             source_breakpoints: BTreeMap::new(),
-        }
+        })
     }
 
     fn send_stopped_event(&mut self, reason: StoppedEventReason) -> Result<(), ServerError> {
@@ -607,7 +614,8 @@ pub fn run_session<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
-) -> Result<(), ServerError> {
+    foreign_call_resolver_url: Option<&str>,
+) -> Result<(), DapError> {
     let debug_artifact = DebugArtifact { debug_symbols: program.debug, file_map: program.file_map };
     let mut session = DapSession::new(
         server,
@@ -616,7 +624,8 @@ pub fn run_session<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
         &debug_artifact,
         initial_witness,
         &program.program.unconstrained_functions,
-    );
+        foreign_call_resolver_url,
+    )?;
 
-    session.run_loop()
+    Ok(session.run_loop()?)
 }
@@ -10,6 +10,9 @@ pub enum DapError {
 
     #[error(transparent)]
     ServerError(#[from] dap::errors::ServerError),
+
+    #[error(transparent)]
+    OracleResolverError(#[from] nargo::errors::OracleResolverError),
 }
 
 #[derive(Debug, Error)]
@@ -3,6 +3,7 @@ use acvm::{
     pwg::ForeignCallWaitInfo,
     AcirField, FieldElement,
 };
+use nargo::errors::OracleResolverError;
 use nargo::ops::{DefaultForeignCallExecutor, ForeignCallExecutor};
 use noirc_artifacts::debug::{DebugArtifact, DebugVars, StackFrame};
 use noirc_errors::debug_info::{DebugFnId, DebugVarId};
@@ -47,17 +48,29 @@ pub struct DefaultDebugForeignCallExecutor {
 }
 
 impl DefaultDebugForeignCallExecutor {
-    pub fn new(show_output: bool) -> Self {
-        Self {
-            executor: DefaultForeignCallExecutor::new(show_output, None, None, None),
+    pub fn new(
+        show_output: bool,
+        foreign_call_resolver_url: Option<&str>,
+    ) -> Result<Self, OracleResolverError> {
+        Ok(Self {
+            executor: DefaultForeignCallExecutor::new(
+                show_output,
+                foreign_call_resolver_url,
+                None,
+                None,
+            )?,
             debug_vars: DebugVars::default(),
-        }
+        })
     }
 
-    pub fn from_artifact(show_output: bool, artifact: &DebugArtifact) -> Self {
-        let mut ex = Self::new(show_output);
+    pub fn from_artifact(
+        show_output: bool,
+        foreign_call_resolver_url: Option<&str>,
+        artifact: &DebugArtifact,
+    ) -> Result<Self, OracleResolverError> {
+        let mut ex = Self::new(show_output, foreign_call_resolver_url)?;
         ex.load_artifact(artifact);
-        ex
+        Ok(ex)
     }
 
     pub fn load_artifact(&mut self, artifact: &DebugArtifact) {
@@ -7,10 +7,10 @@ mod source_code_printer;
 
 use std::io::{Read, Write};
 
-use ::dap::errors::ServerError;
 use ::dap::server::Server;
 use acvm::acir::native_types::{WitnessMap, WitnessStack};
 use acvm::{BlackBoxFunctionSolver, FieldElement};
+use errors::DapError;
 
 use nargo::NargoError;
 use noirc_driver::CompiledProgram;
@@ -19,8 +19,9 @@ pub fn run_repl_session<B: BlackBoxFunctionSolver<FieldElement>>(
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
 ) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
-    repl::run(solver, program, initial_witness)
+    repl::run(solver, program, initial_witness, foreign_call_resolver_url)
 }
 
 pub fn run_dap_loop<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
@@ -28,6 +29,7 @@ pub fn run_dap_loop<R: Read, W: Write, B: BlackBoxFunctionSolver<FieldElement>>(
     solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
-) -> Result<(), ServerError> {
-    dap::run_session(server, solver, program, initial_witness)
+    foreign_call_resolver_url: Option<&str>,
+) -> Result<(), DapError> {
+    dap::run_session(server, solver, program, initial_witness, foreign_call_resolver_url)
 }
@@ -7,6 +7,7 @@ use acvm::acir::native_types::{Witness, WitnessMap, WitnessStack};
 use acvm::brillig_vm::brillig::Opcode as BrilligOpcode;
 use acvm::brillig_vm::MemoryValue;
 use acvm::{BlackBoxFunctionSolver, FieldElement};
+use nargo::errors::OracleResolverError;
 use nargo::NargoError;
 use noirc_driver::CompiledProgram;
 
@@ -24,6 +25,7 @@ pub struct ReplDebugger<'a, B: BlackBoxFunctionSolver<FieldElement>> {
     blackbox_solver: &'a B,
     debug_artifact: &'a DebugArtifact,
     initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&'a str>,
     last_result: DebugCommandResult,
 
     // ACIR functions to debug
@@ -40,9 +42,13 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         debug_artifact: &'a DebugArtifact,
         initial_witness: WitnessMap<FieldElement>,
         unconstrained_functions: &'a [BrilligBytecode<FieldElement>],
-    ) -> Self {
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, debug_artifact));
+        foreign_call_resolver_url: Option<&'a str>,
+    ) -> Result<Self, OracleResolverError> {
+        let foreign_call_executor = Box::new(DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            foreign_call_resolver_url,
+            debug_artifact,
+        )?);
         let context = DebugContext::new(
             blackbox_solver,
             circuits,
@@ -57,15 +63,16 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
         } else {
             DebugCommandResult::Ok
         };
-        Self {
+        Ok(Self {
             context,
             blackbox_solver,
             circuits,
             debug_artifact,
             initial_witness,
+            foreign_call_resolver_url,
             last_result,
             unconstrained_functions,
-        }
+        })
     }
 
     pub fn show_current_vm_status(&self) {
@@ -312,8 +319,17 @@ impl<'a, B: BlackBoxFunctionSolver<FieldElement>> ReplDebugger<'a, B> {
 
     fn restart_session(&mut self) {
         let breakpoints: Vec<DebugLocation> = self.context.iterate_breakpoints().copied().collect();
-        let foreign_call_executor =
-            Box::new(DefaultDebugForeignCallExecutor::from_artifact(true, self.debug_artifact));
+        let foreign_call_executor = match DefaultDebugForeignCallExecutor::from_artifact(
+            true,
+            self.foreign_call_resolver_url,
+            self.debug_artifact,
+        ) {
+            Ok(foreign_call_executor) => Box::new(foreign_call_executor),
+            Err(error) => {
+                println!("Could not restart debugging session: {error}");
+                return;
+            }
+        };
         self.context = DebugContext::new(
             self.blackbox_solver,
             self.circuits,
@@ -420,6 +436,7 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
     blackbox_solver: &B,
     program: CompiledProgram,
     initial_witness: WitnessMap<FieldElement>,
+    foreign_call_resolver_url: Option<&str>,
 ) -> Result<Option<WitnessStack<FieldElement>>, NargoError<FieldElement>> {
     let circuits = &program.program.functions;
     let debug_artifact =
@@ -431,7 +448,8 @@ pub fn run<B: BlackBoxFunctionSolver<FieldElement>>(
         debug_artifact,
         initial_witness,
         unconstrained_functions,
-    ));
+        foreign_call_resolver_url,
+    )?);
     let ref_context = &context;
 
     ref_context.borrow().show_current_vm_status();
@@ -116,7 +116,7 @@ struct PackageCacheData {
 impl LspState {
     fn new(
         client: &ClientSocket,
-        solver: impl BlackBoxFunctionSolver<FieldElement> + 'static,
+        solver: impl BlackBoxFunctionSolver<FieldElement> + Sync + 'static,
     ) -> Self {
         Self {
             client: client.clone(),
@@ -141,7 +141,7 @@ pub struct NargoLspService {
 impl NargoLspService {
     pub fn new(
         client: &ClientSocket,
-        solver: impl BlackBoxFunctionSolver<FieldElement> + 'static,
+        solver: impl BlackBoxFunctionSolver<FieldElement> + Sync + 'static,
     ) -> Self {
         let state = LspState::new(client, solver);
         let mut router = Router::new(state);
@@ -295,6 +295,7 @@ pub(crate) fn resolve_workspace_for_source_path(file_path: &Path) -> Result<Work
             .map_err(|err| LspError::WorkspaceResolutionError(err.to_string()))?,
         dependencies: BTreeMap::new(),
         expression_width: None,
+        field: None,
     };
     let workspace = Workspace {
         root_dir: PathBuf::from(parent_folder),
@@ -3,7 +3,9 @@ use acvm::BlackBoxFunctionSolver;
 // This is a struct that wraps a dynamically dispatched `BlackBoxFunctionSolver`
 // where we proxy the unimplemented stuff to the wrapped backend, but it
 // allows us to avoid changing function signatures to include the `Box`
-pub(super) struct WrapperSolver(pub(super) Box<dyn BlackBoxFunctionSolver<acvm::FieldElement>>);
+pub(super) struct WrapperSolver(
+    pub(super) Box<dyn BlackBoxFunctionSolver<acvm::FieldElement> + Sync>,
+);
 
 impl BlackBoxFunctionSolver<acvm::FieldElement> for WrapperSolver {
     fn schnorr_verify(
@@ -66,4 +68,12 @@ impl BlackBoxFunctionSolver<acvm::FieldElement> for WrapperSolver {
     ) -> Result<Vec<acvm::FieldElement>, acvm::BlackBoxResolutionError> {
         self.0.poseidon2_permutation(inputs, len)
     }
+
+    fn user_defined(
+        &self,
+        name: &str,
+        inputs: &[acvm::FieldElement],
+    ) -> Result<Vec<acvm::FieldElement>, acvm::BlackBoxResolutionError> {
+        self.0.user_defined(name, inputs)
+    }
 }
@@ -52,6 +52,26 @@ pub enum NargoError<F: AcirField> {
     /// Oracle handling error
     #[error(transparent)]
     ForeignCallError(#[from] ForeignCallError),
+
+    /// The external oracle resolver could not be reached
+    #[error(transparent)]
+    OracleResolverError(#[from] OracleResolverError),
+}
+
+/// Errors raised when setting up the transport to an external oracle resolver.
+#[derive(Debug, Error)]
+pub enum OracleResolverError {
+    #[error("Invalid oracle resolver URL `{url}`: {reason}")]
+    InvalidUrl { url: String, reason: String },
+
+    #[error("Invalid oracle resolver command `{command}`: {source}")]
+    InvalidCommand { command: String, source: shell_words::ParseError },
+
+    #[error("Could not spawn oracle resolver `{command}`: {source}")]
+    SpawnFailed { command: String, source: std::io::Error },
+
+    #[error("Oracle resolvers over Unix domain sockets are not supported on this platform")]
+    UnixSocketsUnsupported,
 }
 
 impl<F: AcirField> NargoError<F> {
@@ -80,6 +100,7 @@ impl<F: AcirField> NargoError<F> {
                     }
                     _ => None,
                 },
+                ExecutionError::BrilligLimitExceeded(..) => None,
             },
             NargoError::ForeignCallError(error) => Some(error.to_string()),
             _ => None,
@@ -98,6 +119,12 @@ pub enum ExecutionError<F: AcirField> {
 
     #[error("Failed to solve program: '{}'", .0)]
     SolvingError(OpcodeResolutionError<F>, Option<Vec<ResolvedOpcodeLocation>>),
+
+    /// A Brillig function ran out of memory or steps under the configured VM limits.
+    ///
+    /// This is kept apart from `AssertionFailed` as it isn't a failure of the program itself.
+    #[error("Brillig function exceeded its resource limits: {}", .0)]
+    BrilligLimitExceeded(String, Vec<ResolvedOpcodeLocation>, BrilligFunctionId),
 }
 
 /// Extracts the opcode locations from a nargo error.
@@ -110,7 +137,8 @@ fn extract_locations_from_error<F: AcirField>(
             OpcodeResolutionError::BrilligFunctionFailed { .. },
             acir_call_stack,
         ) => acir_call_stack.clone(),
-        ExecutionError::AssertionFailed(_, call_stack, _) => Some(call_stack.clone()),
+        ExecutionError::AssertionFailed(_, call_stack, _)
+        | ExecutionError::BrilligLimitExceeded(_, call_stack, _) => Some(call_stack.clone()),
         ExecutionError::SolvingError(
             OpcodeResolutionError::IndexOutOfBounds { opcode_location: error_location, .. },
             acir_call_stack,
@@ -122,6 +150,12 @@ fn extract_locations_from_error<F: AcirField>(
         | ExecutionError::SolvingError(
             OpcodeResolutionError::UnsatisfiedConstrain { opcode_location: error_location, .. },
             acir_call_stack,
+        )
+        | ExecutionError::SolvingError(
+            OpcodeResolutionError::InvalidAssertionPayload {
+                opcode_location: error_location, ..
+            },
+            acir_call_stack,
         ) => match error_location {
             ErrorLocation::Unresolved => {
                 unreachable!("Cannot resolve index for unsatisfied constraint")
@@ -157,6 +191,7 @@ fn extract_locations_from_error<F: AcirField>(
             _,
         ) => Some(*function_id),
         ExecutionError::AssertionFailed(_, _, function_id) => *function_id,
+        ExecutionError::BrilligLimitExceeded(_, _, function_id) => Some(*function_id),
         _ => None,
     };
 
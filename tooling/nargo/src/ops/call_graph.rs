@@ -0,0 +1,289 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use acvm::acir::{
+    brillig::Opcode as BrilligOpcode,
+    circuit::{
+        brillig::{BrilligBytecode, BrilligInputs, BrilligOutputs},
+        directives::Directive,
+        opcodes::{BlockId, ConstantOrWitnessEnum},
+        Circuit, Opcode,
+    },
+    native_types::{Expression, Witness},
+};
+use acvm::AcirField;
+
+/// Dependencies between the ACIR calls made by the functions of a program.
+///
+/// This is used to find calls which can be solved independently of each other so that they may be executed in parallel.
+pub(crate) struct ProgramCallGraph {
+    /// The call graph of each ACIR function, if it makes any ACIR calls.
+    circuits: Vec<Option<CircuitCallGraph>>,
+    /// Whether each ACIR function can be executed without making any foreign calls, including within the functions it calls.
+    pure_functions: Vec<bool>,
+}
+
+impl ProgramCallGraph {
+    pub(crate) fn new<F: AcirField>(
+        functions: &[Circuit<F>],
+        unconstrained_functions: &[BrilligBytecode<F>],
+    ) -> Self {
+        let circuits = functions.iter().map(CircuitCallGraph::new).collect();
+        let pure_functions = find_pure_functions(functions, unconstrained_functions);
+        ProgramCallGraph { circuits, pure_functions }
+    }
+
+    pub(crate) fn circuit(&self, function_index: usize) -> Option<&CircuitCallGraph> {
+        self.circuits[function_index].as_ref()
+    }
+
+    pub(crate) fn is_pure(&self, function_index: usize) -> bool {
+        self.pure_functions[function_index]
+    }
+}
+
+/// Dependencies between the [`Opcode::Call`]s within a single circuit.
+pub(crate) struct CircuitCallGraph {
+    /// The opcode index of each call, in the order in which they are executed.
+    calls: Vec<usize>,
+    /// For each call, the positions in `calls` of the earlier calls whose outputs may be needed to compute its inputs.
+    dependencies: Vec<BTreeSet<usize>>,
+}
+
+impl CircuitCallGraph {
+    /// Returns `None` if the circuit makes fewer than two calls, as there is nothing to execute in parallel.
+    fn new<F: AcirField>(circuit: &Circuit<F>) -> Option<Self> {
+        let num_calls =
+            circuit.opcodes.iter().filter(|opcode| matches!(opcode, Opcode::Call { .. })).count();
+        if num_calls < 2 {
+            return None;
+        }
+
+        // Each witness and memory block is tagged with the set of calls which its value may depend upon.
+        // The ACVM solves opcodes in order, so a witness is solved by the first opcode in which it appears
+        // and takes on the dependencies of all other operands of that opcode.
+        let mut witness_dependencies: HashMap<Witness, BTreeSet<usize>> = HashMap::new();
+        let mut block_dependencies: HashMap<BlockId, BTreeSet<usize>> = HashMap::new();
+        let mut solved_witnesses: HashSet<Witness> =
+            circuit.circuit_arguments().into_iter().collect();
+
+        let mut calls = Vec::with_capacity(num_calls);
+        let mut dependencies = Vec::with_capacity(num_calls);
+        for (opcode_index, opcode) in circuit.opcodes.iter().enumerate() {
+            let (witnesses, block) = opcode_operands(opcode);
+            let mut opcode_dependencies: BTreeSet<usize> = witnesses
+                .iter()
+                .filter_map(|witness| witness_dependencies.get(witness))
+                .chain(block.and_then(|block| block_dependencies.get(&block)))
+                .flatten()
+                .copied()
+                .collect();
+
+            let solved_by_opcode = if let Opcode::Call { outputs, .. } = opcode {
+                let call_position = calls.len();
+                calls.push(opcode_index);
+                dependencies.push(opcode_dependencies.clone());
+
+                opcode_dependencies.insert(call_position);
+                outputs.clone()
+            } else {
+                witnesses
+            };
+
+            if !opcode_dependencies.is_empty() {
+                for witness in solved_by_opcode {
+                    if solved_witnesses.insert(witness) {
+                        witness_dependencies.insert(witness, opcode_dependencies.clone());
+                    }
+                }
+                if let Some(block) = block {
+                    block_dependencies.insert(block, opcode_dependencies);
+                }
+            } else {
+                solved_witnesses.extend(solved_by_opcode);
+            }
+        }
+
+        Some(CircuitCallGraph { calls, dependencies })
+    }
+
+    /// Returns the opcode indices of the calls after the call at `opcode_index` which do not depend upon it
+    /// or upon any other call which is yet to be solved when it is reached.
+    pub(crate) fn independent_calls_after(
+        &self,
+        opcode_index: usize,
+    ) -> impl Iterator<Item = usize> + '_ {
+        let position = self.calls.binary_search(&opcode_index).ok();
+        position.into_iter().flat_map(move |position| {
+            (position + 1..self.calls.len())
+                .filter(move |later_position| {
+                    self.dependencies[*later_position]
+                        .iter()
+                        .all(|dependency| *dependency < position)
+                })
+                .map(|later_position| self.calls[later_position])
+        })
+    }
+}
+
+/// Returns the witnesses involved in `opcode`, along with the memory block which it reads from or writes to.
+fn opcode_operands<F: AcirField>(opcode: &Opcode<F>) -> (Vec<Witness>, Option<BlockId>) {
+    let mut witnesses = Vec::new();
+    let mut block = None;
+    match opcode {
+        Opcode::AssertZero(expr) => witnesses.extend(expression_witnesses(expr)),
+        Opcode::BlackBoxFuncCall(bb_func) => {
+            for input in bb_func.get_inputs_vec() {
+                if let ConstantOrWitnessEnum::Witness(witness) = input.input() {
+                    witnesses.push(witness);
+                }
+            }
+            witnesses.extend(bb_func.get_outputs_vec());
+        }
+        Opcode::Directive(directive) => {
+            let Directive::ToLeRadix { a, b, .. } = directive;
+            witnesses.extend(expression_witnesses(a));
+            witnesses.extend(b.iter().copied());
+        }
+        Opcode::MemoryInit { block_id, init, .. } => {
+            witnesses.extend(init.iter().copied());
+            block = Some(*block_id);
+        }
+        Opcode::MemoryOp { block_id, op, predicate } => {
+            witnesses.extend(expression_witnesses(&op.operation));
+            witnesses.extend(expression_witnesses(&op.index));
+            witnesses.extend(expression_witnesses(&op.value));
+            witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+            block = Some(*block_id);
+        }
+        Opcode::BrilligCall { inputs, outputs, predicate, .. } => {
+            for input in inputs {
+                match input {
+                    BrilligInputs::Single(expr) => witnesses.extend(expression_witnesses(expr)),
+                    BrilligInputs::Array(exprs) => {
+                        witnesses.extend(exprs.iter().flat_map(expression_witnesses));
+                    }
+                    BrilligInputs::MemoryArray(block_id) => block = Some(*block_id),
+                }
+            }
+            for output in outputs {
+                match output {
+                    BrilligOutputs::Simple(witness) => witnesses.push(*witness),
+                    BrilligOutputs::Array(outputs) => witnesses.extend(outputs.iter().copied()),
+                }
+            }
+            witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+        }
+        Opcode::Call { inputs, predicate, .. } => {
+            witnesses.extend(inputs.iter().copied());
+            witnesses.extend(predicate.iter().flat_map(expression_witnesses));
+        }
+    }
+    (witnesses, block)
+}
+
+fn expression_witnesses<F>(expr: &Expression<F>) -> impl Iterator<Item = Witness> + '_ {
+    let mul_witnesses = expr.mul_terms.iter().flat_map(|(_, lhs, rhs)| [*lhs, *rhs]);
+    mul_witnesses.chain(expr.linear_combinations.iter().map(|(_, witness)| *witness))
+}
+
+/// Returns whether each function can be executed without making foreign calls.
+fn find_pure_functions<F>(
+    functions: &[Circuit<F>],
+    unconstrained_functions: &[BrilligBytecode<F>],
+) -> Vec<bool> {
+    let pure_unconstrained_functions: Vec<bool> = unconstrained_functions
+        .iter()
+        .map(|function| {
+            !function
+                .bytecode
+                .iter()
+                .any(|opcode| matches!(opcode, BrilligOpcode::ForeignCall { .. }))
+        })
+        .collect();
+
+    let mut pure_functions: Vec<bool> = functions
+        .iter()
+        .map(|circuit| {
+            circuit.opcodes.iter().all(|opcode| match opcode {
+                Opcode::BrilligCall { id, .. } => pure_unconstrained_functions[id.as_usize()],
+                _ => true,
+            })
+        })
+        .collect();
+
+    // A function is only pure if all of the functions it calls are pure, so propagate impurity up the call graph.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (function_index, circuit) in functions.iter().enumerate() {
+            if !pure_functions[function_index] {
+                continue;
+            }
+            let calls_impure_function = circuit.opcodes.iter().any(|opcode| {
+                matches!(opcode, Opcode::Call { id, .. } if !pure_functions[id.as_usize()])
+            });
+            if calls_impure_function {
+                pure_functions[function_index] = false;
+                changed = true;
+            }
+        }
+    }
+    pure_functions
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{acir::circuit::Program, FieldElement};
+
+    use super::ProgramCallGraph;
+
+    #[test]
+    fn finds_independent_calls() {
+        let program: Program<FieldElement> = "
+            func 0 {
+                current_witness_index: 7
+                private_parameters: [_0, _1]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+                ASSERT_ZERO [ (1, _2) (1, _3) (-1, _4) 0 ]
+                CALL { id: 1, inputs: [_4], outputs: [_5] }
+                CALL { id: 2, inputs: [_0], outputs: [_6] }
+                CALL { id: 1, inputs: [_3], outputs: [_7] }
+            }
+
+            func 1 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+            }
+
+            func 2 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_1)] }
+            }
+
+            brillig func 0 {
+                foreign_call { function: \"print\", destinations: [], destination_value_types: [], inputs: [], input_value_types: [] }
+                stop { return_data_offset: 0, return_data_size: 0 }
+            }"
+        .parse()
+        .unwrap();
+
+        let call_graph =
+            ProgramCallGraph::new(&program.functions, &program.unconstrained_functions);
+        assert!(call_graph.is_pure(1));
+        assert!(!call_graph.is_pure(2));
+        assert!(!call_graph.is_pure(0));
+        assert!(call_graph.circuit(1).is_none());
+
+        let circuit = call_graph.circuit(0).unwrap();
+        // The third call depends on the first two through `_4`, and the last depends on the second through `_3`.
+        assert_eq!(circuit.independent_calls_after(0).collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(circuit.independent_calls_after(1).collect::<Vec<_>>(), vec![4]);
+        assert_eq!(circuit.independent_calls_after(3).collect::<Vec<_>>(), vec![4, 5]);
+        assert_eq!(circuit.independent_calls_after(2).count(), 0);
+    }
+}
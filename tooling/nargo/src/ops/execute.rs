@@ -1,17 +1,33 @@
+use std::collections::BTreeMap;
+
 use acvm::acir::circuit::brillig::BrilligBytecode;
 use acvm::acir::circuit::{
-    OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
+    Opcode, OpcodeLocation, Program, ResolvedAssertionPayload, ResolvedOpcodeLocation,
+};
+use acvm::acir::native_types::{Witness, WitnessStack};
+use acvm::pwg::{
+    get_value, ACVMStatus, AcirCallWaitInfo, BrilligCallCache, DecodedBrilligFunctions,
+    ErrorLocation, ForeignCallWaitInfo, OpcodeNotSolvable, OpcodeResolutionError,
+    ProfilingObserver, ProfilingSamples, VMLimits, ACVM,
+};
+use acvm::{
+    acir::brillig::ForeignCallResult, acir::circuit::Circuit, acir::native_types::WitnessMap,
 };
-use acvm::acir::native_types::WitnessStack;
-use acvm::pwg::{ACVMStatus, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError, ACVM};
-use acvm::{acir::circuit::Circuit, acir::native_types::WitnessMap};
 use acvm::{AcirField, BlackBoxFunctionSolver};
+use noirc_printable_type::ForeignCallError;
+use rayon::prelude::*;
 
 use crate::errors::ExecutionError;
 use crate::NargoError;
 
+use super::call_graph::ProgramCallGraph;
 use super::foreign_calls::ForeignCallExecutor;
 
+/// The profiling samples taken while executing each ACIR function of a program, keyed by the function's index.
+///
+/// The call stack of each sample is relative to the circuit of the function it was taken in.
+pub type ProgramProfilingSamples = BTreeMap<usize, ProfilingSamples>;
+
 struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>> {
     functions: &'a [Circuit<F>],
 
@@ -24,6 +40,23 @@ struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecut
 
     foreign_call_executor: &'a mut E,
 
+    // Used to find ACIR calls which can be solved in parallel with each other.
+    call_graph: &'a ProgramCallGraph,
+
+    // Results of earlier Brillig calls, if caching is enabled.
+    // This is lent to the ACVM of the circuit which is currently being executed.
+    brillig_call_cache: Option<BrilligCallCache<F>>,
+
+    // The Brillig functions decoded so far, so that each function is only decoded once.
+    // This is lent to the ACVM of the circuit which is currently being executed.
+    decoded_brillig_functions: DecodedBrilligFunctions<'a, F>,
+
+    // Samples taken from the ACVMs of the circuits executed so far, if profiling is enabled.
+    profiling_samples: Option<ProgramProfilingSamples>,
+
+    // Limits on the resources used by each Brillig call.
+    brillig_vm_limits: VMLimits,
+
     // The Noir compiler codegens per function and call stacks are not shared across ACIR function calls.
     // We must rebuild a call stack when executing a program of many circuits.
     call_stack: Vec<ResolvedOpcodeLocation>,
@@ -34,14 +67,25 @@ struct ProgramExecutor<'a, F, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecut
     current_function_index: usize,
 }
 
-impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
-    ProgramExecutor<'a, F, B, E>
+/// The result of an ACIR call which was solved ahead of the ACVM reaching it.
+struct SolvedCall<F: AcirField> {
+    initial_witness: WitnessMap<F>,
+    result: Result<(WitnessMap<F>, WitnessStack<F>), NargoError<F>>,
+}
+
+impl<
+        'a,
+        F: AcirField + Send + Sync,
+        B: BlackBoxFunctionSolver<F> + Sync,
+        E: ForeignCallExecutor<F>,
+    > ProgramExecutor<'a, F, B, E>
 {
     fn new(
         functions: &'a [Circuit<F>],
         unconstrained_functions: &'a [BrilligBytecode<F>],
         blackbox_solver: &'a B,
         foreign_call_executor: &'a mut E,
+        call_graph: &'a ProgramCallGraph,
     ) -> Self {
         ProgramExecutor {
             functions,
@@ -49,6 +93,11 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             witness_stack: WitnessStack::default(),
             blackbox_solver,
             foreign_call_executor,
+            call_graph,
+            brillig_call_cache: None,
+            decoded_brillig_functions: DecodedBrilligFunctions::new(),
+            profiling_samples: None,
+            brillig_vm_limits: VMLimits::default(),
             call_stack: Vec::default(),
             current_function_index: 0,
         }
@@ -72,8 +121,22 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
             &circuit.assert_messages,
         );
 
+        if let Some(brillig_call_cache) = self.brillig_call_cache.take() {
+            acvm.set_brillig_call_cache(brillig_call_cache);
+        }
+        acvm.set_decoded_brillig_functions(std::mem::take(&mut self.decoded_brillig_functions));
+        acvm.set_brillig_vm_limits(self.brillig_vm_limits);
+
+        // Results of calls from this circuit which were solved in parallel with an earlier call, keyed by opcode index.
+        let mut solved_calls: BTreeMap<usize, SolvedCall<F>> = BTreeMap::new();
+        // Counts the Brillig opcodes executed by this circuit, if profiling is enabled.
+        let mut profiler = self.profiling_samples.is_some().then(ProfilingObserver::new);
+
         loop {
-            let solver_status = acvm.solve();
+            let solver_status = match &mut profiler {
+                Some(profiler) => acvm.solve_with_observer(profiler),
+                None => acvm.solve(),
+            };
 
             match solver_status {
                 ACVMStatus::Solved => break,
@@ -93,6 +156,10 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                         | OpcodeResolutionError::InvalidInputBitSize {
                             opcode_location: ErrorLocation::Resolved(opcode_location),
                             ..
+                        }
+                        | OpcodeResolutionError::InvalidAssertionPayload {
+                            opcode_location: ErrorLocation::Resolved(opcode_location),
+                            ..
                         } => {
                             let resolved_location = ResolvedOpcodeLocation {
                                 acir_function_index: self.current_function_index,
@@ -101,7 +168,8 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                             self.call_stack.push(resolved_location);
                             Some(self.call_stack.clone())
                         }
-                        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. } => {
+                        OpcodeResolutionError::BrilligFunctionFailed { call_stack, .. }
+                        | OpcodeResolutionError::BrilligLimitExceeded { call_stack, .. } => {
                             let brillig_call_stack =
                                 call_stack.iter().map(|location| ResolvedOpcodeLocation {
                                     acir_function_index: self.current_function_index,
@@ -113,6 +181,19 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                         _ => None,
                     };
 
+                    if let OpcodeResolutionError::BrilligLimitExceeded {
+                        function_id, reason, ..
+                    } = error
+                    {
+                        return Err(NargoError::ExecutionError(
+                            ExecutionError::BrilligLimitExceeded(
+                                reason,
+                                call_stack.expect("Should have call stack for a Brillig failure"),
+                                function_id,
+                            ),
+                        ));
+                    }
+
                     let assertion_payload: Option<ResolvedAssertionPayload<F>> = match &error {
                         OpcodeResolutionError::BrilligFunctionFailed { payload, .. }
                         | OpcodeResolutionError::UnsatisfiedConstrain { payload, .. } => {
@@ -144,21 +225,53 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                 ACVMStatus::RequiresAcirCall(call_info) => {
                     // Store the parent function index whose context we are currently executing
                     let acir_function_caller = self.current_function_index;
+                    let call_opcode_index = acvm.instruction_pointer();
                     // Add call opcode to the call stack with a reference to the parent function index
                     self.call_stack.push(ResolvedOpcodeLocation {
                         acir_function_index: acir_function_caller,
-                        opcode_location: OpcodeLocation::Acir(acvm.instruction_pointer()),
+                        opcode_location: OpcodeLocation::Acir(call_opcode_index),
                     });
 
-                    // Set current function to the circuit we are about to execute
-                    self.current_function_index = call_info.id.as_usize();
-                    // Execute the ACIR call
+                    if !solved_calls.contains_key(&call_opcode_index) {
+                        let newly_solved_calls =
+                            self.solve_independent_calls(&mut acvm, &call_info, &solved_calls);
+                        solved_calls.extend(newly_solved_calls);
+                    }
+
                     let acir_to_call = &self.functions[call_info.id.as_usize()];
-                    let initial_witness = call_info.initial_witness;
-                    let call_solved_witness = self.execute_circuit(initial_witness)?;
+                    let (call_solved_witness, call_witness_stack) = match solved_calls
+                        .remove(&call_opcode_index)
+                    {
+                        Some(solved_call)
+                            if solved_call.initial_witness == call_info.initial_witness =>
+                        {
+                            let (call_solved_witness, call_witness_stack) = solved_call
+                                .result
+                                .map_err(|error| prepend_call_stack(error, &self.call_stack))?;
+                            // Match the call stack which is left behind by executing the call in place.
+                            self.call_stack.clear();
+                            (call_solved_witness, call_witness_stack)
+                        }
+                        _ => {
+                            // Set current function to the circuit we are about to execute
+                            self.current_function_index = call_info.id.as_usize();
+                            // Execute the ACIR call
+                            let initial_witness = call_info.initial_witness;
+                            self.brillig_call_cache = acvm.take_brillig_call_cache();
+                            self.decoded_brillig_functions = acvm.take_decoded_brillig_functions();
+                            let call_solved_witness = self.execute_circuit(initial_witness)?;
+                            if let Some(brillig_call_cache) = self.brillig_call_cache.take() {
+                                acvm.set_brillig_call_cache(brillig_call_cache);
+                            }
+                            acvm.set_decoded_brillig_functions(std::mem::take(
+                                &mut self.decoded_brillig_functions,
+                            ));
 
-                    // Set tracking index back to the parent function after ACIR call execution
-                    self.current_function_index = acir_function_caller;
+                            // Set tracking index back to the parent function after ACIR call execution
+                            self.current_function_index = acir_function_caller;
+                            (call_solved_witness, WitnessStack::default())
+                        }
+                    };
 
                     let mut call_resolved_outputs = Vec::new();
                     for return_witness_index in acir_to_call.return_values.indices() {
@@ -175,6 +288,7 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
                         }
                     }
                     acvm.resolve_pending_acir_call(call_resolved_outputs);
+                    self.extend_witness_stack(call_witness_stack);
                     self.witness_stack.push(call_info.id.0, call_solved_witness);
                 }
             }
@@ -184,25 +298,558 @@ impl<'a, F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>
         // included in a failure case.
         self.call_stack.clear();
 
+        self.brillig_call_cache = acvm.take_brillig_call_cache();
+        self.decoded_brillig_functions = acvm.take_decoded_brillig_functions();
+        if let (Some(profiling_samples), Some(profiler)) = (&mut self.profiling_samples, profiler) {
+            let samples = profiler.into_samples();
+            if !samples.is_empty() {
+                let function_samples =
+                    profiling_samples.entry(self.current_function_index).or_default();
+                for (sample, count) in samples {
+                    *function_samples.entry(sample).or_default() += count;
+                }
+            }
+        }
         Ok(acvm.finalize())
     }
+
+    /// Solves the call described by `call_info` in parallel with any later calls from the same circuit which do not
+    /// depend upon it and whose inputs are already known.
+    ///
+    /// Only calls which do not make foreign calls are solved in this way, as foreign calls must be made in order.
+    /// Calls in `solved_calls` are skipped, and an empty map is returned if there is not more than one call to solve.
+    /// No calls are solved ahead of time while profiling, so that every sample is collected by this executor.
+    ///
+    /// If Brillig calls are being cached, each call is solved with a cache of its own which is then merged into the
+    /// cache held by `acvm`. Calls solved together therefore don't reuse each other's results, but later calls do.
+    fn solve_independent_calls(
+        &self,
+        acvm: &mut ACVM<'a, F, B>,
+        call_info: &AcirCallWaitInfo<F>,
+        solved_calls: &BTreeMap<usize, SolvedCall<F>>,
+    ) -> BTreeMap<usize, SolvedCall<F>> {
+        if self.profiling_samples.is_some() {
+            return BTreeMap::new();
+        }
+        let call_opcode_index = acvm.instruction_pointer();
+        let Some(circuit_call_graph) = self.call_graph.circuit(self.current_function_index) else {
+            return BTreeMap::new();
+        };
+        if !self.call_graph.is_pure(call_info.id.as_usize()) {
+            return BTreeMap::new();
+        }
+
+        let mut calls = vec![(call_opcode_index, call_info.id, call_info.initial_witness.clone())];
+        for opcode_index in circuit_call_graph.independent_calls_after(call_opcode_index) {
+            if solved_calls.contains_key(&opcode_index) {
+                continue;
+            }
+            let Opcode::Call { id, inputs, predicate, .. } = &acvm.opcodes()[opcode_index] else {
+                continue;
+            };
+            // Calls to `main` are rejected by the ACVM when it reaches them.
+            if id.as_usize() == 0 || !self.call_graph.is_pure(id.as_usize()) {
+                continue;
+            }
+            // Calls with a false predicate are never executed, while calls with an unknown predicate may not be.
+            let is_executed = predicate.as_ref().map_or(Some(true), |predicate| {
+                get_value(predicate, acvm.witness_map()).ok().map(|value| !value.is_zero())
+            });
+            if is_executed != Some(true) {
+                continue;
+            }
+            let initial_witness: Option<BTreeMap<Witness, F>> = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| {
+                    let value = acvm.witness_map().get(input)?;
+                    Some((Witness(i as u32), *value))
+                })
+                .collect();
+            if let Some(initial_witness) = initial_witness {
+                calls.push((opcode_index, *id, WitnessMap::from(initial_witness)));
+            }
+        }
+        if calls.len() < 2 {
+            return BTreeMap::new();
+        }
+
+        let mut brillig_call_cache = acvm.take_brillig_call_cache();
+        let decoded_brillig_functions = acvm.take_decoded_brillig_functions();
+        let (functions, unconstrained_functions, blackbox_solver, call_graph, brillig_vm_limits) = (
+            self.functions,
+            self.unconstrained_functions,
+            self.blackbox_solver,
+            self.call_graph,
+            self.brillig_vm_limits,
+        );
+        let cache_max_values = brillig_call_cache.as_ref().map(BrilligCallCache::max_values);
+        let solved_calls: Vec<_> = calls
+            .into_par_iter()
+            .map(|(opcode_index, id, initial_witness)| {
+                let mut foreign_call_executor = NoForeignCalls;
+                let mut executor = ProgramExecutor::new(
+                    functions,
+                    unconstrained_functions,
+                    blackbox_solver,
+                    &mut foreign_call_executor,
+                    call_graph,
+                );
+                executor.current_function_index = id.as_usize();
+                executor.brillig_vm_limits = brillig_vm_limits;
+                executor.decoded_brillig_functions = decoded_brillig_functions.clone();
+                executor.brillig_call_cache =
+                    cache_max_values.map(BrilligCallCache::with_max_values);
+                let result = executor.execute_circuit(initial_witness.clone());
+                let call_cache = executor.brillig_call_cache.take();
+                let result = result.map(|solved_witness| (solved_witness, executor.finalize()));
+                (opcode_index, SolvedCall { initial_witness, result }, call_cache)
+            })
+            .collect();
+
+        let solved_calls = solved_calls
+            .into_iter()
+            .map(|(opcode_index, solved_call, call_cache)| {
+                if let (Some(cache), Some(call_cache)) = (&mut brillig_call_cache, call_cache) {
+                    cache.merge(call_cache);
+                }
+                (opcode_index, solved_call)
+            })
+            .collect();
+        if let Some(brillig_call_cache) = brillig_call_cache {
+            acvm.set_brillig_call_cache(brillig_call_cache);
+        }
+        acvm.set_decoded_brillig_functions(decoded_brillig_functions);
+        solved_calls
+    }
+
+    /// Appends the witnesses of the ACIR calls made by a call which was solved separately.
+    fn extend_witness_stack(&mut self, mut witness_stack: WitnessStack<F>) {
+        let mut stack_items = Vec::with_capacity(witness_stack.length());
+        while let Some(stack_item) = witness_stack.pop() {
+            stack_items.push(stack_item);
+        }
+        for stack_item in stack_items.into_iter().rev() {
+            self.witness_stack.push(stack_item.index, stack_item.witness);
+        }
+    }
+}
+
+/// Prefixes the call stack of an error raised within a separately solved call with the call stack leading up to it.
+fn prepend_call_stack<F: AcirField>(
+    error: NargoError<F>,
+    call_stack: &[ResolvedOpcodeLocation],
+) -> NargoError<F> {
+    let NargoError::ExecutionError(error) = error else {
+        return error;
+    };
+    let prepend = |inner_call_stack: Vec<ResolvedOpcodeLocation>| {
+        call_stack.iter().copied().chain(inner_call_stack).collect()
+    };
+    let error = match error {
+        ExecutionError::AssertionFailed(payload, inner_call_stack, brillig_function_id) => {
+            ExecutionError::AssertionFailed(payload, prepend(inner_call_stack), brillig_function_id)
+        }
+        ExecutionError::SolvingError(error, inner_call_stack) => {
+            ExecutionError::SolvingError(error, inner_call_stack.map(prepend))
+        }
+        ExecutionError::BrilligLimitExceeded(reason, inner_call_stack, brillig_function_id) => {
+            ExecutionError::BrilligLimitExceeded(
+                reason,
+                prepend(inner_call_stack),
+                brillig_function_id,
+            )
+        }
+    };
+    NargoError::ExecutionError(error)
+}
+
+/// A foreign call executor for ACIR calls which are known not to make any foreign calls.
+///
+/// Any foreign call which is made regardless fails, as it can't be made in order with the calls of the program.
+struct NoForeignCalls;
+
+impl<F> ForeignCallExecutor<F> for NoForeignCalls {
+    fn execute(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo<F>,
+    ) -> Result<ForeignCallResult<F>, ForeignCallError> {
+        Err(ForeignCallError::NoHandler(foreign_call.function.clone()))
+    }
 }
 
 #[tracing::instrument(level = "trace", skip_all)]
-pub fn execute_program<F: AcirField, B: BlackBoxFunctionSolver<F>, E: ForeignCallExecutor<F>>(
+pub fn execute_program<
+    F: AcirField + Send + Sync,
+    B: BlackBoxFunctionSolver<F> + Sync,
+    E: ForeignCallExecutor<F>,
+>(
     program: &Program<F>,
     initial_witness: WitnessMap<F>,
     blackbox_solver: &B,
     foreign_call_executor: &mut E,
 ) -> Result<WitnessStack<F>, NargoError<F>> {
+    execute_program_with_options(
+        program,
+        initial_witness,
+        blackbox_solver,
+        foreign_call_executor,
+        ExecutionOptions::default(),
+    )
+    .map(|result| result.witness_stack)
+}
+
+/// Options for executing a program with [`execute_program_with_options`].
+#[derive(Debug)]
+pub struct ExecutionOptions<F> {
+    /// Reuse the results of repeated Brillig calls through this cache.
+    ///
+    /// The cache is returned in [`ExecutionResult::brillig_call_cache`] so that its statistics can be inspected,
+    /// or so that it can be reused for further executions of the same program.
+    pub brillig_call_cache: Option<BrilligCallCache<F>>,
+    /// Take a sample of the call stack for each Brillig opcode executed.
+    pub profiling: bool,
+    /// Fail any Brillig call which exceeds these limits.
+    pub brillig_vm_limits: VMLimits,
+}
+
+impl<F> Default for ExecutionOptions<F> {
+    fn default() -> Self {
+        Self { brillig_call_cache: None, profiling: false, brillig_vm_limits: VMLimits::default() }
+    }
+}
+
+/// The result of executing a program with [`execute_program_with_options`].
+#[derive(Debug)]
+pub struct ExecutionResult<F> {
+    pub witness_stack: WitnessStack<F>,
+    /// The cache passed in [`ExecutionOptions::brillig_call_cache`], if any.
+    pub brillig_call_cache: Option<BrilligCallCache<F>>,
+    /// The samples taken while profiling, if [`ExecutionOptions::profiling`] was set.
+    pub profiling_samples: Option<ProgramProfilingSamples>,
+}
+
+/// Executes `program` as [`execute_program`] does, configured by `options`.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn execute_program_with_options<
+    F: AcirField + Send + Sync,
+    B: BlackBoxFunctionSolver<F> + Sync,
+    E: ForeignCallExecutor<F>,
+>(
+    program: &Program<F>,
+    initial_witness: WitnessMap<F>,
+    blackbox_solver: &B,
+    foreign_call_executor: &mut E,
+    options: ExecutionOptions<F>,
+) -> Result<ExecutionResult<F>, NargoError<F>> {
+    let call_graph = ProgramCallGraph::new(&program.functions, &program.unconstrained_functions);
     let mut executor = ProgramExecutor::new(
         &program.functions,
         &program.unconstrained_functions,
         blackbox_solver,
         foreign_call_executor,
+        &call_graph,
     );
+    executor.brillig_call_cache = options.brillig_call_cache;
+    executor.profiling_samples = options.profiling.then(ProgramProfilingSamples::new);
+    executor.brillig_vm_limits = options.brillig_vm_limits;
     let main_witness = executor.execute_circuit(initial_witness)?;
     executor.witness_stack.push(0, main_witness);
 
-    Ok(executor.finalize())
+    let brillig_call_cache = executor.brillig_call_cache.take();
+    let profiling_samples = executor.profiling_samples.take();
+    Ok(ExecutionResult {
+        witness_stack: executor.finalize(),
+        brillig_call_cache,
+        profiling_samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::{
+            circuit::{brillig::BrilligFunctionId, OpcodeLocation, Program},
+            native_types::{Witness, WitnessMap},
+        },
+        blackbox_solver::StubbedBlackBoxSolver,
+        pwg::{
+            BrilligCallCache, BrilligCallCacheStats, ProfilingSample, ProfilingSamples, VMLimits,
+        },
+        FieldElement,
+    };
+
+    use crate::{errors::ExecutionError, ops::DefaultForeignCallExecutor, NargoError};
+
+    use super::{execute_program, execute_program_with_options, ExecutionOptions};
+
+    fn execute(
+        program: &str,
+        inputs: &[u128],
+    ) -> Result<Vec<(u32, WitnessMap<FieldElement>)>, NargoError<FieldElement>> {
+        let program: Program<FieldElement> = program.parse().unwrap();
+        let initial_witness = WitnessMap::from(
+            inputs
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (Witness(index as u32), FieldElement::from(*value)))
+                .collect::<std::collections::BTreeMap<_, _>>(),
+        );
+        let mut witness_stack = execute_program(
+            &program,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None, None, None).unwrap(),
+        )?;
+
+        let mut stack_items = Vec::new();
+        while let Some(stack_item) = witness_stack.pop() {
+            stack_items.push((stack_item.index, stack_item.witness));
+        }
+        stack_items.reverse();
+        Ok(stack_items)
+    }
+
+    #[test]
+    fn independent_calls_produce_ordered_witness_stack() {
+        let stack_items = execute(
+            "func 0 {
+                current_witness_index: 5
+                private_parameters: [_0, _1]
+                return_values: [_5]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+                ASSERT_ZERO [ (1, _2) (1, _3) (-1, _4) 0 ]
+                CALL { id: 1, inputs: [_4], outputs: [_5] }
+            }
+
+            func 1 {
+                current_witness_index: 2
+                private_parameters: [_0]
+                return_values: [_2]
+                CALL { id: 2, inputs: [_0], outputs: [_1] }
+                ASSERT_ZERO [ (2, _1) (-1, _2) 0 ]
+            }
+
+            func 2 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                ASSERT_ZERO [ (1, _0) (-1, _1) 1 ]
+            }",
+            &[1, 2],
+        )
+        .unwrap();
+
+        // Each call is followed by the calls it makes, in the order in which the program makes them.
+        let indices: Vec<u32> = stack_items.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, vec![2, 1, 2, 1, 2, 1, 0]);
+
+        let inputs: Vec<FieldElement> =
+            stack_items.iter().map(|(_, witness)| witness[&Witness(0)]).collect();
+        let expected_inputs: Vec<FieldElement> =
+            [1_u128, 1, 2, 2, 10, 10, 1].into_iter().map(FieldElement::from).collect();
+        assert_eq!(inputs, expected_inputs);
+
+        let (_, main_witness) = stack_items.last().unwrap();
+        assert_eq!(main_witness[&Witness(5)], FieldElement::from(22_u128));
+    }
+
+    #[test]
+    fn failure_in_independent_call_has_full_call_stack() {
+        let error = execute(
+            "func 0 {
+                current_witness_index: 3
+                private_parameters: [_0, _1]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+            }
+
+            func 1 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                ASSERT_ZERO [ (1, _0) -1 ]
+                ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+            }",
+            &[1, 2],
+        )
+        .unwrap_err();
+
+        let NargoError::ExecutionError(ExecutionError::SolvingError(_, Some(call_stack))) = error
+        else {
+            panic!("Expected a solving error with a call stack");
+        };
+        let call_stack: Vec<_> = call_stack
+            .into_iter()
+            .map(|location| (location.acir_function_index, location.opcode_location))
+            .collect();
+        assert_eq!(call_stack, vec![(0, OpcodeLocation::Acir(1)), (1, OpcodeLocation::Acir(0))]);
+    }
+
+    #[test]
+    fn profiling_samples_are_collected_for_each_function() {
+        let program: Program<FieldElement> = "func 0 {
+                current_witness_index: 3
+                private_parameters: [_0, _1]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+            }
+
+            func 1 {
+                current_witness_index: 2
+                private_parameters: [_0]
+                return_values: [_2]
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_1)] }
+                ASSERT_ZERO [ (1, _0) (1, _1) (-1, _2) 0 ]
+            }
+
+            brillig func 0 {
+                const { destination: @0, bit_size: field, value: 1 }
+                stop { return_data_offset: 0, return_data_size: 1 }
+            }"
+        .parse()
+        .unwrap();
+        let initial_witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(0), FieldElement::from(1_u128)),
+            (Witness(1), FieldElement::from(2_u128)),
+        ]));
+
+        let profiling_samples = execute_program_with_options(
+            &program,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None, None, None).unwrap(),
+            ExecutionOptions { profiling: true, ..Default::default() },
+        )
+        .unwrap()
+        .profiling_samples
+        .unwrap();
+
+        // Both calls to function 1 are executed in place and sampled, while `main` makes no Brillig calls.
+        assert_eq!(profiling_samples.keys().copied().collect::<Vec<_>>(), vec![1]);
+        let sample = |brillig_index| ProfilingSample {
+            call_stack: vec![
+                OpcodeLocation::Acir(0),
+                OpcodeLocation::Brillig { acir_index: 0, brillig_index },
+            ],
+            brillig_function_id: Some(BrilligFunctionId(0)),
+        };
+        let expected_samples = ProfilingSamples::from([(sample(0), 2), (sample(1), 2)]);
+        assert_eq!(profiling_samples[&1], expected_samples);
+    }
+
+    #[test]
+    fn brillig_vm_limits_apply_to_independent_calls() {
+        let program: Program<FieldElement> = "func 0 {
+                current_witness_index: 3
+                private_parameters: [_0, _1]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+            }
+
+            func 1 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_1)] }
+            }
+
+            brillig func 0 {
+                jump { location: 0 }
+            }"
+        .parse()
+        .unwrap();
+        let initial_witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(0), FieldElement::from(1_u128)),
+            (Witness(1), FieldElement::from(2_u128)),
+        ]));
+
+        let options = ExecutionOptions {
+            brillig_vm_limits: VMLimits { max_steps: Some(1000), ..Default::default() },
+            ..Default::default()
+        };
+        let error = execute_program_with_options(
+            &program,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None, None, None).unwrap(),
+            options,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.user_defined_failure_message(&Default::default()), None);
+        let NargoError::ExecutionError(ExecutionError::BrilligLimitExceeded(reason, call_stack, _)) =
+            error
+        else {
+            panic!("Expected the Brillig call to exceed its limits");
+        };
+        assert_eq!(reason, "step limit exceeded: execution did not finish within 1000 opcodes");
+        // The failing call is solved in parallel, but its call stack still starts from `main`.
+        let call_stack: Vec<_> = call_stack
+            .into_iter()
+            .map(|location| (location.acir_function_index, location.opcode_location))
+            .collect();
+        assert_eq!(call_stack.first(), Some(&(0, OpcodeLocation::Acir(0))));
+        assert!(matches!(
+            call_stack.last(),
+            Some((1, OpcodeLocation::Brillig { acir_index: 0, .. }))
+        ));
+    }
+
+    #[test]
+    fn independent_calls_share_the_brillig_call_cache() {
+        let program: Program<FieldElement> = "func 0 {
+                current_witness_index: 5
+                private_parameters: [_0, _1]
+                return_values: [_5]
+                CALL { id: 1, inputs: [_0], outputs: [_2] }
+                CALL { id: 1, inputs: [_1], outputs: [_3] }
+                ASSERT_ZERO [ (1, _2) (-1, _3) (1, _0) (-1, _4) 0 ]
+                CALL { id: 1, inputs: [_4], outputs: [_5] }
+            }
+
+            func 1 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                return_values: [_1]
+                BRILLIG_CALL { id: 0, inputs: [single([ (1, _0) 0 ])], outputs: [simple(_1)] }
+            }
+
+            brillig func 0 {
+                const { destination: @1, bit_size: u32, value: 1 }
+                const { destination: @2, bit_size: u32, value: 0 }
+                calldata_copy { destination_address: @0, size_address: @1, offset_address: @2 }
+                binary_field_op { destination: @0, op: mul, lhs: @0, rhs: @0 }
+                stop { return_data_offset: 0, return_data_size: 1 }
+            }"
+        .parse()
+        .unwrap();
+        let initial_witness = WitnessMap::from(std::collections::BTreeMap::from([
+            (Witness(0), FieldElement::from(3_u128)),
+            (Witness(1), FieldElement::from(3_u128)),
+        ]));
+
+        let options = ExecutionOptions {
+            brillig_call_cache: Some(BrilligCallCache::new()),
+            ..Default::default()
+        };
+        let result = execute_program_with_options(
+            &program,
+            initial_witness,
+            &StubbedBlackBoxSolver,
+            &mut DefaultForeignCallExecutor::new(false, None, None, None).unwrap(),
+            options,
+        )
+        .unwrap();
+
+        // The first two calls are solved in parallel with caches of their own, so neither reuses the other's result.
+        // Their results are merged into the cache, so the last call, which depends on them, reuses them.
+        let stats = result.brillig_call_cache.unwrap().stats();
+        assert_eq!(stats, BrilligCallCacheStats { hits: 1, misses: 2, uncacheable: 0, dropped: 0 });
+
+        let mut witness_stack = result.witness_stack;
+        let main_witness = witness_stack.pop().unwrap().witness;
+        assert_eq!(main_witness[&Witness(5)], FieldElement::from(9_u128));
+    }
 }
@@ -0,0 +1,218 @@
+use acvm::{acir::brillig::ForeignCallResult, pwg::ForeignCallWaitInfo, AcirField};
+use noirc_printable_type::ForeignCallError;
+use serde::{Deserialize, Serialize};
+
+use super::foreign_calls::{DefaultForeignCallExecutor, ForeignCall, ForeignCallExecutor};
+
+/// A foreign call made during execution along with the result it was resolved with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignCallTranscriptEntry<F> {
+    pub call: ForeignCallWaitInfo<F>,
+    pub result: ForeignCallResult<F>,
+}
+
+/// The ordered list of foreign calls made during an execution.
+///
+/// A transcript recorded by a [`RecordingForeignCallExecutor`] can be used by a [`ReplayForeignCallExecutor`]
+/// to execute the same program again without access to the oracles which originally resolved its calls.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForeignCallTranscript<F> {
+    pub calls: Vec<ForeignCallTranscriptEntry<F>>,
+}
+
+impl<F> Default for ForeignCallTranscript<F> {
+    fn default() -> Self {
+        Self { calls: Vec::new() }
+    }
+}
+
+/// Wraps a [`ForeignCallExecutor`], recording each foreign call it resolves.
+///
+/// Calls which fail to resolve are not recorded, as they have no result to replay.
+pub struct RecordingForeignCallExecutor<F, E> {
+    executor: E,
+    transcript: ForeignCallTranscript<F>,
+}
+
+impl<F, E> RecordingForeignCallExecutor<F, E> {
+    pub fn new(executor: E) -> Self {
+        Self { executor, transcript: ForeignCallTranscript::default() }
+    }
+
+    pub fn into_transcript(self) -> ForeignCallTranscript<F> {
+        self.transcript
+    }
+}
+
+impl<F: Clone, E: ForeignCallExecutor<F>> ForeignCallExecutor<F>
+    for RecordingForeignCallExecutor<F, E>
+{
+    fn execute(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo<F>,
+    ) -> Result<ForeignCallResult<F>, ForeignCallError> {
+        let result = self.executor.execute(foreign_call)?;
+        self.transcript.calls.push(ForeignCallTranscriptEntry {
+            call: foreign_call.clone(),
+            result: result.clone(),
+        });
+        Ok(result)
+    }
+}
+
+/// Resolves foreign calls from a previously recorded [`ForeignCallTranscript`].
+///
+/// Each call must match the next call in the transcript both in name and inputs, otherwise
+/// execution has diverged from the recorded run and a [`ForeignCallError::TranscriptDivergence`] is returned.
+pub struct ReplayForeignCallExecutor<F> {
+    transcript: ForeignCallTranscript<F>,
+    /// The index of the next call expected in the transcript.
+    position: usize,
+    /// Whether to print [`ForeignCall::Print`] output.
+    show_output: bool,
+}
+
+impl<F> ReplayForeignCallExecutor<F> {
+    pub fn new(transcript: ForeignCallTranscript<F>, show_output: bool) -> Self {
+        Self { transcript, position: 0, show_output }
+    }
+
+    /// Checks that every call in the transcript has been replayed.
+    ///
+    /// This should be called once execution has finished, as a run which makes fewer calls than
+    /// were recorded has also diverged from the transcript.
+    pub fn finish(&self) -> Result<(), ForeignCallError> {
+        match self.transcript.calls.get(self.position) {
+            Some(entry) => Err(ForeignCallError::TranscriptDivergence(format!(
+                "Execution finished after {} call(s) but {} were recorded, the next being `{}`",
+                self.position,
+                self.transcript.calls.len(),
+                entry.call.function
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<F: AcirField> ForeignCallExecutor<F> for ReplayForeignCallExecutor<F> {
+    fn execute(
+        &mut self,
+        foreign_call: &ForeignCallWaitInfo<F>,
+    ) -> Result<ForeignCallResult<F>, ForeignCallError> {
+        let position = self.position;
+        let Some(entry) = self.transcript.calls.get(position) else {
+            return Err(ForeignCallError::TranscriptDivergence(format!(
+                "Call {position} to `{}` was not recorded, the transcript only contains {} call(s)",
+                foreign_call.function,
+                self.transcript.calls.len()
+            )));
+        };
+        if entry.call.function != foreign_call.function {
+            return Err(ForeignCallError::TranscriptDivergence(format!(
+                "Call {position} was recorded as `{}` but execution called `{}`",
+                entry.call.function, foreign_call.function
+            )));
+        }
+        if entry.call.inputs != foreign_call.inputs {
+            return Err(ForeignCallError::TranscriptDivergence(format!(
+                "Call {position} to `{}` was recorded with inputs {:?} but execution passed {:?}",
+                foreign_call.function, entry.call.inputs, foreign_call.inputs
+            )));
+        }
+
+        if self.show_output
+            && matches!(ForeignCall::lookup(&foreign_call.function), Some(ForeignCall::Print))
+        {
+            DefaultForeignCallExecutor::<F>::execute_print(&foreign_call.inputs)?;
+        }
+
+        self.position += 1;
+        Ok(entry.result.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{
+        acir::brillig::{ForeignCallParam, ForeignCallResult},
+        pwg::ForeignCallWaitInfo,
+        FieldElement,
+    };
+    use noirc_printable_type::ForeignCallError;
+
+    use crate::ops::{
+        ForeignCallExecutor, RecordingForeignCallExecutor, ReplayForeignCallExecutor,
+    };
+
+    /// Resolves every call by returning the sum of its inputs.
+    struct SumOracle;
+
+    impl ForeignCallExecutor<FieldElement> for SumOracle {
+        fn execute(
+            &mut self,
+            foreign_call: &ForeignCallWaitInfo<FieldElement>,
+        ) -> Result<ForeignCallResult<FieldElement>, ForeignCallError> {
+            let mut sum: FieldElement = 0_usize.into();
+            for value in foreign_call.inputs.iter().flat_map(|param| param.fields()) {
+                sum += value;
+            }
+            Ok(sum.into())
+        }
+    }
+
+    fn call(function: &str, input: u128) -> ForeignCallWaitInfo<FieldElement> {
+        ForeignCallWaitInfo {
+            function: function.to_string(),
+            inputs: vec![ForeignCallParam::Single(input.into())],
+        }
+    }
+
+    #[test]
+    fn replays_recorded_calls() {
+        let mut recorder = RecordingForeignCallExecutor::new(SumOracle);
+        let first = recorder.execute(&call("sum", 1)).unwrap();
+        let second = recorder.execute(&call("sum", 2)).unwrap();
+        let transcript = recorder.into_transcript();
+        assert_eq!(transcript.calls.len(), 2);
+
+        let mut replayer = ReplayForeignCallExecutor::new(transcript, false);
+        assert_eq!(replayer.execute(&call("sum", 1)).unwrap(), first);
+        assert_eq!(replayer.execute(&call("sum", 2)).unwrap(), second);
+        assert!(replayer.finish().is_ok());
+    }
+
+    #[test]
+    fn reports_divergent_calls() {
+        let mut recorder = RecordingForeignCallExecutor::new(SumOracle);
+        recorder.execute(&call("sum", 1)).unwrap();
+        let transcript = recorder.into_transcript();
+
+        let mut replayer = ReplayForeignCallExecutor::new(transcript.clone(), false);
+        assert!(matches!(
+            replayer.execute(&call("product", 1)),
+            Err(ForeignCallError::TranscriptDivergence(_))
+        ));
+
+        let mut replayer = ReplayForeignCallExecutor::new(transcript.clone(), false);
+        assert!(matches!(
+            replayer.execute(&call("sum", 2)),
+            Err(ForeignCallError::TranscriptDivergence(_))
+        ));
+
+        let mut replayer = ReplayForeignCallExecutor::new(transcript, false);
+        replayer.execute(&call("sum", 1)).unwrap();
+        assert!(matches!(
+            replayer.execute(&call("sum", 1)),
+            Err(ForeignCallError::TranscriptDivergence(_))
+        ));
+    }
+
+    #[test]
+    fn reports_unreplayed_calls() {
+        let mut recorder = RecordingForeignCallExecutor::new(SumOracle);
+        recorder.execute(&call("sum", 1)).unwrap();
+
+        let replayer = ReplayForeignCallExecutor::new(recorder.into_transcript(), false);
+        assert!(matches!(replayer.finish(), Err(ForeignCallError::TranscriptDivergence(_))));
+    }
+}
@@ -5,11 +5,14 @@ use acvm::{
     pwg::ForeignCallWaitInfo,
     AcirField,
 };
-use jsonrpc::{arg as build_json_rpc_arg, minreq_http::Builder, Client};
+use jsonrpc::{arg as build_json_rpc_arg, Client};
 use noirc_printable_type::{decode_string_value, ForeignCallError, PrintableValueDisplay};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
+use super::oracle_resolver::build_oracle_resolver_client;
+use crate::errors::OracleResolverError;
+
 pub trait ForeignCallExecutor<F> {
     fn execute(
         &mut self,
@@ -23,8 +26,12 @@ pub enum ForeignCall {
     Print,
     CreateMock,
     SetMockParams,
+    SetMockParamAt,
+    ClearMockParams,
     GetMockLastParams,
+    GetMockTimesCalled,
     SetMockReturns,
+    AddMockReturns,
     SetMockTimes,
     ClearMock,
 }
@@ -41,8 +48,12 @@ impl ForeignCall {
             ForeignCall::Print => "print",
             ForeignCall::CreateMock => "create_mock",
             ForeignCall::SetMockParams => "set_mock_params",
+            ForeignCall::SetMockParamAt => "set_mock_param_at",
+            ForeignCall::ClearMockParams => "clear_mock_params",
             ForeignCall::GetMockLastParams => "get_mock_last_params",
+            ForeignCall::GetMockTimesCalled => "get_mock_times_called",
             ForeignCall::SetMockReturns => "set_mock_returns",
+            ForeignCall::AddMockReturns => "add_mock_returns",
             ForeignCall::SetMockTimes => "set_mock_times",
             ForeignCall::ClearMock => "clear_mock",
         }
@@ -53,8 +64,12 @@ impl ForeignCall {
             "print" => Some(ForeignCall::Print),
             "create_mock" => Some(ForeignCall::CreateMock),
             "set_mock_params" => Some(ForeignCall::SetMockParams),
+            "set_mock_param_at" => Some(ForeignCall::SetMockParamAt),
+            "clear_mock_params" => Some(ForeignCall::ClearMockParams),
             "get_mock_last_params" => Some(ForeignCall::GetMockLastParams),
+            "get_mock_times_called" => Some(ForeignCall::GetMockTimesCalled),
             "set_mock_returns" => Some(ForeignCall::SetMockReturns),
+            "add_mock_returns" => Some(ForeignCall::AddMockReturns),
             "set_mock_times" => Some(ForeignCall::SetMockTimes),
             "clear_mock" => Some(ForeignCall::ClearMock),
             _ => None,
@@ -62,6 +77,31 @@ impl ForeignCall {
     }
 }
 
+/// The parameters which an oracle mock responds to.
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum MockedParams<F> {
+    /// Match any parameters
+    Any,
+    /// Match exactly these parameters
+    Exact(Vec<ForeignCallParam<F>>),
+    /// Match any parameters which contain each of these runs of parameters at the given position
+    Partial(Vec<(usize, Vec<ForeignCallParam<F>>)>),
+}
+
+impl<F: PartialEq> MockedParams<F> {
+    fn matches(&self, params: &[ForeignCallParam<F>]) -> bool {
+        match self {
+            MockedParams::Any => true,
+            MockedParams::Exact(expected) => expected.as_slice() == params,
+            MockedParams::Partial(expected) => expected.iter().all(|(position, expected)| {
+                let actual =
+                    params.get(*position..).and_then(|params| params.get(..expected.len()));
+                actual == Some(expected.as_slice())
+            }),
+        }
+    }
+}
+
 /// This struct represents an oracle mock. It can be used for testing programs that use oracles.
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct MockedCall<F> {
@@ -69,13 +109,16 @@ struct MockedCall<F> {
     id: usize,
     /// The oracle it's mocking
     name: String,
-    /// Optionally match the parameters
-    params: Option<Vec<ForeignCallParam<F>>>,
+    /// The parameters this mock responds to
+    params: MockedParams<F>,
     /// The parameters with which the mock was last called
     last_called_params: Option<Vec<ForeignCallParam<F>>>,
-    /// The result to return when this mock is called
-    result: ForeignCallResult<F>,
-    /// How many times should this mock be called before it is removed
+    /// The results to return when this mock is called, in order.
+    /// Once all of them have been returned, the last result is returned for any further calls.
+    results: Vec<ForeignCallResult<F>>,
+    /// How many times this mock has been called
+    times_called: u64,
+    /// How many more times this mock is expected to be called, after which it no longer responds to calls
     times_left: Option<u64>,
 }
 
@@ -84,17 +127,30 @@ impl<F> MockedCall<F> {
         Self {
             id,
             name,
-            params: None,
+            params: MockedParams::Any,
             last_called_params: None,
-            result: ForeignCallResult { values: vec![] },
+            results: Vec::new(),
+            times_called: 0,
             times_left: None,
         }
     }
 }
 
+impl<F: Clone> MockedCall<F> {
+    /// The result to return for the next call to this mock.
+    fn next_result(&self) -> ForeignCallResult<F> {
+        let index = usize::try_from(self.times_called).unwrap_or(usize::MAX);
+        self.results
+            .get(index)
+            .or_else(|| self.results.last())
+            .cloned()
+            .unwrap_or(ForeignCallResult { values: vec![] })
+    }
+}
+
 impl<F: PartialEq> MockedCall<F> {
     fn matches(&self, name: &str, params: &[ForeignCallParam<F>]) -> bool {
-        self.name == name && (self.params.is_none() || self.params.as_deref() == Some(params))
+        self.name == name && self.times_left != Some(0) && self.params.matches(params)
     }
 }
 
@@ -112,7 +168,7 @@ pub struct DefaultForeignCallExecutor<F> {
     mocked_responses: Vec<MockedCall<F>>,
     /// Whether to print [`ForeignCall::Print`] output.
     show_output: bool,
-    /// JSON RPC client to resolve foreign calls
+    /// JSON RPC client to resolve foreign calls, over HTTP, a Unix domain socket or a child process' stdio
     external_resolver: Option<Client>,
     /// Root path to the program or workspace in execution.
     root_path: Option<PathBuf>,
@@ -142,25 +198,16 @@ struct ResolveForeignCallRequest<F> {
 }
 
 impl<F> DefaultForeignCallExecutor<F> {
+    /// Creates an executor which passes foreign calls it can't handle itself to the oracle resolver at
+    /// `resolver_url`, failing if the resolver's transport can't be set up.
     pub fn new(
         show_output: bool,
         resolver_url: Option<&str>,
         root_path: Option<PathBuf>,
         package_name: Option<String>,
-    ) -> Self {
-        let oracle_resolver = resolver_url.map(|resolver_url| {
-            let mut transport_builder =
-                Builder::new().url(resolver_url).expect("Invalid oracle resolver URL");
-
-            if let Some(Ok(timeout)) =
-                std::env::var("NARGO_FOREIGN_CALL_TIMEOUT").ok().map(|timeout| timeout.parse())
-            {
-                let timeout_duration = std::time::Duration::from_millis(timeout);
-                transport_builder = transport_builder.timeout(timeout_duration);
-            };
-            Client::with_transport(transport_builder.build())
-        });
-        DefaultForeignCallExecutor {
+    ) -> Result<Self, OracleResolverError> {
+        let oracle_resolver = resolver_url.map(build_oracle_resolver_client).transpose()?;
+        Ok(DefaultForeignCallExecutor {
             show_output,
             external_resolver: oracle_resolver,
             id: rand::thread_rng().gen(),
@@ -168,6 +215,27 @@ impl<F> DefaultForeignCallExecutor<F> {
             last_mock_id: 0,
             root_path,
             package_name,
+        })
+    }
+
+    /// Checks that each mock which was set to be called a number of times has been called that many times.
+    pub fn verify_mocks(&self) -> Result<(), ForeignCallError> {
+        let unsatisfied_mocks: Vec<_> = self
+            .mocked_responses
+            .iter()
+            .filter_map(|mock| match mock.times_left {
+                Some(times_left) if times_left > 0 => Some(format!(
+                    "mock {} of `{}` was called {} time(s), {} fewer than expected",
+                    mock.id, mock.name, mock.times_called, times_left
+                )),
+                _ => None,
+            })
+            .collect();
+
+        if unsatisfied_mocks.is_empty() {
+            Ok(())
+        } else {
+            Err(ForeignCallError::UnsatisfiedMocks(unsatisfied_mocks.join(", ")))
         }
     }
 }
@@ -197,7 +265,9 @@ impl<F: AcirField> DefaultForeignCallExecutor<F> {
         decode_string_value(&fields)
     }
 
-    fn execute_print(foreign_call_inputs: &[ForeignCallParam<F>]) -> Result<(), ForeignCallError> {
+    pub(crate) fn execute_print(
+        foreign_call_inputs: &[ForeignCallParam<F>],
+    ) -> Result<(), ForeignCallError> {
         let skip_newline = foreign_call_inputs[0].unwrap_field().is_zero();
 
         let foreign_call_inputs =
@@ -249,7 +319,36 @@ impl<F: AcirField + Serialize + for<'a> Deserialize<'a>> ForeignCallExecutor<F>
                 let (id, params) = Self::extract_mock_id(&foreign_call.inputs)?;
                 self.find_mock_by_id_mut(id)
                     .unwrap_or_else(|| panic!("Unknown mock id {}", id))
-                    .params = Some(params.to_vec());
+                    .params = MockedParams::Exact(params.to_vec());
+
+                Ok(ForeignCallResult::default())
+            }
+            Some(ForeignCall::SetMockParamAt) => {
+                let (id, params) = Self::extract_mock_id(&foreign_call.inputs)?;
+                let (position, params) =
+                    params.split_first().ok_or(ForeignCallError::MissingForeignCallInputs)?;
+                let position = usize::try_from(
+                    position.unwrap_field().try_to_u64().expect("value does not fit into u64"),
+                )
+                .expect("value does not fit into usize");
+
+                let mock = self
+                    .find_mock_by_id_mut(id)
+                    .unwrap_or_else(|| panic!("Unknown mock id {}", id));
+                match &mut mock.params {
+                    MockedParams::Partial(expected) => expected.push((position, params.to_vec())),
+                    mocked_params => {
+                        *mocked_params = MockedParams::Partial(vec![(position, params.to_vec())]);
+                    }
+                }
+
+                Ok(ForeignCallResult::default())
+            }
+            Some(ForeignCall::ClearMockParams) => {
+                let (id, _) = Self::extract_mock_id(&foreign_call.inputs)?;
+                self.find_mock_by_id_mut(id)
+                    .unwrap_or_else(|| panic!("Unknown mock id {}", id))
+                    .params = MockedParams::Any;
 
                 Ok(ForeignCallResult::default())
             }
@@ -265,11 +364,27 @@ impl<F: AcirField + Serialize + for<'a> Deserialize<'a>> ForeignCallExecutor<F>
 
                 Ok(last_called_params.into())
             }
+            Some(ForeignCall::GetMockTimesCalled) => {
+                let (id, _) = Self::extract_mock_id(&foreign_call.inputs)?;
+                let mock =
+                    self.find_mock_by_id(id).unwrap_or_else(|| panic!("Unknown mock id {}", id));
+
+                Ok(F::from(u128::from(mock.times_called)).into())
+            }
             Some(ForeignCall::SetMockReturns) => {
                 let (id, params) = Self::extract_mock_id(&foreign_call.inputs)?;
                 self.find_mock_by_id_mut(id)
                     .unwrap_or_else(|| panic!("Unknown mock id {}", id))
-                    .result = ForeignCallResult { values: params.to_vec() };
+                    .results = vec![ForeignCallResult { values: params.to_vec() }];
+
+                Ok(ForeignCallResult::default())
+            }
+            Some(ForeignCall::AddMockReturns) => {
+                let (id, params) = Self::extract_mock_id(&foreign_call.inputs)?;
+                self.find_mock_by_id_mut(id)
+                    .unwrap_or_else(|| panic!("Unknown mock id {}", id))
+                    .results
+                    .push(ForeignCallResult { values: params.to_vec() });
 
                 Ok(ForeignCallResult::default())
             }
@@ -306,16 +421,16 @@ impl<F: AcirField + Serialize + for<'a> Deserialize<'a>> ForeignCallExecutor<F>
 
                     mock.last_called_params = Some(foreign_call.inputs.clone());
 
-                    let result = mock.result.values.clone();
+                    let result = mock.next_result();
+                    mock.times_called += 1;
 
+                    // Mocks which have been called as many times as they were set to are kept, so that
+                    // they can still be inspected, but no longer respond to calls.
                     if let Some(times_left) = &mut mock.times_left {
                         *times_left -= 1;
-                        if *times_left == 0 {
-                            self.mocked_responses.remove(response_position);
-                        }
                     }
 
-                    Ok(result.into())
+                    Ok(result)
                 } else if let Some(external_resolver) = &self.external_resolver {
                     // If the user has registered an external resolver then we forward any remaining oracle calls there.
 
@@ -362,9 +477,11 @@ mod tests {
     use jsonrpc_derive::rpc;
     use jsonrpc_http_server::{Server, ServerBuilder};
 
+    use noirc_printable_type::ForeignCallError;
+
     use crate::ops::{DefaultForeignCallExecutor, ForeignCallExecutor};
 
-    use super::ResolveForeignCallRequest;
+    use super::{MockedParams, ResolveForeignCallRequest};
 
     #[allow(unreachable_pub)]
     #[rpc]
@@ -428,7 +545,7 @@ mod tests {
         let (server, url) = build_oracle_server();
 
         let mut executor =
-            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None);
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None).unwrap();
 
         let foreign_call = ForeignCallWaitInfo {
             function: "echo".to_string(),
@@ -445,7 +562,7 @@ mod tests {
     fn test_oracle_resolver_sum() {
         let (server, url) = build_oracle_server();
 
-        let mut executor = DefaultForeignCallExecutor::new(false, Some(&url), None, None);
+        let mut executor = DefaultForeignCallExecutor::new(false, Some(&url), None, None).unwrap();
 
         let foreign_call = ForeignCallWaitInfo {
             function: "sum".to_string(),
@@ -463,7 +580,7 @@ mod tests {
         let (server, url) = build_oracle_server();
 
         let mut executor =
-            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None);
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None).unwrap();
 
         let foreign_call = ForeignCallWaitInfo { function: "id".to_string(), inputs: Vec::new() };
 
@@ -479,9 +596,9 @@ mod tests {
         let (server, url) = build_oracle_server();
 
         let mut executor_1 =
-            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None);
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None).unwrap();
         let mut executor_2 =
-            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None);
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&url), None, None).unwrap();
 
         let foreign_call = ForeignCallWaitInfo { function: "id".to_string(), inputs: Vec::new() };
 
@@ -491,4 +608,50 @@ mod tests {
 
         server.close();
     }
+
+    #[test]
+    fn mock_params_match_partially() {
+        let param = |value: u128| ForeignCallParam::Single(FieldElement::from(value));
+        let params = [param(1), param(2), param(3)];
+
+        assert!(MockedParams::Any.matches(&params));
+        assert!(MockedParams::Exact(params.to_vec()).matches(&params));
+        assert!(!MockedParams::Exact(vec![param(1)]).matches(&params));
+        assert!(MockedParams::Partial(vec![(0, vec![param(1), param(2)])]).matches(&params));
+        assert!(
+            MockedParams::Partial(vec![(0, vec![param(1)]), (2, vec![param(3)])]).matches(&params)
+        );
+        assert!(!MockedParams::Partial(vec![(1, vec![param(3)])]).matches(&params));
+        assert!(!MockedParams::Partial(vec![(2, vec![param(3), param(4)])]).matches(&params));
+    }
+
+    #[test]
+    fn mocks_return_sequences_and_verify_times() {
+        let mut executor =
+            DefaultForeignCallExecutor::<FieldElement>::new(false, None, None, None).unwrap();
+        let call = |function: &str, inputs: Vec<ForeignCallParam<FieldElement>>| {
+            ForeignCallWaitInfo { function: function.to_string(), inputs }
+        };
+        let field = |value: u128| ForeignCallParam::Single(FieldElement::from(value));
+
+        let name = "oracle".bytes().map(|byte| FieldElement::from(u128::from(byte))).collect();
+        let id = executor.execute(&call("create_mock", vec![ForeignCallParam::Array(name)]));
+        let id = id.unwrap().values[0].clone();
+        executor.execute(&call("set_mock_returns", vec![id.clone(), field(1)])).unwrap();
+        executor.execute(&call("add_mock_returns", vec![id.clone(), field(2)])).unwrap();
+        executor.execute(&call("set_mock_times", vec![id.clone(), field(3)])).unwrap();
+
+        let oracle_call = call("oracle", vec![]);
+        assert_eq!(executor.execute(&oracle_call).unwrap(), FieldElement::from(1_u128).into());
+        assert_eq!(executor.execute(&oracle_call).unwrap(), FieldElement::from(2_u128).into());
+        assert!(matches!(executor.verify_mocks(), Err(ForeignCallError::UnsatisfiedMocks(_))));
+
+        assert_eq!(executor.execute(&oracle_call).unwrap(), FieldElement::from(2_u128).into());
+        assert!(executor.verify_mocks().is_ok());
+
+        // The mock no longer responds once it has been called as many times as it was set to.
+        assert_eq!(executor.execute(&oracle_call).unwrap(), ForeignCallResult::default());
+        let times_called = executor.execute(&call("get_mock_times_called", vec![id])).unwrap();
+        assert_eq!(times_called, FieldElement::from(3_u128).into());
+    }
 }
@@ -0,0 +1,329 @@
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use jsonrpc::{minreq_http::Builder, Client, Request, Response, Transport};
+
+use crate::errors::OracleResolverError;
+
+/// The transport over which an external oracle resolver is reached, as selected by the scheme of its URL.
+#[derive(Debug, PartialEq, Eq)]
+enum OracleResolverUrl<'a> {
+    /// A JSON RPC server listening over HTTP, e.g. `http://localhost:5555`.
+    Http(&'a str),
+    /// A command to spawn which reads JSON RPC requests from its stdin and writes responses to its stdout,
+    /// one per line, e.g. `stdio:node oracle.js`.
+    Stdio(&'a str),
+    /// A JSON RPC server listening on a Unix domain socket, e.g. `unix:///tmp/oracle.sock`.
+    UnixSocket(&'a Path),
+}
+
+impl<'a> OracleResolverUrl<'a> {
+    fn parse(resolver_url: &'a str) -> Self {
+        if let Some(command) = resolver_url.strip_prefix("stdio:") {
+            OracleResolverUrl::Stdio(command.trim())
+        } else if let Some(socket_path) = resolver_url.strip_prefix("unix:") {
+            let socket_path = socket_path.strip_prefix("//").unwrap_or(socket_path);
+            OracleResolverUrl::UnixSocket(Path::new(socket_path))
+        } else {
+            OracleResolverUrl::Http(resolver_url)
+        }
+    }
+}
+
+/// Reads the timeout for calls to the oracle resolver from `NARGO_FOREIGN_CALL_TIMEOUT`, in milliseconds.
+fn foreign_call_timeout() -> Option<Duration> {
+    let timeout = std::env::var("NARGO_FOREIGN_CALL_TIMEOUT").ok()?;
+    timeout.parse().ok().map(Duration::from_millis)
+}
+
+/// Builds a JSON RPC client for the oracle resolver at `resolver_url`.
+///
+/// The timeout set through `NARGO_FOREIGN_CALL_TIMEOUT` applies to every transport.
+pub(crate) fn build_oracle_resolver_client(
+    resolver_url: &str,
+) -> Result<Client, OracleResolverError> {
+    let timeout = foreign_call_timeout();
+    match OracleResolverUrl::parse(resolver_url) {
+        OracleResolverUrl::Http(url) => {
+            let mut transport_builder = Builder::new().url(url).map_err(|err| {
+                OracleResolverError::InvalidUrl { url: url.to_string(), reason: err.to_string() }
+            })?;
+            if let Some(timeout) = timeout {
+                transport_builder = transport_builder.timeout(timeout);
+            }
+            Ok(Client::with_transport(transport_builder.build()))
+        }
+        OracleResolverUrl::Stdio(command) => {
+            Ok(Client::with_transport(StdioTransport::spawn(command, timeout)?))
+        }
+        #[cfg(unix)]
+        OracleResolverUrl::UnixSocket(socket_path) => {
+            let mut transport = jsonrpc::simple_uds::UdsTransport::new(socket_path);
+            transport.timeout = timeout;
+            Ok(Client::with_transport(transport))
+        }
+        #[cfg(not(unix))]
+        OracleResolverUrl::UnixSocket(_) => Err(OracleResolverError::UnixSocketsUnsupported),
+    }
+}
+
+/// A JSON RPC transport to a child process, exchanging newline delimited messages over its stdin and stdout.
+///
+/// The command is split into a program and its arguments following shell quoting rules, so arguments
+/// containing spaces may be quoted, e.g. `stdio:node "my oracle.js"`. The command is not run through a shell.
+/// The process is spawned when the transport is created and killed once it is dropped.
+struct StdioTransport {
+    command: String,
+    timeout: Option<Duration>,
+    process: Mutex<StdioProcess>,
+}
+
+struct StdioProcess {
+    child: Child,
+    stdin: ChildStdin,
+    /// Lines read from the process' stdout by a separate thread, so that reads can time out.
+    responses: Receiver<std::io::Result<String>>,
+}
+
+impl StdioTransport {
+    fn spawn(command: &str, timeout: Option<Duration>) -> Result<Self, OracleResolverError> {
+        let arguments = shell_words::split(command).map_err(|source| {
+            OracleResolverError::InvalidCommand { command: command.to_string(), source }
+        })?;
+        let Some((program, arguments)) = arguments.split_first() else {
+            return Err(OracleResolverError::InvalidUrl {
+                url: format!("stdio:{command}"),
+                reason: "expected a command after `stdio:`".to_string(),
+            });
+        };
+
+        let mut child = Command::new(program)
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|source| OracleResolverError::SpawnFailed {
+                command: command.to_string(),
+                source,
+            })?;
+        let stdin = child.stdin.take().expect("stdin should be piped");
+        let stdout = BufReader::new(child.stdout.take().expect("stdout should be piped"));
+
+        let (sender, responses) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line).is_err() {
+                    // The transport has been dropped.
+                    break;
+                }
+            }
+        });
+
+        Ok(StdioTransport {
+            command: command.to_string(),
+            timeout,
+            process: Mutex::new(StdioProcess { child, stdin, responses }),
+        })
+    }
+
+    fn exchange<R: serde::Serialize, T: serde::de::DeserializeOwned>(
+        &self,
+        request: &R,
+    ) -> Result<T, jsonrpc::Error> {
+        let mut process = self.process.lock().expect("oracle resolver process lock was poisoned");
+
+        let mut message = serde_json::to_vec(request)?;
+        message.push(b'\n');
+        process.stdin.write_all(&message).map_err(transport_error)?;
+        process.stdin.flush().map_err(transport_error)?;
+
+        let response = match self.timeout {
+            Some(timeout) => process.responses.recv_timeout(timeout).map_err(|err| match err {
+                RecvTimeoutError::Timeout => std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!(
+                        "oracle resolver `{}` did not respond within {}ms",
+                        self.command,
+                        timeout.as_millis()
+                    ),
+                ),
+                RecvTimeoutError::Disconnected => self.closed_stdout_error(),
+            }),
+            None => process.responses.recv().map_err(|_| self.closed_stdout_error()),
+        };
+        let line = response.map_err(transport_error)?.map_err(transport_error)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    fn closed_stdout_error(&self) -> std::io::Error {
+        std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            format!("oracle resolver `{}` closed its stdout", self.command),
+        )
+    }
+}
+
+fn transport_error(error: std::io::Error) -> jsonrpc::Error {
+    jsonrpc::Error::Transport(Box::new(error))
+}
+
+impl Transport for StdioTransport {
+    fn send_request(&self, request: Request) -> Result<Response, jsonrpc::Error> {
+        self.exchange(&request)
+    }
+
+    fn send_batch(&self, requests: &[Request]) -> Result<Vec<Response>, jsonrpc::Error> {
+        self.exchange(&requests)
+    }
+
+    fn fmt_target(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "stdio:{}", self.command)
+    }
+}
+
+impl Drop for StdioTransport {
+    fn drop(&mut self) {
+        if let Ok(process) = self.process.get_mut() {
+            // The process may have already exited, in which case there is nothing to clean up.
+            let _ = process.child.kill();
+            let _ = process.child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use acvm::{acir::brillig::ForeignCallParam, pwg::ForeignCallWaitInfo, FieldElement};
+
+    use super::OracleResolverUrl;
+    use crate::errors::OracleResolverError;
+    use crate::ops::{DefaultForeignCallExecutor, ForeignCallExecutor};
+
+    #[test]
+    fn selects_transport_from_url_scheme() {
+        assert_eq!(
+            OracleResolverUrl::parse("http://127.0.0.1:5555"),
+            OracleResolverUrl::Http("http://127.0.0.1:5555")
+        );
+        assert_eq!(
+            OracleResolverUrl::parse("stdio:node oracle.js --verbose"),
+            OracleResolverUrl::Stdio("node oracle.js --verbose")
+        );
+        assert_eq!(
+            OracleResolverUrl::parse("unix:///tmp/oracle.sock"),
+            OracleResolverUrl::UnixSocket(Path::new("/tmp/oracle.sock"))
+        );
+        assert_eq!(
+            OracleResolverUrl::parse("unix:oracle.sock"),
+            OracleResolverUrl::UnixSocket(Path::new("oracle.sock"))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolves_foreign_calls_over_unix_socket() {
+        let socket_dir = tempfile::tempdir().unwrap();
+        let socket_path = socket_dir.path().join("oracle.sock");
+        let listener = std::os::unix::net::UnixListener::bind(&socket_path).unwrap();
+
+        // Echoes back the first input of a single foreign call.
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let request: serde_json::Value = serde_json::Deserializer::from_reader(&stream)
+                .into_iter()
+                .next()
+                .expect("Expected a request")
+                .unwrap();
+            let response = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": { "values": [request["params"][0]["inputs"][0]] },
+            });
+            serde_json::to_writer(&stream, &response).unwrap();
+        });
+
+        let resolver_url = format!("unix://{}", socket_path.display());
+        let mut executor =
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&resolver_url), None, None)
+                .unwrap();
+
+        let foreign_call = ForeignCallWaitInfo {
+            function: "echo".to_string(),
+            inputs: vec![ForeignCallParam::Single(1_u128.into())],
+        };
+
+        let result = executor.execute(&foreign_call);
+        assert_eq!(result.unwrap().values, foreign_call.inputs);
+
+        server.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolves_foreign_calls_over_stdio() {
+        let script_dir = tempfile::tempdir().unwrap();
+        let script_path = script_dir.path().join("oracle resolver.sh");
+        // Answers every request with the same value, echoing back the request's id.
+        std::fs::write(
+            &script_path,
+            r#"while read -r request; do
+                id=$(printf '%s' "$request" | sed 's/.*"id":\([0-9]*\).*/\1/')
+                printf '{"jsonrpc":"2.0","id":%s,"result":{"values":["0x2a"]}}\n' "$id"
+            done"#,
+        )
+        .unwrap();
+
+        // The script's path contains a space, so it has to be quoted.
+        let resolver_url = format!("stdio:sh '{}'", script_path.display());
+        let mut executor =
+            DefaultForeignCallExecutor::<FieldElement>::new(false, Some(&resolver_url), None, None)
+                .unwrap();
+
+        let foreign_call = ForeignCallWaitInfo {
+            function: "answer".to_string(),
+            inputs: vec![ForeignCallParam::Single(1_u128.into())],
+        };
+        let expected = vec![ForeignCallParam::Single(FieldElement::from(42_u128))];
+
+        // The same process answers each call.
+        assert_eq!(executor.execute(&foreign_call).unwrap().values, expected);
+        assert_eq!(executor.execute(&foreign_call).unwrap().values, expected);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn stdio_calls_time_out() {
+        let timeout = Some(std::time::Duration::from_millis(100));
+        let transport = super::StdioTransport::spawn("sleep 5", timeout).unwrap();
+
+        let result = transport.exchange::<_, serde_json::Value>(&serde_json::json!({ "id": 1 }));
+        assert!(result.unwrap_err().to_string().contains("did not respond within 100ms"));
+    }
+
+    #[test]
+    fn reports_stdio_resolvers_which_cannot_be_spawned() {
+        let result = DefaultForeignCallExecutor::<FieldElement>::new(
+            false,
+            Some("stdio:nargo-test-missing-oracle-resolver"),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(OracleResolverError::SpawnFailed { .. })));
+
+        let result = DefaultForeignCallExecutor::<FieldElement>::new(
+            false,
+            Some("stdio:node 'oracle.js"),
+            None,
+            None,
+        );
+        assert!(matches!(result, Err(OracleResolverError::InvalidCommand { .. })));
+    }
+}
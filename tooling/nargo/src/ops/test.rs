@@ -26,7 +26,7 @@ impl TestStatus {
 }
 
 #[allow(clippy::too_many_arguments)]
-pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
+pub fn run_test<B: BlackBoxFunctionSolver<FieldElement> + Sync>(
     blackbox_solver: &B,
     context: &mut Context,
     test_function: &TestFunction,
@@ -46,19 +46,40 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
     match compile_no_check(context, config, test_function.get_id(), None, false) {
         Ok(compiled_program) => {
             if test_function_has_no_arguments {
+                let mut foreign_call_executor = match DefaultForeignCallExecutor::new(
+                    show_output,
+                    foreign_call_resolver_url,
+                    root_path,
+                    package_name,
+                ) {
+                    Ok(executor) => executor,
+                    Err(error) => {
+                        return TestStatus::Fail {
+                            message: error.to_string(),
+                            error_diagnostic: None,
+                        }
+                    }
+                };
                 // Run the backend to ensure the PWG evaluates functions like std::hash::pedersen,
                 // otherwise constraints involving these expressions will not error.
                 let circuit_execution = execute_program(
                     &compiled_program.program,
                     WitnessMap::new(),
                     blackbox_solver,
-                    &mut DefaultForeignCallExecutor::new(
-                        show_output,
-                        foreign_call_resolver_url,
-                        root_path,
-                        package_name,
-                    ),
+                    &mut foreign_call_executor,
                 );
+
+                // A test whose mocks were not called as many times as expected fails,
+                // even if the test is expected to fail.
+                if circuit_execution.is_ok() {
+                    if let Err(error) = foreign_call_executor.verify_mocks() {
+                        return TestStatus::Fail {
+                            message: error.to_string(),
+                            error_diagnostic: None,
+                        };
+                    }
+                }
+
                 test_status_program_compile_pass(
                     test_function,
                     compiled_program.abi,
@@ -86,18 +107,23 @@ pub fn run_test<B: BlackBoxFunctionSolver<FieldElement>>(
                         |program: &Program<FieldElement>,
                          initial_witness: WitnessMap<FieldElement>|
                          -> Result<WitnessStack<FieldElement>, String> {
-                            execute_program(
-                                program,
-                                initial_witness,
-                                blackbox_solver,
-                                &mut DefaultForeignCallExecutor::<FieldElement>::new(
+                            let mut foreign_call_executor =
+                                DefaultForeignCallExecutor::<FieldElement>::new(
                                     false,
                                     foreign_call_resolver_url,
                                     root_path.clone(),
                                     package_name.clone(),
-                                ),
+                                )
+                                .map_err(|err| err.to_string())?;
+                            let witness_stack = execute_program(
+                                program,
+                                initial_witness,
+                                blackbox_solver,
+                                &mut foreign_call_executor,
                             )
-                            .map_err(|err| err.to_string())
+                            .map_err(|err| err.to_string())?;
+                            foreign_call_executor.verify_mocks().map_err(|err| err.to_string())?;
+                            Ok(witness_stack)
                         };
                     let fuzzer = FuzzedExecutor::new(compiled_program.into(), executor, runner);
 
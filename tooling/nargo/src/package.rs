@@ -53,6 +53,8 @@ pub struct Package {
     pub name: CrateName,
     pub dependencies: BTreeMap<CrateName, Dependency>,
     pub expression_width: Option<ExpressionWidth>,
+    /// The field which the package must be compiled over, if it requires a specific one.
+    pub field: Option<String>,
 }
 
 impl Package {
@@ -13,7 +13,8 @@ use nargo::{
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::{AbiParameter, AbiType, MAIN_RETURN_NAME};
 use noirc_driver::{
-    check_crate, compute_function_abi, CompileOptions, CrateId, NOIR_ARTIFACT_VERSION_STRING,
+    check_crate, check_unreachable_items, compute_function_abi, CompileOptions, CrateId,
+    NOIR_ARTIFACT_VERSION_STRING,
 };
 use noirc_frontend::hir::{Context, ParsedFiles};
 
@@ -26,18 +27,22 @@ use super::NargoConfig;
 pub(crate) struct CheckCommand {
     /// The name of the package to check
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Check all packages in the workspace
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     /// Force overwrite of existing files
     #[clap(long = "overwrite")]
     allow_overwrite: bool,
 
+    /// Report functions and globals which are never reachable from an entry point or test
+    #[clap(long)]
+    unused: bool,
+
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 }
 
 pub(crate) fn run(args: CheckCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -62,6 +67,7 @@ pub(crate) fn run(args: CheckCommand, config: NargoConfig) -> Result<(), CliErro
             package,
             &args.compile_options,
             args.allow_overwrite,
+            args.unused,
         )?;
         if any_file_written {
             println!("[{}] Constraint system successfully built!", package.name);
@@ -78,10 +84,22 @@ fn check_package(
     package: &Package,
     compile_options: &CompileOptions,
     allow_overwrite: bool,
+    report_unreachable_items: bool,
 ) -> Result<bool, CompileError> {
     let (mut context, crate_id) = prepare_package(file_manager, parsed_files, package);
     check_crate_and_report_errors(&mut context, crate_id, compile_options)?;
 
+    if report_unreachable_items {
+        let is_library = package.is_library();
+        let result = check_unreachable_items(&context, crate_id, is_library, compile_options);
+        report_errors(
+            result,
+            &context.file_manager,
+            compile_options.deny_warnings,
+            compile_options.silence_warnings,
+        )?;
+    }
+
     if package.is_library() || package.is_contract() {
         // Libraries do not have ABIs while contracts have many, so we cannot generate a `Prover.toml` file.
         Ok(false)
@@ -29,14 +29,14 @@ use rayon::prelude::*;
 pub(crate) struct CompileCommand {
     /// The name of the package to compile
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Compile all packages in the workspace.
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 
     /// Watch workspace and recompile on changes.
     #[clap(long, hide = true)]
@@ -1,7 +1,6 @@
 use acvm::acir::circuit::ExpressionWidth;
 use acvm::acir::native_types::WitnessMap;
 use acvm::FieldElement;
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::workspace::Workspace;
@@ -21,6 +20,7 @@ use serde_json::Value;
 
 use super::debug_cmd::compile_bin_package_for_debugging;
 use super::fs::inputs::read_inputs_from_file;
+use super::FieldBlackBoxSolver;
 use crate::errors::CliError;
 
 use super::NargoConfig;
@@ -172,6 +172,8 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                     .get("proverName")
                     .and_then(|v| v.as_str())
                     .unwrap_or(PROVER_INPUT_FILE);
+                let oracle_resolver =
+                    additional_data.get("oracleResolver").and_then(|v| v.as_str());
 
                 let generate_acir =
                     additional_data.get("generateAcir").and_then(|v| v.as_bool()).unwrap_or(false);
@@ -183,6 +185,9 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
                 eprintln!("Project folder: {}", project_folder);
                 eprintln!("Package: {}", package.unwrap_or("(default)"));
                 eprintln!("Prover name: {}", prover_name);
+                if let Some(oracle_resolver) = oracle_resolver {
+                    eprintln!("Oracle resolver: {}", oracle_resolver);
+                }
 
                 match load_and_compile_project(
                     project_folder,
@@ -197,9 +202,10 @@ fn loop_uninitialized_dap<R: Read, W: Write>(
 
                         noir_debugger::run_dap_loop(
                             server,
-                            &Bn254BlackBoxSolver,
+                            &FieldBlackBoxSolver::default(),
                             compiled_program,
                             initial_witness,
+                            oracle_resolver,
                         )?;
                         break;
                     }
@@ -2,7 +2,6 @@ use std::path::PathBuf;
 
 use acvm::acir::native_types::WitnessStack;
 use acvm::FieldElement;
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
 use fm::FileManager;
@@ -23,6 +22,7 @@ use noirc_frontend::hir::ParsedFiles;
 
 use super::compile_cmd::get_target_width;
 use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
+use super::FieldBlackBoxSolver;
 use super::NargoConfig;
 use crate::errors::CliError;
 
@@ -38,10 +38,10 @@ pub(crate) struct DebugCommand {
 
     /// The name of the package to execute
     #[clap(long)]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 
     /// Force ACIR output (disabling instrumentation)
     #[clap(long)]
@@ -50,6 +50,13 @@ pub(crate) struct DebugCommand {
     /// Disable vars debug instrumentation (enabled by default)
     #[clap(long)]
     skip_instrumentation: Option<bool>,
+
+    /// JSON RPC url to solve oracle calls
+    ///
+    /// Besides `http://` URLs, a resolver may be reached over a Unix domain socket with `unix://<path>`,
+    /// or spawned as a child process speaking JSON RPC over its stdin and stdout with `stdio:<command>`.
+    #[clap(long)]
+    oracle_resolver: Option<String>,
 }
 
 pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -85,7 +92,14 @@ pub(crate) fn run(args: DebugCommand, config: NargoConfig) -> Result<(), CliErro
 
     let compiled_program = nargo::ops::transform_program(compiled_program, target_width);
 
-    run_async(package, compiled_program, &args.prover_name, &args.witness_name, target_dir)
+    run_async(
+        package,
+        compiled_program,
+        &args.prover_name,
+        &args.witness_name,
+        target_dir,
+        args.oracle_resolver.as_deref(),
+    )
 }
 
 pub(crate) fn compile_bin_package_for_debugging(
@@ -172,6 +186,7 @@ fn run_async(
     prover_name: &str,
     witness_name: &Option<String>,
     target_dir: &PathBuf,
+    foreign_call_resolver_url: Option<&str>,
 ) -> Result<(), CliError> {
     use tokio::runtime::Builder;
     let runtime = Builder::new_current_thread().enable_all().build().unwrap();
@@ -179,7 +194,7 @@ fn run_async(
     runtime.block_on(async {
         println!("[{}] Starting debugger", package.name);
         let (return_value, witness_stack) =
-            debug_program_and_decode(program, package, prover_name)?;
+            debug_program_and_decode(program, package, prover_name, foreign_call_resolver_url)?;
 
         if let Some(solved_witness_stack) = witness_stack {
             println!("[{}] Circuit witness successfully solved", package.name);
@@ -206,12 +221,13 @@ fn debug_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    foreign_call_resolver_url: Option<&str>,
 ) -> Result<(Option<InputValue>, Option<WitnessStack<FieldElement>>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
     let program_abi = program.abi.clone();
-    let witness_stack = debug_program(program, &inputs_map)?;
+    let witness_stack = debug_program(program, &inputs_map, foreign_call_resolver_url)?;
 
     match witness_stack {
         Some(witness_stack) => {
@@ -229,9 +245,15 @@ fn debug_program_and_decode(
 pub(crate) fn debug_program(
     compiled_program: CompiledProgram,
     inputs_map: &InputMap,
+    foreign_call_resolver_url: Option<&str>,
 ) -> Result<Option<WitnessStack<FieldElement>>, CliError> {
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    noir_debugger::run_repl_session(&Bn254BlackBoxSolver, compiled_program, initial_witness)
-        .map_err(CliError::from)
+    noir_debugger::run_repl_session(
+        &FieldBlackBoxSolver::default(),
+        compiled_program,
+        initial_witness,
+        foreign_call_resolver_url,
+    )
+    .map_err(CliError::from)
 }
@@ -1,14 +1,18 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use acvm::acir::native_types::WitnessStack;
+use acvm::acir::native_types::{WitnessMap, WitnessStack};
+use acvm::pwg::{BrilligCallCache, BrilligCallCacheStats, VMLimits};
 use acvm::FieldElement;
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 
 use nargo::constants::PROVER_INPUT_FILE;
 use nargo::errors::try_to_diagnose_runtime_error;
-use nargo::ops::DefaultForeignCallExecutor;
+use nargo::ops::{
+    execute_program_with_options, DefaultForeignCallExecutor, ExecutionOptions,
+    ForeignCallExecutor, RecordingForeignCallExecutor, ReplayForeignCallExecutor,
+};
 use nargo::package::{CrateName, Package};
+use nargo::NargoError;
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
 use noirc_abi::input_parser::{Format, InputValue};
 use noirc_abi::InputMap;
@@ -16,7 +20,12 @@ use noirc_artifacts::debug::DebugArtifact;
 use noirc_driver::{CompileOptions, CompiledProgram, NOIR_ARTIFACT_VERSION_STRING};
 
 use super::compile_cmd::compile_workspace_full;
-use super::fs::{inputs::read_inputs_from_file, witness::save_witness_to_dir};
+use super::fs::{
+    inputs::read_inputs_from_file,
+    oracle_transcript::{read_oracle_transcript_from_file, save_oracle_transcript_to_file},
+    witness::save_witness_to_dir,
+};
+use super::FieldBlackBoxSolver;
 use super::NargoConfig;
 use crate::cli::fs::program::read_program_from_file;
 use crate::errors::CliError;
@@ -36,18 +45,48 @@ pub(crate) struct ExecuteCommand {
 
     /// The name of the package to execute
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Execute all packages in the workspace
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 
     /// JSON RPC url to solve oracle calls
+    ///
+    /// Besides `http://` URLs, a resolver may be reached over a Unix domain socket with `unix://<path>`,
+    /// or spawned as a child process speaking JSON RPC over its stdin and stdout with `stdio:<command>`.
     #[clap(long)]
     oracle_resolver: Option<String>,
+
+    /// Save every oracle call made during execution, along with its result, to the given file
+    ///
+    /// Only a single binary package may be executed when recording.
+    #[clap(long, conflicts_with = "replay_oracles")]
+    record_oracles: Option<PathBuf>,
+
+    /// Resolve oracle calls from a file previously saved with `--record-oracles`
+    ///
+    /// Execution fails if the oracle calls made differ from those which were recorded.
+    /// Only a single binary package may be executed when replaying.
+    #[clap(long, conflicts_with = "oracle_resolver")]
+    replay_oracles: Option<PathBuf>,
+
+    /// Cache the results of unconstrained functions which do not make foreign calls
+    ///
+    /// Repeated calls to the same function with the same inputs are then only executed once.
+    #[clap(long)]
+    cache_brillig_calls: bool,
+
+    /// The maximum number of memory slots each unconstrained function call may use
+    #[clap(long)]
+    brillig_max_memory: Option<usize>,
+
+    /// The maximum number of opcodes each unconstrained function call may execute
+    #[clap(long)]
+    brillig_max_steps: Option<usize>,
 }
 
 pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -61,6 +100,29 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
     )?;
     let target_dir = &workspace.target_directory_path();
+    let brillig_vm_limits =
+        VMLimits { max_memory_size: args.brillig_max_memory, max_steps: args.brillig_max_steps };
+    let oracle_transcript = match (&args.record_oracles, &args.replay_oracles) {
+        (Some(path), _) => OracleTranscript::Record(path),
+        (None, Some(path)) => OracleTranscript::Replay(path),
+        (None, None) => OracleTranscript::None,
+    };
+
+    // A transcript holds the oracle calls of a single program, so it can't be shared between packages.
+    let binary_packages_count = workspace.into_iter().filter(|package| package.is_binary()).count();
+    let transcript_flag = match oracle_transcript {
+        OracleTranscript::None => None,
+        OracleTranscript::Record(_) => Some("--record-oracles"),
+        OracleTranscript::Replay(_) => Some("--replay-oracles"),
+    };
+    if let Some(flag) = transcript_flag {
+        if binary_packages_count != 1 {
+            return Err(CliError::OracleTranscriptRequiresSinglePackage {
+                flag,
+                count: binary_packages_count,
+            });
+        }
+    }
 
     // Compile the full workspace in order to generate any build artifacts.
     compile_workspace_full(&workspace, &args.compile_options)?;
@@ -71,16 +133,22 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
         let program: CompiledProgram =
             read_program_from_file(program_artifact_path.clone())?.into();
 
-        let (return_value, witness_stack) = execute_program_and_decode(
+        let (return_value, witness_stack, cache_stats) = execute_program_and_decode(
             program,
             package,
             &args.prover_name,
             args.oracle_resolver.as_deref(),
+            oracle_transcript,
             Some(workspace.root_dir.clone()),
             Some(package.name.to_string()),
+            args.cache_brillig_calls,
+            brillig_vm_limits,
         )?;
 
         println!("[{}] Circuit witness successfully solved", package.name);
+        if let Some(cache_stats) = cache_stats {
+            println!("[{}] Brillig call cache: {cache_stats}", package.name);
+        }
         if let Some(return_value) = return_value {
             println!("[{}] Circuit output: {return_value:?}", package.name);
         }
@@ -93,49 +161,104 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
     Ok(())
 }
 
+/// Whether the oracle calls made during execution are recorded to, or replayed from, a transcript file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum OracleTranscript<'a> {
+    None,
+    Record(&'a Path),
+    Replay(&'a Path),
+}
+
+#[allow(clippy::too_many_arguments)]
 fn execute_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
     foreign_call_resolver_url: Option<&str>,
+    oracle_transcript: OracleTranscript,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
-) -> Result<(Option<InputValue>, WitnessStack<FieldElement>), CliError> {
+    cache_brillig_calls: bool,
+    brillig_vm_limits: VMLimits,
+) -> Result<(Option<InputValue>, WitnessStack<FieldElement>, Option<BrilligCallCacheStats>), CliError>
+{
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
         read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
-    let witness_stack =
-        execute_program(&program, &inputs_map, foreign_call_resolver_url, root_path, package_name)?;
+    let (witness_stack, cache_stats) = execute_program(
+        &program,
+        &inputs_map,
+        foreign_call_resolver_url,
+        oracle_transcript,
+        root_path,
+        package_name,
+        cache_brillig_calls,
+        brillig_vm_limits,
+    )?;
     // Get the entry point witness for the ABI
     let main_witness =
         &witness_stack.peek().expect("Should have at least one witness on the stack").witness;
     let (_, return_value) = program.abi.decode(main_witness)?;
 
-    Ok((return_value, witness_stack))
+    Ok((return_value, witness_stack, cache_stats))
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn execute_program(
     compiled_program: &CompiledProgram,
     inputs_map: &InputMap,
     foreign_call_resolver_url: Option<&str>,
+    oracle_transcript: OracleTranscript,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
-) -> Result<WitnessStack<FieldElement>, CliError> {
+    cache_brillig_calls: bool,
+    brillig_vm_limits: VMLimits,
+) -> Result<(WitnessStack<FieldElement>, Option<BrilligCallCacheStats>), CliError> {
     let initial_witness = compiled_program.abi.encode(inputs_map, None)?;
 
-    let solved_witness_stack_err = nargo::ops::execute_program(
-        &compiled_program.program,
-        initial_witness,
-        &Bn254BlackBoxSolver,
-        &mut DefaultForeignCallExecutor::new(
-            true,
-            foreign_call_resolver_url,
-            root_path,
-            package_name,
+    let default_executor = || {
+        DefaultForeignCallExecutor::new(true, foreign_call_resolver_url, root_path, package_name)
+            .map_err(NargoError::<FieldElement>::from)
+    };
+    let solved_witness_stack_err = match oracle_transcript {
+        OracleTranscript::None => solve_witness_stack(
+            compiled_program,
+            initial_witness,
+            &mut default_executor()?,
+            cache_brillig_calls,
+            brillig_vm_limits,
         ),
-    );
+        OracleTranscript::Record(transcript_path) => {
+            let mut recording_executor = RecordingForeignCallExecutor::new(default_executor()?);
+            let solved = solve_witness_stack(
+                compiled_program,
+                initial_witness,
+                &mut recording_executor,
+                cache_brillig_calls,
+                brillig_vm_limits,
+            );
+            // The transcript is saved even if execution failed so that the failure can be reproduced.
+            save_oracle_transcript_to_file(&recording_executor.into_transcript(), transcript_path);
+            solved
+        }
+        OracleTranscript::Replay(transcript_path) => {
+            let transcript = read_oracle_transcript_from_file(transcript_path)?;
+            let mut replay_executor = ReplayForeignCallExecutor::new(transcript, true);
+            solve_witness_stack(
+                compiled_program,
+                initial_witness,
+                &mut replay_executor,
+                cache_brillig_calls,
+                brillig_vm_limits,
+            )
+            .and_then(|solved| {
+                replay_executor.finish()?;
+                Ok(solved)
+            })
+        }
+    };
     match solved_witness_stack_err {
-        Ok(solved_witness_stack) => Ok(solved_witness_stack),
+        Ok(solved) => Ok(solved),
         Err(err) => {
             let debug_artifact = DebugArtifact {
                 debug_symbols: compiled_program.debug.clone(),
@@ -152,3 +275,26 @@ pub(crate) fn execute_program(
         }
     }
 }
+
+fn solve_witness_stack<E: ForeignCallExecutor<FieldElement>>(
+    compiled_program: &CompiledProgram,
+    initial_witness: WitnessMap<FieldElement>,
+    foreign_call_executor: &mut E,
+    cache_brillig_calls: bool,
+    brillig_vm_limits: VMLimits,
+) -> Result<(WitnessStack<FieldElement>, Option<BrilligCallCacheStats>), NargoError<FieldElement>> {
+    let blackbox_solver = FieldBlackBoxSolver::default();
+    let options = ExecutionOptions {
+        brillig_call_cache: cache_brillig_calls.then(BrilligCallCache::new),
+        brillig_vm_limits,
+        ..Default::default()
+    };
+    execute_program_with_options(
+        &compiled_program.program,
+        initial_witness,
+        &blackbox_solver,
+        foreign_call_executor,
+        options,
+    )
+    .map(|result| (result.witness_stack, result.brillig_call_cache.map(|cache| cache.stats())))
+}
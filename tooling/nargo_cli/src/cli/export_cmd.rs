@@ -31,14 +31,14 @@ use super::NargoConfig;
 pub(crate) struct ExportCommand {
     /// The name of the package to compile
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Compile all packages in the workspace
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 }
 
 pub(crate) fn run(args: ExportCommand, config: NargoConfig) -> Result<(), CliError> {
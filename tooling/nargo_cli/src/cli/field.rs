@@ -0,0 +1,73 @@
+use std::{collections::BTreeSet, path::PathBuf, process::Command};
+
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_driver::{NATIVE_FIELD, NOIR_ARTIFACT_VERSION_STRING};
+
+use super::NargoConfig;
+use crate::errors::CliError;
+
+/// Returns the field which the command should run over.
+///
+/// The `--field` compile option takes precedence over the `field` set by the packages in `selection`,
+/// which must all agree. Returns `None` if neither selects a field.
+pub(super) fn selected_field(
+    compile_field: Option<&String>,
+    selection: PackageSelection,
+    config: &NargoConfig,
+) -> Result<Option<String>, CliError> {
+    if let Some(field) = compile_field {
+        return Ok(Some(field.clone()));
+    }
+
+    // If the workspace can't be resolved then the command itself reports why.
+    let Ok(toml_path) = get_package_manifest(&config.program_dir) else {
+        return Ok(None);
+    };
+    let Ok(workspace) = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    ) else {
+        return Ok(None);
+    };
+    let fields: BTreeSet<_> =
+        workspace.into_iter().filter_map(|package| package.field.clone()).collect();
+    match fields.len() {
+        0 => Ok(None),
+        1 => Ok(fields.into_iter().next()),
+        _ => Err(CliError::ConflictingFields(fields.into_iter().collect())),
+    }
+}
+
+/// Environment variable set when running the build of nargo for another field, holding that field.
+const FIELD_BUILD_ENV: &str = "NARGO_FIELD_BUILD";
+
+/// Runs the current command with the build of nargo for `field`, exiting with its status.
+///
+/// The field is fixed when nargo is built, so each field has its own build. The build for `field` is expected
+/// to be installed as `nargo-<field>`, either next to this executable or on the `PATH`.
+pub(super) fn run_with_field_build(field: &str) -> Result<(), CliError> {
+    assert_ne!(field, NATIVE_FIELD, "nargo should run commands over its own field itself");
+
+    // This is the build which was run for `field`, so running another build would never terminate.
+    if std::env::var_os(FIELD_BUILD_ENV).is_some() {
+        return Err(CliError::WrongFieldBuild {
+            field: field.to_string(),
+            native_field: NATIVE_FIELD,
+        });
+    }
+
+    let binary = format!("nargo-{field}{}", std::env::consts::EXE_SUFFIX);
+    let sibling = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&binary)))
+        .filter(|path| path.is_file());
+    let program = sibling.unwrap_or_else(|| PathBuf::from(&binary));
+
+    let status = Command::new(&program)
+        .args(std::env::args_os().skip(1))
+        .env(FIELD_BUILD_ENV, field)
+        .status()
+        .map_err(|err| CliError::MissingFieldBuild { field: field.to_string(), binary, err })?;
+    std::process::exit(status.code().unwrap_or(1));
+}
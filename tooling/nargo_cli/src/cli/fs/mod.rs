@@ -5,6 +5,7 @@ use std::{
 };
 
 pub(super) mod inputs;
+pub(super) mod oracle_transcript;
 pub(super) mod program;
 pub(super) mod witness;
 
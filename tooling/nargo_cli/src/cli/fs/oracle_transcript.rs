@@ -0,0 +1,28 @@
+use std::path::Path;
+
+use acvm::FieldElement;
+use nargo::ops::ForeignCallTranscript;
+
+use crate::errors::FilesystemError;
+
+use super::write_to_file;
+
+pub(crate) fn save_oracle_transcript_to_file(
+    transcript: &ForeignCallTranscript<FieldElement>,
+    transcript_path: &Path,
+) {
+    write_to_file(&serde_json::to_vec(transcript).unwrap(), transcript_path);
+}
+
+pub(crate) fn read_oracle_transcript_from_file(
+    transcript_path: &Path,
+) -> Result<ForeignCallTranscript<FieldElement>, FilesystemError> {
+    let input_string = std::fs::read(transcript_path)
+        .map_err(|_| FilesystemError::PathNotValid(transcript_path.to_path_buf()))?;
+    serde_json::from_slice(&input_string).map_err(|err| {
+        FilesystemError::OracleTranscriptSerializationError(
+            transcript_path.to_path_buf(),
+            err.to_string(),
+        )
+    })
+}
@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
-use acvm::acir::circuit::ExpressionWidth;
+use acvm::acir::circuit::{brillig::BrilligFunctionId, ExpressionWidth};
+use acvm::gates::{GateEstimator, PlonkGateEstimator};
 use clap::Args;
 use iter_extended::vecmap;
 use nargo::package::{CrateName, Package};
 use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
-use noirc_artifacts::{debug::DebugArtifact, program::ProgramArtifact};
+use noirc_artifacts::{
+    debug::DebugArtifact, disassembly::disassemble_brillig, program::ProgramArtifact,
+};
 use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
 use noirc_errors::{debug_info::OpCodesCount, Location};
 use prettytable::{row, table, Row};
@@ -24,17 +27,21 @@ use super::{
 ///
 /// Current information provided per circuit:
 /// 1. The number of ACIR opcodes
-/// 2. Counts the final number gates in the circuit used by a backend
+/// 2. The number of ACIR opcodes removed by ACIR-level optimizations
+/// 3. An estimate of the number of gates in the circuit used by a backend
+/// 4. The number of Brillig opcodes of each unconstrained function, and the number removed by Brillig-level optimizations
+///
+/// With `--brillig`, the bytecode of each unconstrained function is also printed in a disassembled form.
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "i")]
 pub(crate) struct InfoCommand {
     /// The name of the package to detail
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Detail all packages in the workspace
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     /// Output a JSON formatted report. Changes to this format are not currently considered breaking.
     #[clap(long, hide = true)]
@@ -43,8 +50,12 @@ pub(crate) struct InfoCommand {
     #[clap(long, hide = true)]
     profile_info: bool,
 
+    /// Print the disassembled Brillig bytecode of each unconstrained function, annotated with the Noir source lines it was generated from
+    #[clap(long, conflicts_with = "json")]
+    brillig: bool,
+
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 }
 
 pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError> {
@@ -81,6 +92,19 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
         }
     }
 
+    if args.brillig {
+        for (package, compiled_program) in &binary_packages {
+            let debug_artifact = DebugArtifact::from(compiled_program.clone());
+            let disassembly = disassemble_brillig(
+                &compiled_program.bytecode,
+                &compiled_program.brillig_names,
+                &debug_artifact,
+            );
+            println!("Brillig bytecode of package '{}':\n", package.name);
+            println!("{disassembly}");
+        }
+    }
+
     let program_info = binary_packages
         .into_iter()
         .par_bridge()
@@ -99,7 +123,7 @@ pub(crate) fn run(args: InfoCommand, config: NargoConfig) -> Result<(), CliError
     } else {
         // Otherwise print human-readable table.
         if !info_report.programs.is_empty() {
-            let mut program_table = table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes"]);
+            let mut program_table = table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Optimized Away", Fm->"Estimated Gates", Fm->"Brillig Opcodes"]);
 
             for program_info in info_report.programs {
                 let program_rows: Vec<Row> = program_info.into();
@@ -187,6 +211,8 @@ impl From<ProgramInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("{:?}", program_info.expression_width),
                 Fc->format!("{}", function.opcodes),
+                Fc->format!("{}", function.opcodes_optimized_away.unwrap_or_default()),
+                Fc->format!("{}", function.estimated_gates.unwrap_or_default()),
                 Fc->format!("{}", program_info.unconstrained_functions_opcodes),
             ]
         });
@@ -196,6 +222,8 @@ impl From<ProgramInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("N/A", ),
                 Fc->format!("N/A"),
+                Fc->format!("{}", function.opcodes_optimized_away.map_or("N/A".to_string(), |count| count.to_string())),
+                Fc->format!("N/A"),
                 Fc->format!("{}", function.opcodes),
             ]
         }));
@@ -216,6 +244,10 @@ struct ContractInfo {
 struct FunctionInfo {
     name: String,
     opcodes: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcodes_optimized_away: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    estimated_gates: Option<usize>,
 }
 
 impl From<ContractInfo> for Vec<Row> {
@@ -236,6 +268,8 @@ fn count_opcodes_and_gates_in_program(
     package: &Package,
     expression_width: ExpressionWidth,
 ) -> ProgramInfo {
+    let gate_estimator = PlonkGateEstimator::for_expression_width(expression_width);
+
     let functions = compiled_program
         .bytecode
         .functions
@@ -244,6 +278,12 @@ fn count_opcodes_and_gates_in_program(
         .map(|(i, function)| FunctionInfo {
             name: compiled_program.names[i].clone(),
             opcodes: function.opcodes.len(),
+            opcodes_optimized_away: compiled_program
+                .debug_symbols
+                .debug_infos
+                .get(i)
+                .map(|debug_info| debug_info.acir_opcodes_optimized_away),
+            estimated_gates: Some(gate_estimator.circuit_gates(&function)),
         })
         .collect();
 
@@ -264,7 +304,20 @@ fn count_opcodes_and_gates_in_program(
         .clone()
         .iter()
         .zip(opcodes_len)
-        .map(|(name, len)| FunctionInfo { name: name.clone(), opcodes: len })
+        .enumerate()
+        .map(|(i, (name, len))| FunctionInfo {
+            name: name.clone(),
+            opcodes: len,
+            opcodes_optimized_away: compiled_program.debug_symbols.debug_infos.iter().find_map(
+                |debug_info| {
+                    debug_info
+                        .brillig_opcodes_optimized_away
+                        .get(&BrilligFunctionId(i as u32))
+                        .copied()
+                },
+            ),
+            estimated_gates: None,
+        })
         .collect();
 
     ProgramInfo {
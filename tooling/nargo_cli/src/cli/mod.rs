@@ -1,7 +1,7 @@
 use clap::{Args, Parser, Subcommand};
 use const_format::formatcp;
-use nargo_toml::find_package_root;
-use noirc_driver::NOIR_ARTIFACT_VERSION_STRING;
+use nargo_toml::{find_package_root, PackageSelection};
+use noirc_driver::{CompileOptions, NATIVE_FIELD, NOIR_ARTIFACT_VERSION_STRING};
 use std::path::PathBuf;
 
 use color_eyre::eyre;
@@ -14,6 +14,7 @@ mod dap_cmd;
 mod debug_cmd;
 mod execute_cmd;
 mod export_cmd;
+mod field;
 mod fmt_cmd;
 mod info_cmd;
 mod init_cmd;
@@ -21,14 +22,21 @@ mod lsp_cmd;
 mod new_cmd;
 mod test_cmd;
 
+/// Solver for the blackbox functions of the field which nargo was built for.
+#[cfg(not(feature = "bls12_381"))]
+type FieldBlackBoxSolver = bn254_blackbox_solver::Bn254BlackBoxSolver;
+#[cfg(feature = "bls12_381")]
+type FieldBlackBoxSolver = acvm::blackbox_solver::Bls12381BlackBoxSolver;
+
 const GIT_HASH: &str = env!("GIT_COMMIT");
 const IS_DIRTY: &str = env!("GIT_DIRTY");
 const NARGO_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 static VERSION_STRING: &str = formatcp!(
-    "version = {}\nnoirc version = {}\n(git version hash: {}, is dirty: {})",
+    "version = {}\nnoirc version = {}\nfield = {}\n(git version hash: {}, is dirty: {})",
     NARGO_VERSION,
     NOIR_ARTIFACT_VERSION_STRING,
+    NATIVE_FIELD,
     GIT_HASH,
     IS_DIRTY
 );
@@ -88,6 +96,19 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         config.program_dir = find_package_root(&config.program_dir)?;
     }
 
+    // Commands which compile a program run with the build of nargo for the field which it is compiled over.
+    if let (Some(compile_options), Some(selection)) =
+        (command.compile_options(), command.package_selection())
+    {
+        if let Some(field) =
+            field::selected_field(compile_options.field.as_ref(), selection, &config)?
+        {
+            if field != NATIVE_FIELD {
+                field::run_with_field_build(&field)?;
+            }
+        }
+    }
+
     match command {
         NargoCommand::New(args) => new_cmd::run(args, config),
         NargoCommand::Init(args) => init_cmd::run(args, config),
@@ -106,6 +127,46 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     Ok(())
 }
 
+impl NargoCommand {
+    fn compile_options(&self) -> Option<&CompileOptions> {
+        match self {
+            NargoCommand::Check(args) => Some(&args.compile_options),
+            NargoCommand::Compile(args) => Some(&args.compile_options),
+            NargoCommand::Execute(args) => Some(&args.compile_options),
+            NargoCommand::Export(args) => Some(&args.compile_options),
+            NargoCommand::Debug(args) => Some(&args.compile_options),
+            NargoCommand::Test(args) => Some(&args.compile_options),
+            NargoCommand::Info(args) => Some(&args.compile_options),
+            NargoCommand::Fmt(_)
+            | NargoCommand::New(_)
+            | NargoCommand::Init(_)
+            | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_) => None,
+        }
+    }
+
+    /// Returns the packages which the command runs over, as selected by its `--package` and `--workspace` options.
+    fn package_selection(&self) -> Option<PackageSelection> {
+        let (package, workspace) = match self {
+            NargoCommand::Check(args) => (&args.package, args.workspace),
+            NargoCommand::Compile(args) => (&args.package, args.workspace),
+            NargoCommand::Execute(args) => (&args.package, args.workspace),
+            NargoCommand::Export(args) => (&args.package, args.workspace),
+            NargoCommand::Debug(args) => (&args.package, false),
+            NargoCommand::Test(args) => (&args.package, args.workspace),
+            NargoCommand::Info(args) => (&args.package, args.workspace),
+            NargoCommand::Fmt(_)
+            | NargoCommand::New(_)
+            | NargoCommand::Init(_)
+            | NargoCommand::Lsp(_)
+            | NargoCommand::Dap(_) => return None,
+        };
+        let default_selection =
+            if workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+        Some(package.clone().map_or(default_selection, PackageSelection::Selected))
+    }
+}
+
 #[cfg(feature = "codegen-docs")]
 pub(crate) fn start_cli() -> eyre::Result<()> {
     let markdown: String = clap_markdown::help_markdown::<NargoCli>();
@@ -1,7 +1,6 @@
 use std::{io::Write, path::PathBuf};
 
 use acvm::{BlackBoxFunctionSolver, FieldElement};
-use bn254_blackbox_solver::Bn254BlackBoxSolver;
 use clap::Args;
 use fm::FileManager;
 use nargo::{
@@ -18,6 +17,7 @@ use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::{cli::check_cmd::check_crate_and_report_errors, errors::CliError};
 
+use super::FieldBlackBoxSolver;
 use super::NargoConfig;
 
 /// Run the tests for this program
@@ -37,16 +37,19 @@ pub(crate) struct TestCommand {
 
     /// The name of the package to test
     #[clap(long, conflicts_with = "workspace")]
-    package: Option<CrateName>,
+    pub(super) package: Option<CrateName>,
 
     /// Test all packages in the workspace
     #[clap(long, conflicts_with = "package")]
-    workspace: bool,
+    pub(super) workspace: bool,
 
     #[clap(flatten)]
-    compile_options: CompileOptions,
+    pub(super) compile_options: CompileOptions,
 
     /// JSON RPC url to solve oracle calls
+    ///
+    /// Besides `http://` URLs, a resolver may be reached over a Unix domain socket with `unix://<path>`,
+    /// or spawned as a child process speaking JSON RPC over its stdin and stdout with `stdio:<command>`.
     #[clap(long)]
     oracle_resolver: Option<String>,
 }
@@ -85,7 +88,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
             .into_iter()
             .par_bridge()
             .map(|package| {
-                run_tests::<Bn254BlackBoxSolver>(
+                run_tests::<FieldBlackBoxSolver>(
                     &workspace_file_manager,
                     &parsed_files,
                     package,
@@ -124,7 +127,7 @@ pub(crate) fn run(args: TestCommand, config: NargoConfig) -> Result<(), CliError
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
+fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default + Sync>(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
     package: &Package,
@@ -167,7 +170,7 @@ fn run_tests<S: BlackBoxFunctionSolver<FieldElement> + Default>(
 }
 
 #[allow(clippy::too_many_arguments)]
-fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default>(
+fn run_test<S: BlackBoxFunctionSolver<FieldElement> + Default + Sync>(
     file_manager: &FileManager,
     parsed_files: &ParsedFiles,
     package: &Package,
@@ -26,6 +26,9 @@ pub(crate) enum FilesystemError {
 
     #[error("Error: could not deserialize build program: {0}")]
     ProgramSerializationError(String),
+
+    #[error("Error: could not deserialize oracle transcript {}: {1}", .0.display())]
+    OracleTranscriptSerializationError(PathBuf, String),
 }
 
 #[derive(Debug, Error)]
@@ -63,4 +66,16 @@ pub(crate) enum CliError {
     /// Error from the compilation pipeline
     #[error(transparent)]
     CompileError(#[from] CompileError),
+
+    #[error("Packages in the workspace target different fields: {}", .0.join(", "))]
+    ConflictingFields(Vec<String>),
+
+    #[error("Cannot run nargo over the {field} field: failed to run `{binary}`: {err}\nInstall it with `cargo install --path tooling/nargo_cli --no-default-features --features {field} --bin {binary}`")]
+    MissingFieldBuild { field: String, binary: String, err: std::io::Error },
+
+    #[error("Cannot run nargo over the {field} field: `nargo-{field}` was run to do so but is built for the {native_field} field")]
+    WrongFieldBuild { field: String, native_field: &'static str },
+
+    #[error("`{flag}` requires exactly one binary package to be executed, but {count} were selected. Select a single package with `--package`")]
+    OracleTranscriptRequiresSinglePackage { flag: &'static str, count: usize },
 }
@@ -0,0 +1,70 @@
+//! These integration tests check that nargo runs packages compiled over another field with the nargo build for that field.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::path::PathBuf;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use noirc_driver::{NATIVE_FIELD, SUPPORTED_FIELDS};
+
+/// `x + 1 == 0` only holds for this input when `Field` is the bls12_381 scalar field.
+const MAIN_SOURCE: &str = "fn main(x: Field) { assert(x + 1 == 0); }";
+const BLS12_381_MINUS_ONE: &str =
+    "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000000";
+
+fn write_package(dir: &TempDir, field: &str) {
+    dir.child("Nargo.toml")
+        .write_str(&format!(
+            "[package]\nname = \"field_selection\"\ntype = \"bin\"\nauthors = [\"\"]\nfield = \"{field}\"\n\n[dependencies]\n"
+        ))
+        .unwrap();
+    dir.child("src").child("main.nr").write_str(MAIN_SOURCE).unwrap();
+    dir.child("Prover.toml").write_str(&format!("x = \"{BLS12_381_MINUS_ONE}\"")).unwrap();
+}
+
+#[test]
+fn rejects_field_builds_for_the_wrong_field() {
+    let other_field = SUPPORTED_FIELDS.into_iter().find(|field| *field != NATIVE_FIELD).unwrap();
+    let test_dir = TempDir::new().unwrap();
+    write_package(&test_dir, other_field);
+
+    // Pretend that this nargo was run as the build for `other_field`.
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").env("NARGO_FIELD_BUILD", other_field);
+    cmd.assert().failure().stderr(predicate::str::contains(format!(
+        "`nargo-{other_field}` was run to do so but is built for the {NATIVE_FIELD} field"
+    )));
+}
+
+/// Requires the bls12_381 build of nargo, installed with
+/// `cargo install --path tooling/nargo_cli --no-default-features --features bls12_381 --bin nargo-bls12_381`,
+/// with the directory containing it set in `NARGO_BLS12_381_BIN_DIR`.
+#[test]
+#[ignore = "requires nargo-bls12_381 to be installed"]
+fn executes_bls12_381_packages_with_the_bls12_381_build() {
+    let bin_dir = PathBuf::from(
+        std::env::var("NARGO_BLS12_381_BIN_DIR").expect("NARGO_BLS12_381_BIN_DIR should be set"),
+    );
+    let path = std::env::join_paths(
+        std::iter::once(bin_dir).chain(std::env::split_paths(&std::env::var_os("PATH").unwrap())),
+    )
+    .unwrap();
+
+    let test_dir = TempDir::new().unwrap();
+    write_package(&test_dir, "bls12_381");
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").env("PATH", &path);
+    cmd.assert().success().stdout(predicate::str::contains("Circuit witness successfully solved"));
+
+    // The `--field` option selects the field when the package doesn't.
+    test_dir.child("Nargo.toml").write_str(
+        "[package]\nname = \"field_selection\"\ntype = \"bin\"\nauthors = [\"\"]\n\n[dependencies]\n",
+    )
+    .unwrap();
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").arg("--field").arg("bls12_381").env("PATH", &path);
+    cmd.assert().success().stdout(predicate::str::contains("Circuit witness successfully solved"));
+}
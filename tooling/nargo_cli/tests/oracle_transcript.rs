@@ -0,0 +1,76 @@
+//! These integration tests record the oracle calls made by `nargo execute` to a transcript and replay them.
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathAssert, PathChild};
+use assert_fs::TempDir;
+
+const MAIN_SOURCE: &str = "fn main(x: Field) { println(x); }";
+
+fn write_package(dir: &TempDir, path: &str, name: &str) {
+    let package_dir = dir.child(path);
+    package_dir
+        .child("Nargo.toml")
+        .write_str(&format!(
+            "[package]\nname = \"{name}\"\ntype = \"bin\"\nauthors = [\"\"]\n\n[dependencies]\n"
+        ))
+        .unwrap();
+    package_dir.child("src").child("main.nr").write_str(MAIN_SOURCE).unwrap();
+    package_dir.child("Prover.toml").write_str("x = 1").unwrap();
+}
+
+#[test]
+fn replays_recorded_oracle_calls() {
+    let test_dir = TempDir::new().unwrap();
+    write_package(&test_dir, ".", "transcript");
+    let transcript = test_dir.child("oracles.json");
+
+    // `nargo execute --record-oracles oracles.json`
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").arg("--record-oracles").arg(transcript.path());
+    cmd.assert().success();
+    transcript.assert(predicate::path::is_file());
+    transcript.assert(predicate::str::contains("print"));
+
+    // `nargo execute --replay-oracles oracles.json`
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").arg("--replay-oracles").arg(transcript.path());
+    cmd.assert().success().stdout(predicate::str::contains("Circuit witness successfully solved"));
+
+    // Replaying with different inputs diverges from the transcript.
+    test_dir.child("Prover.toml").write_str("x = 2").unwrap();
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("execute").arg("--replay-oracles").arg(transcript.path());
+    cmd.assert().failure();
+}
+
+#[test]
+fn rejects_oracle_transcripts_for_several_packages() {
+    let test_dir = TempDir::new().unwrap();
+    test_dir.child("Nargo.toml").write_str("[workspace]\nmembers = [\"a\", \"b\"]\n").unwrap();
+    write_package(&test_dir, "a", "a");
+    write_package(&test_dir, "b", "b");
+    let transcript = test_dir.child("oracles.json");
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir)
+        .arg("execute")
+        .arg("--workspace")
+        .arg("--record-oracles")
+        .arg(transcript.path());
+    cmd.assert().failure().stderr(predicate::str::contains("requires exactly one binary package"));
+    transcript.assert(predicate::path::missing());
+
+    // Selecting a single package records its oracle calls.
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir)
+        .arg("execute")
+        .arg("--package")
+        .arg("a")
+        .arg("--record-oracles")
+        .arg(transcript.path());
+    cmd.assert().success();
+    transcript.assert(predicate::path::is_file());
+}
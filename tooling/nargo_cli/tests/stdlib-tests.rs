@@ -28,6 +28,7 @@ fn run_stdlib_tests() {
         name: "stdlib".parse().unwrap(),
         dependencies: BTreeMap::new(),
         expression_width: None,
+        field: None,
     };
 
     let (mut context, dummy_crate_id) =
@@ -75,6 +75,9 @@ pub enum ManifestError {
 
     #[error("Failed to parse expression width with the following error: {0}")]
     ParseExpressionWidth(String),
+
+    #[error("Failed to parse field with the following error: {0}")]
+    ParseField(String),
 }
 
 #[allow(clippy::enum_variant_names)]
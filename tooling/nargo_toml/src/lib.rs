@@ -14,7 +14,7 @@ use nargo::{
     package::{Dependency, Package, PackageType},
     workspace::Workspace,
 };
-use noirc_driver::parse_expression_width;
+use noirc_driver::{parse_expression_width, parse_field};
 use noirc_frontend::graph::CrateName;
 use serde::Deserialize;
 
@@ -210,6 +210,15 @@ impl PackageConfig {
             })
             .map_or(Ok(None), |res| res.map(Some))?;
 
+        let field = self
+            .package
+            .field
+            .as_ref()
+            .map(|field| {
+                parse_field(field).map_err(|err| ManifestError::ParseField(err.to_string()))
+            })
+            .map_or(Ok(None), |res| res.map(Some))?;
+
         Ok(Package {
             version: self.package.version.clone(),
             compiler_required_version: self.package.compiler_version.clone(),
@@ -219,6 +228,7 @@ impl PackageConfig {
             name,
             dependencies,
             expression_width,
+            field,
         })
     }
 }
@@ -288,6 +298,7 @@ struct PackageMetadata {
     compiler_version: Option<String>,
     license: Option<String>,
     expression_width: Option<String>,
+    field: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -559,3 +570,20 @@ fn parse_package_expression_width_toml() {
     assert!(Config::try_from(String::from(src)).is_ok());
     assert!(Config::try_from(src).is_ok());
 }
+
+#[test]
+fn parse_package_field_toml() {
+    let src = r#"
+    [package]
+    name = "test"
+    version = "0.1.0"
+    type = "bin"
+    authors = [""]
+    field = "bls12_381"
+    "#;
+
+    let Config::Package { package_config } = Config::try_from(src).unwrap() else {
+        panic!("expected a package config");
+    };
+    assert_eq!(package_config.package.field.as_deref(), Some("bls12_381"));
+}
@@ -90,6 +90,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
         if let Err(err) = semver_check_package(&package, &compiler_version) {
             panic!("semver check should have passed. compiler version is 0.1.0 and required version from the package is 0.1.0\n error: {err:?}")
@@ -122,6 +123,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
 
         let valid_dependency = Package {
@@ -133,6 +135,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
         let invalid_dependency = Package {
             compiler_required_version: Some("0.2.0".to_string()),
@@ -143,6 +146,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
 
         package.dependencies.insert(
@@ -184,6 +188,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
@@ -204,6 +209,7 @@ mod tests {
             dependencies: BTreeMap::new(),
             version: Some("1.0".to_string()),
             expression_width: None,
+            field: None,
         };
 
         if let Err(err) = semver_check_package(&package, &compiler_version) {
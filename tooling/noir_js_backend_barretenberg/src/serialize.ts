@@ -1,8 +1,20 @@
 import { decompressSync as gunzip } from 'fflate';
 import { base64Decode } from './base64_decode.js';
 
+// Serialized programs begin with the magic bytes `ACIR` followed by a little-endian u32 format version.
+const PROGRAM_MAGIC = Uint8Array.from([65, 67, 73, 82]);
+const PROGRAM_HEADER_LENGTH = PROGRAM_MAGIC.length + 4;
+
+function hasProgramHeader(bytecode: Uint8Array): boolean {
+  return PROGRAM_MAGIC.every((byte, index) => bytecode[index] === byte);
+}
+
 // Converts bytecode from a base64 string to a Uint8Array
 export function acirToUint8Array(base64EncodedBytecode: string): Uint8Array {
-  const compressedByteCode = base64Decode(base64EncodedBytecode);
+  const serializedProgram = base64Decode(base64EncodedBytecode);
+  // The backend expects the uncompressed program without the format header.
+  const compressedByteCode = hasProgramHeader(serializedProgram)
+    ? serializedProgram.subarray(PROGRAM_HEADER_LENGTH)
+    : serializedProgram;
   return gunzip(compressedByteCode);
 }
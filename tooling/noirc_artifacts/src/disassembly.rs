@@ -0,0 +1,239 @@
+//! Human readable listings of the unconstrained functions of a compiled program.
+use std::collections::{BTreeMap, BTreeSet};
+
+use acvm::acir::asm::print_brillig_opcode;
+use acvm::acir::brillig::Opcode as BrilligOpcode;
+use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId};
+use acvm::acir::circuit::{BrilligOpcodeLocation, Program};
+use acvm::FieldElement;
+use noirc_errors::Location;
+
+use crate::debug::DebugArtifact;
+
+/// Disassembles every unconstrained function in `program`.
+///
+/// Each function is printed with its name taken from `brillig_names`, followed by its opcodes.
+/// The entry point of each function called internally (e.g. procedures) is marked as a function
+/// boundary, and each jump target is marked with a label of the form `L{index}`.
+/// Opcodes are annotated with the Noir source line which generated them, as recorded in the
+/// Brillig locations of the `debug_artifact`.
+pub fn disassemble_brillig(
+    program: &Program<FieldElement>,
+    brillig_names: &[String],
+    debug_artifact: &DebugArtifact,
+) -> String {
+    let mut functions = Vec::new();
+    for (index, function) in program.unconstrained_functions.iter().enumerate() {
+        let function_id = BrilligFunctionId(index as u32);
+        let name = brillig_names.get(index).map(String::as_str);
+        functions.push(disassemble_function(function_id, name, function, debug_artifact));
+    }
+    functions.join("\n")
+}
+
+fn disassemble_function(
+    function_id: BrilligFunctionId,
+    name: Option<&str>,
+    function: &BrilligBytecode<FieldElement>,
+    debug_artifact: &DebugArtifact,
+) -> String {
+    let (call_targets, jump_targets) = branch_targets(&function.bytecode);
+    let locations = source_locations(function_id, debug_artifact);
+    let index_width = function.bytecode.len().saturating_sub(1).to_string().len();
+
+    let mut lines = vec![match name {
+        Some(name) => format!("brillig func {function_id} ({name}) {{"),
+        None => format!("brillig func {function_id} {{"),
+    }];
+    let mut previous_line = None;
+    for (index, opcode) in function.bytecode.iter().enumerate() {
+        if index > 0 && call_targets.contains(&index) {
+            lines.push(String::new());
+            lines.push(format!("  function at {index}:"));
+        }
+        if jump_targets.contains(&index) {
+            lines.push(format!("  L{index}:"));
+        }
+
+        let mut line = format!("    {index:>index_width$}: {}", print_brillig_opcode(opcode));
+        if let Some(source_line) =
+            locations.get(&index).and_then(|location| SourceLine::new(*location, debug_artifact))
+        {
+            line.push_str(&format!("  // {}:{}", source_line.path, source_line.number));
+            // Only repeat the source text when moving onto a different line, to keep long runs of
+            // opcodes generated from the same statement readable.
+            let line_key = (source_line.path.clone(), source_line.number);
+            if previous_line.as_ref() != Some(&line_key) {
+                line.push_str(&format!(": {}", source_line.text));
+                previous_line = Some(line_key);
+            }
+        }
+        lines.push(line);
+    }
+    lines.push("}\n".to_string());
+    lines.join("\n")
+}
+
+/// Returns the opcode indices which are called into and those which are jumped to.
+fn branch_targets(bytecode: &[BrilligOpcode<FieldElement>]) -> (BTreeSet<usize>, BTreeSet<usize>) {
+    let mut call_targets = BTreeSet::new();
+    let mut jump_targets = BTreeSet::new();
+    for opcode in bytecode {
+        match opcode {
+            BrilligOpcode::Call { location } => {
+                call_targets.insert(*location);
+            }
+            BrilligOpcode::Jump { location }
+            | BrilligOpcode::JumpIf { location, .. }
+            | BrilligOpcode::JumpIfNot { location, .. } => {
+                jump_targets.insert(*location);
+            }
+            _ => (),
+        }
+    }
+    (call_targets, jump_targets)
+}
+
+/// Collects the innermost source location of each opcode of a Brillig function.
+///
+/// A Brillig function may be called from several ACIR functions, each of which records the
+/// locations of its opcodes, so the locations are gathered from all of the debug symbols.
+fn source_locations(
+    function_id: BrilligFunctionId,
+    debug_artifact: &DebugArtifact,
+) -> BTreeMap<usize, Location> {
+    let mut locations = BTreeMap::new();
+    for debug_info in &debug_artifact.debug_symbols {
+        let Some(brillig_locations) = debug_info.brillig_locations.get(&function_id) else {
+            continue;
+        };
+        for (BrilligOpcodeLocation(index), call_stack) in brillig_locations {
+            if let Some(location) = call_stack.last() {
+                locations.entry(*index).or_insert(*location);
+            }
+        }
+    }
+    locations
+}
+
+struct SourceLine {
+    path: String,
+    number: usize,
+    text: String,
+}
+
+impl SourceLine {
+    fn new(location: Location, debug_artifact: &DebugArtifact) -> Option<Self> {
+        let file = debug_artifact.file_map.get(&location.file)?;
+        let number = debug_artifact.location_line_number(location).ok()?;
+        let text = file.source.lines().nth(number - 1).unwrap_or_default().trim().to_string();
+        Some(SourceLine { path: file.path.display().to_string(), number, text })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use acvm::acir::brillig::{BitSize, MemoryAddress, Opcode as BrilligOpcode};
+    use acvm::acir::circuit::brillig::{BrilligBytecode, BrilligFunctionId};
+    use acvm::acir::circuit::{BrilligOpcodeLocation, Program};
+    use acvm::{AcirField, FieldElement};
+    use fm::FileId;
+    use noirc_driver::DebugFile;
+    use noirc_errors::{debug_info::DebugInfo, Location, Span};
+
+    use super::disassemble_brillig;
+    use crate::debug::DebugArtifact;
+
+    #[test]
+    fn disassembles_brillig_functions_with_labels_and_source_lines() {
+        let source = "fn main() {\n    let x = 1;\n    assert(x == 1);\n}\n";
+        let file_id = FileId::dummy();
+        let let_statement = Location::new(Span::inclusive(16, 25), file_id);
+        let assertion = Location::new(Span::inclusive(31, 45), file_id);
+
+        let bytecode = vec![
+            BrilligOpcode::Const {
+                destination: MemoryAddress(0),
+                bit_size: BitSize::Field,
+                value: FieldElement::one(),
+            },
+            BrilligOpcode::JumpIfNot { condition: MemoryAddress(0), location: 3 },
+            BrilligOpcode::Call { location: 4 },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            BrilligOpcode::Return,
+        ];
+        let program = Program {
+            functions: vec![],
+            unconstrained_functions: vec![BrilligBytecode { bytecode }],
+        };
+
+        let mut opcode_locations = BTreeMap::new();
+        opcode_locations.insert(BrilligOpcodeLocation(0), vec![let_statement]);
+        opcode_locations.insert(BrilligOpcodeLocation(1), vec![let_statement]);
+        opcode_locations.insert(BrilligOpcodeLocation(2), vec![let_statement, assertion]);
+        let mut brillig_locations = BTreeMap::new();
+        brillig_locations.insert(BrilligFunctionId(0), opcode_locations);
+        let debug_info = DebugInfo::new(
+            BTreeMap::new(),
+            brillig_locations,
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+        );
+
+        let mut file_map = BTreeMap::new();
+        file_map.insert(
+            file_id,
+            DebugFile { source: source.to_string(), path: PathBuf::from("src/main.nr") },
+        );
+        let debug_artifact = DebugArtifact { debug_symbols: vec![debug_info], file_map };
+
+        let disassembly = disassemble_brillig(&program, &["helper".to_string()], &debug_artifact);
+        let expected = "brillig func 0 (helper) {
+    0: const { destination: @0, bit_size: field, value: 1 }  // src/main.nr:2: let x = 1;
+    1: jump_if_not { condition: @0, location: 3 }  // src/main.nr:2
+    2: call { location: 4 }  // src/main.nr:3: assert(x == 1);
+  L3:
+    3: stop { return_data_offset: 0, return_data_size: 0 }
+
+  function at 4:
+    4: return
+}
+";
+        assert_eq!(disassembly, expected);
+    }
+
+    #[test]
+    fn labels_functions_which_are_also_jump_targets() {
+        let bytecode = vec![
+            BrilligOpcode::Call { location: 2 },
+            BrilligOpcode::Stop { return_data_offset: 0, return_data_size: 0 },
+            BrilligOpcode::JumpIfNot { condition: MemoryAddress(0), location: 4 },
+            BrilligOpcode::Jump { location: 2 },
+            BrilligOpcode::Return,
+        ];
+        let program = Program {
+            functions: vec![],
+            unconstrained_functions: vec![BrilligBytecode { bytecode }],
+        };
+        let debug_artifact = DebugArtifact { debug_symbols: vec![], file_map: BTreeMap::new() };
+
+        let disassembly = disassemble_brillig(&program, &[], &debug_artifact);
+        let expected = "brillig func 0 {
+    0: call { location: 2 }
+    1: stop { return_data_offset: 0, return_data_size: 0 }
+
+  function at 2:
+  L2:
+    2: jump_if_not { condition: @0, location: 4 }
+    3: jump { location: 2 }
+  L4:
+    4: return
+}
+";
+        assert_eq!(disassembly, expected);
+    }
+}
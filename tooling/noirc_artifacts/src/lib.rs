@@ -12,4 +12,5 @@
 pub mod contract;
 pub mod debug;
 mod debug_vars;
+pub mod disassembly;
 pub mod program;
@@ -0,0 +1,231 @@
+use std::path::{Path, PathBuf};
+
+use acir::circuit::OpcodeLocation;
+use acir::FieldElement;
+use acvm::pwg::ProfilingSample;
+use clap::Args;
+use color_eyre::eyre::{self, Context};
+use nargo::ops::{execute_program_with_options, DefaultForeignCallExecutor, ExecutionOptions};
+use noirc_abi::input_parser::Format;
+use noirc_abi::MAIN_RETURN_NAME;
+use noirc_artifacts::debug::DebugArtifact;
+
+use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator, Sample};
+use crate::fs::read_program_from_file;
+use crate::opcode_formatter::AcirOrBrilligOpcode;
+
+use super::FieldBlackBoxSolver;
+
+/// Executes a program and generates a flamegraph of the Brillig opcodes it executes
+#[derive(Debug, Clone, Args)]
+pub(crate) struct ExecutionFlamegraphCommand {
+    /// The path to the artifact JSON file
+    #[clap(long, short)]
+    artifact_path: String,
+
+    /// The path to the Prover.toml file containing the program's inputs
+    #[clap(long, short)]
+    prover_toml_path: String,
+
+    /// The output folder for the flamegraph svg files
+    #[clap(long, short)]
+    output: String,
+
+    /// JSON RPC url to solve oracle calls
+    #[clap(long)]
+    oracle_resolver: Option<String>,
+}
+
+pub(crate) fn run(args: ExecutionFlamegraphCommand) -> eyre::Result<()> {
+    run_with_generator(
+        &PathBuf::from(args.artifact_path),
+        &PathBuf::from(args.prover_toml_path),
+        &InfernoFlamegraphGenerator { count_name: "samples".to_string() },
+        &PathBuf::from(args.output),
+        args.oracle_resolver.as_deref(),
+    )
+}
+
+fn run_with_generator<Generator: FlamegraphGenerator>(
+    artifact_path: &Path,
+    prover_toml_path: &Path,
+    flamegraph_generator: &Generator,
+    output_path: &Path,
+    oracle_resolver: Option<&str>,
+) -> eyre::Result<()> {
+    let program =
+        read_program_from_file(artifact_path).context("Error reading program from file")?;
+
+    let inputs = std::fs::read_to_string(prover_toml_path)
+        .with_context(|| format!("Error reading inputs from {}", prover_toml_path.display()))?;
+    let mut input_map = Format::Toml.parse(&inputs, &program.abi)?;
+    input_map.remove(MAIN_RETURN_NAME);
+    let initial_witness = program.abi.encode(&input_map, None)?;
+
+    let mut foreign_call_executor =
+        DefaultForeignCallExecutor::new(true, oracle_resolver, None, None)
+            .context("Error setting up the oracle resolver")?;
+
+    println!("Executing...");
+    let profiling_samples = execute_program_with_options(
+        &program.bytecode,
+        initial_witness,
+        &FieldBlackBoxSolver::default(),
+        &mut foreign_call_executor,
+        ExecutionOptions { profiling: true, ..Default::default() },
+    )
+    .map_err(|error| eyre::eyre!("Error executing program: {error}"))?
+    .profiling_samples
+    .expect("Profiling samples should be returned when profiling");
+    println!("Executed");
+
+    let function_names = program.names.clone();
+    let unconstrained_functions = program.bytecode.unconstrained_functions.clone();
+    let debug_artifact: DebugArtifact = program.into();
+
+    for (func_idx, samples) in profiling_samples {
+        let func_name = &function_names[func_idx];
+        println!("Brillig opcodes executed by {}: {}", func_name, samples.values().sum::<usize>());
+
+        let samples = samples
+            .into_iter()
+            .filter_map(|(ProfilingSample { call_stack, brillig_function_id }, count)| {
+                let Some(OpcodeLocation::Brillig { brillig_index, .. }) = call_stack.last() else {
+                    return None;
+                };
+                let brillig_function = &unconstrained_functions[brillig_function_id?.as_usize()];
+                let opcode = brillig_function.bytecode[*brillig_index].clone();
+                Some(Sample {
+                    opcode: AcirOrBrilligOpcode::<FieldElement>::Brillig(opcode),
+                    call_stack,
+                    count,
+                    brillig_function_id,
+                })
+            })
+            .collect();
+
+        flamegraph_generator.generate_flamegraph(
+            samples,
+            &debug_artifact.debug_symbols[func_idx],
+            &debug_artifact,
+            artifact_path.to_str().unwrap(),
+            func_name,
+            &Path::new(&output_path).join(Path::new(&format!("{}_brillig_trace.svg", func_name))),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::{
+        circuit::{OpcodeLocation, Program},
+        AcirField, FieldElement,
+    };
+    use color_eyre::eyre::{self};
+    use fm::codespan_files::Files;
+    use noirc_abi::{Abi, AbiParameter, AbiType, AbiVisibility};
+    use noirc_artifacts::program::ProgramArtifact;
+    use noirc_errors::debug_info::{DebugInfo, ProgramDebugInfo};
+    use std::{cell::RefCell, collections::BTreeMap, path::Path};
+
+    use crate::flamegraph::Sample;
+
+    #[derive(Default)]
+    struct TestFlamegraphGenerator {
+        call_stacks: RefCell<Vec<(Vec<OpcodeLocation>, usize)>>,
+    }
+
+    impl super::FlamegraphGenerator for TestFlamegraphGenerator {
+        fn generate_flamegraph<'files, F: AcirField>(
+            &self,
+            samples: Vec<Sample<F>>,
+            _debug_symbols: &DebugInfo,
+            _files: &'files impl Files<'files, FileId = fm::FileId>,
+            _artifact_name: &str,
+            _function_name: &str,
+            output_path: &Path,
+        ) -> eyre::Result<()> {
+            self.call_stacks
+                .borrow_mut()
+                .extend(samples.into_iter().map(|sample| (sample.call_stack, sample.count)));
+
+            let output_file = std::fs::File::create(output_path).unwrap();
+            std::io::Write::write_all(&mut std::io::BufWriter::new(output_file), b"success")
+                .unwrap();
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn brillig_execution_is_sampled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+
+        let artifact_path = temp_dir.path().join("test.json");
+        let prover_toml_path = temp_dir.path().join("Prover.toml");
+
+        let bytecode: Program<FieldElement> = "func 0 {
+                current_witness_index: 1
+                private_parameters: [_0]
+                BRILLIG_CALL { id: 0, inputs: [], outputs: [simple(_1)] }
+                ASSERT_ZERO [ (1, _0) (-1, _1) 0 ]
+            }
+
+            brillig func 0 {
+                const { destination: @0, bit_size: field, value: 1 }
+                stop { return_data_offset: 0, return_data_size: 1 }
+            }"
+        .parse()
+        .unwrap();
+
+        let artifact = ProgramArtifact {
+            noir_version: "0.0.0".to_string(),
+            hash: 27,
+            abi: Abi {
+                parameters: vec![AbiParameter {
+                    name: "x".to_string(),
+                    typ: AbiType::Field,
+                    visibility: AbiVisibility::Private,
+                }],
+                return_type: None,
+                error_types: BTreeMap::default(),
+            },
+            bytecode,
+            debug_symbols: ProgramDebugInfo { debug_infos: vec![DebugInfo::default()] },
+            file_map: BTreeMap::default(),
+            names: vec!["main".to_string()],
+            brillig_names: vec!["main".to_string()],
+        };
+
+        // Write the artifact and its inputs to files
+        let artifact_file = std::fs::File::create(&artifact_path).unwrap();
+        serde_json::to_writer(artifact_file, &artifact).unwrap();
+        std::fs::write(&prover_toml_path, "x = \"1\"").unwrap();
+
+        let flamegraph_generator = TestFlamegraphGenerator::default();
+
+        super::run_with_generator(
+            &artifact_path,
+            &prover_toml_path,
+            &flamegraph_generator,
+            temp_dir.path(),
+            None,
+        )
+        .expect("should run without errors");
+
+        let output_file = temp_dir.path().join("main_brillig_trace.svg");
+        assert!(output_file.exists());
+
+        let brillig_location =
+            |brillig_index| OpcodeLocation::Brillig { acir_index: 0, brillig_index };
+        assert_eq!(
+            flamegraph_generator.call_stacks.into_inner(),
+            vec![
+                (vec![OpcodeLocation::Acir(0), brillig_location(0)], 1),
+                (vec![OpcodeLocation::Acir(0), brillig_location(1)], 1),
+            ]
+        );
+    }
+}
@@ -8,7 +8,7 @@ use noirc_artifacts::debug::DebugArtifact;
 
 use crate::flamegraph::{FlamegraphGenerator, InfernoFlamegraphGenerator, Sample};
 use crate::fs::read_program_from_file;
-use crate::gates_provider::{BackendGatesProvider, GatesProvider};
+use crate::gates_provider::{BackendGatesProvider, EstimatedGatesProvider, GatesProvider};
 use crate::opcode_formatter::AcirOrBrilligOpcode;
 
 #[derive(Debug, Clone, Args)]
@@ -17,9 +17,10 @@ pub(crate) struct GatesFlamegraphCommand {
     #[clap(long, short)]
     artifact_path: String,
 
-    /// Path to the noir backend binary
+    /// Path to the noir backend binary.
+    /// If omitted, gates are estimated in-process for a width-N PLONK arithmetisation.
     #[clap(long, short)]
-    backend_path: String,
+    backend_path: Option<String>,
 
     /// Command to get a gates report from the backend. Defaults to "gates"
     #[clap(long, short = 'g', default_value = "gates")]
@@ -34,16 +35,27 @@ pub(crate) struct GatesFlamegraphCommand {
 }
 
 pub(crate) fn run(args: GatesFlamegraphCommand) -> eyre::Result<()> {
-    run_with_provider(
-        &PathBuf::from(args.artifact_path),
-        &BackendGatesProvider {
-            backend_path: PathBuf::from(args.backend_path),
-            gates_command: args.backend_gates_command,
-            extra_args: args.backend_extra_args,
-        },
-        &InfernoFlamegraphGenerator { count_name: "gates".to_string() },
-        &PathBuf::from(args.output),
-    )
+    let artifact_path = PathBuf::from(args.artifact_path);
+    let output_path = PathBuf::from(args.output);
+
+    match args.backend_path {
+        Some(backend_path) => run_with_provider(
+            &artifact_path,
+            &BackendGatesProvider {
+                backend_path: PathBuf::from(backend_path),
+                gates_command: args.backend_gates_command,
+                extra_args: args.backend_extra_args,
+            },
+            &InfernoFlamegraphGenerator { count_name: "gates".to_string() },
+            &output_path,
+        ),
+        None => run_with_provider(
+            &artifact_path,
+            &EstimatedGatesProvider,
+            &InfernoFlamegraphGenerator { count_name: "estimated gates".to_string() },
+            &output_path,
+        ),
+    }
 }
 
 fn run_with_provider<Provider: GatesProvider, Generator: FlamegraphGenerator>(
@@ -2,9 +2,16 @@ use clap::{Parser, Subcommand};
 use color_eyre::eyre;
 use const_format::formatcp;
 
+mod execution_flamegraph_cmd;
 mod gates_flamegraph_cmd;
 mod opcodes_flamegraph_cmd;
 
+/// Solver for the blackbox functions of the field which the profiler was built for.
+#[cfg(not(feature = "bls12_381"))]
+type FieldBlackBoxSolver = bn254_blackbox_solver::Bn254BlackBoxSolver;
+#[cfg(feature = "bls12_381")]
+type FieldBlackBoxSolver = acvm::blackbox_solver::Bls12381BlackBoxSolver;
+
 const PROFILER_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 static VERSION_STRING: &str = formatcp!("version = {}\n", PROFILER_VERSION,);
@@ -18,7 +25,9 @@ struct ProfilerCli {
 
 #[non_exhaustive]
 #[derive(Subcommand, Clone, Debug)]
+#[allow(clippy::enum_variant_names)]
 enum ProfilerCommand {
+    ExecutionFlamegraph(execution_flamegraph_cmd::ExecutionFlamegraphCommand),
     GatesFlamegraph(gates_flamegraph_cmd::GatesFlamegraphCommand),
     OpcodesFlamegraph(opcodes_flamegraph_cmd::OpcodesFlamegraphCommand),
 }
@@ -27,6 +36,7 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     let ProfilerCli { command } = ProfilerCli::parse();
 
     match command {
+        ProfilerCommand::ExecutionFlamegraph(args) => execution_flamegraph_cmd::run(args),
         ProfilerCommand::GatesFlamegraph(args) => gates_flamegraph_cmd::run(args),
         ProfilerCommand::OpcodesFlamegraph(args) => opcodes_flamegraph_cmd::run(args),
     }
@@ -1,9 +1,12 @@
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+use acvm::gates::{GateEstimator, PlonkGateEstimator};
 use color_eyre::eyre::{self};
 use serde::{Deserialize, Serialize};
 
+use crate::fs::read_program_from_file;
+
 pub(crate) trait GatesProvider {
     fn get_gates(&self, artifact_path: &Path) -> eyre::Result<BackendGatesResponse>;
 }
@@ -33,6 +36,32 @@ impl GatesProvider for BackendGatesProvider {
     }
 }
 
+/// Estimates gates in-process using the cost model in `acvm`, so that no backend needs to be installed.
+pub(crate) struct EstimatedGatesProvider;
+
+impl GatesProvider for EstimatedGatesProvider {
+    fn get_gates(&self, artifact_path: &Path) -> eyre::Result<BackendGatesResponse> {
+        let program = read_program_from_file(artifact_path)?;
+
+        let functions = program
+            .bytecode
+            .functions
+            .iter()
+            .map(|circuit| {
+                let estimator = PlonkGateEstimator::for_expression_width(circuit.expression_width);
+                let gates_per_opcode = estimator.gates_per_opcode(circuit);
+                BackendGatesReport {
+                    acir_opcodes: circuit.opcodes.len(),
+                    circuit_size: gates_per_opcode.iter().sum(),
+                    gates_per_opcode,
+                }
+            })
+            .collect();
+
+        Ok(BackendGatesResponse { functions })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct BackendGatesReport {
     pub(crate) acir_opcodes: usize,
@@ -34,6 +34,7 @@ fn format_blackbox_function<F>(call: &BlackBoxFuncCall<F>) -> String {
         BlackBoxFuncCall::BigIntToLeBytes { .. } => "big_int_to_le_bytes".to_string(),
         BlackBoxFuncCall::Poseidon2Permutation { .. } => "poseidon2_permutation".to_string(),
         BlackBoxFuncCall::Sha256Compression { .. } => "sha256_compression".to_string(),
+        BlackBoxFuncCall::UserDefined { name, .. } => name.clone(),
     }
 }
 
@@ -60,6 +61,7 @@ fn format_blackbox_op(call: &BlackBoxOp) -> String {
         BlackBoxOp::Poseidon2Permutation { .. } => "poseidon2_permutation".to_string(),
         BlackBoxOp::Sha256Compression { .. } => "sha256_compression".to_string(),
         BlackBoxOp::ToRadix { .. } => "to_radix".to_string(),
+        BlackBoxOp::UserDefined { name, .. } => name.clone(),
     }
 }
 